@@ -1,35 +1,209 @@
 use crate::config::AppSettings;
+use crate::database::er_model::ErModel;
+use crate::database::schema_diff::{self, SchemaDiff};
 use crate::database::{
-    ConnectionParams, DatabaseConnection, DatabaseError, ObjectCategory, PostgreSQLConnection,
-    QueryExecutor, QueryResult,
+    split_statements, Column, ConnectionParams, Constraint, Database, DatabaseError, DmlStatement,
+    DriverCapabilities, DriverRegistry, EditTarget, ExplainFormat, ForeignKey, Function, Index,
+    ObjectCategory, ObjectCounts, QueryCursor, QueryResult, QueryRow, QuerySession, Schema,
+    ScriptStatement, Sequence, Table, Trigger, View,
 };
+use crate::ui::dependencies_dialog::DependencyLists;
 use crate::ui::{
-    ConfirmationDialog, ConnectionAction, ConnectionDialog, DatabaseTree, DialogAction,
-    QueryEditor, ResultTable, TreeItem,
+    CompareRequest, ConfirmationDialog, ConnectionAction, ConnectionDialog, DatabaseTree,
+    DdlDialog, DependenciesDialog, DialogAction, ErDiagramView, QueryEditor, ResultTable, RowFetch,
+    SchemaCompareDialog, ScriptResults, TaskPanel, TreeItem,
 };
+use crate::utils::TaskManager;
 use eframe::egui;
 use std::collections::HashMap;
+use std::future::Future;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::runtime::Runtime;
 
+/// Schemas of a connection together with the object counts of each schema
+type SchemaListing = (Vec<Schema>, Vec<(String, ObjectCounts)>);
+
+/// Result of a background database operation, applied to the UI in `update()`
+enum TaskOutput {
+    Connected {
+        params: ConnectionParams,
+        result: Result<Box<dyn Database>, DatabaseError>,
+    },
+    ConnectionTested {
+        name: String,
+        result: Result<(), DatabaseError>,
+    },
+    SchemasLoaded {
+        connection_id: String,
+        result: Result<SchemaListing, DatabaseError>,
+    },
+    QueryExecuted {
+        result: Result<(QueryResult, QueryCursor), DatabaseError>,
+        execution_time: Duration,
+    },
+    ScriptSessionOpened {
+        result: Result<QuerySession, DatabaseError>,
+    },
+    StatementExecuted {
+        /// The result and whether rows beyond the display limit were left out
+        result: Result<(QueryResult, bool), DatabaseError>,
+    },
+    RowsFetched {
+        connection_id: String,
+        cursor: QueryCursor,
+        result: Result<Vec<QueryRow>, DatabaseError>,
+    },
+    CancelRequested {
+        result: Result<(), DatabaseError>,
+    },
+    TablesLoaded {
+        connection_id: String,
+        schema: String,
+        result: Result<Vec<Table>, DatabaseError>,
+    },
+    ColumnsLoaded {
+        connection_id: String,
+        schema: String,
+        table: String,
+        result: Result<Vec<Column>, DatabaseError>,
+    },
+    /// Foreign keys and constraints of a table expanded in the tree
+    KeysLoaded {
+        connection_id: String,
+        schema: String,
+        table: String,
+        result: Result<(Vec<ForeignKey>, Vec<Constraint>), DatabaseError>,
+    },
+    ObjectsLoaded {
+        connection_id: String,
+        schema: String,
+        category: ObjectCategory,
+        result: Result<SchemaObjects, DatabaseError>,
+    },
+    /// Columns of the table the displayed result was read from
+    EditTargetLoaded {
+        connection_id: String,
+        schema: String,
+        table: String,
+        result: Result<Vec<Column>, DatabaseError>,
+    },
+    /// Foreign keys of the table the displayed result was read from
+    ResultKeysLoaded {
+        connection_id: String,
+        schema: String,
+        table: String,
+        result: Result<Vec<ForeignKey>, DatabaseError>,
+    },
+    /// Statements recreating an object picked in the tree
+    DdlGenerated {
+        /// Qualified name the DDL dialog shows the statements under
+        title: String,
+        result: Result<String, DatabaseError>,
+    },
+    /// What an object picked in the tree depends on, and its dependents
+    DependenciesLoaded {
+        /// Qualified name the dependencies dialog shows them under
+        title: String,
+        result: Result<DependencyLists, DatabaseError>,
+    },
+    /// Differences between two schemas and the script migrating the target
+    SchemasCompared {
+        request: CompareRequest,
+        result: Result<(Box<SchemaDiff>, String), DatabaseError>,
+    },
+    /// Tables and foreign keys of a schema shown as an ER diagram
+    DiagramLoaded {
+        connection_id: String,
+        schema: String,
+        result: Result<ErModel, DatabaseError>,
+    },
+    ChangesSaved {
+        connection_id: String,
+        /// Query whose result was edited, run again to show the saved rows
+        query: String,
+        result: Result<u64, DatabaseError>,
+    },
+    /// A task the UI waits on panicked instead of delivering its output
+    Failed {
+        kind: TaskKind,
+    },
+}
+
+/// Tasks whose panic leaves UI state to reset
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TaskKind {
+    Query,
+    Script,
+    Fetch,
+    Cancel,
+}
+
+/// Schema objects of a single category loaded for the tree
+enum SchemaObjects {
+    Views(Vec<View>),
+    Functions(Vec<Function>),
+    Triggers(Vec<Trigger>),
+    Sequences(Vec<Sequence>),
+    Indexes(Vec<Index>),
+}
+
+/// Progress of a script run from the query editor
+struct ScriptRun {
+    connection_id: String,
+    /// Session all statements run on, once it is open
+    session: Option<QuerySession>,
+    statements: Vec<ScriptStatement>,
+    /// Index of the statement running now
+    current: usize,
+    continue_on_error: bool,
+    cancelled: bool,
+    started_at: Instant,
+}
+
 /// Main application state and logic
 pub struct RBeaverApp {
-    /// Current database connections
-    connections: HashMap<String, PostgreSQLConnection>,
+    /// Current database connections, shared with running tasks
+    connections: HashMap<String, Arc<dyn Database>>,
+
+    /// Database drivers available in this build
+    drivers: DriverRegistry,
 
     /// Active connection ID
     active_connection: Option<String>,
 
+    /// Connection running the query editor's statement
+    running_query: Option<String>,
+
+    /// Rows of the displayed result not fetched yet, with their connection
+    result_cursor: Option<(String, QueryCursor)>,
+
+    /// Connection the displayed result was read from, which saves its edits
+    result_connection: Option<String>,
+
+    /// Script being run statement by statement
+    script_run: Option<ScriptRun>,
+
     /// UI Components
     connection_dialog: ConnectionDialog,
     query_editor: QueryEditor,
     result_table: ResultTable,
+    script_results: ScriptResults,
     database_tree: DatabaseTree,
     confirmation_dialog: ConfirmationDialog,
+    ddl_dialog: DdlDialog,
+    dependencies_dialog: DependenciesDialog,
+    schema_compare: SchemaCompareDialog,
+    task_panel: TaskPanel,
+
+    /// ER diagram tabs next to the SQL editor
+    diagrams: Vec<ErDiagramView>,
+    /// Diagram tab shown; `None` shows the SQL editor
+    active_diagram: Option<usize>,
 
     /// Application state
     show_connection_dialog: bool,
     is_connecting: bool,
-    is_testing_connection: bool,
     last_error: Option<String>,
     pending_connection_deletion: Option<String>,
     clipboard_message: Option<String>,
@@ -39,8 +213,8 @@ pub struct RBeaverApp {
     saved_connections: Vec<ConnectionParams>,
     settings: AppSettings,
 
-    /// Async runtime for database operations
-    runtime: Runtime,
+    /// Background database operations and the async runtime they run on
+    tasks: TaskManager<TaskOutput>,
 }
 
 impl Default for RBeaverApp {
@@ -51,22 +225,33 @@ impl Default for RBeaverApp {
 
         let mut app = Self {
             connections: HashMap::new(),
+            drivers: DriverRegistry::new(),
             active_connection: None,
+            running_query: None,
+            result_cursor: None,
+            result_connection: None,
+            script_run: None,
             connection_dialog: ConnectionDialog::default(),
             query_editor: QueryEditor::default(),
             result_table: ResultTable::default(),
+            script_results: ScriptResults::default(),
             database_tree: DatabaseTree::default(),
             confirmation_dialog: ConfirmationDialog::default(),
+            ddl_dialog: DdlDialog::default(),
+            dependencies_dialog: DependenciesDialog::default(),
+            schema_compare: SchemaCompareDialog::default(),
+            task_panel: TaskPanel::default(),
+            diagrams: Vec::new(),
+            active_diagram: None,
             show_connection_dialog: false,
             is_connecting: false,
-            is_testing_connection: false,
             last_error: None,
             pending_connection_deletion: None,
             clipboard_message: None,
             clipboard_message_time: None,
             saved_connections: saved_connections.clone(),
             settings,
-            runtime: Runtime::new().expect("Failed to create async runtime"),
+            tasks: TaskManager::new(Runtime::new().expect("Failed to create async runtime")),
         };
 
         // Initialize database tree with saved connections
@@ -77,21 +262,37 @@ impl Default for RBeaverApp {
 
 impl eframe::App for RBeaverApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        // Apply results of finished background tasks
+        self.process_finished_tasks();
+
+        if let Some(fetch) = self.result_table.take_fetch_request() {
+            self.fetch_more_rows(fetch);
+        }
+
+        if let Some(statements) = self.result_table.take_save_request() {
+            self.save_changes(statements);
+        }
+
+        if self.result_table.take_server_filter_request() {
+            self.filter_on_server();
+        }
+
+        if let Some(sql) = self.result_table.take_navigate_request() {
+            self.execute_query(&sql);
+        }
+
         // Handle global keyboard shortcuts
         ctx.input(|i| {
             if i.modifiers.ctrl && i.key_pressed(egui::Key::N) {
                 self.show_connection_dialog = true;
             }
 
-            // F5 or Ctrl+Enter for query execution
-            if i.key_pressed(egui::Key::F5) || (i.modifiers.ctrl && i.key_pressed(egui::Key::Enter))
+            // Esc cancels the running query
+            if i.key_pressed(egui::Key::Escape)
+                && self.query_editor.is_executing()
+                && self.can_cancel_query()
             {
-                if self.active_connection.is_some() {
-                    let sql = self.query_editor.get_sql().trim().to_string();
-                    if !sql.is_empty() {
-                        self.execute_query(&sql);
-                    }
-                }
+                self.cancel_query();
             }
 
             // Ctrl+Shift+C for new connection
@@ -107,6 +308,35 @@ impl eframe::App for RBeaverApp {
         if let Some((action, connection_id)) = self.database_tree.get_pending_action() {
             self.handle_connection_action(action, connection_id);
         }
+        if let Some((connection_id, category, schema, name)) = self.database_tree.take_ddl_request()
+        {
+            self.generate_ddl(&connection_id, category, schema, name);
+        }
+        if let Some((connection_id, category, schema, name)) =
+            self.database_tree.take_dependencies_request()
+        {
+            self.load_dependencies(&connection_id, category, schema, name);
+        }
+
+        if let Some((connection_id, schema)) = self.database_tree.take_diagram_request() {
+            self.open_diagram(connection_id, schema);
+        }
+        self.handle_diagram_requests();
+
+        self.ddl_dialog.render(ctx);
+        self.dependencies_dialog.render(ctx);
+        if let Some(ddl) = self.ddl_dialog.take_editor_request() {
+            self.query_editor.set_sql(ddl);
+        }
+
+        self.schema_compare
+            .render(ctx, &self.database_tree.connected_schemas());
+        if let Some(request) = self.schema_compare.take_compare_request() {
+            self.compare_schemas(request);
+        }
+        if let Some(script) = self.schema_compare.take_editor_request() {
+            self.query_editor.set_sql(script);
+        }
 
         // Handle connection dialog
         if self.show_connection_dialog {
@@ -130,14 +360,23 @@ impl eframe::App for RBeaverApp {
 
         // Status bar
         self.render_status_bar(ctx);
+
+        // Running tasks
+        self.task_panel.render(ctx, self.tasks.running());
+        if self.tasks.has_running_tasks() {
+            // Keep elapsed times ticking while work is in flight
+            ctx.request_repaint_after(Duration::from_millis(100));
+        }
     }
 }
 
 impl RBeaverApp {
-    pub fn new(_cc: &eframe::CreationContext<'_>) -> Self {
+    pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
         log::info!("Initializing RBeaver application");
 
-        Self::default()
+        let mut app = Self::default();
+        app.tasks.set_repaint_context(cc.egui_ctx.clone());
+        app
     }
 
     fn render_menu_bar(&mut self, ctx: &egui::Context) {
@@ -165,6 +404,13 @@ impl RBeaverApp {
                     ui.label("Coming soon...");
                 });
 
+                ui.menu_button("Tools", |ui| {
+                    if ui.button("⚖ Compare Schemas...").clicked() {
+                        self.schema_compare.open = true;
+                        ui.close();
+                    }
+                });
+
                 ui.menu_button("Help", |ui| {
                     if ui.button("About").clicked() {
                         // TODO: Show about dialog
@@ -186,13 +432,21 @@ impl RBeaverApp {
                 // Handle tree interactions
                 if let Some(selected_item) = self.database_tree.get_selected_item() {
                     match selected_item {
-                        crate::ui::database_tree::TreeItem::Table { schema, table, .. } => {
-                            if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
-                                let sql = format!(
-                                    "SELECT * FROM \"{}\".\"{}\" LIMIT 100;",
-                                    schema, table
-                                );
-                                self.query_editor.set_sql(sql);
+                        crate::ui::database_tree::TreeItem::Table { connection_id, .. } => {
+                            // A plain Enter, and only while no text field such as
+                            // the editor has keyboard focus
+                            let enter_pressed = ui.input(|i| {
+                                i.modifiers.is_none() && i.key_pressed(egui::Key::Enter)
+                            });
+                            if enter_pressed && !ui.ctx().wants_keyboard_input() {
+                                let sql =
+                                    self.connection_params(connection_id).and_then(|params| {
+                                        self.database_tree
+                                            .get_sql_for_selected(&params.database_type)
+                                    });
+                                if let Some(sql) = sql {
+                                    self.query_editor.set_sql(sql);
+                                }
                             }
                         }
                         _ => {}
@@ -203,8 +457,17 @@ impl RBeaverApp {
                 self.handle_tree_expansion_requests();
             });
 
-        // Central panel for query editor and results
+        // Central panel for query editor and results, or a diagram tab
         egui::CentralPanel::default().show(ctx, |ui| {
+            if !self.diagrams.is_empty() {
+                self.render_tab_strip(ui);
+                ui.separator();
+            }
+            if let Some(index) = self.active_diagram {
+                self.diagrams[index].render(ui);
+                return;
+            }
+
             // Split vertically: query editor on top, results on bottom
             let available_height = ui.available_height();
             let editor_height = available_height * 0.4;
@@ -239,20 +502,81 @@ impl RBeaverApp {
                         });
                     });
 
+                    let running = self
+                        .running_query
+                        .as_deref()
+                        .and_then(|connection_id| self.driver_capabilities(connection_id))
+                        .cloned();
+                    let active = self
+                        .active_connection
+                        .as_deref()
+                        .and_then(|connection_id| self.driver_capabilities(connection_id))
+                        .cloned();
+                    self.query_editor
+                        .set_capabilities(running.as_ref(), active.as_ref());
                     self.query_editor.render(ui);
 
-                    // Handle query execution from button or keyboard shortcuts
+                    if self.query_editor.is_cancel_requested() {
+                        self.query_editor.clear_cancel_request();
+                        self.cancel_query();
+                    }
+
+                    // EXPLAIN the selection or the statement under the cursor
+                    if let Some(format) = self.query_editor.take_explain_request() {
+                        self.explain_query(format);
+                    }
+
+                    // F5 runs the selection or the whole editor
                     let should_execute = self.query_editor.is_execute_requested()
-                        || ui.input(|i| i.key_pressed(egui::Key::F5))
-                        || (ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Enter)));
+                        || ui.input(|i| i.key_pressed(egui::Key::F5));
 
                     if should_execute {
                         self.query_editor.clear_execute_request();
-                        let sql = self.query_editor.get_sql().trim().to_string();
-                        if !sql.is_empty() && self.active_connection.is_some() {
-                            self.execute_query(&sql);
-                        } else if sql.is_empty() {
+                        let sql = self.query_editor.get_sql();
+                        let range = self.query_editor.selection_range().unwrap_or(0..sql.len());
+                        let sql = sql[range.clone()].to_string();
+                        self.execute_editor_text(&sql, range);
+                    }
+
+                    // Ctrl+Enter runs the statement under the cursor
+                    let should_execute_statement =
+                        self.query_editor.is_execute_statement_requested()
+                            || ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Enter));
+
+                    if should_execute_statement {
+                        self.query_editor.clear_execute_statement_request();
+                        match self.query_editor.statement_at_cursor() {
+                            Some(statement) => {
+                                self.execute_editor_text(&statement.sql, statement.range)
+                            }
+                            None => {
+                                self.last_error =
+                                    Some("No SQL statement at the cursor".to_string());
+                            }
+                        }
+                    }
+
+                    // Alt+X runs the selection or the whole editor as a script
+                    let should_run_script = self.query_editor.is_run_script_requested()
+                        || ui.input(|i| i.modifiers.alt && i.key_pressed(egui::Key::X));
+
+                    if should_run_script {
+                        self.query_editor.clear_run_script_request();
+                        let sql = self.query_editor.get_sql();
+                        let range = self.query_editor.selection_range().unwrap_or(0..sql.len());
+                        // Report lines of the editor, not of the selection
+                        let line_offset = sql[..range.start].matches('\n').count();
+                        let mut statements = split_statements(&sql[range.clone()]);
+                        for statement in &mut statements {
+                            statement.line += line_offset;
+                        }
+                        if statements.is_empty() {
                             self.last_error = Some("Please enter a SQL query".to_string());
+                        } else if self.active_connection.is_some() {
+                            if !self.query_editor.is_executing() {
+                                self.query_editor.highlight_range(range);
+                            }
+                            self.run_script(statements);
                         } else {
                             self.last_error = Some("No database connection available".to_string());
                         }
@@ -268,7 +592,11 @@ impl RBeaverApp {
                 egui::Layout::top_down(egui::Align::LEFT),
                 |ui| {
                     ui.heading("Query Results");
-                    self.result_table.render(ui);
+                    if self.script_results.is_empty() {
+                        self.result_table.render(ui);
+                    } else {
+                        self.script_results.render(ui);
+                    }
                 },
             );
         });
@@ -308,16 +636,12 @@ impl RBeaverApp {
                     DialogAction::None => {}
                 }
 
+                // The dialog shows its own progress while testing
                 if self.is_connecting {
                     ui.horizontal(|ui| {
                         ui.spinner();
                         ui.label("Connecting...");
                     });
-                } else if self.is_testing_connection {
-                    ui.horizontal(|ui| {
-                        ui.spinner();
-                        ui.label("Testing...");
-                    });
                 }
             });
     }
@@ -355,6 +679,17 @@ impl RBeaverApp {
 
                 ui.separator();
 
+                // Statement timeout of the active connection
+                if let Some(connection_id) = &self.active_connection {
+                    let timeout = self.query_timeout(connection_id);
+                    ui.label(format!("⏱ Timeout: {}", format_query_timeout(timeout)))
+                        .on_hover_text("Statement timeout for queries on this connection");
+                    ui.separator();
+                }
+
+                // Background task indicator
+                self.task_panel.render_status(ui, self.tasks.running());
+
                 // Show clipboard message if recent
                 if let (Some(message), Some(time)) =
                     (&self.clipboard_message, &self.clipboard_message_time)
@@ -374,7 +709,7 @@ impl RBeaverApp {
 
                 // Keyboard shortcuts help
                 ui.label(
-                    "Shortcuts: F5 (Execute), Ctrl+N (New Connection), Ctrl+Shift+C (Connect)",
+                    "Shortcuts: F5 (Execute), Ctrl+Enter (Statement), Alt+X (Script), Esc (Cancel), Ctrl+N (New Connection), Ctrl+Shift+C (Connect)",
                 );
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
@@ -385,23 +720,62 @@ impl RBeaverApp {
     }
 
     fn attempt_connection(&mut self, params: ConnectionParams) {
+        // Create a driver for the selected database type
+        let mut connection = match self.drivers.create_connection(&params.database_type) {
+            Ok(connection) => connection,
+            Err(err) => {
+                self.last_error = Some(format!("Connection failed: {}", err));
+                log::error!("Connection failed: {}", err);
+                return;
+            }
+        };
+
         self.is_connecting = true;
 
-        // Create a new PostgreSQL connection
-        let mut connection = PostgreSQLConnection::new();
+        // Sessions get the effective statement timeout; `params` keeps the
+        // override exactly as the user saved it
+        let mut session_params = params.clone();
+        session_params.query_timeout =
+            Some(params.effective_query_timeout(self.settings.general_settings.query_timeout));
+
+        // Connect in the background; the driver comes back with the result
+        let description = format!("Connecting to {}", params.name);
+        self.tasks.spawn(description, async move {
+            let result = connection
+                .connect(&session_params)
+                .await
+                .map(|()| connection);
+            TaskOutput::Connected { params, result }
+        });
+    }
+
+    fn on_connected(
+        &mut self,
+        params: ConnectionParams,
+        result: Result<Box<dyn Database>, DatabaseError>,
+    ) {
+        self.is_connecting = false;
 
-        // Attempt to connect in the runtime
-        match self.runtime.block_on(connection.connect(&params)) {
-            Ok(()) => {
+        match result {
+            Ok(connection) => {
                 // Connection successful
-                self.connections.insert(params.id.clone(), connection);
+                self.connections
+                    .insert(params.id.clone(), Arc::from(connection));
                 self.active_connection = Some(params.id.clone());
                 // Keep dialog open for multiple connections
                 self.connection_dialog.reset();
 
                 // Add connection to database tree
-                self.database_tree
-                    .add_connection(params.id.clone(), params.name.clone());
+                let capabilities = self
+                    .drivers
+                    .capabilities(&params.database_type)
+                    .cloned()
+                    .unwrap_or_default();
+                self.database_tree.add_connection(
+                    params.id.clone(),
+                    params.name.clone(),
+                    capabilities,
+                );
                 self.database_tree.set_connection_status(&params.id, true);
 
                 // Save connection if not already saved
@@ -417,22 +791,40 @@ impl RBeaverApp {
                 log::error!("Connection failed: {}", err);
             }
         }
-
-        self.is_connecting = false;
     }
 
     fn test_connection(&mut self) {
-        self.is_testing_connection = true;
-        let params = self.connection_dialog.get_params().clone();
+        // Tested with the statement timeout its sessions would get
+        let mut params = self.connection_dialog.get_params().clone();
+        params.query_timeout =
+            Some(params.effective_query_timeout(self.settings.general_settings.query_timeout));
 
         // Create a temporary connection for testing
-        let connection = PostgreSQLConnection::new();
+        let connection = match self.drivers.create_connection(&params.database_type) {
+            Ok(connection) => connection,
+            Err(err) => {
+                self.on_connection_tested(&params.name, Err(err));
+                return;
+            }
+        };
 
-        // Test the connection
-        match self.runtime.block_on(connection.test_connection(&params)) {
+        self.connection_dialog.set_testing(true);
+
+        let description = format!("Testing connection {}", params.name);
+        self.tasks.spawn(description, async move {
+            let result = connection.test_connection(&params).await;
+            TaskOutput::ConnectionTested {
+                name: params.name,
+                result,
+            }
+        });
+    }
+
+    fn on_connection_tested(&mut self, name: &str, result: Result<(), DatabaseError>) {
+        match result {
             Ok(()) => {
                 self.connection_dialog.set_test_result(Ok(()));
-                log::info!("Connection test successful for: {}", params.name);
+                log::info!("Connection test successful for: {}", name);
             }
             Err(err) => {
                 let error_msg = format!("Connection test failed: {}", err);
@@ -441,8 +833,6 @@ impl RBeaverApp {
                 log::error!("Connection test failed: {}", err);
             }
         }
-
-        self.is_testing_connection = false;
     }
 
     fn disconnect_database(&mut self, connection_id: &str) {
@@ -453,6 +843,16 @@ impl RBeaverApp {
             }
         }
 
+        // Release the session holding the rest of the result
+        if self
+            .result_cursor
+            .as_ref()
+            .is_some_and(|(cursor_connection, _)| cursor_connection == connection_id)
+        {
+            self.result_cursor = None;
+            self.result_table.set_fetch_state(false, false);
+        }
+
         // Remove from connections map
         self.connections.remove(connection_id);
 
@@ -636,6 +1036,11 @@ impl RBeaverApp {
                 self.show_connection_dialog = false;
                 self.connection_dialog.reset();
 
+                // An open connection's next sessions get the new timeout
+                if let Some(connection) = self.connections.get(&params.id) {
+                    connection.set_query_timeout(self.query_timeout(&params.id));
+                }
+
                 log::info!("Updated connection: {}", params.name);
             }
             Err(err) => {
@@ -645,25 +1050,24 @@ impl RBeaverApp {
     }
 
     fn load_database_structure(&mut self) {
-        if let Some(connection_id) = &self.active_connection {
-            if let Some(connection) = self.connections.get(connection_id) {
+        if let Some(connection_id) = self.active_connection.clone() {
+            if let Some(connection) = self.connections.get(&connection_id).cloned() {
                 self.database_tree.set_loading(true);
 
-                // Load schemas for this specific connection
-                match self.runtime.block_on(connection.get_schemas()) {
-                    Ok(schemas) => {
-                        // Load object counts for each schema
+                // Load schemas and their object counts for this specific connection
+                let description = format!(
+                    "Loading schemas of {}",
+                    self.connection_name(&connection_id)
+                );
+                self.tasks.spawn(description, async move {
+                    let result = async {
+                        let schemas = connection.get_schemas().await?;
+
+                        let mut counts = Vec::new();
                         for schema in &schemas {
-                            match self
-                                .runtime
-                                .block_on(connection.get_object_counts(&schema.name))
-                            {
-                                Ok(counts) => {
-                                    self.database_tree.set_object_counts(
-                                        connection_id,
-                                        schema.name.clone(),
-                                        counts,
-                                    );
+                            match connection.get_object_counts(&schema.name).await {
+                                Ok(schema_counts) => {
+                                    counts.push((schema.name.clone(), schema_counts))
                                 }
                                 Err(err) => {
                                     log::warn!(
@@ -675,39 +1079,128 @@ impl RBeaverApp {
                             }
                         }
 
-                        self.database_tree.set_schemas(connection_id, schemas);
-                        log::info!("Loaded database schemas for connection: {}", connection_id);
+                        Ok::<_, DatabaseError>((schemas, counts))
                     }
-                    Err(err) => {
-                        self.last_error = Some(format!("Failed to load schemas: {}", err));
-                        log::error!("Failed to load schemas: {}", err);
+                    .await;
+
+                    TaskOutput::SchemasLoaded {
+                        connection_id,
+                        result,
                     }
+                });
+            }
+        }
+    }
+
+    fn on_schemas_loaded(
+        &mut self,
+        connection_id: &str,
+        result: Result<SchemaListing, DatabaseError>,
+    ) {
+        match result {
+            Ok((schemas, counts)) => {
+                for (schema, schema_counts) in counts {
+                    self.database_tree
+                        .set_object_counts(connection_id, schema, schema_counts);
                 }
 
-                self.database_tree.set_loading(false);
+                self.database_tree.set_schemas(connection_id, schemas);
+                log::info!("Loaded database schemas for connection: {}", connection_id);
+            }
+            Err(err) => {
+                self.last_error = Some(format!("Failed to load schemas: {}", err));
+                log::error!("Failed to load schemas: {}", err);
+            }
+        }
+
+        self.database_tree.set_loading(false);
+    }
+
+    /// Run EXPLAIN for the selection, or else the statement under the cursor
+    fn explain_query(&mut self, format: ExplainFormat) {
+        let Some(database_type) = self
+            .active_connection
+            .as_deref()
+            .and_then(|connection_id| self.connection_params(connection_id))
+            .map(|params| params.database_type.clone())
+        else {
+            self.last_error = Some("No database connection available".to_string());
+            return;
+        };
+
+        // EXPLAIN takes a single statement
+        let statement = match self.query_editor.selection_range() {
+            Some(range) => {
+                let mut statements = split_statements(&self.query_editor.get_sql()[range.clone()]);
+                (statements.len() == 1).then(|| (statements.remove(0).sql, range))
+            }
+            None => self
+                .query_editor
+                .statement_at_cursor()
+                .map(|statement| (statement.sql, statement.range)),
+        };
+        match statement {
+            Some((sql, range)) => {
+                let sql = format.statement(&database_type, &sql);
+                self.execute_editor_text(&sql, range);
             }
+            None => self.last_error = Some("Select a single SQL statement to explain".to_string()),
+        }
+    }
+
+    /// Run text from the query editor, highlighting the part that runs
+    fn execute_editor_text(&mut self, sql: &str, range: std::ops::Range<usize>) {
+        if sql.trim().is_empty() {
+            self.last_error = Some("Please enter a SQL query".to_string());
+        } else if self.active_connection.is_none() {
+            self.last_error = Some("No database connection available".to_string());
+        } else if !self.query_editor.is_executing() && !self.result_table.is_fetching() {
+            self.query_editor.highlight_range(range);
+            self.execute_query(sql.trim());
         }
     }
 
     fn execute_query(&mut self, sql: &str) {
+        // Only one query runs at a time, including fetches of its later rows
+        if self.query_editor.is_executing() || self.result_table.is_fetching() {
+            return;
+        }
+
+        // Several statements cannot be sent as one prepared statement
+        let statements = split_statements(sql);
+        if statements.len() > 1 {
+            self.run_script(statements);
+            return;
+        }
+        let sql = statements.first().map_or(sql, |statement| &statement.sql);
+
         if let Some(connection_id) = &self.active_connection {
-            if let Some(connection) = self.connections.get(connection_id) {
-                self.query_editor.set_executing(true);
+            if let Some(connection) = self.connections.get(connection_id).cloned() {
+                let client_limit = self.client_time_limit(connection_id);
 
-                let start_time = std::time::Instant::now();
-                match self.runtime.block_on(connection.execute_query(sql)) {
-                    Ok(result) => {
-                        let execution_time = start_time.elapsed();
-                        self.query_editor.set_execution_time(execution_time);
-                        self.result_table.set_result(result);
-                        log::info!("Query executed successfully in {:?}", execution_time);
-                    }
-                    Err(err) => {
-                        self.last_error = Some(format!("Query execution failed: {}", err));
-                        self.query_editor.set_executing(false);
-                        log::error!("Query execution failed: {}", err);
+                // Close the previous result before starting another
+                self.result_cursor = None;
+                self.script_results.clear();
+                self.query_editor.set_executing(true);
+                self.running_query = Some(connection_id.clone());
+
+                let first_rows = self.result_table.page_size().min(self.row_limit());
+                let description = format!("Query: {}", summarize_sql(sql));
+                let sql = sql.to_string();
+                let on_panic = TaskOutput::Failed {
+                    kind: TaskKind::Query,
+                };
+                self.tasks.spawn_or(description, on_panic, async move {
+                    let start_time = Instant::now();
+                    let cursor = connection.open_cursor(&sql);
+                    let result =
+                        execute_with_timeout(connection.as_ref(), cursor, first_rows, client_limit)
+                            .await;
+                    TaskOutput::QueryExecuted {
+                        result,
+                        execution_time: start_time.elapsed(),
                     }
-                }
+                });
             } else {
                 self.last_error = Some("No active database connection".to_string());
                 self.query_editor.set_executing(false);
@@ -718,238 +1211,1093 @@ impl RBeaverApp {
         }
     }
 
+    /// How long the client waits for a statement on the given connection
+    fn client_time_limit(&self, connection_id: &str) -> Option<Duration> {
+        // Drivers without a server-side limit rely on the client timer
+        // alone; the others only get it as a backstop for a hung server
+        let timeout = self.query_timeout(connection_id);
+        let server_side = self
+            .driver_capabilities(connection_id)
+            .is_some_and(|capabilities| capabilities.supports_statement_timeout);
+        (timeout > 0).then(|| {
+            let limit = Duration::from_secs(timeout as u64);
+            if server_side {
+                limit + CLIENT_TIMEOUT_GRACE
+            } else {
+                limit
+            }
+        })
+    }
+
+    /// Run statements one after another on the active connection, each with
+    /// its own result tab
+    fn run_script(&mut self, statements: Vec<ScriptStatement>) {
+        if self.query_editor.is_executing() || self.result_table.is_fetching() {
+            return;
+        }
+
+        let Some(connection_id) = self.active_connection.clone() else {
+            self.last_error = Some("No database connection available".to_string());
+            return;
+        };
+        let Some(connection) = self.connections.get(&connection_id).cloned() else {
+            self.last_error = Some("No active database connection".to_string());
+            return;
+        };
+
+        self.result_cursor = None;
+        self.result_table.clear();
+        self.script_results.start(statements.len());
+        self.query_editor.set_executing(true);
+        self.running_query = Some(connection_id.clone());
+        self.script_run = Some(ScriptRun {
+            connection_id,
+            session: None,
+            statements,
+            current: 0,
+            continue_on_error: self.query_editor.continue_on_error(),
+            cancelled: false,
+            started_at: Instant::now(),
+        });
+
+        // Statements such as BEGIN and COMMIT only make sense on one session
+        let on_panic = TaskOutput::Failed {
+            kind: TaskKind::Script,
+        };
+        self.tasks
+            .spawn_or("Opening script session", on_panic, async move {
+                let result = connection.open_session().await;
+                TaskOutput::ScriptSessionOpened { result }
+            });
+    }
+
+    fn on_script_session_opened(&mut self, result: Result<QuerySession, DatabaseError>) {
+        let Some(run) = &mut self.script_run else {
+            return;
+        };
+
+        match result {
+            Ok(session) if !run.cancelled => {
+                run.session = Some(session);
+                self.run_current_statement();
+            }
+            Ok(_) => self.finish_script(),
+            Err(err) => {
+                self.last_error = Some(format!("Failed to run script: {}", err));
+                log::error!("Failed to open script session: {}", err);
+                self.finish_script();
+            }
+        }
+    }
+
+    fn run_current_statement(&mut self) {
+        let Some(run) = &self.script_run else {
+            return;
+        };
+        let Some(statement) = run.statements.get(run.current) else {
+            self.finish_script();
+            return;
+        };
+        let (Some(connection), Some(session)) = (
+            self.connections.get(&run.connection_id).cloned(),
+            run.session.clone(),
+        ) else {
+            self.last_error = Some("The script's connection was closed".to_string());
+            self.finish_script();
+            return;
+        };
+
+        let client_limit = self.client_time_limit(&run.connection_id);
+        let row_limit = self.row_limit();
+        let description = format!(
+            "Script {}/{}: {}",
+            run.current + 1,
+            run.statements.len(),
+            summarize_sql(&statement.sql)
+        );
+        let sql = statement.sql.clone();
+        let on_panic = TaskOutput::Failed {
+            kind: TaskKind::Script,
+        };
+        self.tasks.spawn_or(description, on_panic, async move {
+            // Script results are not paged, so the cursor is closed right away
+            let cursor = session.open_cursor(&sql);
+            let result = execute_with_timeout(connection.as_ref(), cursor, row_limit, client_limit)
+                .await
+                .map(|(result, cursor)| (result, !cursor.is_exhausted()));
+            TaskOutput::StatementExecuted { result }
+        });
+    }
+
+    fn on_statement_executed(&mut self, result: Result<(QueryResult, bool), DatabaseError>) {
+        let Some(run) = &mut self.script_run else {
+            return;
+        };
+        let statement = &run.statements[run.current];
+
+        let stop = match result {
+            Ok((result, row_limit_reached)) => {
+                self.script_results
+                    .add_result(statement, result, row_limit_reached);
+                run.cancelled
+            }
+            Err(DatabaseError::Cancelled) => {
+                self.script_results
+                    .add_error(statement, "Cancelled".to_string());
+                true
+            }
+            Err(err) => {
+                log::error!("Statement at line {} failed: {}", statement.line, err);
+                self.script_results.add_error(statement, err.to_string());
+                run.cancelled || !run.continue_on_error
+            }
+        };
+
+        run.current += 1;
+        if stop {
+            self.finish_script();
+        } else {
+            self.run_current_statement();
+        }
+    }
+
+    fn finish_script(&mut self) {
+        let Some(run) = self.script_run.take() else {
+            return;
+        };
+
+        self.running_query = None;
+        self.script_results.finish();
+        self.query_editor
+            .set_execution_time(run.started_at.elapsed());
+
+        let failed = self
+            .script_results
+            .tabs()
+            .iter()
+            .filter(|tab| tab.is_failed())
+            .count();
+        log::info!(
+            "Script finished: {} of {} statements executed, {} failed, in {:?}",
+            run.current,
+            run.statements.len(),
+            failed,
+            run.started_at.elapsed()
+        );
+    }
+
+    fn on_query_executed(
+        &mut self,
+        result: Result<(QueryResult, QueryCursor), DatabaseError>,
+        execution_time: Duration,
+    ) {
+        let connection_id = self.running_query.take();
+
+        match result {
+            Ok((result, cursor)) => {
+                self.query_editor.set_execution_time(execution_time);
+                if let Some(connection_id) = &connection_id {
+                    self.load_edit_target(connection_id, &result);
+                }
+                self.result_connection = connection_id.clone();
+                self.result_table.set_result(result);
+                if let Some(params) = connection_id
+                    .as_deref()
+                    .and_then(|connection_id| self.connection_params(connection_id))
+                {
+                    self.result_table
+                        .set_database_type(params.database_type.clone());
+                }
+                if let Some(connection_id) = &connection_id {
+                    self.keep_result_cursor(connection_id.clone(), cursor);
+                }
+                log::info!("Query executed successfully in {:?}", execution_time);
+            }
+            Err(DatabaseError::Cancelled) => {
+                // Cancelling is not an error worth a dialog
+                self.query_editor.set_executing(false);
+                log::info!("Query cancelled after {:?}", execution_time);
+            }
+            Err(DatabaseError::Timeout(msg)) => {
+                let timeout = connection_id
+                    .map(|connection_id| self.query_timeout(&connection_id))
+                    .unwrap_or(self.settings.general_settings.query_timeout);
+                self.last_error = Some(format!(
+                    "Query timed out: {}\n\nThe limit for this connection is {}. Raise the \
+                     query timeout in the connection's advanced settings if the statement \
+                     needs longer.",
+                    msg,
+                    format_query_timeout(timeout)
+                ));
+                self.query_editor.set_executing(false);
+                log::error!("Query timed out after {:?}: {}", execution_time, msg);
+            }
+            Err(err) => {
+                self.last_error = Some(format!("Query execution failed: {}", err));
+                self.query_editor.set_executing(false);
+                log::error!("Query execution failed: {}", err);
+            }
+        }
+    }
+
+    /// Look up the primary key of the table a result was read from, so the
+    /// result can be edited
+    fn load_edit_target(&mut self, connection_id: &str, result: &QueryResult) {
+        let Some((schema, table)) = EditTarget::source_table(result) else {
+            return;
+        };
+        let Some(connection) = self.connections.get(connection_id).cloned() else {
+            return;
+        };
+        let supports_editing = self
+            .driver_capabilities(connection_id)
+            .is_some_and(|capabilities| capabilities.supports_result_editing);
+
+        let connection_id = connection_id.to_string();
+        if supports_editing {
+            let description = format!("Loading columns of {}.{}", schema, table);
+            let task = (
+                connection.clone(),
+                connection_id.clone(),
+                schema.clone(),
+                table.clone(),
+            );
+            self.tasks.spawn(description, async move {
+                let (connection, connection_id, schema, table) = task;
+                let result = connection.get_columns(&schema, &table).await;
+                TaskOutput::EditTargetLoaded {
+                    connection_id,
+                    schema,
+                    table,
+                    result,
+                }
+            });
+        }
+
+        // The foreign keys let the result's cells lead to related rows
+        let description = format!("Loading foreign keys of {}.{}", schema, table);
+        self.tasks.spawn(description, async move {
+            let result = connection.get_foreign_keys(&schema, &table).await;
+            TaskOutput::ResultKeysLoaded {
+                connection_id,
+                schema,
+                table,
+                result,
+            }
+        });
+    }
+
+    fn on_edit_target_loaded(
+        &mut self,
+        connection_id: &str,
+        schema: &str,
+        table: &str,
+        result: Result<Vec<Column>, DatabaseError>,
+    ) {
+        if self.result_connection.as_deref() != Some(connection_id) {
+            return;
+        }
+
+        match result {
+            Ok(columns) => self.result_table.enable_editing(schema, table, &columns),
+            // The result stays readable; it just cannot be edited
+            Err(err) => log::warn!(
+                "Failed to load columns of {}.{} for editing: {}",
+                schema,
+                table,
+                err
+            ),
+        }
+    }
+
+    /// Run the displayed result's query again with its filters and sort
+    /// added, for results too large to fetch whole
+    fn filter_on_server(&mut self) {
+        let Some(database_type) = self
+            .result_connection
+            .as_deref()
+            .filter(|connection_id| self.active_connection.as_deref() == Some(*connection_id))
+            .and_then(|connection_id| self.connection_params(connection_id))
+            .map(|params| params.database_type.clone())
+        else {
+            self.last_error =
+                Some("The result's connection is no longer the active one".to_string());
+            return;
+        };
+
+        match self.result_table.server_query(&database_type) {
+            Some(sql) => self.execute_query(&sql),
+            None => {
+                self.last_error = Some(
+                    "Cannot filter on the server: a filtered or sorted column shares its \
+                     name with another column"
+                        .to_string(),
+                )
+            }
+        }
+    }
+
+    /// Write the result grid's changes in one transaction
+    fn save_changes(&mut self, statements: Vec<DmlStatement>) {
+        let Some((connection_id, connection)) = self.result_connection.clone().and_then(|id| {
+            let connection = self.connections.get(&id).cloned()?;
+            Some((id, connection))
+        }) else {
+            self.last_error = Some("The result's connection was closed".to_string());
+            self.result_table.finish_saving(false);
+            return;
+        };
+
+        let query = self.result_table.query().unwrap_or_default().to_string();
+        let description = format!("Saving {} changed rows", statements.len());
+        self.tasks.spawn(description, async move {
+            let result = connection.execute_dml(&statements).await;
+            TaskOutput::ChangesSaved {
+                connection_id,
+                query,
+                result,
+            }
+        });
+    }
+
+    fn on_changes_saved(
+        &mut self,
+        connection_id: &str,
+        query: &str,
+        result: Result<u64, DatabaseError>,
+    ) {
+        match result {
+            Ok(rows) => {
+                log::info!("Saved changes to {} rows", rows);
+                self.result_table.finish_saving(true);
+                // Show the rows as the server has them now, with defaults and
+                // trigger changes filled in
+                if self.active_connection.as_deref() == Some(connection_id) && !query.is_empty() {
+                    self.execute_query(query);
+                }
+            }
+            Err(err) => {
+                self.last_error = Some(format!("Failed to save changes: {}", err));
+                self.result_table.finish_saving(false);
+                log::error!("Failed to save changes: {}", err);
+            }
+        }
+    }
+
+    /// Maximum number of rows fetched for one result, `usize::MAX` when unlimited
+    fn row_limit(&self) -> usize {
+        match self.settings.general_settings.max_rows_display {
+            0 => usize::MAX,
+            limit => limit,
+        }
+    }
+
+    /// Hold on to a cursor while it has rows left below the display limit
+    fn keep_result_cursor(&mut self, connection_id: String, cursor: QueryCursor) {
+        let has_more_rows = !cursor.is_exhausted();
+        let row_limit_reached = has_more_rows && cursor.rows_fetched() >= self.row_limit();
+
+        self.result_table
+            .set_fetch_state(has_more_rows && !row_limit_reached, row_limit_reached);
+        if has_more_rows && !row_limit_reached {
+            self.result_cursor = Some((connection_id, cursor));
+        }
+    }
+
+    fn fetch_more_rows(&mut self, fetch: RowFetch) {
+        let Some((connection_id, mut cursor)) = self.result_cursor.take() else {
+            return;
+        };
+
+        let remaining = self.row_limit().saturating_sub(cursor.rows_fetched());
+        let max_rows = match fetch {
+            RowFetch::NextPage => self.result_table.page_size().min(remaining),
+            RowFetch::All => remaining,
+        };
+
+        self.result_table.set_fetching(true);
+        let description = format!("Fetch rows: {}", summarize_sql(cursor.sql()));
+        let on_panic = TaskOutput::Failed {
+            kind: TaskKind::Fetch,
+        };
+        self.tasks.spawn_or(description, on_panic, async move {
+            // The cursor travels with the task and comes back with the rows
+            let result = cursor.fetch(max_rows).await;
+            TaskOutput::RowsFetched {
+                connection_id,
+                cursor,
+                result,
+            }
+        });
+    }
+
+    fn on_rows_fetched(
+        &mut self,
+        connection_id: String,
+        cursor: QueryCursor,
+        result: Result<Vec<QueryRow>, DatabaseError>,
+    ) {
+        match result {
+            Ok(rows) => {
+                log::info!("Fetched {} more rows", rows.len());
+                self.result_table.append_rows(rows);
+                if self.connections.contains_key(&connection_id) {
+                    self.keep_result_cursor(connection_id, cursor);
+                } else {
+                    self.result_table.set_fetch_state(false, false);
+                }
+            }
+            Err(err) => {
+                self.last_error = Some(format!("Failed to fetch rows: {}", err));
+                self.result_table.set_fetch_state(false, false);
+                log::error!("Failed to fetch rows: {}", err);
+            }
+        }
+    }
+
+    /// Reset what waited on a task that panicked
+    fn on_task_failed(&mut self, kind: TaskKind) {
+        log::error!("{:?} task panicked", kind);
+        match kind {
+            TaskKind::Query => {
+                self.last_error = Some("The query failed unexpectedly".to_string());
+                self.running_query = None;
+                self.query_editor.set_executing(false);
+            }
+            TaskKind::Script => {
+                self.last_error = Some("The script failed unexpectedly".to_string());
+                if let Some(run) = &self.script_run {
+                    if let (Some(_), Some(statement)) =
+                        (&run.session, run.statements.get(run.current))
+                    {
+                        self.script_results
+                            .add_error(statement, "Failed unexpectedly".to_string());
+                    }
+                }
+                self.finish_script();
+            }
+            TaskKind::Fetch => {
+                // The cursor went down with the task
+                self.last_error = Some("Fetching rows failed unexpectedly".to_string());
+                self.result_table.set_fetch_state(false, false);
+            }
+            TaskKind::Cancel => {
+                self.last_error = Some("Cancelling the query failed unexpectedly".to_string());
+                self.query_editor.set_cancelling(false);
+            }
+        }
+    }
+
+    /// Whether the driver of the running statement can cancel it
+    fn can_cancel_query(&self) -> bool {
+        self.running_query
+            .as_deref()
+            .and_then(|connection_id| self.driver_capabilities(connection_id))
+            .is_some_and(|capabilities| capabilities.supports_cancellation)
+    }
+
+    fn cancel_query(&mut self) {
+        if !self.can_cancel_query() {
+            return;
+        }
+        let Some(connection) = self
+            .running_query
+            .as_ref()
+            .and_then(|connection_id| self.connections.get(connection_id))
+            .cloned()
+        else {
+            return;
+        };
+
+        // A script stops after the statement running now
+        if let Some(run) = &mut self.script_run {
+            run.cancelled = true;
+        }
+
+        self.query_editor.set_cancelling(true);
+        let on_panic = TaskOutput::Failed {
+            kind: TaskKind::Cancel,
+        };
+        self.tasks
+            .spawn_or("Cancelling query", on_panic, async move {
+                let result = connection.cancel_query().await;
+                TaskOutput::CancelRequested { result }
+            });
+    }
+
+    fn on_cancel_requested(&mut self, result: Result<(), DatabaseError>) {
+        if let Err(err) = result {
+            self.last_error = Some(format!("Failed to cancel query: {}", err));
+            self.query_editor.set_cancelling(false);
+            log::error!("Failed to cancel query: {}", err);
+        }
+    }
+
+    /// Generate the statements recreating a tree object, shown in the DDL dialog
+    fn generate_ddl(
+        &mut self,
+        connection_id: &str,
+        category: ObjectCategory,
+        schema: String,
+        name: String,
+    ) {
+        let Some(connection) = self.connections.get(connection_id).cloned() else {
+            return;
+        };
+
+        let title = format!("{}.{}", schema, name);
+        self.ddl_dialog.show_loading(title.clone());
+        let description = format!("Generating DDL of {}", title);
+        self.tasks.spawn(description, async move {
+            let result = connection.get_object_ddl(&category, &schema, &name).await;
+            TaskOutput::DdlGenerated { title, result }
+        });
+    }
+
+    /// Read what a tree object depends on and what depends on it, shown in
+    /// the dependencies dialog
+    fn load_dependencies(
+        &mut self,
+        connection_id: &str,
+        category: ObjectCategory,
+        schema: String,
+        name: String,
+    ) {
+        let Some(connection) = self.connections.get(connection_id).cloned() else {
+            return;
+        };
+
+        let title = format!("{}.{}", schema, name);
+        self.dependencies_dialog.show_loading(title.clone());
+        let description = format!("Loading dependencies of {}", title);
+        self.tasks.spawn(description, async move {
+            let result = match connection.get_dependencies(&category, &schema, &name).await {
+                Ok(dependencies) => connection
+                    .get_dependents(&category, &schema, &name)
+                    .await
+                    .map(|dependents| (dependencies, dependents)),
+                Err(err) => Err(err),
+            };
+            TaskOutput::DependenciesLoaded { title, result }
+        });
+    }
+
+    /// Show the ER diagram tab of a schema, opening it if needed
+    fn open_diagram(&mut self, connection_id: String, schema: String) {
+        if let Some(index) = self
+            .diagrams
+            .iter()
+            .position(|d| d.connection_id == connection_id && d.schema == schema)
+        {
+            self.active_diagram = Some(index);
+            return;
+        }
+
+        let saved_layout = self
+            .settings
+            .diagram_layouts
+            .get(&AppSettings::diagram_key(&connection_id, &schema))
+            .cloned()
+            .unwrap_or_default();
+        self.diagrams
+            .push(ErDiagramView::new(&connection_id, &schema, saved_layout));
+        self.active_diagram = Some(self.diagrams.len() - 1);
+        self.load_diagram(connection_id, schema);
+    }
+
+    fn load_diagram(&mut self, connection_id: String, schema: String) {
+        let Some(connection) = self.connections.get(&connection_id).cloned() else {
+            if let Some(diagram) = self
+                .diagrams
+                .iter_mut()
+                .find(|d| d.connection_id == connection_id && d.schema == schema)
+            {
+                diagram.set_model(Err(DatabaseError::NotConnected.to_string()));
+            }
+            return;
+        };
+
+        let description = format!("Loading ER diagram of {}", schema);
+        self.tasks.spawn(description, async move {
+            let result = ErModel::load(connection.as_ref(), &schema).await;
+            TaskOutput::DiagramLoaded {
+                connection_id,
+                schema,
+                result,
+            }
+        });
+    }
+
+    /// Reload diagrams on request and save the positions the user arranged
+    fn handle_diagram_requests(&mut self) {
+        let mut reloads = Vec::new();
+        let mut layout_changed = false;
+        for diagram in &mut self.diagrams {
+            if diagram.take_reload_request() {
+                reloads.push((diagram.connection_id.clone(), diagram.schema.clone()));
+            }
+            if let Some(layout) = diagram.take_layout_change() {
+                self.settings.diagram_layouts.insert(
+                    AppSettings::diagram_key(&diagram.connection_id, &diagram.schema),
+                    layout,
+                );
+                layout_changed = true;
+            }
+        }
+        if layout_changed {
+            if let Err(err) = self.settings.save() {
+                self.last_error = Some(format!("Failed to save settings: {}", err));
+            }
+        }
+        for (connection_id, schema) in reloads {
+            self.load_diagram(connection_id, schema);
+        }
+    }
+
+    /// Tabs switching between the SQL editor and the open diagrams
+    fn render_tab_strip(&mut self, ui: &mut egui::Ui) {
+        let mut closed = None;
+        ui.horizontal(|ui| {
+            if ui
+                .selectable_label(self.active_diagram.is_none(), "📝 SQL Editor")
+                .clicked()
+            {
+                self.active_diagram = None;
+            }
+            for (index, diagram) in self.diagrams.iter().enumerate() {
+                let tab = ui.selectable_label(
+                    self.active_diagram == Some(index),
+                    format!("🗺 {}", diagram.title()),
+                );
+                let connection_name = self
+                    .connection_params(&diagram.connection_id)
+                    .map(|params| params.name.clone())
+                    .unwrap_or_else(|| diagram.connection_id.clone());
+                if tab.on_hover_text(connection_name).clicked() {
+                    self.active_diagram = Some(index);
+                }
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    closed = Some(index);
+                }
+            }
+        });
+
+        if let Some(index) = closed {
+            self.diagrams.remove(index);
+            self.active_diagram = match self.active_diagram {
+                Some(active) if active == index => None,
+                Some(active) if active > index => Some(active - 1),
+                active => active,
+            };
+        }
+    }
+
+    fn compare_schemas(&mut self, request: CompareRequest) {
+        let (Some(source), Some(target)) = (
+            self.connections.get(&request.source_connection).cloned(),
+            self.connections.get(&request.target_connection).cloned(),
+        ) else {
+            return;
+        };
+        let Some(target_type) = self
+            .connection_params(&request.target_connection)
+            .map(|params| params.database_type.clone())
+        else {
+            return;
+        };
+
+        let description = format!(
+            "Comparing {} with {}",
+            request.source_schema, request.target_schema
+        );
+        self.tasks.spawn(description, async move {
+            let result = schema_diff::compare_schemas(
+                source.as_ref(),
+                &request.source_schema,
+                target.as_ref(),
+                &request.target_schema,
+                &target_type,
+            )
+            .await
+            .map(|(diff, script)| (Box::new(diff), script));
+            TaskOutput::SchemasCompared { request, result }
+        });
+    }
+
     fn handle_tree_expansion_requests(&mut self) {
         // Get schemas that need table loading
         let schemas_to_load = self.database_tree.get_schemas_needing_tables();
-        for (connection_id, schema_name) in schemas_to_load {
-            if let Some(connection) = self.connections.get(&connection_id) {
-                match self.runtime.block_on(connection.get_tables(&schema_name)) {
+        for (connection_id, schema) in schemas_to_load {
+            if let Some(connection) = self.connections.get(&connection_id).cloned() {
+                let description = format!("Loading tables of {}", schema);
+                self.tasks.spawn(description, async move {
+                    let result = connection.get_tables(&schema).await;
+                    TaskOutput::TablesLoaded {
+                        connection_id,
+                        schema,
+                        result,
+                    }
+                });
+            }
+        }
+
+        // Get tables that need column loading
+        let tables_to_load = self.database_tree.get_tables_needing_columns();
+        for (connection_id, schema, table) in tables_to_load {
+            if let Some(connection) = self.connections.get(&connection_id).cloned() {
+                let description = format!("Loading columns of {}.{}", schema, table);
+                let task = (
+                    connection.clone(),
+                    connection_id.clone(),
+                    schema.clone(),
+                    table.clone(),
+                );
+                self.tasks.spawn(description, async move {
+                    let (connection, connection_id, schema, table) = task;
+                    let result = connection.get_columns(&schema, &table).await;
+                    TaskOutput::ColumnsLoaded {
+                        connection_id,
+                        schema,
+                        table,
+                        result,
+                    }
+                });
+
+                let description = format!("Loading keys of {}.{}", schema, table);
+                self.tasks.spawn(description, async move {
+                    let result = match connection.get_foreign_keys(&schema, &table).await {
+                        Ok(foreign_keys) => connection
+                            .get_constraints(&schema, &table)
+                            .await
+                            .map(|constraints| (foreign_keys, constraints)),
+                        Err(err) => Err(err),
+                    };
+                    TaskOutput::KeysLoaded {
+                        connection_id,
+                        schema,
+                        table,
+                        result,
+                    }
+                });
+            }
+        }
+
+        // Get schemas that need object loading
+        let objects_to_load = self.database_tree.get_schemas_needing_objects();
+        for (connection_id, schema, category) in objects_to_load {
+            match category {
+                ObjectCategory::Tables => {
+                    // Tables are already handled above
+                    continue;
+                }
+                ObjectCategory::SystemCatalog => {
+                    // TODO: Implement system catalog loading
+                    log::info!("System catalog loading not yet implemented");
+                    continue;
+                }
+                _ => {}
+            }
+
+            if let Some(connection) = self.connections.get(&connection_id).cloned() {
+                let description = format!("Loading {} of {}", category_label(&category), schema);
+                self.tasks.spawn(description, async move {
+                    let result = match category {
+                        ObjectCategory::Views => connection
+                            .get_views(&schema)
+                            .await
+                            .map(SchemaObjects::Views),
+                        ObjectCategory::Functions => connection
+                            .get_functions(&schema)
+                            .await
+                            .map(SchemaObjects::Functions),
+                        ObjectCategory::Triggers => connection
+                            .get_triggers(&schema)
+                            .await
+                            .map(SchemaObjects::Triggers),
+                        ObjectCategory::Sequences => connection
+                            .get_sequences(&schema)
+                            .await
+                            .map(SchemaObjects::Sequences),
+                        ObjectCategory::Indexes => connection
+                            .get_indexes(&schema)
+                            .await
+                            .map(SchemaObjects::Indexes),
+                        ObjectCategory::Tables | ObjectCategory::SystemCatalog => {
+                            unreachable!("filtered out before spawning")
+                        }
+                    };
+
+                    TaskOutput::ObjectsLoaded {
+                        connection_id,
+                        schema,
+                        category,
+                        result,
+                    }
+                });
+            }
+        }
+    }
+
+    fn on_objects_loaded(
+        &mut self,
+        connection_id: &str,
+        schema: String,
+        category: ObjectCategory,
+        result: Result<SchemaObjects, DatabaseError>,
+    ) {
+        let label = category_label(&category);
+        match result {
+            Ok(objects) => {
+                log::info!(
+                    "Loaded {} for schema: {} in connection: {}",
+                    label,
+                    schema,
+                    connection_id
+                );
+
+                match objects {
+                    SchemaObjects::Views(views) => {
+                        self.database_tree.set_views(connection_id, schema, views)
+                    }
+                    SchemaObjects::Functions(functions) => {
+                        self.database_tree
+                            .set_functions(connection_id, schema, functions)
+                    }
+                    SchemaObjects::Triggers(triggers) => {
+                        self.database_tree
+                            .set_triggers(connection_id, schema, triggers)
+                    }
+                    SchemaObjects::Sequences(sequences) => {
+                        self.database_tree
+                            .set_sequences(connection_id, schema, sequences)
+                    }
+                    SchemaObjects::Indexes(indexes) => {
+                        self.database_tree
+                            .set_indexes(connection_id, schema, indexes)
+                    }
+                }
+            }
+            Err(err) => {
+                self.last_error = Some(format!(
+                    "Failed to load {} for schema {} in connection {}: {}",
+                    label, schema, connection_id, err
+                ));
+                log::error!(
+                    "Failed to load {} for schema {} in connection {}: {}",
+                    label,
+                    schema,
+                    connection_id,
+                    err
+                );
+            }
+        }
+    }
+
+    /// Apply the results of background tasks that finished since the last frame
+    fn process_finished_tasks(&mut self) {
+        for output in self.tasks.poll() {
+            match output {
+                TaskOutput::Connected { params, result } => self.on_connected(params, result),
+                TaskOutput::ConnectionTested { name, result } => {
+                    self.on_connection_tested(&name, result)
+                }
+                TaskOutput::SchemasLoaded {
+                    connection_id,
+                    result,
+                } => self.on_schemas_loaded(&connection_id, result),
+                TaskOutput::QueryExecuted {
+                    result,
+                    execution_time,
+                } => self.on_query_executed(result, execution_time),
+                TaskOutput::ScriptSessionOpened { result } => self.on_script_session_opened(result),
+                TaskOutput::StatementExecuted { result } => self.on_statement_executed(result),
+                TaskOutput::RowsFetched {
+                    connection_id,
+                    cursor,
+                    result,
+                } => self.on_rows_fetched(connection_id, cursor, result),
+                TaskOutput::CancelRequested { result } => self.on_cancel_requested(result),
+                TaskOutput::Failed { kind } => self.on_task_failed(kind),
+                TaskOutput::EditTargetLoaded {
+                    connection_id,
+                    schema,
+                    table,
+                    result,
+                } => self.on_edit_target_loaded(&connection_id, &schema, &table, result),
+                TaskOutput::ResultKeysLoaded {
+                    connection_id,
+                    schema,
+                    table,
+                    result,
+                } => match result {
+                    Ok(foreign_keys) => {
+                        if self.result_connection.as_deref() == Some(connection_id.as_str()) {
+                            self.result_table
+                                .set_foreign_keys(&schema, &table, foreign_keys);
+                        }
+                    }
+                    // The result stays usable; its cells just lead nowhere
+                    Err(err) => log::warn!(
+                        "Failed to load foreign keys of {}.{}: {}",
+                        schema,
+                        table,
+                        err
+                    ),
+                },
+                TaskOutput::DdlGenerated { title, result } => self
+                    .ddl_dialog
+                    .set_ddl(&title, result.map_err(|err| err.to_string())),
+                TaskOutput::DependenciesLoaded { title, result } => self
+                    .dependencies_dialog
+                    .set_dependencies(&title, result.map_err(|err| err.to_string())),
+                TaskOutput::DiagramLoaded {
+                    connection_id,
+                    schema,
+                    result,
+                } => {
+                    if let Some(diagram) = self
+                        .diagrams
+                        .iter_mut()
+                        .find(|d| d.connection_id == connection_id && d.schema == schema)
+                    {
+                        diagram.set_model(result.map_err(|err| err.to_string()));
+                    }
+                }
+                TaskOutput::SchemasCompared { request, result } => self.schema_compare.set_result(
+                    &request,
+                    result
+                        .map(|(diff, script)| (*diff, script))
+                        .map_err(|err| err.to_string()),
+                ),
+                TaskOutput::KeysLoaded {
+                    connection_id,
+                    schema,
+                    table,
+                    result,
+                } => match result {
+                    Ok((foreign_keys, constraints)) => {
+                        self.database_tree.set_keys(
+                            &connection_id,
+                            schema,
+                            table,
+                            foreign_keys,
+                            constraints,
+                        );
+                    }
+                    Err(err) => {
+                        log::error!(
+                            "Failed to load keys for table {}.{} in connection {}: {}",
+                            schema,
+                            table,
+                            connection_id,
+                            err
+                        );
+                    }
+                },
+                TaskOutput::ChangesSaved {
+                    connection_id,
+                    query,
+                    result,
+                } => self.on_changes_saved(&connection_id, &query, result),
+                TaskOutput::TablesLoaded {
+                    connection_id,
+                    schema,
+                    result,
+                } => match result {
                     Ok(tables) => {
                         self.database_tree
-                            .set_tables(&connection_id, schema_name.clone(), tables);
+                            .set_tables(&connection_id, schema.clone(), tables);
                         log::info!(
                             "Loaded tables for schema: {} in connection: {}",
-                            schema_name,
+                            schema,
                             connection_id
                         );
                     }
                     Err(err) => {
                         self.last_error = Some(format!(
                             "Failed to load tables for schema {} in connection {}: {}",
-                            schema_name, connection_id, err
+                            schema, connection_id, err
                         ));
                         log::error!(
                             "Failed to load tables for schema {} in connection {}: {}",
-                            schema_name,
+                            schema,
                             connection_id,
                             err
                         );
                     }
-                }
-            }
-        }
-
-        // Get tables that need column loading
-        let tables_to_load = self.database_tree.get_tables_needing_columns();
-        for (connection_id, schema_name, table_name) in tables_to_load {
-            if let Some(connection) = self.connections.get(&connection_id) {
-                match self
-                    .runtime
-                    .block_on(connection.get_columns(&schema_name, &table_name))
-                {
+                },
+                TaskOutput::ColumnsLoaded {
+                    connection_id,
+                    schema,
+                    table,
+                    result,
+                } => match result {
                     Ok(columns) => {
                         self.database_tree.set_columns(
                             &connection_id,
-                            schema_name.clone(),
-                            table_name.clone(),
+                            schema.clone(),
+                            table.clone(),
                             columns,
                         );
                         log::info!(
                             "Loaded columns for table: {}.{} in connection: {}",
-                            schema_name,
-                            table_name,
+                            schema,
+                            table,
                             connection_id
                         );
                     }
                     Err(err) => {
                         self.last_error = Some(format!(
                             "Failed to load columns for table {}.{} in connection {}: {}",
-                            schema_name, table_name, connection_id, err
+                            schema, table, connection_id, err
                         ));
                         log::error!(
                             "Failed to load columns for table {}.{} in connection {}: {}",
-                            schema_name,
-                            table_name,
+                            schema,
+                            table,
                             connection_id,
                             err
                         );
                     }
-                }
+                },
+                TaskOutput::ObjectsLoaded {
+                    connection_id,
+                    schema,
+                    category,
+                    result,
+                } => self.on_objects_loaded(&connection_id, schema, category, result),
             }
         }
+    }
 
-        // Get schemas that need object loading
-        let objects_to_load = self.database_tree.get_schemas_needing_objects();
-        for (connection_id, schema_name, category) in objects_to_load {
-            if let Some(connection) = self.connections.get(&connection_id) {
-                match category {
-                    ObjectCategory::Tables => {
-                        // Tables are already handled above
-                        continue;
-                    }
-                    ObjectCategory::Views => {
-                        match self.runtime.block_on(connection.get_views(&schema_name)) {
-                            Ok(views) => {
-                                self.database_tree.set_views(
-                                    &connection_id,
-                                    schema_name.clone(),
-                                    views,
-                                );
-                                log::info!(
-                                    "Loaded views for schema: {} in connection: {}",
-                                    schema_name,
-                                    connection_id
-                                );
-                            }
-                            Err(err) => {
-                                self.last_error = Some(format!(
-                                    "Failed to load views for schema {} in connection {}: {}",
-                                    schema_name, connection_id, err
-                                ));
-                                log::error!(
-                                    "Failed to load views for schema {} in connection {}: {}",
-                                    schema_name,
-                                    connection_id,
-                                    err
-                                );
-                            }
-                        }
-                    }
-                    ObjectCategory::Functions => {
-                        match self
-                            .runtime
-                            .block_on(connection.get_functions(&schema_name))
-                        {
-                            Ok(functions) => {
-                                self.database_tree.set_functions(
-                                    &connection_id,
-                                    schema_name.clone(),
-                                    functions,
-                                );
-                                log::info!(
-                                    "Loaded functions for schema: {} in connection: {}",
-                                    schema_name,
-                                    connection_id
-                                );
-                            }
-                            Err(err) => {
-                                self.last_error = Some(format!(
-                                    "Failed to load functions for schema {} in connection {}: {}",
-                                    schema_name, connection_id, err
-                                ));
-                                log::error!(
-                                    "Failed to load functions for schema {} in connection {}: {}",
-                                    schema_name,
-                                    connection_id,
-                                    err
-                                );
-                            }
-                        }
-                    }
-                    ObjectCategory::Triggers => {
-                        match self.runtime.block_on(connection.get_triggers(&schema_name)) {
-                            Ok(triggers) => {
-                                self.database_tree.set_triggers(
-                                    &connection_id,
-                                    schema_name.clone(),
-                                    triggers,
-                                );
-                                log::info!(
-                                    "Loaded triggers for schema: {} in connection: {}",
-                                    schema_name,
-                                    connection_id
-                                );
-                            }
-                            Err(err) => {
-                                self.last_error = Some(format!(
-                                    "Failed to load triggers for schema {} in connection {}: {}",
-                                    schema_name, connection_id, err
-                                ));
-                                log::error!(
-                                    "Failed to load triggers for schema {} in connection {}: {}",
-                                    schema_name,
-                                    connection_id,
-                                    err
-                                );
-                            }
-                        }
-                    }
-                    ObjectCategory::Sequences => {
-                        match self
-                            .runtime
-                            .block_on(connection.get_sequences(&schema_name))
-                        {
-                            Ok(sequences) => {
-                                self.database_tree.set_sequences(
-                                    &connection_id,
-                                    schema_name.clone(),
-                                    sequences,
-                                );
-                                log::info!(
-                                    "Loaded sequences for schema: {} in connection: {}",
-                                    schema_name,
-                                    connection_id
-                                );
-                            }
-                            Err(err) => {
-                                self.last_error = Some(format!(
-                                    "Failed to load sequences for schema {} in connection {}: {}",
-                                    schema_name, connection_id, err
-                                ));
-                                log::error!(
-                                    "Failed to load sequences for schema {} in connection {}: {}",
-                                    schema_name,
-                                    connection_id,
-                                    err
-                                );
-                            }
-                        }
-                    }
-                    ObjectCategory::Indexes => {
-                        match self.runtime.block_on(connection.get_indexes(&schema_name)) {
-                            Ok(indexes) => {
-                                self.database_tree.set_indexes(
-                                    &connection_id,
-                                    schema_name.clone(),
-                                    indexes,
-                                );
-                                log::info!(
-                                    "Loaded indexes for schema: {} in connection: {}",
-                                    schema_name,
-                                    connection_id
-                                );
-                            }
-                            Err(err) => {
-                                self.last_error = Some(format!(
-                                    "Failed to load indexes for schema {} in connection {}: {}",
-                                    schema_name, connection_id, err
-                                ));
-                                log::error!(
-                                    "Failed to load indexes for schema {} in connection {}: {}",
-                                    schema_name,
-                                    connection_id,
-                                    err
-                                );
-                            }
-                        }
-                    }
-                    ObjectCategory::SystemCatalog => {
-                        // TODO: Implement system catalog loading
-                        log::info!("System catalog loading not yet implemented");
-                    }
-                }
-            }
-        }
+    fn connection_params(&self, connection_id: &str) -> Option<&ConnectionParams> {
+        self.saved_connections
+            .iter()
+            .find(|c| c.id == connection_id)
+    }
+
+    /// Features of the driver behind a connection
+    fn driver_capabilities(&self, connection_id: &str) -> Option<&DriverCapabilities> {
+        self.connection_params(connection_id)
+            .and_then(|params| self.drivers.capabilities(&params.database_type))
+    }
+
+    /// Statement timeout in seconds that applies to a connection
+    fn query_timeout(&self, connection_id: &str) -> u32 {
+        let default_timeout = self.settings.general_settings.query_timeout;
+        self.connection_params(connection_id)
+            .map_or(default_timeout, |params| {
+                params.effective_query_timeout(default_timeout)
+            })
+    }
+
+    /// Display name of a connection, falling back to its ID
+    fn connection_name(&self, connection_id: &str) -> String {
+        self.saved_connections
+            .iter()
+            .find(|c| c.id == connection_id)
+            .map(|c| c.name.clone())
+            .unwrap_or_else(|| connection_id.to_string())
     }
 
     fn save_connection_if_new(&mut self, params: ConnectionParams) {
@@ -976,3 +2324,81 @@ impl RBeaverApp {
         }
     }
 }
+
+/// Extra time the client waits past a server-enforced statement timeout
+const CLIENT_TIMEOUT_GRACE: Duration = Duration::from_secs(5);
+
+/// Open a cursor on `connection` and fetch its first `first_rows` rows,
+/// giving up once `limit` has passed
+///
+/// On expiry the statement is cancelled where the driver supports it, so it
+/// does not keep running on the server after the client stopped waiting.
+async fn execute_with_timeout(
+    connection: &dyn Database,
+    cursor: impl Future<Output = Result<QueryCursor, DatabaseError>>,
+    first_rows: usize,
+    limit: Option<Duration>,
+) -> Result<(QueryResult, QueryCursor), DatabaseError> {
+    let query = async {
+        let start_time = Instant::now();
+        let mut cursor = cursor.await?;
+        let rows = cursor.fetch(first_rows).await?;
+        let result = cursor
+            .result_with_rows(rows)
+            .with_execution_time(start_time.elapsed());
+        Ok((result, cursor))
+    };
+
+    let Some(limit) = limit else {
+        return query.await;
+    };
+
+    tokio::pin!(query);
+    tokio::select! {
+        result = &mut query => result,
+        _ = tokio::time::sleep(limit) => {
+            if let Err(err) = connection.cancel_query().await {
+                log::warn!("Could not cancel timed out query: {}", err);
+            }
+            Err(DatabaseError::Timeout(format!(
+                "no result after {} seconds",
+                limit.as_secs()
+            )))
+        }
+    }
+}
+
+/// Human-readable statement timeout, where 0 means no limit
+fn format_query_timeout(seconds: u32) -> String {
+    if seconds == 0 {
+        "none".to_string()
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Lower-case plural name of an object category for messages
+fn category_label(category: &ObjectCategory) -> &'static str {
+    match category {
+        ObjectCategory::Tables => "tables",
+        ObjectCategory::Views => "views",
+        ObjectCategory::Functions => "functions",
+        ObjectCategory::Triggers => "triggers",
+        ObjectCategory::Sequences => "sequences",
+        ObjectCategory::Indexes => "indexes",
+        ObjectCategory::SystemCatalog => "system catalog",
+    }
+}
+
+/// First line of a statement, shortened for the running tasks list
+fn summarize_sql(sql: &str) -> String {
+    const MAX_CHARS: usize = 60;
+
+    let first_line = sql.trim().lines().next().unwrap_or_default();
+    if first_line.chars().count() > MAX_CHARS {
+        let truncated: String = first_line.chars().take(MAX_CHARS).collect();
+        format!("{}…", truncated)
+    } else {
+        first_line.to_string()
+    }
+}
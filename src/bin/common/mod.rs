@@ -0,0 +1,45 @@
+//! Fixtures shared by the test binaries, which include this file with
+//! `#[path = "common/mod.rs"] mod common;`
+
+// Each binary uses only some of the fixtures
+#![allow(dead_code)]
+
+use rbeaver::database::{ConnectionParams, DatabaseType, SslMode};
+
+/// Connection parameters for a local PostgreSQL test server, taken from the
+/// `RBEAVER_PG_*` environment variables
+pub fn server_params() -> Option<ConnectionParams> {
+    let mut params = env_params("RBEAVER_PG", DatabaseType::PostgreSQL, "postgres")?;
+    params.database =
+        std::env::var("RBEAVER_PG_DATABASE").unwrap_or_else(|_| "postgres".to_string());
+    Some(params)
+}
+
+/// Connection parameters for a local MySQL test server, taken from the
+/// `RBEAVER_MYSQL_*` environment variables
+pub fn mysql_server_params() -> Option<ConnectionParams> {
+    let mut params = env_params("RBEAVER_MYSQL", DatabaseType::MySQL, "root")?;
+    params.database = "mysql".to_string();
+    Some(params)
+}
+
+/// Host, port and credentials from `<prefix>_HOST` and friends, or None
+/// without a host
+fn env_params(
+    prefix: &str,
+    database_type: DatabaseType,
+    default_user: &str,
+) -> Option<ConnectionParams> {
+    let var = |name: &str| std::env::var(format!("{}_{}", prefix, name)).ok();
+    let host = var("HOST")?;
+
+    let mut params = ConnectionParams::new(format!("{:?} Test", database_type), database_type);
+    params.host = host;
+    if let Some(port) = var("PORT").and_then(|p| p.parse().ok()) {
+        params.port = port;
+    }
+    params.username = var("USER").unwrap_or_else(|| default_user.to_string());
+    params.password = var("PASSWORD").unwrap_or_default();
+    params.ssl_mode = SslMode::Disable;
+    Some(params)
+}
@@ -0,0 +1,132 @@
+use rbeaver::ui::task_panel::format_elapsed;
+use rbeaver::utils::TaskManager;
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+/// Poll until every task has finished or the deadline passes
+fn wait_for_all<T: Send + 'static>(tasks: &mut TaskManager<T>, timeout: Duration) -> Vec<T> {
+    let deadline = Instant::now() + timeout;
+    let mut outputs = Vec::new();
+    while tasks.has_running_tasks() && Instant::now() < deadline {
+        outputs.extend(tasks.poll());
+        std::thread::sleep(Duration::from_millis(5));
+    }
+    outputs.extend(tasks.poll());
+    outputs
+}
+
+fn main() {
+    println!("🔧 Testing RBeaver Background Tasks");
+    println!("===================================\n");
+
+    let mut tasks = TaskManager::new(Runtime::new().expect("Failed to create async runtime"));
+
+    // Test 1: Tasks run without blocking the caller
+    println!("📋 Test 1: Spawning tasks");
+    let started = Instant::now();
+    for (delay, name) in [
+        (150, "slow query"),
+        (50, "load tables"),
+        (100, "load columns"),
+    ] {
+        tasks.spawn(name, async move {
+            tokio::time::sleep(Duration::from_millis(delay)).await;
+            name
+        });
+    }
+    println!("  ✓ Spawned 3 tasks in {:?}", started.elapsed());
+    for task in tasks.running() {
+        println!(
+            "  ✓ Running #{}: {} ({})",
+            task.id,
+            task.description,
+            format_elapsed(task.elapsed())
+        );
+    }
+
+    // Test 2: Results arrive through polling
+    println!("\n📋 Test 2: Polling for results");
+    let outputs = wait_for_all(&mut tasks, Duration::from_secs(5));
+    for output in &outputs {
+        println!("  ✓ Finished: {}", output);
+    }
+    println!("  ✓ All tasks finished in {:?}", started.elapsed());
+
+    println!("\n🎉 Background task tests completed!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spawn_does_not_block() {
+        let mut tasks = TaskManager::new(Runtime::new().unwrap());
+
+        let started = Instant::now();
+        let id = tasks.spawn("sleep", async {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            42
+        });
+
+        assert!(started.elapsed() < Duration::from_millis(100));
+        assert_eq!(tasks.running()[0].id, id);
+        assert_eq!(tasks.running().len(), 1);
+        assert_eq!(tasks.running()[0].description, "sleep");
+        assert!(tasks.poll().is_empty());
+
+        let outputs = wait_for_all(&mut tasks, Duration::from_secs(5));
+        assert_eq!(outputs, vec![42]);
+        assert!(!tasks.has_running_tasks());
+    }
+
+    #[test]
+    fn test_results_arrive_in_completion_order() {
+        let mut tasks = TaskManager::new(Runtime::new().unwrap());
+
+        let slow = tasks.spawn("slow", async {
+            tokio::time::sleep(Duration::from_millis(150)).await;
+            "slow"
+        });
+        let fast = tasks.spawn("fast", async {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            "fast"
+        });
+        assert_ne!(slow, fast);
+
+        let outputs = wait_for_all(&mut tasks, Duration::from_secs(5));
+        assert_eq!(outputs, vec!["fast", "slow"]);
+    }
+
+    #[test]
+    fn test_panicking_task_is_removed() {
+        let mut tasks = TaskManager::<u32>::new(Runtime::new().unwrap());
+
+        tasks.spawn("panics", async { panic!("task failure") });
+        tasks.spawn("succeeds", async { 7 });
+
+        let outputs = wait_for_all(&mut tasks, Duration::from_secs(5));
+        assert_eq!(outputs, vec![7]);
+        assert!(!tasks.has_running_tasks());
+    }
+
+    #[test]
+    fn test_panicking_task_delivers_its_failure() {
+        let mut tasks = TaskManager::<Result<u32, &str>>::new(Runtime::new().unwrap());
+
+        tasks.spawn_or("panics", Err("query"), async { panic!("task failure") });
+        tasks.spawn_or("succeeds", Err("fetch"), async { Ok(7) });
+
+        let mut outputs = wait_for_all(&mut tasks, Duration::from_secs(5));
+        outputs.sort();
+        assert_eq!(outputs, vec![Ok(7), Err("query")]);
+        assert!(!tasks.has_running_tasks());
+    }
+
+    #[test]
+    fn test_format_elapsed() {
+        assert_eq!(format_elapsed(Duration::from_millis(0)), "0:00.0");
+        assert_eq!(format_elapsed(Duration::from_millis(2_345)), "0:02.3");
+        assert_eq!(format_elapsed(Duration::from_secs(75)), "1:15.0");
+    }
+}
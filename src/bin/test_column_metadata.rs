@@ -0,0 +1,178 @@
+use rbeaver::database::{
+    DatabaseConnection, PostgreSQLConnection, QueryExecutor, QueryResult, QuerySession,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const SETUP_SCRIPT: &[&str] = &[
+    "CREATE DOMAIN pg_temp.email AS varchar(40)",
+    "CREATE TYPE pg_temp.mood AS ENUM ('happy', 'sad')",
+    "CREATE TEMP TABLE people (
+        id serial PRIMARY KEY,
+        name varchar(20) NOT NULL,
+        balance numeric(10, 2),
+        contact pg_temp.email,
+        feeling pg_temp.mood,
+        tags integer[] NOT NULL DEFAULT '{}',
+        seen_at time(3)
+    )",
+    "INSERT INTO people (name, balance, contact, feeling, tags, seen_at)
+     VALUES ('Alice', 12.50, 'alice@example.com', 'happy', '{1,2}', '12:00')",
+];
+
+const PEOPLE_QUERY: &str = "SELECT id, name AS person, balance, contact, feeling, tags, seen_at,
+        balance * 2 AS doubled
+     FROM people";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Result Column Metadata");
+    println!("=========================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    let session = connection.open_session().await?;
+
+    // Test 1: Columns of a table query
+    println!("📋 Test 1: Describing the columns of a query");
+    let result = query_people(&session).await?;
+    for column in &result.columns {
+        println!(
+            "  ✓ {}: {}{}{}",
+            column.name,
+            column.display_type(),
+            if column.nullable { "" } else { " NOT NULL" },
+            column
+                .source()
+                .map(|source| format!(" (from {})", source))
+                .unwrap_or_default()
+        );
+    }
+    drop(session);
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 Column metadata tests completed!");
+    Ok(())
+}
+
+/// Create the test table on the session and query it
+async fn query_people(session: &QuerySession) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    for sql in SETUP_SCRIPT {
+        let mut cursor = session.open_cursor(sql).await?;
+        cursor.fetch(1).await?;
+    }
+
+    let mut cursor = session.open_cursor(PEOPLE_QUERY).await?;
+    let rows = cursor.fetch(10).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::QueryColumn;
+
+    async fn people_result() -> Option<QueryResult> {
+        let params = common::server_params()?;
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        Some(query_people(&session).await.unwrap())
+    }
+
+    fn column<'a>(result: &'a QueryResult, name: &str) -> &'a QueryColumn {
+        result
+            .get_column_by_name(name)
+            .unwrap_or_else(|| panic!("no column {}", name))
+    }
+
+    #[test]
+    fn test_display_type_prefers_server_spelling() {
+        let mut column = QueryColumn::new("price".to_string(), "NUMERIC".to_string(), 0, true);
+        assert_eq!(column.display_type(), "NUMERIC");
+        assert_eq!(column.source(), None);
+
+        column.type_name = Some("numeric(10,2)".to_string());
+        column.source_table = Some("products".to_string());
+        assert_eq!(column.display_type(), "numeric(10,2)");
+        assert_eq!(column.source().as_deref(), Some("products.price"));
+    }
+
+    #[tokio::test]
+    async fn test_base_columns_report_their_source() {
+        let Some(result) = people_result().await else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let person = column(&result, "person");
+        assert!(person.table_oid.is_some());
+        assert_eq!(person.column_number, Some(2));
+        // Temporary tables are qualified with the session's temporary schema
+        assert!(person.source().unwrap().ends_with("people.name"));
+        assert_eq!(person.source_column.as_deref(), Some("name"));
+        assert!(!person.nullable);
+        assert_eq!(person.type_name.as_deref(), Some("character varying(20)"));
+        assert_eq!(person.precision, Some(20));
+
+        let id = column(&result, "id");
+        assert_eq!(id.column_number, Some(1));
+        assert!(!id.nullable);
+        assert_eq!(id.type_name.as_deref(), Some("integer"));
+    }
+
+    #[tokio::test]
+    async fn test_type_details() {
+        let Some(result) = people_result().await else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let balance = column(&result, "balance");
+        assert!(balance.nullable);
+        assert_eq!(balance.type_name.as_deref(), Some("numeric(10,2)"));
+        assert_eq!((balance.precision, balance.scale), (Some(10), Some(2)));
+
+        let tags = column(&result, "tags");
+        assert!(!tags.nullable);
+        assert_eq!(tags.type_name.as_deref(), Some("integer[]"));
+        assert_eq!(tags.element_type.as_deref(), Some("integer"));
+
+        let seen_at = column(&result, "seen_at");
+        assert_eq!(
+            seen_at.type_name.as_deref(),
+            Some("time(3) without time zone")
+        );
+        assert_eq!(seen_at.precision, Some(3));
+
+        // Domains take precision from their base type; enums keep their name
+        let contact = column(&result, "contact");
+        assert!(contact.display_type().ends_with("email"));
+        assert_eq!(contact.precision, Some(40));
+        assert!(column(&result, "feeling").display_type().ends_with("mood"));
+    }
+
+    #[tokio::test]
+    async fn test_computed_columns_have_no_source() {
+        let Some(result) = people_result().await else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let doubled = column(&result, "doubled");
+        assert_eq!(doubled.table_oid, None);
+        assert_eq!(doubled.source(), None);
+        assert!(doubled.nullable);
+        assert_eq!(doubled.type_name.as_deref(), Some("numeric"));
+        assert_eq!((doubled.precision, doubled.scale), (None, None));
+    }
+}
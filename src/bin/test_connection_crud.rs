@@ -54,6 +54,7 @@ fn test_create_connections() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -68,6 +69,7 @@ fn test_create_connections() {
         password: "mypass".to_string(),
         ssl_mode: SslMode::Require,
         connection_timeout: Some(60),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -114,6 +116,7 @@ fn test_edit_connections() {
         password: "originalpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -165,6 +168,7 @@ fn test_duplicate_connections() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -220,6 +224,7 @@ fn test_delete_connections() {
         password: "testpass1".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -234,6 +239,7 @@ fn test_delete_connections() {
         password: "testpass2".to_string(),
         ssl_mode: SslMode::Require,
         connection_timeout: Some(60),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -282,6 +288,7 @@ fn test_database_tree_integration() {
             password: "pass1".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
         ConnectionParams {
@@ -295,6 +302,7 @@ fn test_database_tree_integration() {
             password: "pass2".to_string(),
             ssl_mode: SslMode::Require,
             connection_timeout: Some(60),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
     ];
@@ -57,6 +57,7 @@ fn test_settings_management() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -71,6 +72,7 @@ fn test_settings_management() {
         password: "mypass".to_string(),
         ssl_mode: SslMode::Require,
         connection_timeout: Some(60),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -137,6 +139,7 @@ fn test_connection_dialog() {
         password: "editpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -199,6 +202,7 @@ fn test_database_tree_integration() {
             password: "pass1".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
         ConnectionParams {
@@ -212,6 +216,7 @@ fn test_database_tree_integration() {
             password: "pass2".to_string(),
             ssl_mode: SslMode::Require,
             connection_timeout: Some(60),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
     ];
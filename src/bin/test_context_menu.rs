@@ -52,6 +52,7 @@ fn test_context_menu_implementation() {
             password: "pass1".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
         ConnectionParams {
@@ -65,6 +66,7 @@ fn test_context_menu_implementation() {
             password: "pass2".to_string(),
             ssl_mode: SslMode::Require,
             connection_timeout: Some(60),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
     ];
@@ -114,6 +116,7 @@ fn test_action_handling() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -163,6 +166,7 @@ fn test_database_tree_integration() {
             password: "password".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
         ConnectionParams {
@@ -176,6 +180,7 @@ fn test_database_tree_integration() {
             password: "apppass".to_string(),
             ssl_mode: SslMode::Require,
             connection_timeout: Some(60),
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
         ConnectionParams {
@@ -189,6 +194,7 @@ fn test_database_tree_integration() {
             password: "".to_string(),
             ssl_mode: SslMode::Disable,
             connection_timeout: None,
+            query_timeout: None,
             additional_params: HashMap::new(),
         },
     ];
@@ -253,6 +259,7 @@ mod tests {
             password: "testpass".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         };
 
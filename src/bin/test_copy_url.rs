@@ -53,6 +53,7 @@ fn test_connection_url_generation() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -85,6 +86,7 @@ fn test_connection_url_generation() {
         password: "apppass".to_string(),
         ssl_mode: SslMode::Require,
         connection_timeout: Some(60),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -107,6 +109,7 @@ fn test_connection_url_generation() {
         password: "".to_string(),
         ssl_mode: SslMode::Disable,
         connection_timeout: None,
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -132,6 +135,7 @@ fn test_context_menu_integration() {
         password: "testpass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -167,6 +171,7 @@ fn test_different_database_types() {
         password: "pass@123".to_string(),
         ssl_mode: SslMode::Require,
         connection_timeout: Some(45),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -185,6 +190,7 @@ fn test_different_database_types() {
         password: "secure_pass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(120),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -203,6 +209,7 @@ fn test_different_database_types() {
         password: "".to_string(),
         ssl_mode: SslMode::Disable,
         connection_timeout: None,
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -227,6 +234,7 @@ fn test_edge_cases() {
         password: "".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -246,6 +254,7 @@ fn test_edge_cases() {
         password: "pass".to_string(),
         ssl_mode: SslMode::Prefer,
         connection_timeout: Some(30),
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -265,6 +274,7 @@ fn test_edge_cases() {
         password: "".to_string(),
         ssl_mode: SslMode::Disable,
         connection_timeout: None,
+        query_timeout: None,
         additional_params: HashMap::new(),
     };
 
@@ -292,6 +302,7 @@ mod tests {
             password: "pass".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         };
 
@@ -314,6 +325,7 @@ mod tests {
             password: "pass".to_string(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         };
 
@@ -336,6 +348,7 @@ mod tests {
             password: "".to_string(),
             ssl_mode: SslMode::Disable,
             connection_timeout: None,
+            query_timeout: None,
             additional_params: HashMap::new(),
         };
 
@@ -0,0 +1,266 @@
+use rbeaver::database::{DatabaseConnection, ObjectCategory, PostgreSQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const SCHEMA: &str = "rbeaver_ddl_demo";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP SCHEMA IF EXISTS rbeaver_ddl_demo CASCADE",
+    "CREATE SCHEMA rbeaver_ddl_demo",
+    "CREATE TABLE rbeaver_ddl_demo.customers (
+        id integer GENERATED ALWAYS AS IDENTITY PRIMARY KEY,
+        email text NOT NULL UNIQUE,
+        name text COLLATE \"C\" DEFAULT 'anonymous',
+        name_length integer GENERATED ALWAYS AS (length(name)) STORED,
+        CONSTRAINT email_has_at CHECK (email LIKE '%@%')
+    )",
+    "CREATE TABLE rbeaver_ddl_demo.orders (
+        id integer GENERATED BY DEFAULT AS IDENTITY PRIMARY KEY,
+        customer_id integer REFERENCES rbeaver_ddl_demo.customers ON DELETE CASCADE,
+        total numeric(10,2)
+    )",
+    "CREATE INDEX orders_total_idx ON rbeaver_ddl_demo.orders (total DESC)",
+    "COMMENT ON TABLE rbeaver_ddl_demo.orders IS 'Placed orders'",
+    "COMMENT ON COLUMN rbeaver_ddl_demo.orders.total IS 'Gross, in euros'",
+    "COMMENT ON INDEX rbeaver_ddl_demo.orders_total_idx IS 'For the largest orders'",
+    "GRANT SELECT, INSERT ON rbeaver_ddl_demo.orders TO PUBLIC",
+    "CREATE VIEW rbeaver_ddl_demo.big_orders AS
+        SELECT id, total FROM rbeaver_ddl_demo.orders WHERE total > 100",
+    "CREATE MATERIALIZED VIEW rbeaver_ddl_demo.order_totals AS
+        SELECT customer_id, sum(total) AS total FROM rbeaver_ddl_demo.orders GROUP BY customer_id",
+    "CREATE FUNCTION rbeaver_ddl_demo.touch() RETURNS trigger LANGUAGE plpgsql
+        AS $$ BEGIN RETURN NEW; END $$",
+    "CREATE FUNCTION rbeaver_ddl_demo.touch(x integer) RETURNS integer LANGUAGE sql
+        AS 'SELECT x'",
+    "CREATE TRIGGER orders_touch BEFORE UPDATE ON rbeaver_ddl_demo.orders
+        FOR EACH ROW EXECUTE FUNCTION rbeaver_ddl_demo.touch()",
+    "CREATE SEQUENCE rbeaver_ddl_demo.invoice_numbers START 1000 INCREMENT 10",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver DDL Generation");
+    println!("=================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    for (category, name) in [
+        (ObjectCategory::Tables, "orders"),
+        (ObjectCategory::Views, "order_totals"),
+        (ObjectCategory::Functions, "touch"),
+        (ObjectCategory::Triggers, "orders_touch"),
+        (ObjectCategory::Sequences, "invoice_numbers"),
+        (ObjectCategory::Indexes, "orders_total_idx"),
+    ] {
+        println!("📋 {:?} {}", category, name);
+        let ddl = connection.get_object_ddl(&category, SCHEMA, name).await?;
+        for line in ddl.lines() {
+            println!("  {}", line);
+        }
+        println!();
+    }
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_ddl_demo CASCADE")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("🎉 DDL tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::ddl::{grant_statements, sequence_ddl, Grant};
+    use rbeaver::database::{split_statements, DatabaseType, Sequence};
+
+    /// Run the statements of a script one at a time
+    async fn run_script(connection: &PostgreSQLConnection, script: &str) {
+        for statement in split_statements(script) {
+            connection.execute_non_query(&statement.sql).await.unwrap();
+        }
+    }
+
+    #[test]
+    fn test_sequence_ddl() {
+        let sequence = Sequence {
+            name: "order_ids".to_string(),
+            schema: "shop".to_string(),
+            data_type: "integer".to_string(),
+            start_value: 1,
+            min_value: Some(1),
+            max_value: None,
+            increment: 1,
+            cycle: false,
+            cache_size: 20,
+            last_value: Some(41),
+            owner_table: Some("orders".to_string()),
+            owner_column: Some("id".to_string()),
+            comment: None,
+        };
+        assert_eq!(
+            sequence_ddl(&DatabaseType::PostgreSQL, &sequence),
+            "CREATE SEQUENCE \"shop\".\"order_ids\"\n    AS integer\n    INCREMENT BY 1\n    \
+             MINVALUE 1\n    NO MAXVALUE\n    START WITH 1\n    CACHE 20\n    NO CYCLE;\n\n\
+             ALTER SEQUENCE \"shop\".\"order_ids\" OWNED BY \"shop\".\"orders\".\"id\";"
+        );
+    }
+
+    #[test]
+    fn test_grant_statements() {
+        let grant = |grantee: Option<&str>, privilege: &str, grantable| Grant {
+            grantee: grantee.map(str::to_string),
+            privilege: privilege.to_string(),
+            grantable,
+        };
+        // Privileges of a grantee share a statement unless they may be passed on
+        let grants = [
+            grant(None, "SELECT", false),
+            grant(Some("clerk"), "INSERT", false),
+            grant(Some("clerk"), "SELECT", true),
+            grant(Some("clerk"), "UPDATE", false),
+        ];
+        assert_eq!(
+            grant_statements(
+                &DatabaseType::PostgreSQL,
+                "TABLE \"shop\".\"orders\"",
+                &grants
+            ),
+            vec![
+                "GRANT SELECT ON TABLE \"shop\".\"orders\" TO PUBLIC;",
+                "GRANT INSERT, UPDATE ON TABLE \"shop\".\"orders\" TO \"clerk\";",
+                "GRANT SELECT ON TABLE \"shop\".\"orders\" TO \"clerk\" WITH GRANT OPTION;",
+            ]
+        );
+        assert!(grant_statements(&DatabaseType::PostgreSQL, "TABLE t", &[]).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_ddl_from_catalog() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The schema is shared with the demo in main, which tests may run beside
+        let schema = "rbeaver_ddl_test";
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in FIXTURE_SQL {
+            connection
+                .execute_non_query(&sql.replace(SCHEMA, schema))
+                .await
+                .unwrap();
+        }
+
+        let customers = connection
+            .get_object_ddl(&ObjectCategory::Tables, schema, "customers")
+            .await
+            .unwrap();
+        for line in [
+            "\"id\" integer GENERATED ALWAYS AS IDENTITY NOT NULL,",
+            "\"name\" text COLLATE pg_catalog.\"C\" DEFAULT 'anonymous'::text,",
+            "\"name_length\" integer GENERATED ALWAYS AS (length(name)) STORED,",
+            "CONSTRAINT \"customers_pkey\" PRIMARY KEY (id),",
+            "CONSTRAINT \"customers_email_key\" UNIQUE (email),",
+        ] {
+            assert!(customers.contains(line), "{} not in\n{}", line, customers);
+        }
+
+        let orders = connection
+            .get_object_ddl(&ObjectCategory::Tables, schema, "orders")
+            .await
+            .unwrap();
+        for line in [
+            "FOREIGN KEY (\"customer_id\") REFERENCES \"rbeaver_ddl_test\".\"customers\" (\"id\") ON DELETE CASCADE\n);",
+            "CREATE INDEX orders_total_idx ON rbeaver_ddl_test.orders USING btree (total DESC);",
+            "COMMENT ON COLUMN \"rbeaver_ddl_test\".\"orders\".\"total\" IS 'Gross, in euros';",
+            "COMMENT ON INDEX \"rbeaver_ddl_test\".\"orders_total_idx\" IS 'For the largest orders';",
+            "GRANT INSERT, SELECT ON TABLE \"rbeaver_ddl_test\".\"orders\" TO PUBLIC;",
+        ] {
+            assert!(orders.contains(line), "{} not in\n{}", line, orders);
+        }
+
+        // Views and triggers go with the table below, so they come first
+        let view = connection
+            .get_object_ddl(&ObjectCategory::Views, schema, "big_orders")
+            .await
+            .unwrap();
+        assert!(view.starts_with(
+            "CREATE OR REPLACE VIEW \"rbeaver_ddl_test\".\"big_orders\" AS\n SELECT "
+        ));
+        let totals = connection
+            .get_object_ddl(&ObjectCategory::Views, schema, "order_totals")
+            .await
+            .unwrap();
+        assert!(totals.starts_with("CREATE MATERIALIZED VIEW"));
+        assert!(totals.contains("\nWITH DATA;\n"));
+        let trigger = connection
+            .get_object_ddl(&ObjectCategory::Triggers, schema, "orders_touch")
+            .await
+            .unwrap();
+        assert_eq!(
+            trigger,
+            "CREATE TRIGGER orders_touch BEFORE UPDATE ON rbeaver_ddl_test.orders \
+             FOR EACH ROW EXECUTE FUNCTION rbeaver_ddl_test.touch();\n"
+        );
+
+        // The statements rebuild the table as it was
+        connection
+            .execute_non_query(&format!("DROP TABLE {}.orders CASCADE", schema))
+            .await
+            .unwrap();
+        run_script(&connection, &orders).await;
+        let rebuilt = connection
+            .get_object_ddl(&ObjectCategory::Tables, schema, "orders")
+            .await
+            .unwrap();
+        assert_eq!(rebuilt, orders);
+
+        // Both overloads of the function, with their signatures
+        let functions = connection
+            .get_object_ddl(&ObjectCategory::Functions, schema, "touch")
+            .await
+            .unwrap();
+        assert!(functions.contains("CREATE OR REPLACE FUNCTION rbeaver_ddl_test.touch()\n"));
+        assert!(
+            functions.contains("CREATE OR REPLACE FUNCTION rbeaver_ddl_test.touch(x integer)\n")
+        );
+        assert!(
+            functions.contains("ALTER FUNCTION \"rbeaver_ddl_test\".\"touch\"(x integer) OWNER TO")
+        );
+
+        let sequence = connection
+            .get_object_ddl(&ObjectCategory::Sequences, schema, "invoice_numbers")
+            .await
+            .unwrap();
+        assert!(sequence.starts_with(
+            "CREATE SEQUENCE \"rbeaver_ddl_test\".\"invoice_numbers\"\n    AS bigint\n    \
+             INCREMENT BY 10\n    MINVALUE 1\n    MAXVALUE 9223372036854775807\n    \
+             START WITH 1000\n    CACHE 1\n    NO CYCLE;"
+        ));
+
+        assert!(connection
+            .get_object_ddl(&ObjectCategory::Indexes, schema, "missing")
+            .await
+            .is_err());
+
+        connection
+            .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+}
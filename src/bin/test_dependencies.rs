@@ -0,0 +1,513 @@
+use rbeaver::database::{DatabaseConnection, ObjectCategory, PostgreSQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const SCHEMA: &str = "rbeaver_deps_demo";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP SCHEMA IF EXISTS rbeaver_deps_demo CASCADE",
+    "CREATE SCHEMA rbeaver_deps_demo",
+    "CREATE TABLE rbeaver_deps_demo.customers (
+        id serial PRIMARY KEY,
+        name text NOT NULL
+    )",
+    "CREATE TABLE rbeaver_deps_demo.orders (
+        id serial PRIMARY KEY,
+        customer_id integer NOT NULL REFERENCES rbeaver_deps_demo.customers,
+        total numeric(10, 2) NOT NULL CHECK (total >= 0)
+    )",
+    "CREATE INDEX orders_customer_idx ON rbeaver_deps_demo.orders (customer_id)",
+    "CREATE VIEW rbeaver_deps_demo.order_totals AS
+        SELECT customer_id, sum(total) AS total
+        FROM rbeaver_deps_demo.orders
+        GROUP BY customer_id",
+    "CREATE VIEW rbeaver_deps_demo.top_customers AS
+        SELECT c.name, t.total
+        FROM rbeaver_deps_demo.customers c
+        JOIN rbeaver_deps_demo.order_totals t ON t.customer_id = c.id",
+    "CREATE FUNCTION rbeaver_deps_demo.touch() RETURNS trigger LANGUAGE plpgsql
+        AS 'BEGIN RETURN NEW; END'",
+    "CREATE TRIGGER orders_touch BEFORE UPDATE ON rbeaver_deps_demo.orders
+        FOR EACH ROW EXECUTE FUNCTION rbeaver_deps_demo.touch()",
+    "CREATE FUNCTION rbeaver_deps_demo.order_count(customer integer) RETURNS bigint
+        LANGUAGE sql
+        AS 'SELECT count(*) FROM rbeaver_deps_demo.orders WHERE customer_id = customer'",
+    "CREATE VIEW rbeaver_deps_demo.customer_orders AS
+        SELECT id, rbeaver_deps_demo.order_count(id) AS orders
+        FROM rbeaver_deps_demo.customers",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Object Dependencies");
+    println!("======================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    for (category, name) in [
+        (ObjectCategory::Tables, "orders"),
+        (ObjectCategory::Views, "order_totals"),
+        (ObjectCategory::Functions, "order_count"),
+    ] {
+        println!("🕸 {}.{}", SCHEMA, name);
+        for dependency in connection.get_dependencies(&category, SCHEMA, name).await? {
+            println!(
+                "   ⬅ {} {} {:?} (depth {})",
+                dependency.kind.label(),
+                dependency.qualified_name(),
+                dependency.columns,
+                dependency.depth
+            );
+        }
+        for dependent in connection.get_dependents(&category, SCHEMA, name).await? {
+            println!(
+                "   ➡ {} {} {:?} (depth {}{})",
+                dependent.kind.label(),
+                dependent.qualified_name(),
+                dependent.columns,
+                dependent.depth,
+                if dependent.automatic {
+                    ", dropped along"
+                } else {
+                    ""
+                }
+            );
+        }
+        println!();
+    }
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_deps_demo CASCADE")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("🎉 Dependency tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::schema_diff::{
+        compare, compare_schemas, migration_script, Definitions, DropDependents, SchemaSnapshot,
+        TableSnapshot,
+    };
+    use rbeaver::database::{Column, DatabaseType, Dependency, DependencyKind};
+
+    fn dependency(kind: DependencyKind, name: &str, table: Option<&str>) -> Dependency {
+        Dependency {
+            kind,
+            schema: "shop".to_string(),
+            name: name.to_string(),
+            table: table.map(str::to_string),
+            columns: Vec::new(),
+            depth: 1,
+            automatic: false,
+        }
+    }
+
+    /// Kind, qualified name, columns, depth and whether it is automatic
+    fn summary(dependency: &Dependency) -> (String, String, Vec<String>, u32, bool) {
+        (
+            dependency.kind.label().to_string(),
+            dependency.qualified_name(),
+            dependency.columns.clone(),
+            dependency.depth,
+            dependency.automatic,
+        )
+    }
+
+    fn fixture(schema: &str) -> Vec<String> {
+        FIXTURE_SQL
+            .iter()
+            .map(|sql| sql.replace(SCHEMA, schema))
+            .collect()
+    }
+
+    #[test]
+    fn test_dependency_kinds() {
+        for name in [
+            "table",
+            "view",
+            "materialized view",
+            "sequence",
+            "index",
+            "function",
+            "procedure",
+            "trigger",
+            "constraint",
+            "column",
+            "default",
+            "policy",
+        ] {
+            assert_eq!(DependencyKind::from_name(name).label(), name);
+        }
+        assert_eq!(
+            DependencyKind::from_name("policy"),
+            DependencyKind::Other("policy".to_string())
+        );
+        assert_eq!(
+            DependencyKind::from_name("default"),
+            DependencyKind::ColumnDefault
+        );
+    }
+
+    #[test]
+    fn test_qualified_names() {
+        let view = dependency(DependencyKind::View, "order_totals", None);
+        assert_eq!(view.qualified_name(), "shop.order_totals");
+
+        let column = dependency(DependencyKind::Column, "total", Some("orders"));
+        assert_eq!(column.qualified_name(), "shop.orders.total");
+        let default = dependency(DependencyKind::ColumnDefault, "id", Some("orders"));
+        assert_eq!(default.qualified_name(), "shop.orders.id");
+
+        let trigger = dependency(DependencyKind::Trigger, "orders_touch", Some("orders"));
+        assert_eq!(trigger.qualified_name(), "orders_touch on shop.orders");
+
+        let policy = Dependency {
+            schema: String::new(),
+            ..dependency(
+                DependencyKind::Other("policy".to_string()),
+                "own_rows on shop.orders",
+                None,
+            )
+        };
+        assert_eq!(policy.qualified_name(), "own_rows on shop.orders");
+    }
+
+    #[test]
+    fn test_script_warns_about_dependents() {
+        let column = |name: &str| Column {
+            name: name.to_string(),
+            data_type: "integer".to_string(),
+            is_nullable: false,
+            default_value: None,
+            is_primary_key: false,
+            comment: None,
+        };
+        let table = |columns: Vec<Column>| TableSnapshot {
+            name: "orders".to_string(),
+            columns,
+            constraints: Vec::new(),
+            foreign_keys: Vec::new(),
+        };
+        let source = SchemaSnapshot {
+            schema: "shop".to_string(),
+            tables: vec![table(vec![column("id")])],
+            ..Default::default()
+        };
+        let target = SchemaSnapshot {
+            schema: "shop_live".to_string(),
+            tables: vec![table(vec![column("id"), column("total")])],
+            ..Default::default()
+        };
+
+        let mut diff = compare(source, target);
+        let script = migration_script(&diff, &DatabaseType::PostgreSQL, &Definitions::new());
+        assert!(!script.contains("Warning"));
+
+        diff.drop_dependents = vec![DropDependents {
+            object: "column orders.total".to_string(),
+            dependents: vec![
+                Dependency {
+                    schema: "reports".to_string(),
+                    ..dependency(DependencyKind::View, "big_orders", None)
+                },
+                Dependency {
+                    schema: "reports".to_string(),
+                    ..dependency(DependencyKind::Function, "order_sum(integer)", None)
+                },
+            ],
+        }];
+        let script = migration_script(&diff, &DatabaseType::PostgreSQL, &Definitions::new());
+        let expected = "\
+-- Warning: other objects depend on what this script drops or alters;
+-- it fails until they are dropped or changed first
+--   column orders.total: view reports.big_orders, function reports.order_sum(integer)
+
+-- Alter columns
+";
+        assert!(script.contains(expected), "{}", script);
+    }
+
+    #[tokio::test]
+    async fn test_dependencies_and_dependents() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The schema is shared with the demo in main, which tests may run beside
+        let schema = "rbeaver_deps_test";
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in fixture(schema) {
+            connection.execute_non_query(&sql).await.unwrap();
+        }
+        let strings =
+            |items: &[&str]| -> Vec<String> { items.iter().map(|s| s.to_string()).collect() };
+        let qualified = |name: &str| format!("{}.{}", schema, name);
+
+        let dependents = connection
+            .get_dependents(&ObjectCategory::Tables, schema, "orders")
+            .await
+            .unwrap();
+        let found = |kind: &str, name: &str| {
+            dependents
+                .iter()
+                .map(summary)
+                .find(|d| d.0 == kind && d.1 == name)
+                .unwrap_or_else(|| panic!("no {} {} in {:?}", kind, name, dependents))
+        };
+        assert_eq!(
+            found("view", &qualified("order_totals")),
+            (
+                "view".to_string(),
+                qualified("order_totals"),
+                strings(&["customer_id", "total"]),
+                1,
+                false
+            )
+        );
+        // Through order_totals, so with the columns it reads
+        let top = found("view", &qualified("top_customers"));
+        assert_eq!(
+            (top.2, top.3, top.4),
+            (strings(&["customer_id", "total"]), 2, false)
+        );
+        for (kind, name) in [
+            ("index", "orders_customer_idx"),
+            ("constraint", "orders_total_check"),
+            ("constraint", "orders_customer_id_fkey"),
+            ("trigger", "orders_touch"),
+        ] {
+            let dependent = found(kind, &format!("{} on {}", name, qualified("orders")));
+            assert!(dependent.4, "{} should go away with its table", name);
+            assert_eq!(dependent.3, 1);
+        }
+        let sequence = found("sequence", &qualified("orders_id_seq"));
+        assert_eq!((sequence.2, sequence.4), (strings(&["id"]), true));
+
+        // The key of orders blocks dropping customers
+        let dependents = connection
+            .get_dependents(&ObjectCategory::Tables, schema, "customers")
+            .await
+            .unwrap();
+        let key = dependents
+            .iter()
+            .find(|d| d.name == "orders_customer_id_fkey")
+            .unwrap();
+        assert_eq!(key.kind, DependencyKind::Constraint);
+        assert_eq!(key.table.as_deref(), Some("orders"));
+        assert_eq!(key.columns, strings(&["id"]));
+        assert!(!key.automatic);
+
+        let dependencies = connection
+            .get_dependencies(&ObjectCategory::Views, schema, "top_customers")
+            .await
+            .unwrap();
+        let direct: Vec<_> = dependencies
+            .iter()
+            .filter(|d| d.depth == 1)
+            .map(summary)
+            .collect();
+        assert_eq!(
+            direct,
+            [
+                (
+                    "table".to_string(),
+                    qualified("customers"),
+                    strings(&["id", "name"]),
+                    1,
+                    false
+                ),
+                (
+                    "view".to_string(),
+                    qualified("order_totals"),
+                    strings(&["customer_id", "total"]),
+                    1,
+                    false
+                ),
+            ]
+        );
+        assert!(dependencies
+            .iter()
+            .any(|d| d.depth == 2 && d.name == "orders" && d.kind == DependencyKind::Table));
+
+        // The trigger of a table counts among what the table depends on
+        let dependencies = connection
+            .get_dependencies(&ObjectCategory::Tables, schema, "orders")
+            .await
+            .unwrap();
+        for name in ["customers", "touch()", "orders_id_seq"] {
+            assert!(
+                dependencies.iter().any(|d| d.depth == 1 && d.name == name),
+                "orders should depend on {}: {:?}",
+                name,
+                dependencies
+            );
+        }
+
+        // Functions match by plain name or with their argument types
+        for name in ["order_count", "order_count(integer)"] {
+            let dependents = connection
+                .get_dependents(&ObjectCategory::Functions, schema, name)
+                .await
+                .unwrap();
+            assert_eq!(
+                dependents.iter().map(summary).collect::<Vec<_>>(),
+                [(
+                    "view".to_string(),
+                    qualified("customer_orders"),
+                    Vec::new(),
+                    1,
+                    false
+                )]
+            );
+        }
+        let dependents = connection
+            .get_dependents(&ObjectCategory::Functions, schema, "touch")
+            .await
+            .unwrap();
+        assert_eq!(dependents.len(), 1);
+        assert_eq!(dependents[0].kind, DependencyKind::Trigger);
+        let dependencies = connection
+            .get_dependencies(&ObjectCategory::Triggers, schema, "orders_touch")
+            .await
+            .unwrap();
+        assert!(dependencies
+            .iter()
+            .any(|d| d.depth == 1 && d.name == "touch()" && !d.automatic));
+
+        connection
+            .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compare_finds_dependents_in_the_way() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let (source, target, reports) = (
+            "rbeaver_deps_test_source",
+            "rbeaver_deps_test_target",
+            "rbeaver_deps_test_reports",
+        );
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in fixture(target) {
+            connection.execute_non_query(&sql).await.unwrap();
+        }
+        // The source has no views, no order_count and no orders.total
+        let setup = [
+            format!("DROP SCHEMA IF EXISTS {} CASCADE", source),
+            format!("CREATE SCHEMA {}", source),
+            format!(
+                "CREATE TABLE {0}.customers (id serial PRIMARY KEY, name text NOT NULL)",
+                source
+            ),
+            format!(
+                "CREATE TABLE {0}.orders (
+                    id serial PRIMARY KEY,
+                    customer_id integer NOT NULL REFERENCES {0}.customers
+                )",
+                source
+            ),
+            format!(
+                "CREATE INDEX orders_customer_idx ON {}.orders (customer_id)",
+                source
+            ),
+            format!(
+                "CREATE FUNCTION {}.touch() RETURNS trigger LANGUAGE plpgsql
+                    AS 'BEGIN RETURN NEW; END'",
+                source
+            ),
+            format!(
+                "CREATE TRIGGER orders_touch BEFORE UPDATE ON {0}.orders
+                    FOR EACH ROW EXECUTE FUNCTION {0}.touch()",
+                source
+            ),
+            // Views the script knows nothing about
+            format!("DROP SCHEMA IF EXISTS {} CASCADE", reports),
+            format!("CREATE SCHEMA {}", reports),
+            format!(
+                "CREATE VIEW {}.big_orders AS SELECT id FROM {}.orders WHERE total > 100",
+                reports, target
+            ),
+            format!(
+                "CREATE VIEW {}.customer_names AS SELECT name FROM {}.top_customers",
+                reports, target
+            ),
+        ];
+        for sql in &setup {
+            connection.execute_non_query(sql).await.unwrap();
+        }
+
+        let (diff, script) = compare_schemas(
+            &connection,
+            source,
+            &connection,
+            target,
+            &DatabaseType::PostgreSQL,
+        )
+        .await
+        .unwrap();
+
+        // Views, the function and columns the script drops itself are fine
+        let warnings: Vec<(&str, Vec<String>)> = diff
+            .drop_dependents
+            .iter()
+            .map(|warning| {
+                (
+                    warning.object.as_str(),
+                    warning
+                        .dependents
+                        .iter()
+                        .map(|d| d.qualified_name())
+                        .collect(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            warnings,
+            [
+                (
+                    "view top_customers",
+                    vec![format!("{}.customer_names", reports)]
+                ),
+                (
+                    "column orders.total",
+                    vec![format!("{}.big_orders", reports)]
+                ),
+            ]
+        );
+        assert!(script.contains(&format!(
+            "--   view top_customers: view {}.customer_names",
+            reports
+        )));
+
+        for schema in [reports, source, target] {
+            connection
+                .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+                .await
+                .unwrap();
+        }
+        connection.disconnect().await.unwrap();
+    }
+}
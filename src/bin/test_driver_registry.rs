@@ -0,0 +1,151 @@
+use rbeaver::database::{DatabaseType, DriverRegistry};
+
+fn main() {
+    println!("🔧 Testing RBeaver Driver Registry");
+    println!("==================================\n");
+
+    let registry = DriverRegistry::new();
+
+    // Test 1: Available drivers
+    println!("📋 Test 1: Available drivers");
+    for database_type in [
+        DatabaseType::PostgreSQL,
+        DatabaseType::MySQL,
+        DatabaseType::SQLite,
+    ] {
+        match registry.create_connection(&database_type) {
+            Ok(connection) => println!(
+                "  ✓ {:?}: driver reports '{}'",
+                database_type,
+                connection.database_type()
+            ),
+            Err(err) => println!("  ✗ {:?}: {}", database_type, err),
+        }
+    }
+
+    // Test 2: Capabilities
+    println!("\n📋 Test 2: Capabilities");
+    for database_type in [
+        DatabaseType::PostgreSQL,
+        DatabaseType::MySQL,
+        DatabaseType::SQLite,
+    ] {
+        if let Some(capabilities) = registry.capabilities(&database_type) {
+            println!(
+                "  ✓ {:?}: schemas={} sequences={} matviews={} cancel={} explain={:?}",
+                database_type,
+                capabilities.supports_schemas,
+                capabilities.supports_sequences,
+                capabilities.supports_materialized_views,
+                capabilities.supports_cancellation,
+                capabilities.explain_formats
+            );
+        }
+    }
+
+    println!("\n🎉 Driver registry tests completed!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::{ExplainFormat, ObjectCategory, ViewType};
+
+    #[test]
+    fn test_postgresql_is_always_registered() {
+        let registry = DriverRegistry::new();
+        let connection = registry
+            .create_connection(&DatabaseType::PostgreSQL)
+            .unwrap();
+        assert_eq!(connection.database_type(), "PostgreSQL");
+
+        let capabilities = registry.capabilities(&DatabaseType::PostgreSQL).unwrap();
+        assert!(capabilities.supports_category(&ObjectCategory::Sequences));
+        assert!(capabilities.supports_cancellation);
+        assert!(capabilities.supports_dependencies);
+        assert!(capabilities.supports_result_editing);
+    }
+
+    #[test]
+    fn test_drivers_follow_cargo_features() {
+        let registry = DriverRegistry::new();
+
+        assert_eq!(
+            registry.create_connection(&DatabaseType::MySQL).is_ok(),
+            cfg!(feature = "mysql")
+        );
+        assert_eq!(
+            registry.create_connection(&DatabaseType::SQLite).is_ok(),
+            cfg!(feature = "sqlite")
+        );
+    }
+
+    #[test]
+    fn test_unsupported_categories_are_hidden() {
+        let registry = DriverRegistry::new();
+
+        if let Some(capabilities) = registry.capabilities(&DatabaseType::SQLite) {
+            assert!(!capabilities.supports_category(&ObjectCategory::Sequences));
+            assert!(!capabilities.supports_category(&ObjectCategory::Functions));
+            assert!(capabilities.supports_category(&ObjectCategory::Tables));
+            assert!(!capabilities.supports_dependencies);
+            assert!(!capabilities.supports_result_editing);
+        }
+
+        if let Some(capabilities) = registry.capabilities(&DatabaseType::MySQL) {
+            assert!(!capabilities.supports_category(&ObjectCategory::Sequences));
+            assert!(capabilities.supports_category(&ObjectCategory::Functions));
+            assert!(!capabilities.supports_schemas);
+            assert!(!capabilities.supports_dependencies);
+            assert!(!capabilities.supports_result_editing);
+        }
+    }
+
+    #[test]
+    fn test_materialized_views_follow_capability() {
+        let registry = DriverRegistry::new();
+
+        let postgresql = registry.capabilities(&DatabaseType::PostgreSQL).unwrap();
+        assert!(postgresql.supports_view_type(&ViewType::Materialized));
+
+        if let Some(capabilities) = registry.capabilities(&DatabaseType::MySQL) {
+            assert!(capabilities.supports_view_type(&ViewType::Regular));
+            assert!(!capabilities.supports_view_type(&ViewType::Materialized));
+        }
+    }
+
+    #[test]
+    fn test_explain_statements() {
+        let sql = "SELECT * FROM users;";
+
+        assert_eq!(
+            ExplainFormat::Text.statement(&DatabaseType::PostgreSQL, sql),
+            "EXPLAIN SELECT * FROM users"
+        );
+        assert_eq!(
+            ExplainFormat::Json.statement(&DatabaseType::PostgreSQL, sql),
+            "EXPLAIN (FORMAT JSON) SELECT * FROM users"
+        );
+        assert_eq!(
+            ExplainFormat::Tree.statement(&DatabaseType::MySQL, sql),
+            "EXPLAIN FORMAT=TREE SELECT * FROM users"
+        );
+        assert_eq!(
+            ExplainFormat::Tree.statement(&DatabaseType::SQLite, sql),
+            "EXPLAIN QUERY PLAN SELECT * FROM users"
+        );
+    }
+
+    #[test]
+    fn test_cancellation_capability() {
+        let registry = DriverRegistry::new();
+
+        if let Some(capabilities) = registry.capabilities(&DatabaseType::SQLite) {
+            assert!(!capabilities.supports_cancellation);
+            assert_eq!(capabilities.explain_formats, vec![ExplainFormat::Tree]);
+        }
+        if let Some(capabilities) = registry.capabilities(&DatabaseType::MySQL) {
+            assert!(capabilities.supports_cancellation);
+        }
+    }
+}
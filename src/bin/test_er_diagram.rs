@@ -0,0 +1,456 @@
+use rbeaver::database::er_model::ErModel;
+use rbeaver::database::{DatabaseConnection, PostgreSQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const SCHEMA: &str = "rbeaver_er_demo";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP SCHEMA IF EXISTS rbeaver_er_demo CASCADE",
+    "CREATE SCHEMA rbeaver_er_demo",
+    "CREATE TABLE rbeaver_er_demo.customers (
+        id serial PRIMARY KEY,
+        email text NOT NULL
+    )",
+    "CREATE TABLE rbeaver_er_demo.orders (
+        id serial PRIMARY KEY,
+        customer_id integer NOT NULL REFERENCES rbeaver_er_demo.customers,
+        coupon_id integer,
+        placed_at timestamp
+    )",
+    "CREATE TABLE rbeaver_er_demo.order_lines (
+        order_id integer REFERENCES rbeaver_er_demo.orders,
+        line_no integer,
+        quantity integer NOT NULL,
+        PRIMARY KEY (order_id, line_no)
+    )",
+    "CREATE TABLE rbeaver_er_demo.employees (
+        id serial PRIMARY KEY,
+        manager_id integer REFERENCES rbeaver_er_demo.employees
+    )",
+    "CREATE TABLE public.rbeaver_er_regions (id integer PRIMARY KEY)",
+    "ALTER TABLE rbeaver_er_demo.customers
+        ADD COLUMN region_id integer REFERENCES public.rbeaver_er_regions",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver ER Diagrams");
+    println!("==============================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    connection
+        .execute_non_query("DROP TABLE IF EXISTS public.rbeaver_er_regions CASCADE")
+        .await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    let model = ErModel::load(&connection, SCHEMA).await?;
+    println!(
+        "📋 {} tables, {} relationships\n",
+        model.tables.len(),
+        model.relationships.len()
+    );
+    println!("🧜 Mermaid:\n{}", model.to_mermaid());
+    println!("🌱 PlantUML:\n{}", model.to_plantuml());
+    println!("🔵 Graphviz:\n{}", model.to_dot());
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_er_demo CASCADE")
+        .await?;
+    connection
+        .execute_non_query("DROP TABLE public.rbeaver_er_regions")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("🎉 ER diagram tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use egui::epaint::{ClippedPrimitive, ColorImage, Mesh, Primitive};
+    use egui::{pos2, vec2, Color32, Rect, Vec2};
+    use rbeaver::database::{Column, ForeignKey, ForeignKeyAction};
+    use rbeaver::ui::er_diagram::{
+        force_layout, layered_layout, rasterize, DiagramFormat, DiagramLayout,
+    };
+    use rbeaver::ui::ErDiagramView;
+    use std::collections::HashMap;
+
+    fn column(name: &str, data_type: &str, is_nullable: bool, is_primary_key: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable,
+            default_value: None,
+            is_primary_key,
+            comment: None,
+        }
+    }
+
+    fn foreign_key(name: &str, table: &str, column: &str, referenced: &str) -> ForeignKey {
+        ForeignKey {
+            name: name.to_string(),
+            schema: "shop".to_string(),
+            table_name: table.to_string(),
+            columns: vec![column.to_string()],
+            referenced_schema: "shop".to_string(),
+            referenced_table: referenced.to_string(),
+            referenced_columns: vec!["id".to_string()],
+            on_update: ForeignKeyAction::NoAction,
+            on_delete: ForeignKeyAction::NoAction,
+            comment: None,
+        }
+    }
+
+    /// customers <- orders (mandatory), orders <- refunds (optional), and a
+    /// key to another schema that stays out of the diagram
+    fn shop() -> ErModel {
+        let mut external = foreign_key("customers_region_fkey", "customers", "region_id", "x");
+        external.referenced_schema = "geo".to_string();
+        let orders_customer =
+            foreign_key("orders_customer_fkey", "orders", "customer_id", "customers");
+        ErModel::from_parts(
+            "shop",
+            vec![
+                (
+                    "customers".to_string(),
+                    vec![
+                        column("id", "integer", false, true),
+                        column("region_id", "integer", true, false),
+                    ],
+                    // Keys referencing the table are listed with it too
+                    vec![external, orders_customer.clone()],
+                ),
+                (
+                    "orders".to_string(),
+                    vec![
+                        column("id", "integer", false, true),
+                        column("customer_id", "integer", false, false),
+                        column("total", "numeric(10,2)", true, false),
+                    ],
+                    vec![orders_customer],
+                ),
+                (
+                    "refunds".to_string(),
+                    vec![
+                        column("id", "integer", false, true),
+                        column("order id", "integer", true, false),
+                    ],
+                    vec![foreign_key(
+                        "refunds_order_fkey",
+                        "refunds",
+                        "order id",
+                        "orders",
+                    )],
+                ),
+            ],
+        )
+    }
+
+    fn overlapping(rects: &[Rect]) -> Vec<(usize, usize)> {
+        let mut pairs = Vec::new();
+        for i in 0..rects.len() {
+            for j in i + 1..rects.len() {
+                if rects[i].intersects(rects[j]) {
+                    pairs.push((i, j));
+                }
+            }
+        }
+        pairs
+    }
+
+    /// Run `f` inside a frame of a headless context, where fonts are loaded
+    fn with_context(f: impl FnOnce(&egui::Context)) {
+        let ctx = egui::Context::default();
+        let mut f = Some(f);
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            if let Some(f) = f.take() {
+                f(ctx);
+            }
+        });
+    }
+
+    #[test]
+    fn test_model_from_parts() {
+        let model = shop();
+        let names: Vec<&str> = model.tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, ["customers", "orders", "refunds"]);
+
+        let relationships: Vec<(&str, &str, bool)> = model
+            .relationships
+            .iter()
+            .map(|r| (r.from_table.as_str(), r.to_table.as_str(), r.optional))
+            .collect();
+        assert_eq!(
+            relationships,
+            [("orders", "customers", false), ("refunds", "orders", true)]
+        );
+
+        let orders = &model.tables[1];
+        assert!(orders.columns[0].is_primary_key);
+        assert!(orders.columns[1].is_foreign_key);
+        assert!(!orders.columns[2].is_foreign_key);
+        // Part of a key, but one to a table outside the diagram
+        assert!(model.tables[0].columns[1].is_foreign_key);
+        assert_eq!(model.table_index("refunds"), Some(2));
+    }
+
+    #[test]
+    fn test_text_exports() {
+        let model = shop();
+        assert_eq!(
+            model.to_mermaid(),
+            "erDiagram
+    customers {
+        integer id PK
+        integer region_id FK
+    }
+    orders {
+        integer id PK
+        integer customer_id FK
+        numeric(10_2) total
+    }
+    refunds {
+        integer id PK
+        integer order_id FK
+    }
+    customers ||--o{ orders : \"orders_customer_fkey\"
+    orders |o--o{ refunds : \"refunds_order_fkey\"
+"
+        );
+
+        let plantuml = model.to_plantuml();
+        assert!(plantuml.starts_with("@startuml\n"));
+        assert!(plantuml.ends_with("@enduml\n"));
+        assert!(plantuml.contains("entity \"orders\" as orders {\n  * id : integer <<PK>>\n  --\n"));
+        assert!(plantuml.contains("  order id : integer <<FK>>\n"));
+        assert!(plantuml.contains("orders }o--|| customers : orders_customer_fkey\n"));
+        assert!(plantuml.contains("refunds }o--o| orders : refunds_order_fkey\n"));
+
+        let dot = model.to_dot();
+        assert!(dot.starts_with("digraph \"shop\" {\n"));
+        assert!(dot.contains("port=\"order id\">FK order id : integer</td>"));
+        assert!(dot.contains("numeric(10,2)"));
+        assert!(dot.contains(
+            "    \"orders\":\"customer_id\" -> \"customers\":\"id\" [label=\"orders_customer_fkey\"];\n"
+        ));
+        assert!(dot.contains(
+            "    \"refunds\":\"order id\" -> \"orders\":\"id\" \
+             [label=\"refunds_order_fkey\", arrowhead=teeodot];\n"
+        ));
+        assert!(dot.ends_with("}\n"));
+    }
+
+    #[test]
+    fn test_layouts() {
+        let model = shop();
+        let sizes = vec![vec2(160.0, 70.0), vec2(200.0, 90.0), vec2(150.0, 70.0)];
+
+        // Referenced tables left of the tables referencing them
+        let layered = layered_layout(&model, &sizes);
+        assert!(layered[0].x < layered[1].x);
+        assert!(layered[1].x < layered[2].x);
+        let rects: Vec<Rect> = layered
+            .iter()
+            .zip(&sizes)
+            .map(|(p, s)| Rect::from_min_size(*p, *s))
+            .collect();
+        assert!(overlapping(&rects).is_empty());
+
+        // Everything piled up in one spot spreads out
+        let forced = force_layout(&model, &sizes, &[pos2(0.0, 0.0); 3]);
+        let rects: Vec<Rect> = forced
+            .iter()
+            .zip(&sizes)
+            .map(|(p, s)| Rect::from_min_size(*p, *s))
+            .collect();
+        assert!(overlapping(&rects).is_empty(), "{:?}", rects);
+        assert!(forced.iter().all(|p| p.x >= 0.0 && p.y >= 0.0));
+        // Deterministic, so a relayout does not shuffle the diagram
+        assert_eq!(forced, force_layout(&model, &sizes, &[pos2(0.0, 0.0); 3]));
+    }
+
+    #[test]
+    fn test_diagram_view_layout() {
+        let mut saved = HashMap::new();
+        saved.insert("orders".to_string(), (500.0, 40.0));
+        let mut view = ErDiagramView::new("connection", "shop", saved);
+        assert!(view.take_layout_change().is_none());
+        view.set_model(Ok(shop()));
+
+        with_context(|ctx| view.arrange(ctx));
+        let layout = view.layout();
+        assert_eq!(layout["orders"], (500.0, 40.0));
+        // Tables without a saved position go below the saved ones
+        let rects = view.table_rects();
+        assert!(rects[0].min.y > rects[1].max.y);
+        assert!(overlapping(&rects).is_empty());
+        assert!(view.take_layout_change().is_none());
+
+        view.apply_layout(DiagramLayout::Layered);
+        let changed = view.take_layout_change().unwrap();
+        assert_eq!(changed.len(), 3);
+        assert_eq!(changed["customers"], (0.0, 0.0));
+        assert!(view.take_layout_change().is_none());
+
+        // Reloading keeps the tables where the user left them
+        view.set_model(Ok(shop()));
+        with_context(|ctx| view.arrange(ctx));
+        assert_eq!(view.layout(), changed);
+    }
+
+    #[test]
+    fn test_image_exports() {
+        let mut view = ErDiagramView::new("connection", "shop", HashMap::new());
+        view.set_model(Ok(shop()));
+        let mut exports = Vec::new();
+        with_context(|ctx| {
+            view.arrange(ctx);
+            for format in DiagramFormat::ALL {
+                exports.push(view.export(ctx, format).unwrap());
+            }
+        });
+
+        let svg = String::from_utf8(exports[0].clone()).unwrap();
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\""));
+        assert!(svg.ends_with("</svg>\n"));
+        assert!(svg.contains(">customers</text>"));
+        assert!(svg.contains(">numeric(10,2)</text>"));
+        // Two relationships, and a circle on the optional one
+        assert_eq!(svg.matches("<path d=\"M").count(), 3 + 2);
+        assert_eq!(svg.matches("<circle").count(), 1);
+
+        let png = image::load_from_memory(&exports[1]).unwrap().to_rgba8();
+        let bounds = view
+            .table_rects()
+            .into_iter()
+            .fold(Rect::NOTHING, |bounds, rect| bounds.union(rect));
+        assert!(png.width() as f32 >= bounds.width());
+        assert!(png.height() as f32 >= bounds.height());
+        let header = [214, 230, 245, 255];
+        assert!(png.pixels().any(|pixel| pixel.0 == header));
+        // Dark text on top of the boxes
+        assert!(png.pixels().any(|pixel| pixel.0[0] < 80));
+        assert_eq!(png.get_pixel(0, 0).0, [255, 255, 255, 255]);
+
+        assert_eq!(exports[2], shop().to_mermaid().into_bytes());
+        assert_eq!(exports[3], shop().to_plantuml().into_bytes());
+        assert_eq!(exports[4], shop().to_dot().into_bytes());
+    }
+
+    #[test]
+    fn test_rasterize() {
+        // An opaque red square and a half transparent blue one over it
+        let mut mesh = Mesh::default();
+        mesh.add_colored_rect(
+            Rect::from_min_size(pos2(1.0, 1.0), Vec2::splat(4.0)),
+            Color32::RED,
+        );
+        mesh.add_colored_rect(
+            Rect::from_min_size(pos2(3.0, 3.0), Vec2::splat(4.0)),
+            Color32::from_rgba_premultiplied(0, 0, 128, 128),
+        );
+        let primitives = [ClippedPrimitive {
+            clip_rect: Rect::from_min_size(pos2(0.0, 0.0), Vec2::splat(6.0)),
+            primitive: Primitive::Mesh(mesh),
+        }];
+        // A white atlas, as at egui's white texel
+        let atlas = ColorImage::new([1, 1], vec![Color32::WHITE]);
+        let image = rasterize(&primitives, &atlas, 2.0, [16, 16], Color32::WHITE);
+
+        let at = |x: usize, y: usize| image.pixels[y * 16 + x];
+        assert_eq!(at(0, 0), Color32::WHITE);
+        assert_eq!(at(3, 3), Color32::RED);
+        assert_eq!(at(7, 7), Color32::from_rgb(127, 0, 128));
+        assert_eq!(at(11, 11), Color32::from_rgb(127, 127, 255));
+        // Cut off at the clip rectangle, which is in points
+        assert_eq!(at(13, 13), Color32::WHITE);
+    }
+
+    #[tokio::test]
+    async fn test_load_model() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The schema is shared with the demo in main, which tests may run beside
+        let schema = "rbeaver_er_test";
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        connection
+            .execute_non_query("DROP TABLE IF EXISTS public.rbeaver_er_test_regions CASCADE")
+            .await
+            .unwrap();
+        for sql in FIXTURE_SQL {
+            connection
+                .execute_non_query(
+                    &sql.replace(SCHEMA, schema)
+                        .replace("rbeaver_er_regions", "rbeaver_er_test_regions"),
+                )
+                .await
+                .unwrap();
+        }
+
+        let model = ErModel::load(&connection, schema).await.unwrap();
+        let mut names: Vec<&str> = model.tables.iter().map(|t| t.name.as_str()).collect();
+        names.sort();
+        assert_eq!(names, ["customers", "employees", "order_lines", "orders"]);
+
+        let mut relationships: Vec<(&str, &str, &str, bool)> = model
+            .relationships
+            .iter()
+            .map(|r| {
+                (
+                    r.from_table.as_str(),
+                    r.from_columns[0].as_str(),
+                    r.to_table.as_str(),
+                    r.optional,
+                )
+            })
+            .collect();
+        relationships.sort();
+        assert_eq!(
+            relationships,
+            [
+                ("employees", "manager_id", "employees", true),
+                ("order_lines", "order_id", "orders", false),
+                ("orders", "customer_id", "customers", false),
+            ]
+        );
+
+        let lines = &model.tables[model.table_index("order_lines").unwrap()];
+        let order_id = &lines.columns[0];
+        assert!(order_id.is_primary_key && order_id.is_foreign_key);
+        assert!(lines.columns[1].is_primary_key && !lines.columns[1].is_foreign_key);
+        let customers = &model.tables[model.table_index("customers").unwrap()];
+        let region = customers
+            .columns
+            .iter()
+            .find(|c| c.name == "region_id")
+            .unwrap();
+        assert!(region.is_foreign_key);
+
+        connection
+            .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .await
+            .unwrap();
+        connection
+            .execute_non_query("DROP TABLE public.rbeaver_er_test_regions")
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+}
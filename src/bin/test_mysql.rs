@@ -0,0 +1,298 @@
+use rbeaver::database::{DatabaseConnection, MySQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+/// Scratch database created and dropped by the tests
+const TEST_SCHEMA: &str = "rbeaver_test";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP DATABASE IF EXISTS rbeaver_test",
+    "CREATE DATABASE rbeaver_test",
+    "CREATE TABLE rbeaver_test.products (
+        id INT UNSIGNED AUTO_INCREMENT PRIMARY KEY,
+        name VARCHAR(100) NOT NULL,
+        price DECIMAL(10, 2) NOT NULL DEFAULT 0.00,
+        status ENUM('draft', 'active', 'retired') NOT NULL DEFAULT 'draft',
+        tags SET('new', 'sale', 'featured'),
+        flags BIT(4),
+        in_stock BOOLEAN,
+        attributes JSON,
+        created_at DATETIME,
+        UNIQUE KEY uk_products_name (name),
+        KEY idx_products_created (created_at DESC)
+    )",
+    "CREATE TABLE rbeaver_test.price_log (id BIGINT AUTO_INCREMENT PRIMARY KEY, message TEXT)",
+    "CREATE VIEW rbeaver_test.active_products AS
+     SELECT id, name FROM rbeaver_test.products WHERE status = 'active'",
+    "CREATE TRIGGER rbeaver_test.products_price_log AFTER UPDATE ON rbeaver_test.products
+     FOR EACH ROW INSERT INTO rbeaver_test.price_log (message) VALUES (CONCAT('updated ', NEW.name))",
+    "CREATE FUNCTION rbeaver_test.with_tax(amount DECIMAL(10, 2)) RETURNS DECIMAL(10, 2)
+     DETERMINISTIC RETURN amount * 1.2",
+    "CREATE PROCEDURE rbeaver_test.count_products(IN min_price DECIMAL(10, 2), OUT total INT)
+     SELECT COUNT(*) INTO total FROM rbeaver_test.products WHERE price >= min_price",
+    "INSERT INTO rbeaver_test.products
+        (name, price, status, tags, flags, in_stock, attributes, created_at)
+     VALUES
+        ('Widget', 19.99, 'active', 'new,sale', b'1010', TRUE, '{\"color\": \"red\"}', '2024-03-01 12:30:00'),
+        ('Gadget', 5.00, 'draft', NULL, NULL, FALSE, NULL, NULL)",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver MySQL Support");
+    println!("================================\n");
+
+    let Some(params) = common::mysql_server_params() else {
+        println!("⚠️  RBEAVER_MYSQL_HOST is not set; start mysqld/mariadbd and export");
+        println!("   RBEAVER_MYSQL_HOST, RBEAVER_MYSQL_PORT, RBEAVER_MYSQL_USER and");
+        println!("   RBEAVER_MYSQL_PASSWORD to run these tests.");
+        return Ok(());
+    };
+
+    let mut connection = MySQLConnection::new();
+    connection.connect(&params).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    // Test 1: Schemas
+    println!("📋 Test 1: Schemas");
+    let schemas = connection.get_schemas().await?;
+    assert!(schemas.iter().any(|s| s.name == TEST_SCHEMA));
+    assert!(!schemas.iter().any(|s| s.name == "information_schema"));
+    for schema in &schemas {
+        println!("  ✓ Found schema: {}", schema.name);
+    }
+
+    // Test 2: Tables and columns
+    println!("\n📋 Test 2: Tables and columns");
+    let tables = connection.get_tables(TEST_SCHEMA).await?;
+    for table in &tables {
+        println!("  ✓ {} ({})", table.name, table.table_type);
+    }
+
+    let columns = connection.get_columns(TEST_SCHEMA, "products").await?;
+    for column in &columns {
+        println!(
+            "  ✓ {} {} nullable={} pk={}",
+            column.name, column.data_type, column.is_nullable, column.is_primary_key
+        );
+    }
+
+    // Test 3: Routines, triggers and indexes
+    println!("\n📋 Test 3: Routines, triggers and indexes");
+    for function in connection.get_functions(TEST_SCHEMA).await? {
+        println!(
+            "  ✓ {:?} {}({}) -> {}",
+            function.function_type,
+            function.name,
+            function
+                .arguments
+                .iter()
+                .map(|a| format!("{:?} {}", a.mode, a.data_type))
+                .collect::<Vec<_>>()
+                .join(", "),
+            function.return_type
+        );
+    }
+
+    for trigger in connection.get_triggers(TEST_SCHEMA).await? {
+        println!(
+            "  ✓ {} on {}: {:?} {:?}",
+            trigger.name, trigger.table_name, trigger.timing, trigger.events
+        );
+    }
+
+    for index in connection.get_indexes(TEST_SCHEMA).await? {
+        println!(
+            "  ✓ {} on {} {:?} unique={} primary={}",
+            index.name, index.table_name, index.index_type, index.is_unique, index.is_primary
+        );
+    }
+
+    // Test 4: Value conversion
+    println!("\n📋 Test 4: Value conversion");
+    let result = connection
+        .execute_query(
+            "SELECT id, name, price, status, tags, flags, in_stock, attributes, created_at
+             FROM rbeaver_test.products ORDER BY id",
+        )
+        .await?;
+    for row in &result.rows {
+        let values: Vec<String> = row.values.iter().map(|v| v.to_display_string()).collect();
+        println!("  ✓ {}", values.join(" | "));
+    }
+
+    // Test 5: Object counts
+    println!("\n📋 Test 5: Object counts");
+    let counts = connection.get_object_counts(TEST_SCHEMA).await?;
+    println!(
+        "  ✓ tables={} views={} functions={} procedures={} triggers={} indexes={}",
+        counts.tables,
+        counts.views,
+        counts.functions,
+        counts.procedures,
+        counts.triggers,
+        counts.indexes
+    );
+
+    connection
+        .execute_non_query("DROP DATABASE rbeaver_test")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("\n🎉 All MySQL tests completed successfully!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::{
+        ArgumentMode, ConnectionParams, DatabaseType, FunctionType, IndexType, QueryValue,
+        TriggerEvent, TriggerTiming,
+    };
+
+    // A single test owns the scratch database so parallel tests cannot race on it
+    #[tokio::test]
+    async fn test_mysql_backend() {
+        let Some(params) = common::mysql_server_params() else {
+            eprintln!("skipping: RBEAVER_MYSQL_HOST is not set");
+            return;
+        };
+
+        let mut connection = MySQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in FIXTURE_SQL {
+            connection.execute_non_query(sql).await.unwrap();
+        }
+
+        // Metadata
+        let tables = connection.get_tables(TEST_SCHEMA).await.unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["active_products", "price_log", "products"]);
+        assert_eq!(tables[0].table_type, "VIEW");
+        assert!(tables[0].comment.is_none());
+
+        let columns = connection
+            .get_columns(TEST_SCHEMA, "products")
+            .await
+            .unwrap();
+        assert_eq!(columns.len(), 9);
+        assert!(columns[0].is_primary_key);
+        assert!(!columns[1].is_nullable);
+        assert_eq!(columns[3].data_type, "enum('draft','active','retired')");
+        assert!(columns[4].default_value.is_none());
+
+        let functions = connection.get_functions(TEST_SCHEMA).await.unwrap();
+        let procedure = functions
+            .iter()
+            .find(|f| f.name == "count_products")
+            .unwrap();
+        assert_eq!(procedure.function_type, FunctionType::Procedure);
+        assert_eq!(procedure.arguments.len(), 2);
+        assert_eq!(procedure.arguments[1].mode, ArgumentMode::Out);
+        let function = functions.iter().find(|f| f.name == "with_tax").unwrap();
+        assert_eq!(function.function_type, FunctionType::Function);
+        assert_eq!(function.arguments[0].name.as_deref(), Some("amount"));
+
+        let triggers = connection.get_triggers(TEST_SCHEMA).await.unwrap();
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].timing, TriggerTiming::After);
+        assert_eq!(triggers[0].events, vec![TriggerEvent::Update]);
+        assert_eq!(triggers[0].condition, None);
+
+        let indexes = connection.get_indexes(TEST_SCHEMA).await.unwrap();
+        let primary = indexes
+            .iter()
+            .find(|i| i.table_name == "products" && i.is_primary)
+            .unwrap();
+        assert!(primary.is_unique);
+        assert_eq!(primary.index_type, IndexType::BTree);
+        let unique = indexes
+            .iter()
+            .find(|i| i.name == "uk_products_name")
+            .unwrap();
+        assert!(unique.is_unique && !unique.is_primary);
+        let created = indexes
+            .iter()
+            .find(|i| i.name == "idx_products_created")
+            .unwrap();
+        assert_eq!(created.columns[0].name, "created_at");
+
+        let counts = connection.get_object_counts(TEST_SCHEMA).await.unwrap();
+        assert_eq!(counts.tables, 2);
+        assert_eq!(counts.views, 1);
+        assert_eq!(counts.functions, 1);
+        assert_eq!(counts.procedures, 1);
+        assert_eq!(counts.triggers, 1);
+
+        // Value conversion
+        let result = connection
+            .execute_query(
+                "SELECT id, name, price, status, tags, flags, in_stock, attributes, created_at
+                 FROM rbeaver_test.products ORDER BY id",
+            )
+            .await
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+
+        let widget = &result.rows[0].values;
+        assert!(matches!(widget[0], QueryValue::Int64(1)));
+        assert!(matches!(&widget[2], QueryValue::Decimal(d) if d.to_string() == "19.99"));
+        assert!(matches!(&widget[3], QueryValue::String(s) if s == "active"));
+        assert!(matches!(&widget[4], QueryValue::String(s) if s == "new,sale"));
+        assert!(matches!(widget[5], QueryValue::Int64(0b1010)));
+        assert!(matches!(widget[6], QueryValue::Bool(true)));
+        assert!(matches!(&widget[7], QueryValue::Json(j) if j["color"] == "red"));
+        assert!(
+            matches!(&widget[8], QueryValue::NaiveDateTime(dt) if dt.to_string() == "2024-03-01 12:30:00")
+        );
+
+        // Only table data knows which columns are declared NOT NULL
+        assert!(result.columns[1].nullable);
+        let result = connection
+            .get_table_data(TEST_SCHEMA, "products", Some(1))
+            .await
+            .unwrap();
+        assert!(!result.columns[1].nullable);
+        assert!(result.columns[8].nullable);
+
+        let gadget = &result.rows[1].values;
+        assert!(gadget[4].is_null());
+        assert!(gadget[5].is_null());
+        assert!(matches!(gadget[6], QueryValue::Bool(false)));
+
+        // DATE and TIME have their own variants; TIME outside a day stays text
+        let result = connection
+            .execute_query(
+                "SELECT CAST('2024-03-01' AS DATE), CAST('12:30:05' AS TIME),
+                        CAST('-838:59:59' AS TIME)",
+            )
+            .await
+            .unwrap();
+        let values = &result.rows[0].values;
+        assert!(matches!(&values[0], QueryValue::Date(d) if d.to_string() == "2024-03-01"));
+        assert!(matches!(&values[1], QueryValue::Time(t, None) if t.to_string() == "12:30:05"));
+        assert!(matches!(&values[2], QueryValue::String(s) if s == "-838:59:59"));
+
+        connection
+            .execute_non_query("DROP DATABASE rbeaver_test")
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_mysql_connection_refused() {
+        let mut params = ConnectionParams::new("Unreachable".to_string(), DatabaseType::MySQL);
+        params.host = "127.0.0.1".to_string();
+        params.port = 1; // Nothing listens on tcpmux
+        params.database = "mysql".to_string();
+        params.connection_timeout = Some(2);
+
+        let connection = MySQLConnection::new();
+        assert!(connection.test_connection(&params).await.is_err());
+    }
+}
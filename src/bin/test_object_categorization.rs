@@ -3,7 +3,7 @@
 /// This test demonstrates the new object categorization functionality
 /// including hierarchical tree structure, object counts, and search capabilities.
 use eframe::egui;
-use rbeaver::database::{ObjectCounts, Schema};
+use rbeaver::database::{ObjectCounts, PostgreSQLConnection, Schema};
 use rbeaver::ui::DatabaseTree;
 
 fn main() -> Result<(), eframe::Error> {
@@ -45,8 +45,11 @@ impl ObjectCategorizationTestApp {
         let connection_id = "test-connection-1".to_string();
         let connection_name = "Test PostgreSQL Database".to_string();
 
-        self.database_tree
-            .add_connection(connection_id.clone(), connection_name);
+        self.database_tree.add_connection(
+            connection_id.clone(),
+            connection_name,
+            PostgreSQLConnection::capabilities(),
+        );
 
         // Add test schemas
         let schemas = vec![
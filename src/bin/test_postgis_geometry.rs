@@ -24,13 +24,17 @@ fn main() {
     println!("\n📋 Test 5: SRID Handling");
     test_srid_handling();
 
+    // Test 6: EWKB Decoding
+    println!("\n📋 Test 6: EWKB Decoding");
+    test_ewkb_decoding();
+
     println!("\n🎉 All PostGIS Geometry Tests Completed Successfully!");
     println!("\n✅ Features Implemented:");
     println!("  🔧 Added GeometryValue type to QueryValue enum");
     println!("  🔧 Support for PostGIS geometry data types");
     println!("  🔧 WKT (Well-Known Text) format parsing");
     println!("  🔧 SRID (Spatial Reference System Identifier) support");
-    println!("  🔧 Binary geometry data handling");
+    println!("  🔧 EWKB decoding with SRID, Z/M and bounding boxes");
     println!("  🔧 Smart display formatting for long WKT strings");
     println!("  🔧 Geometry type detection and classification");
 
@@ -233,6 +237,54 @@ fn test_srid_handling() {
     println!("  ✅ SRID Handling: PASSED");
 }
 
+fn test_ewkb_decoding() {
+    let samples = vec![
+        POINT_4326,
+        POINT_Z,
+        POINT_M_ISO,
+        POINT_EMPTY,
+        LINESTRING_BIG_ENDIAN,
+        POLYGON,
+        MULTIPOINT,
+        COLLECTION_3857,
+    ];
+
+    for sample in samples {
+        let geom = GeometryValue::from_ewkb(&hex(sample)).expect("valid EWKB");
+        assert!(geom.wkt.starts_with(&geom.geometry_type));
+        println!(
+            "  ✓ {} decoded: {}",
+            geom.get_summary(),
+            geom.to_display_string()
+        );
+    }
+
+    assert!(GeometryValue::from_ewkb(&hex(&POINT_4326[..30])).is_none());
+    println!("  ✓ Truncated EWKB is rejected");
+
+    println!("  ✅ EWKB Decoding: PASSED");
+}
+
+// EWKB samples as PostGIS sends them, little-endian unless noted
+const POINT_4326: &str = "0101000020E6100000000000000000F03F0000000000000040";
+const POINT_Z: &str = "0101000080000000000000F03F00000000000000400000000000000840";
+// ISO WKB type code 2001 rather than the EWKB M flag
+const POINT_M_ISO: &str = "01D1070000000000000000F03F00000000000000400000000000001440";
+const POINT_EMPTY: &str = "0101000000000000000000F87F000000000000F87F";
+const LINESTRING_BIG_ENDIAN: &str =
+    "0000000002000000020000000000000000000000000000000040100000000000003FF0000000000000";
+const POLYGON: &str = "0103000000010000000400000000000000000000000000000000000000000000000000104000000000000000000000000000001040000000000000104000000000000000000000000000000000";
+const MULTIPOINT: &str = "0104000000020000000101000000000000000000F03F0000000000000040010100000000000000000008400000000000001040";
+const COLLECTION_3857: &str =
+    "0107000020110F0000020000000101000000000000000000F03F0000000000000040010200000000000000";
+
+fn hex(text: &str) -> Vec<u8> {
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&text[i..i + 2], 16).unwrap())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -282,4 +334,118 @@ mod tests {
         let display = geom.to_display_string();
         assert_eq!(display, "SRID=4326;POINT(1.0 2.0)");
     }
+
+    #[test]
+    fn test_ewkb_points() {
+        let point = GeometryValue::from_ewkb(&hex(POINT_4326)).unwrap();
+        assert_eq!(point.geometry_type, "POINT");
+        assert_eq!(point.srid, Some(4326));
+        assert_eq!(point.wkt, "POINT(1 2)");
+        assert_eq!(point.binary_data, Some(hex(POINT_4326)));
+        let bbox = point.bbox.unwrap();
+        assert_eq!(
+            (bbox.min_x, bbox.min_y, bbox.max_x, bbox.max_y),
+            (1.0, 2.0, 1.0, 2.0)
+        );
+
+        let point_z = GeometryValue::from_ewkb(&hex(POINT_Z)).unwrap();
+        assert!(point_z.has_z && !point_z.has_m);
+        assert_eq!(point_z.srid, None);
+        assert_eq!(point_z.wkt, "POINT Z (1 2 3)");
+        assert_eq!(point_z.get_summary(), "POINT Z");
+
+        let point_m = GeometryValue::from_ewkb(&hex(POINT_M_ISO)).unwrap();
+        assert!(point_m.has_m && !point_m.has_z);
+        assert_eq!(point_m.wkt, "POINT M (1 2 5)");
+
+        let empty = GeometryValue::from_ewkb(&hex(POINT_EMPTY)).unwrap();
+        assert_eq!(empty.wkt, "POINT EMPTY");
+        assert_eq!(empty.bbox, None);
+    }
+
+    #[test]
+    fn test_ewkb_lines_and_polygons() {
+        let line = GeometryValue::from_ewkb(&hex(LINESTRING_BIG_ENDIAN)).unwrap();
+        assert_eq!(line.geometry_type, "LINESTRING");
+        assert_eq!(line.wkt, "LINESTRING(0 0,4 1)");
+        let bbox = line.bbox.unwrap();
+        assert_eq!((bbox.min_x, bbox.max_x, bbox.max_y), (0.0, 4.0, 1.0));
+
+        let polygon = GeometryValue::from_ewkb(&hex(POLYGON)).unwrap();
+        assert_eq!(polygon.wkt, "POLYGON((0 0,4 0,4 4,0 0))");
+    }
+
+    #[test]
+    fn test_ewkb_collections() {
+        let multipoint = GeometryValue::from_ewkb(&hex(MULTIPOINT)).unwrap();
+        assert_eq!(multipoint.geometry_type, "MULTIPOINT");
+        assert_eq!(multipoint.wkt, "MULTIPOINT((1 2),(3 4))");
+
+        let collection = GeometryValue::from_ewkb(&hex(COLLECTION_3857)).unwrap();
+        assert_eq!(collection.geometry_type, "GEOMETRYCOLLECTION");
+        assert_eq!(collection.srid, Some(3857));
+        assert_eq!(
+            collection.wkt,
+            "GEOMETRYCOLLECTION(POINT(1 2),LINESTRING EMPTY)"
+        );
+        assert_eq!(
+            QueryValue::Geometry(collection).to_sql_literal(),
+            "'SRID=3857;GEOMETRYCOLLECTION(POINT(1 2),LINESTRING EMPTY)'"
+        );
+    }
+
+    #[test]
+    fn test_invalid_ewkb() {
+        assert!(GeometryValue::from_ewkb(&[]).is_none());
+        assert!(GeometryValue::from_ewkb(&hex(&POINT_4326[..30])).is_none());
+        // Trailing bytes after the geometry
+        assert!(GeometryValue::from_ewkb(&hex(&format!("{}00", POINT_Z))).is_none());
+        // Curved types such as CIRCULARSTRING are not decoded
+        assert!(GeometryValue::from_ewkb(&hex("010800000000000000")).is_none());
+    }
+
+    #[test]
+    fn test_geography_summary() {
+        let mut geog = GeometryValue::from_ewkb(&hex(POINT_4326)).unwrap();
+        geog.geography = true;
+        assert_eq!(geog.get_summary(), "GEOGRAPHY POINT (SRID: 4326)");
+    }
+
+    #[test]
+    fn test_geojson() {
+        let point = GeometryValue::from_ewkb(&hex(POINT_Z)).unwrap();
+        assert_eq!(
+            point.to_geojson(),
+            Some(serde_json::json!({ "type": "Point", "coordinates": [1.0, 2.0, 3.0] }))
+        );
+
+        let multipoint = GeometryValue::from_ewkb(&hex(MULTIPOINT)).unwrap();
+        assert_eq!(
+            multipoint.to_geojson(),
+            Some(serde_json::json!({
+                "type": "MultiPoint",
+                "coordinates": [[1.0, 2.0], [3.0, 4.0]]
+            }))
+        );
+
+        let polygon = GeometryValue::from_ewkb(&hex(POLYGON)).unwrap();
+        assert_eq!(
+            polygon.to_geojson().unwrap()["coordinates"][0][2],
+            serde_json::json!([4.0, 4.0])
+        );
+
+        let collection = GeometryValue::from_ewkb(&hex(COLLECTION_3857)).unwrap();
+        let geojson = collection.to_geojson().unwrap();
+        assert_eq!(geojson["type"], "GeometryCollection");
+        assert_eq!(geojson["geometries"][1]["type"], "LineString");
+        assert_eq!(
+            geojson["geometries"][1]["coordinates"],
+            serde_json::json!([])
+        );
+
+        // Values known only as WKT have no coordinates to convert
+        let wkt_only =
+            GeometryValue::new("POINT".to_string(), None, "POINT(1 2)".to_string(), None);
+        assert_eq!(wkt_only.to_geojson(), None);
+    }
 }
@@ -0,0 +1,119 @@
+use rbeaver::database::{DatabaseConnection, DatabaseError, PostgreSQLConnection, QueryExecutor};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Query Cancellation");
+    println!("=====================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run these tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    let connection = Arc::new(connection);
+
+    // Test 1: Cancelling with nothing running
+    println!("📋 Test 1: Cancel without a running statement");
+    connection.cancel_query().await?;
+    println!("  ✓ No-op when idle");
+
+    // Test 2: Cancelling a long statement
+    println!("\n📋 Test 2: Cancel pg_sleep(30)");
+    let (result, elapsed) = run_and_cancel(&connection).await;
+    match result {
+        Err(DatabaseError::Cancelled) => println!("  ✓ Cancelled after {:?}", elapsed),
+        other => println!("  ✗ Unexpected result: {:?}", other.map(|r| r.row_count())),
+    }
+
+    // Test 3: The connection is still usable
+    println!("\n📋 Test 3: Query after cancellation");
+    let result = connection.execute_query("SELECT 1 AS one").await?;
+    println!("  ✓ Returned {} row(s)", result.row_count());
+
+    println!("\n🎉 Query cancellation tests completed!");
+    Ok(())
+}
+
+/// Start a long-running statement and cancel it once it is on the server
+async fn run_and_cancel(
+    connection: &Arc<PostgreSQLConnection>,
+) -> (
+    Result<rbeaver::database::QueryResult, DatabaseError>,
+    Duration,
+) {
+    let started = Instant::now();
+    let running = {
+        let connection = Arc::clone(connection);
+        tokio::spawn(async move { connection.execute_query("SELECT pg_sleep(30)").await })
+    };
+
+    // Give the statement time to reach the server
+    tokio::time::sleep(Duration::from_millis(500)).await;
+    connection.cancel_query().await.unwrap();
+
+    let result = running.await.unwrap();
+    (result, started.elapsed())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_cancel_running_query() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let connection = Arc::new(connection);
+
+        // Nothing to cancel yet
+        connection.cancel_query().await.unwrap();
+
+        let (result, elapsed) = run_and_cancel(&connection).await;
+        assert!(matches!(result, Err(DatabaseError::Cancelled)));
+        assert!(elapsed < Duration::from_secs(10));
+
+        // The session went back to the pool in a usable state
+        let result = connection.execute_query("SELECT 1 AS one").await.unwrap();
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_is_not_mistaken_for_timeout() {
+        let Some(mut params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The server reports both the same way; the client knows it cancelled
+        params.query_timeout = Some(60);
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let connection = Arc::new(connection);
+
+        let (result, _) = run_and_cancel(&connection).await;
+        assert!(matches!(result, Err(DatabaseError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_requires_connection() {
+        let connection = PostgreSQLConnection::new();
+        assert!(matches!(
+            connection.cancel_query().await,
+            Err(DatabaseError::NotConnected)
+        ));
+    }
+}
@@ -0,0 +1,201 @@
+use rbeaver::database::{
+    ConnectionParams, DatabaseConnection, DatabaseError, DatabaseType, PostgreSQLConnection,
+    QueryExecutor,
+};
+use std::time::Instant;
+
+#[path = "common/mod.rs"]
+mod common;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Query Timeouts");
+    println!("=================================\n");
+
+    // Test 1: Per-connection override
+    println!("📋 Test 1: Effective timeout");
+    let mut params = ConnectionParams::new("Timeout".to_string(), DatabaseType::PostgreSQL);
+    println!(
+        "  ✓ Without override: {}s",
+        params.effective_query_timeout(30)
+    );
+    params.query_timeout = Some(5);
+    println!("  ✓ With override: {}s", params.effective_query_timeout(30));
+
+    let Some(mut params) = common::server_params() else {
+        println!("\n⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    // Test 2: statement_timeout on the server
+    println!("\n📋 Test 2: statement_timeout = 1s");
+    params.query_timeout = Some(1);
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+
+    let started = Instant::now();
+    match connection.execute_query("SELECT pg_sleep(5)").await {
+        Err(DatabaseError::Timeout(msg)) => {
+            println!("  ✓ Timed out after {:?}: {}", started.elapsed(), msg)
+        }
+        other => println!("  ✗ Unexpected result: {:?}", other.map(|r| r.row_count())),
+    }
+
+    let result = connection.execute_query("SHOW statement_timeout").await?;
+    println!(
+        "  ✓ Session setting: {}",
+        result.rows[0].values[0].to_display_string()
+    );
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 Query timeout tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn test_effective_query_timeout() {
+        let mut params = ConnectionParams::new("Timeout".to_string(), DatabaseType::PostgreSQL);
+        assert_eq!(params.query_timeout, None);
+        assert_eq!(params.effective_query_timeout(30), 30);
+
+        params.query_timeout = Some(0);
+        assert_eq!(params.effective_query_timeout(30), 0);
+
+        let duplicate = params.duplicate(None);
+        assert_eq!(duplicate.query_timeout, Some(0));
+    }
+
+    #[test]
+    fn test_saved_connections_without_timeout_still_load() {
+        let mut value = serde_json::to_value(ConnectionParams::new(
+            "Old".to_string(),
+            DatabaseType::MySQL,
+        ))
+        .unwrap();
+        value.as_object_mut().unwrap().remove("query_timeout");
+
+        let params: ConnectionParams = serde_json::from_value(value).unwrap();
+        assert_eq!(params.query_timeout, None);
+    }
+
+    #[tokio::test]
+    async fn test_statement_timeout_is_reported() {
+        let Some(mut params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        params.query_timeout = Some(1);
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+
+        let started = Instant::now();
+        let result = connection.execute_query("SELECT pg_sleep(5)").await;
+        assert!(matches!(result, Err(DatabaseError::Timeout(_))));
+        assert!(started.elapsed() < Duration::from_secs(4));
+
+        // Every pooled session carries the limit
+        let result = connection
+            .execute_query("SHOW statement_timeout")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[0].to_display_string(), "1s");
+
+        // A changed setting reaches the connection without reconnecting
+        connection.set_query_timeout(0);
+        let result = connection
+            .execute_query("SELECT pg_sleep(1.5), current_setting('statement_timeout')")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[1].to_display_string(), "0");
+        connection.set_query_timeout(7);
+        let result = connection
+            .execute_query("SHOW statement_timeout")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[0].to_display_string(), "7s");
+
+        // Without a limit the sessions keep the server default
+        params.query_timeout = None;
+        let mut unlimited = PostgreSQLConnection::new();
+        unlimited.connect(&params).await.unwrap();
+        let result = unlimited
+            .execute_query("SHOW statement_timeout")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[0].to_display_string(), "0");
+    }
+
+    #[tokio::test]
+    async fn test_paused_cursor_outlives_statement_timeout() {
+        let Some(mut params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        params.query_timeout = Some(1);
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+
+        // Far more than the socket buffers hold, so a plain query would sit
+        // unfinished on the server while the cursor waits
+        let mut cursor = connection
+            .open_cursor("SELECT n, repeat('x', 200) AS filler FROM generate_series(1, 50000) n")
+            .await
+            .unwrap();
+        assert_eq!(cursor.fetch(200).await.unwrap().len(), 200);
+
+        tokio::time::sleep(Duration::from_millis(2500)).await;
+        let rows = cursor.fetch(usize::MAX).await.unwrap();
+        assert_eq!(rows.len(), 49800);
+        assert_eq!(rows[0].values[0].to_display_string(), "201");
+        assert!(cursor.is_exhausted());
+
+        // A query inside a transaction of the session's own stays in it
+        let session = connection.open_session().await.unwrap();
+        for sql in [
+            "BEGIN",
+            "CREATE TEMP TABLE paused (n int)",
+            "INSERT INTO paused SELECT generate_series(1, 1500)",
+        ] {
+            let mut cursor = session.open_cursor(sql).await.unwrap();
+            cursor.fetch(usize::MAX).await.unwrap();
+        }
+        let mut cursor = session
+            .open_cursor("SELECT n FROM paused ORDER BY n")
+            .await
+            .unwrap();
+        assert_eq!(cursor.fetch(10).await.unwrap().len(), 10);
+        tokio::time::sleep(Duration::from_millis(1500)).await;
+        assert_eq!(cursor.fetch(usize::MAX).await.unwrap().len(), 1490);
+        drop(cursor);
+
+        let mut cursor = session.open_cursor("ROLLBACK").await.unwrap();
+        cursor.fetch(usize::MAX).await.unwrap();
+        let mut cursor = session.open_cursor("SELECT n FROM paused").await.unwrap();
+        assert!(matches!(
+            cursor.fetch(10).await,
+            Err(DatabaseError::QueryFailed(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_connection_test_applies_timeout() {
+        let Some(mut params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        params.query_timeout = Some(1);
+
+        let connection = PostgreSQLConnection::new();
+        connection.test_connection(&params).await.unwrap();
+    }
+}
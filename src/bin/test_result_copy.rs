@@ -0,0 +1,161 @@
+use rbeaver::database::{
+    CopyFormat, ExportOptions, ExportSource, QueryColumn, QueryResult, QueryRow, QueryValue,
+};
+use rbeaver::ui::GridSelection;
+
+fn main() {
+    println!("🔧 Testing RBeaver Copying Selected Cells");
+    println!("=========================================\n");
+
+    let result = sample();
+    // The name and price of the first two rows
+    let mut selection = GridSelection::cell(0, 1);
+    selection.extend_to(1, 2);
+    println!(
+        "📋 Selection: rows {:?}, columns {:?}\n",
+        selection.rows(),
+        selection.columns()
+    );
+
+    for format in CopyFormat::ALL {
+        println!("📋 Copy as {}", format.name());
+        let text = format
+            .formatter()
+            .format(&source(&result, selection), &options());
+        for line in text.lines() {
+            println!("  {}", line);
+        }
+        println!();
+    }
+
+    println!("🎉 Copy tests completed!");
+}
+
+/// A result with an id, a name and a price column
+fn sample() -> QueryResult {
+    let columns = ["id", "name", "price"]
+        .iter()
+        .enumerate()
+        .map(|(i, name)| QueryColumn::new(name.to_string(), "TEXT".to_string(), i, true))
+        .collect();
+    let rows = vec![
+        (1, "Anvil", Some("12.50")),
+        (2, "Rope", None),
+        (3, "Anvil", Some("12.50")),
+    ]
+    .into_iter()
+    .map(|(id, name, price)| {
+        QueryRow::new(vec![
+            QueryValue::Int32(id),
+            QueryValue::String(name.to_string()),
+            price.map_or(QueryValue::Null, |p| {
+                QueryValue::Decimal(p.parse().unwrap())
+            }),
+        ])
+    })
+    .collect();
+    QueryResult::new("SELECT id, name, price FROM items".to_string())
+        .with_columns(columns)
+        .with_rows(rows)
+}
+
+/// The selected cells, with rows shown in the result's order
+fn source(result: &QueryResult, selection: GridSelection) -> ExportSource<'_> {
+    ExportSource::new(
+        result,
+        selection.columns().collect(),
+        selection.rows().collect(),
+    )
+}
+
+fn options() -> ExportOptions {
+    ExportOptions {
+        table_name: "items".to_string(),
+        ..ExportOptions::default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn copy(format: CopyFormat, selection: GridSelection, include_header: bool) -> String {
+        let result = sample();
+        let options = ExportOptions {
+            include_header,
+            ..options()
+        };
+        format
+            .formatter()
+            .format(&source(&result, selection), &options)
+    }
+
+    #[test]
+    fn test_selection_shapes() {
+        let mut selection = GridSelection::cell(4, 2);
+        selection.extend_to(1, 0);
+        assert_eq!(selection.rows(), 1..=4);
+        assert_eq!(selection.columns(), 0..=2);
+        assert_eq!(selection.cell_count(), 12);
+        assert!(selection.contains(2, 1));
+        assert!(!selection.contains(5, 1));
+        assert!(selection.contains_row(3, 3));
+        assert!(!selection.contains_row(3, 4));
+
+        let mut rows = GridSelection::row(2, 3);
+        rows.extend_rows_to(0, 3);
+        assert_eq!(rows.rows(), 0..=2);
+        assert_eq!(rows.columns(), 0..=2);
+
+        let column = GridSelection::column(1, 10);
+        assert_eq!(column.rows(), 0..=9);
+        assert_eq!(column.columns(), 1..=1);
+        assert_eq!(GridSelection::all(10, 3).cell_count(), 30);
+    }
+
+    #[test]
+    fn test_tsv_with_and_without_header() {
+        let mut selection = GridSelection::cell(0, 1);
+        selection.extend_to(1, 2);
+        assert_eq!(
+            copy(CopyFormat::Tsv, selection, true),
+            "name\tprice\r\nAnvil\t12.50\r\nRope\t\r\n"
+        );
+        assert_eq!(
+            copy(CopyFormat::Tsv, selection, false),
+            "Anvil\t12.50\r\nRope\t\r\n"
+        );
+    }
+
+    #[test]
+    fn test_in_lists() {
+        // One column gives plain values, repeated ones only once
+        assert_eq!(
+            copy(CopyFormat::InList, GridSelection::column(1, 3), false),
+            "IN ('Anvil', 'Rope')"
+        );
+        let mut selection = GridSelection::cell(0, 0);
+        selection.extend_to(1, 1);
+        assert_eq!(
+            copy(CopyFormat::InList, selection, false),
+            "IN ((1, 'Anvil'), (2, 'Rope'))"
+        );
+    }
+
+    #[test]
+    fn test_structured_formats() {
+        let row = GridSelection::row(1, 3);
+        assert_eq!(
+            copy(CopyFormat::Json, row, true),
+            "[\n  {\"id\":2,\"name\":\"Rope\",\"price\":null}\n]\n"
+        );
+        assert_eq!(
+            copy(CopyFormat::Markdown, row, false),
+            "| id | name | price |\n| --- | --- | --- |\n| 2 | Rope |  |\n"
+        );
+        assert_eq!(
+            copy(CopyFormat::Insert, row, false),
+            "INSERT INTO \"items\" (\"id\", \"name\", \"price\") VALUES (2, 'Rope', NULL);\n"
+        );
+    }
+}
@@ -0,0 +1,436 @@
+use rbeaver::database::{
+    DatabaseConnection, EditTarget, PostgreSQLConnection, QueryExecutor, QueryResult,
+};
+use rbeaver::ui::pending_changes::{EditRow, PendingChanges};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const ITEMS_QUERY: &str = "id, name, price, price * 2 AS doubled";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Result Editing");
+    println!("=================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    create_items(&connection, "rbeaver_edit_demo").await?;
+
+    // Test 1: Finding the table a result can be edited in
+    println!("📋 Test 1: Finding the table behind a result");
+    let result = query_items(&connection, "rbeaver_edit_demo").await?;
+    let target = edit_target(&connection, &result)
+        .await?
+        .ok_or("result is not editable")?;
+    println!(
+        "  ✓ {}.{} keyed by column {:?}",
+        target.schema, target.table, target.key_columns
+    );
+
+    // Test 2: Generating statements for pending changes
+    println!("\n📋 Test 2: Generating statements");
+    let mut changes = PendingChanges::default();
+    changes.set_value(EditRow::Existing(0), 1, Some("Anvil (heavy)".to_string()));
+    changes.delete_row(EditRow::Existing(2));
+    let row = changes.insert_row();
+    changes.set_value(row, 1, Some("Dynamite".to_string()));
+    let statements = changes.statements(&target, &result);
+    for statement in &statements {
+        println!("  ✓ {}", statement.preview);
+    }
+
+    // Test 3: Saving them
+    println!("\n📋 Test 3: Saving in one transaction");
+    let rows = connection.execute_dml(&statements).await?;
+    println!("  ✓ {} rows changed", rows);
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_edit_demo CASCADE")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("\n🎉 Result editing tests completed!");
+    Ok(())
+}
+
+/// Create a schema holding a small table to edit
+async fn create_items(
+    connection: &PostgreSQLConnection,
+    schema: &str,
+) -> Result<(), Box<dyn std::error::Error>> {
+    for sql in [
+        format!("DROP SCHEMA IF EXISTS {} CASCADE", schema),
+        format!("CREATE SCHEMA {}", schema),
+        format!(
+            "CREATE TABLE {}.items (
+                id serial PRIMARY KEY,
+                name varchar(20) NOT NULL DEFAULT 'unnamed',
+                price numeric(10, 2)
+            )",
+            schema
+        ),
+        format!(
+            "INSERT INTO {}.items (name, price)
+             VALUES ('Anvil', 12.50), ('Rope', 3.25), ('Bird seed', NULL)",
+            schema
+        ),
+    ] {
+        connection.execute_non_query(&sql).await?;
+    }
+    Ok(())
+}
+
+async fn query_items(
+    connection: &PostgreSQLConnection,
+    schema: &str,
+) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    let sql = format!("SELECT {} FROM {}.items ORDER BY id", ITEMS_QUERY, schema);
+    let mut cursor = connection.open_cursor(&sql).await?;
+    let rows = cursor.fetch(100).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+/// Load the key of the result's table, as the application does
+async fn edit_target(
+    connection: &PostgreSQLConnection,
+    result: &QueryResult,
+) -> Result<Option<EditTarget>, Box<dyn std::error::Error>> {
+    let Some((schema, table)) = EditTarget::source_table(result) else {
+        return Ok(None);
+    };
+    let columns = connection.get_columns(&schema, &table).await?;
+    Ok(EditTarget::new(result, &columns))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::dml::TargetColumn;
+    use rbeaver::database::{Column, DatabaseError, QueryColumn, QueryRow, QueryValue, RowChange};
+
+    fn target() -> EditTarget {
+        let column = |name: &str, cast_type: &str| {
+            Some(TargetColumn {
+                name: name.to_string(),
+                cast_type: Some(cast_type.to_string()),
+            })
+        };
+        EditTarget {
+            schema: "shop".to_string(),
+            table: "items".to_string(),
+            columns: vec![
+                column("id", "integer"),
+                column("name", "character varying"),
+                None,
+            ],
+            key_columns: vec![0],
+        }
+    }
+
+    /// A result read from `shop.items`, with a computed third column
+    fn items_result() -> QueryResult {
+        let column = |name: &str, ordinal| {
+            let mut column = QueryColumn::new(name.to_string(), "TEXT".to_string(), ordinal, true);
+            column.table_oid = Some(16384);
+            column.source_schema = Some("shop".to_string());
+            column.source_table = Some("items".to_string());
+            column.source_column = Some(name.to_string());
+            column
+        };
+        let rows = (1..=3)
+            .map(|id| {
+                QueryRow::new(vec![
+                    QueryValue::Int32(id),
+                    QueryValue::String(format!("item {}", id)),
+                    QueryValue::Int64(id as i64 * 2),
+                ])
+            })
+            .collect();
+        QueryResult::new("SELECT id, name, id * 2 FROM shop.items".to_string())
+            .with_columns(vec![
+                column("id", 0),
+                column("name", 1),
+                QueryColumn::new("doubled".to_string(), "INT8".to_string(), 2, true),
+            ])
+            .with_rows(rows)
+    }
+
+    fn table_column(name: &str, is_primary_key: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: "text".to_string(),
+            is_nullable: !is_primary_key,
+            default_value: None,
+            is_primary_key,
+            comment: None,
+        }
+    }
+
+    #[test]
+    fn test_postgres_parameters_are_cast() {
+        let statement = target().statement(&RowChange::Update {
+            key: vec![QueryValue::Int32(7)],
+            values: vec![(1, Some("O'Brien".to_string()))],
+        });
+        assert_eq!(
+            statement.sql,
+            r#"UPDATE "shop"."items" SET "name" = $1::character varying WHERE "id" = $2::integer"#
+        );
+        assert_eq!(
+            statement.params,
+            vec![Some("O'Brien".to_string()), Some("7".to_string())]
+        );
+        assert_eq!(
+            statement.preview,
+            r#"UPDATE "shop"."items" SET "name" = 'O''Brien'::character varying WHERE "id" = '7'::integer"#
+        );
+    }
+
+    #[test]
+    fn test_inserts_and_deletes() {
+        let target = target();
+        let insert = target.statement(&RowChange::Insert {
+            values: vec![(0, Some("1".to_string())), (1, None)],
+        });
+        assert_eq!(
+            insert.sql,
+            r#"INSERT INTO "shop"."items" ("id", "name") VALUES ($1::integer, $2::character varying)"#
+        );
+        assert_eq!(insert.params, vec![Some("1".to_string()), None]);
+        assert!(insert
+            .preview
+            .ends_with("('1'::integer, NULL::character varying)"));
+
+        let empty = target.statement(&RowChange::Insert { values: vec![] });
+        assert_eq!(empty.sql, r#"INSERT INTO "shop"."items" DEFAULT VALUES"#);
+
+        let delete = target.statement(&RowChange::Delete {
+            key: vec![QueryValue::Int32(3)],
+        });
+        assert_eq!(
+            delete.sql,
+            r#"DELETE FROM "shop"."items" WHERE "id" = $1::integer"#
+        );
+    }
+
+    #[test]
+    fn test_rows_affected_must_be_one() {
+        let statement = target().statement(&RowChange::Delete {
+            key: vec![QueryValue::Int32(3)],
+        });
+        assert!(statement.check_rows_affected(1).is_ok());
+        for rows in [0, 2] {
+            let Err(DatabaseError::QueryFailed(message)) = statement.check_rows_affected(rows)
+            else {
+                panic!("{} rows were accepted", rows);
+            };
+            assert!(message.contains(&statement.preview));
+        }
+    }
+
+    #[test]
+    fn test_edit_target_needs_the_whole_key() {
+        let result = items_result();
+        assert_eq!(
+            EditTarget::source_table(&result),
+            Some(("shop".to_string(), "items".to_string()))
+        );
+
+        let target = EditTarget::new(
+            &result,
+            &[table_column("id", true), table_column("name", false)],
+        )
+        .unwrap();
+        assert_eq!(target.key_columns, vec![0]);
+        assert!(target.is_editable(1));
+        assert!(!target.is_editable(2));
+        let key = target.row_key(&result, 1).unwrap();
+        assert_eq!(key.len(), 1);
+        assert_eq!(key[0].to_display_string(), "2");
+
+        // A key column the result leaves out, or no key at all
+        let partial_key = [table_column("id", true), table_column("sku", true)];
+        assert!(EditTarget::new(&result, &partial_key).is_none());
+        let no_key = [table_column("id", false), table_column("name", false)];
+        assert!(EditTarget::new(&result, &no_key).is_none());
+
+        // Columns read from a second table
+        let mut joined = items_result();
+        joined.columns[1].table_oid = Some(16390);
+        assert_eq!(EditTarget::source_table(&joined), None);
+
+        // Rows returned by a statement that wrote them
+        let mut returning = items_result();
+        returning.query = "INSERT INTO shop.items (name) VALUES ('x') RETURNING *".to_string();
+        let columns = [table_column("id", true), table_column("name", false)];
+        assert!(EditTarget::new(&returning, &columns).is_none());
+    }
+
+    #[test]
+    fn test_pending_changes_become_statements() {
+        let result = items_result();
+        let target = target();
+
+        let mut changes = PendingChanges::default();
+        changes.set_value(EditRow::Existing(2), 1, Some("renamed".to_string()));
+        changes.set_value(EditRow::Existing(0), 1, Some("gone anyway".to_string()));
+        changes.delete_row(EditRow::Existing(0));
+        let row = changes.insert_row();
+        changes.set_value(row, 1, Some("new".to_string()));
+        let dropped = changes.insert_row();
+        changes.delete_row(dropped);
+        assert_eq!(changes.row_count(), 3);
+
+        let statements: Vec<String> = changes
+            .statements(&target, &result)
+            .into_iter()
+            .map(|statement| statement.preview)
+            .collect();
+        assert_eq!(
+            statements,
+            vec![
+                r#"UPDATE "shop"."items" SET "name" = 'renamed'::character varying WHERE "id" = '3'::integer"#,
+                r#"DELETE FROM "shop"."items" WHERE "id" = '1'::integer"#,
+                r#"INSERT INTO "shop"."items" ("name") VALUES ('new'::character varying)"#,
+            ]
+        );
+
+        changes.clear();
+        assert!(changes.is_empty());
+    }
+
+    #[test]
+    fn test_pending_changes_follow_sorting() {
+        let mut changes = PendingChanges::default();
+        changes.set_value(EditRow::Existing(0), 1, None);
+        changes.delete_row(EditRow::Existing(2));
+
+        // Reversing three rows
+        changes.reorder_rows(&[2, 1, 0]);
+        assert_eq!(changes.value(EditRow::Existing(2), 1), Some(&None));
+        assert_eq!(changes.value(EditRow::Existing(0), 1), None);
+        assert!(changes.deleted.contains(&0));
+
+        changes.revert_value(EditRow::Existing(2), 1);
+        changes.undelete_row(0);
+        assert!(changes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_saved_changes_reach_the_table() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        create_items(&connection, "rbeaver_edit_saved")
+            .await
+            .unwrap();
+
+        let result = query_items(&connection, "rbeaver_edit_saved")
+            .await
+            .unwrap();
+        let target = edit_target(&connection, &result).await.unwrap().unwrap();
+        assert_eq!(target.key_columns, vec![0]);
+        assert!(!target.is_editable(3));
+
+        let mut changes = PendingChanges::default();
+        changes.set_value(EditRow::Existing(0), 1, Some("Anvil (heavy)".to_string()));
+        changes.set_value(EditRow::Existing(0), 2, Some("99.95".to_string()));
+        changes.set_value(EditRow::Existing(1), 2, None);
+        changes.delete_row(EditRow::Existing(2));
+        let row = changes.insert_row();
+        changes.set_value(row, 2, Some("1.5".to_string()));
+
+        let statements = changes.statements(&target, &result);
+        let rows = connection.execute_dml(&statements).await.unwrap();
+        assert_eq!(rows, 4);
+
+        let result = query_items(&connection, "rbeaver_edit_saved")
+            .await
+            .unwrap();
+        let rows: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| {
+                let values: Vec<String> =
+                    row.values.iter().map(|v| v.to_display_string()).collect();
+                values.join("|")
+            })
+            .collect();
+        assert_eq!(
+            rows,
+            vec![
+                "1|Anvil (heavy)|99.95|199.90",
+                "2|Rope|NULL|NULL",
+                "4|unnamed|1.50|3.00",
+            ]
+        );
+
+        connection
+            .execute_non_query("DROP SCHEMA rbeaver_edit_saved CASCADE")
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_failed_save_changes_nothing() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        create_items(&connection, "rbeaver_edit_failed")
+            .await
+            .unwrap();
+
+        let result = query_items(&connection, "rbeaver_edit_failed")
+            .await
+            .unwrap();
+        let target = edit_target(&connection, &result).await.unwrap().unwrap();
+        let rename = RowChange::Update {
+            key: vec![QueryValue::Int32(1)],
+            values: vec![(1, Some("Renamed".to_string()))],
+        };
+
+        // A row deleted since the result was read
+        let vanished = RowChange::Delete {
+            key: vec![QueryValue::Int32(42)],
+        };
+        let statements = [target.statement(&rename), target.statement(&vanished)];
+        assert!(connection.execute_dml(&statements).await.is_err());
+
+        // Too long for varchar(20), which an explicit cast would truncate
+        let too_long = RowChange::Update {
+            key: vec![QueryValue::Int32(2)],
+            values: vec![(1, Some("x".repeat(30)))],
+        };
+        let statements = [target.statement(&rename), target.statement(&too_long)];
+        assert!(connection.execute_dml(&statements).await.is_err());
+
+        let result = query_items(&connection, "rbeaver_edit_failed")
+            .await
+            .unwrap();
+        let names: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| row.values[1].to_display_string())
+            .collect();
+        assert_eq!(names, vec!["Anvil", "Rope", "Bird seed"]);
+
+        connection
+            .execute_non_query("DROP SCHEMA rbeaver_edit_failed CASCADE")
+            .await
+            .unwrap();
+    }
+}
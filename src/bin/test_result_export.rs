@@ -0,0 +1,319 @@
+use rbeaver::database::export::export;
+use rbeaver::database::{
+    DatabaseConnection, ExportFormat, ExportOptions, ExportSource, PostgreSQLConnection,
+    QueryExecutor, QueryResult,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const TYPED_QUERY: &str =
+    "SELECT 1 AS id, 'O''Brien, \"Bob\"' AS name, 12.50::numeric(10,2) AS price,
+        DATE '2024-02-29' AS added, TIMESTAMP '2024-02-29 13:45:00.5' AS updated,
+        INTERVAL '1 day 02:00' AS lease, '\\x00ff'::bytea AS data, '{\"a\": [1, 2]}'::jsonb AS doc,
+        '6ecd8c99-4036-403d-bf84-cf8400f67836'::uuid AS ref, '10.0.0.0/8'::cidr AS net,
+        ARRAY[1, NULL, 3] AS scores, B'1010' AS flags, 'NaN'::float8 AS ratio, NULL::text AS note
+    UNION ALL
+    SELECT 2, '', NULL, NULL, NULL, NULL, NULL, 'null'::jsonb, NULL, NULL, '{}', NULL, 1.5, 'line 1
+line 2'";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Result Export");
+    println!("================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    let result = run_query(&connection, TYPED_QUERY).await?;
+
+    // Test 1: Every format
+    for format in ExportFormat::ALL {
+        println!("📋 Test: {}", format.name());
+        let options = ExportOptions {
+            format,
+            table_name: "public.typed_rows".to_string(),
+            ..ExportOptions::default()
+        };
+        let bytes = export(&source(&result), &options);
+        for line in String::from_utf8(bytes)?.lines().take(8) {
+            println!("  {}", line);
+        }
+        println!();
+    }
+
+    connection.disconnect().await?;
+
+    println!("🎉 Result export tests completed!");
+    Ok(())
+}
+
+fn source(result: &QueryResult) -> ExportSource<'_> {
+    ExportSource::new(
+        result,
+        (0..result.column_count()).collect(),
+        (0..result.row_count()).collect(),
+    )
+}
+
+async fn run_query(
+    connection: &PostgreSQLConnection,
+    sql: &str,
+) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    let mut cursor = connection.open_cursor(sql).await?;
+    let rows = cursor.fetch(100).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::export::{json_value, sql_literal};
+    use rbeaver::database::{
+        DatabaseType, QueryColumn, QueryRow, QueryValue, QuoteStyle, TextEncoding,
+    };
+
+    /// A result with a number, a text and a decimal column
+    fn sample() -> QueryResult {
+        let columns = ["id", "name", "price"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| QueryColumn::new(name.to_string(), "TEXT".to_string(), i, true))
+            .collect();
+        let rows = vec![
+            QueryRow::new(vec![
+                QueryValue::Int32(1),
+                QueryValue::String("O'Brien, \"Bob\"".to_string()),
+                QueryValue::Decimal("12.50".parse().unwrap()),
+            ]),
+            QueryRow::new(vec![
+                QueryValue::Int32(2),
+                QueryValue::String(String::new()),
+                QueryValue::Null,
+            ]),
+        ];
+        QueryResult::new("SELECT id, name, price FROM items".to_string())
+            .with_columns(columns)
+            .with_rows(rows)
+    }
+
+    fn text(format: ExportFormat, options: ExportOptions) -> String {
+        let result = sample();
+        let options = ExportOptions { format, ..options };
+        String::from_utf8(export(&source(&result), &options)).unwrap()
+    }
+
+    #[test]
+    fn test_csv_quoting_and_nulls() {
+        // Empty text is quoted so it reads back differently from NULL
+        assert_eq!(
+            text(ExportFormat::Csv, ExportOptions::default()),
+            "id,name,price\r\n1,\"O'Brien, \"\"Bob\"\"\",12.50\r\n2,\"\",\r\n"
+        );
+        assert_eq!(
+            text(
+                ExportFormat::Csv,
+                ExportOptions {
+                    delimiter: ';',
+                    quoting: QuoteStyle::Always,
+                    null_text: "NULL".to_string(),
+                    include_header: false,
+                    ..ExportOptions::default()
+                }
+            ),
+            "\"1\";\"O'Brien, \"\"Bob\"\"\";\"12.50\"\r\n\"2\";\"\";NULL\r\n"
+        );
+        assert_eq!(
+            text(
+                ExportFormat::Tsv,
+                ExportOptions {
+                    quoting: QuoteStyle::Never,
+                    null_text: "\\N".to_string(),
+                    ..ExportOptions::default()
+                }
+            ),
+            "id\tname\tprice\r\n1\tO'Brien, \"Bob\"\t12.50\r\n2\t\t\\N\r\n"
+        );
+    }
+
+    #[test]
+    fn test_json_keeps_types_and_column_order() {
+        let expected_rows = [
+            r#"{"id":1,"name":"O'Brien, \"Bob\"","price":12.5}"#,
+            r#"{"id":2,"name":"","price":null}"#,
+        ];
+        assert_eq!(
+            text(ExportFormat::Json, ExportOptions::default()),
+            format!("[\n  {},\n  {}\n]\n", expected_rows[0], expected_rows[1])
+        );
+        assert_eq!(
+            text(ExportFormat::Ndjson, ExportOptions::default()),
+            format!("{}\n{}\n", expected_rows[0], expected_rows[1])
+        );
+
+        // Decimals a double cannot hold stay exact as strings
+        let big = QueryValue::Decimal("12345678901234567890.01".parse().unwrap());
+        assert_eq!(json_value(&big), "12345678901234567890.01");
+        let nested = QueryValue::Array(vec![QueryValue::Int64(1), QueryValue::Null]);
+        assert_eq!(json_value(&nested).to_string(), "[1,null]");
+        assert_eq!(json_value(&QueryValue::Float64(f64::NAN)), "NaN");
+    }
+
+    #[test]
+    fn test_markdown_and_html_escaping() {
+        let mut result = sample();
+        result.rows[1].values[1] = QueryValue::String("a|b\nc <d>".to_string());
+        let options = ExportOptions {
+            null_text: "NULL".to_string(),
+            ..ExportOptions::default()
+        };
+
+        let markdown = ExportFormat::Markdown
+            .formatter()
+            .format(&source(&result), &options);
+        assert_eq!(
+            markdown,
+            "| id | name | price |\n| --- | --- | --- |\n\
+             | 1 | O'Brien, \"Bob\" | 12.50 |\n| 2 | a\\|b<br>c <d> | NULL |\n"
+        );
+
+        let html = ExportFormat::Html
+            .formatter()
+            .format(&source(&result), &options);
+        assert!(html.contains("<tr><th>id</th><th>name</th><th>price</th></tr>"));
+        assert!(html
+            .contains("<tr><td>2</td><td>a|b\nc &lt;d&gt;</td><td class=\"null\">NULL</td></tr>"));
+    }
+
+    #[test]
+    fn test_insert_statements() {
+        let options = ExportOptions {
+            table_name: "shop.items".to_string(),
+            ..ExportOptions::default()
+        };
+        assert_eq!(
+            text(ExportFormat::SqlInsert, options.clone()),
+            "INSERT INTO \"shop\".\"items\" (\"id\", \"name\", \"price\") \
+             VALUES (1, 'O''Brien, \"Bob\"', 12.50);\n\
+             INSERT INTO \"shop\".\"items\" (\"id\", \"name\", \"price\") \
+             VALUES (2, '', NULL);\n"
+        );
+
+        let mysql = ExportOptions {
+            database_type: DatabaseType::MySQL,
+            ..options
+        };
+        assert!(text(ExportFormat::SqlInsert, mysql)
+            .starts_with("INSERT INTO `shop`.`items` (`id`, `name`, `price`) VALUES (1, "));
+    }
+
+    #[test]
+    fn test_typed_literals() {
+        let postgres = |value: &QueryValue, column: Option<&QueryColumn>| {
+            sql_literal(value, column, &DatabaseType::PostgreSQL)
+        };
+        let date = QueryValue::Date("2024-02-29".parse().unwrap());
+        let bytes = QueryValue::Bytes(vec![0x00, 0xff]);
+        let json = QueryValue::Json(serde_json::json!({"a": 1}));
+
+        assert_eq!(postgres(&QueryValue::Bool(true), None), "TRUE");
+        assert_eq!(postgres(&date, None), "DATE '2024-02-29'");
+        assert_eq!(postgres(&bytes, None), "'\\x00ff'::bytea");
+        assert_eq!(
+            postgres(&QueryValue::Float64(f64::INFINITY), None),
+            "'Infinity'::double precision"
+        );
+        assert_eq!(
+            postgres(&QueryValue::Bits("101".to_string()), None),
+            "B'101'"
+        );
+        assert_eq!(postgres(&json, None), "'{\"a\":1}'::jsonb");
+
+        // The column's server type tells json from jsonb
+        let mut column = QueryColumn::new("doc".to_string(), "JSON".to_string(), 0, true);
+        column.type_name = Some("json".to_string());
+        assert_eq!(postgres(&json, Some(&column)), "'{\"a\":1}'::json");
+
+        let mysql = |value: &QueryValue| sql_literal(value, None, &DatabaseType::MySQL);
+        assert_eq!(mysql(&bytes), "X'00ff'");
+        assert_eq!(mysql(&json), "CAST('{\"a\":1}' AS JSON)");
+        assert_eq!(
+            mysql(&QueryValue::String("C:\\tmp".to_string())),
+            "'C:\\\\tmp'"
+        );
+        assert_eq!(mysql(&QueryValue::Float64(f64::NAN)), "NULL");
+
+        let sqlite = |value: &QueryValue| sql_literal(value, None, &DatabaseType::SQLite);
+        assert_eq!(sqlite(&QueryValue::Bool(false)), "0");
+        assert_eq!(sqlite(&date), "'2024-02-29'");
+        assert_eq!(sqlite(&QueryValue::Float32(f32::NEG_INFINITY)), "-9e999");
+    }
+
+    #[test]
+    fn test_encodings() {
+        assert_eq!(
+            TextEncoding::Utf8.encode("é", true),
+            vec![0xEF, 0xBB, 0xBF, 0xC3, 0xA9]
+        );
+        assert_eq!(
+            TextEncoding::Utf16Le.encode("é", true),
+            vec![0xFF, 0xFE, 0xE9, 0x00]
+        );
+        assert_eq!(TextEncoding::Utf16Be.encode("é", false), vec![0x00, 0xE9]);
+    }
+
+    #[tokio::test]
+    async fn test_exported_inserts_read_back_equal() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let result = run_query(&connection, TYPED_QUERY).await.unwrap();
+
+        for sql in [
+            "DROP SCHEMA IF EXISTS rbeaver_export_demo CASCADE".to_string(),
+            "CREATE SCHEMA rbeaver_export_demo".to_string(),
+            format!(
+                "CREATE TABLE rbeaver_export_demo.copy AS {} LIMIT 0",
+                TYPED_QUERY
+            ),
+        ] {
+            connection.execute_non_query(&sql).await.unwrap();
+        }
+        let options = ExportOptions {
+            format: ExportFormat::SqlInsert,
+            table_name: "rbeaver_export_demo.copy".to_string(),
+            ..ExportOptions::default()
+        };
+        let sql = String::from_utf8(export(&source(&result), &options)).unwrap();
+        for statement in sql.split(";\n").filter(|statement| !statement.is_empty()) {
+            connection.execute_non_query(statement).await.unwrap();
+        }
+
+        let copy = run_query(&connection, "SELECT * FROM rbeaver_export_demo.copy")
+            .await
+            .unwrap();
+        let texts = |result: &QueryResult| -> Vec<Vec<Option<String>>> {
+            result
+                .rows
+                .iter()
+                .map(|row| row.values.iter().map(|value| value.to_sql_text()).collect())
+                .collect()
+        };
+        assert_eq!(texts(&result), texts(&copy), "{}", sql);
+
+        connection
+            .execute_non_query("DROP SCHEMA rbeaver_export_demo CASCADE")
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+}
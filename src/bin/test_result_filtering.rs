@@ -0,0 +1,320 @@
+use rbeaver::database::result_filter::{filter_rows, rewrite_query, sort_order};
+use rbeaver::database::{
+    ColumnFilter, DatabaseConnection, DatabaseType, FilterCondition, NullsOrder,
+    PostgreSQLConnection, QueryExecutor, QueryResult, SortDirection, SortKey,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const PRODUCTS_QUERY: &str = "SELECT * FROM (VALUES
+        (1, 'Anvil', 12.50, DATE '2024-01-05'),
+        (2, 'Rope', NULL, DATE '2024-02-10'),
+        (3, 'anvil case', 7.25, NULL),
+        (4, 'Bird seed', 3.00, DATE '2023-12-24'),
+        (5, '100% wool', 12.50, DATE '2024-01-05'),
+        (6, NULL, 0.99, DATE '2024-03-01')
+    ) AS products(id, name, price, added);";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Result Sorting and Filtering");
+    println!("===============================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    let result = run_query(&connection, PRODUCTS_QUERY).await?;
+
+    // Test 1: Sorting in memory
+    println!("📋 Test 1: Sorting by price, then name");
+    let sort = [
+        SortKey {
+            column: 2,
+            direction: SortDirection::Descending,
+            nulls: NullsOrder::Last,
+        },
+        SortKey::ascending(1),
+    ];
+    for row in sort_order(&result, &sort) {
+        println!("  ✓ {}", row_text(&result, row));
+    }
+
+    // Test 2: Filtering in memory
+    println!("\n📋 Test 2: Filtering names containing \"anvil\"");
+    let filters = [ColumnFilter {
+        column: 1,
+        condition: FilterCondition::parse("anvil").unwrap(),
+    }];
+    for row in filter_rows(&result, &filters) {
+        println!("  ✓ {}", row_text(&result, row));
+    }
+
+    // Test 3: The same on the server
+    println!("\n📋 Test 3: Filtering and sorting on the server");
+    let sql = rewrite_query(&DatabaseType::PostgreSQL, &result, &filters, &sort)
+        .ok_or("query could not be rewritten")?;
+    let server = run_query(&connection, &sql).await?;
+    for row in 0..server.rows.len() {
+        println!("  ✓ {}", row_text(&server, row));
+    }
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 Result sorting and filtering tests completed!");
+    Ok(())
+}
+
+async fn run_query(
+    connection: &PostgreSQLConnection,
+    sql: &str,
+) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    let mut cursor = connection.open_cursor(sql).await?;
+    let rows = cursor.fetch(100).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+fn row_text(result: &QueryResult, row: usize) -> String {
+    let values: Vec<String> = result.rows[row]
+        .values
+        .iter()
+        .map(|value| value.to_display_string())
+        .collect();
+    values.join(" | ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::{QueryColumn, QueryRow, QueryValue};
+
+    /// A result with an integer, a text and a decimal column
+    fn sample() -> QueryResult {
+        let columns = ["id", "name", "price"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| QueryColumn::new(name.to_string(), "TEXT".to_string(), i, true))
+            .collect();
+        let decimal = |text: &str| QueryValue::Decimal(text.parse().unwrap());
+        let rows = vec![
+            (1, Some("Anvil"), Some("12.50")),
+            (2, Some("rope"), None),
+            (3, Some("Bird seed"), Some("3")),
+            (10, None, Some("12.5")),
+        ]
+        .into_iter()
+        .map(|(id, name, price)| {
+            QueryRow::new(vec![
+                QueryValue::Int32(id),
+                name.map_or(QueryValue::Null, |n| QueryValue::String(n.to_string())),
+                price.map_or(QueryValue::Null, decimal),
+            ])
+        })
+        .collect();
+        QueryResult::new("SELECT id, name, price FROM products;".to_string())
+            .with_columns(columns)
+            .with_rows(rows)
+    }
+
+    /// A column and the filter text typed for it
+    type ColumnFilterText = (usize, &'static str);
+
+    fn filter(column: usize, text: &str) -> Vec<usize> {
+        let filters = [ColumnFilter {
+            column,
+            condition: FilterCondition::parse(text).unwrap(),
+        }];
+        filter_rows(&sample(), &filters)
+    }
+
+    #[test]
+    fn test_filter_parsing() {
+        assert_eq!(FilterCondition::parse("  "), None);
+        assert_eq!(
+            FilterCondition::parse("null"),
+            Some(FilterCondition::IsNull)
+        );
+        assert_eq!(
+            FilterCondition::parse("NOT NULL"),
+            Some(FilterCondition::IsNotNull)
+        );
+        assert_eq!(
+            FilterCondition::parse(">= 5"),
+            Some(FilterCondition::AtLeast("5".to_string()))
+        );
+        assert_eq!(
+            FilterCondition::parse("2..4"),
+            Some(FilterCondition::Between("2".to_string(), "4".to_string()))
+        );
+        assert_eq!(
+            FilterCondition::parse("=Anvil"),
+            Some(FilterCondition::Equals("Anvil".to_string()))
+        );
+        assert_eq!(
+            FilterCondition::parse("seed"),
+            Some(FilterCondition::Contains("seed".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_filters_compare_by_type() {
+        // Numbers compare as numbers, not as text
+        assert_eq!(filter(0, ">=3"), vec![2, 3]);
+        assert_eq!(filter(0, "2..9"), vec![1, 2]);
+        assert_eq!(filter(2, "=12.5"), vec![0, 3]);
+        assert_eq!(filter(2, "<=4"), vec![2]);
+
+        // Text searches ignore case; NULLs only pass the NULL tests
+        assert_eq!(filter(1, "AN"), vec![0]);
+        assert_eq!(filter(1, "=rope"), vec![1]);
+        assert_eq!(filter(1, "null"), vec![3]);
+        assert_eq!(filter(2, "not null"), vec![0, 2, 3]);
+    }
+
+    #[test]
+    fn test_multi_column_sort_with_nulls() {
+        let result = sample();
+        let by_price = SortKey {
+            column: 2,
+            direction: SortDirection::Descending,
+            nulls: NullsOrder::Last,
+        };
+        assert_eq!(
+            sort_order(&result, &[by_price.clone(), SortKey::ascending(0)]),
+            vec![0, 3, 2, 1]
+        );
+
+        let mut by_id = SortKey::ascending(0);
+        by_id.reverse();
+        assert_eq!(by_id.nulls, NullsOrder::First);
+        assert_eq!(sort_order(&result, &[by_price, by_id]), vec![3, 0, 2, 1]);
+
+        let mut nulls_first = SortKey::ascending(1);
+        nulls_first.nulls = NullsOrder::First;
+        assert_eq!(sort_order(&result, &[nulls_first]), vec![3, 0, 2, 1]);
+    }
+
+    #[test]
+    fn test_rewritten_queries() {
+        let result = sample();
+        let filters = [
+            ColumnFilter {
+                column: 1,
+                condition: FilterCondition::Contains("50%_off!".to_string()),
+            },
+            ColumnFilter {
+                column: 2,
+                condition: FilterCondition::Between("1".to_string(), "9".to_string()),
+            },
+        ];
+        let sort = [SortKey::ascending(2)];
+
+        assert_eq!(
+            rewrite_query(&DatabaseType::PostgreSQL, &result, &filters, &sort).unwrap(),
+            "SELECT * FROM (\nSELECT id, name, price FROM products\n) AS filtered\n\
+             WHERE CAST(\"name\" AS text) ILIKE '%50!%!_off!!%' ESCAPE '!' \
+             AND \"price\" BETWEEN '1' AND '9'\n\
+             ORDER BY \"price\" ASC NULLS LAST"
+        );
+        assert_eq!(
+            rewrite_query(&DatabaseType::MySQL, &result, &[], &sort).unwrap(),
+            "SELECT * FROM (\nSELECT id, name, price FROM products\n) AS filtered\n\
+             ORDER BY `price` IS NULL ASC, `price` ASC"
+        );
+
+        // MySQL strings take backslashes as escapes
+        let path = [ColumnFilter {
+            column: 1,
+            condition: FilterCondition::Equals("C:\\temp\\it's".to_string()),
+        }];
+        assert_eq!(
+            rewrite_query(&DatabaseType::MySQL, &result, &path, &[]).unwrap(),
+            "SELECT * FROM (\nSELECT id, name, price FROM products\n) AS filtered\n\
+             WHERE `name` = 'C:\\\\temp\\\\it''s'"
+        );
+        assert!(
+            rewrite_query(&DatabaseType::PostgreSQL, &result, &path, &[])
+                .unwrap()
+                .ends_with("WHERE \"name\" = 'C:\\temp\\it''s'")
+        );
+
+        // Columns sharing a name cannot be told apart outside the query
+        let mut ambiguous = sample();
+        ambiguous.columns[0].name = "name".to_string();
+        assert!(rewrite_query(&DatabaseType::PostgreSQL, &ambiguous, &[], &sort).is_some());
+        assert!(rewrite_query(&DatabaseType::PostgreSQL, &ambiguous, &filters, &sort).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_server_agrees_with_local_filtering_and_sorting() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let result = run_query(&connection, PRODUCTS_QUERY).await.unwrap();
+
+        let cases: Vec<(Vec<ColumnFilterText>, Vec<SortKey>)> = vec![
+            (vec![(1, "ANVIL")], vec![SortKey::ascending(0)]),
+            (vec![(1, "%")], vec![SortKey::ascending(0)]),
+            (
+                vec![(2, ">=3")],
+                vec![
+                    SortKey {
+                        column: 2,
+                        direction: SortDirection::Descending,
+                        nulls: NullsOrder::Last,
+                    },
+                    SortKey::ascending(1),
+                ],
+            ),
+            (
+                vec![(3, "2024-01-01..2024-02-10")],
+                vec![SortKey::ascending(3), SortKey::ascending(0)],
+            ),
+            (
+                vec![(1, "not null")],
+                vec![
+                    SortKey {
+                        column: 3,
+                        direction: SortDirection::Ascending,
+                        nulls: NullsOrder::First,
+                    },
+                    SortKey::ascending(0),
+                ],
+            ),
+            (vec![(0, "=5")], vec![]),
+        ];
+
+        for (filters, sort) in cases {
+            let filters: Vec<ColumnFilter> = filters
+                .into_iter()
+                .map(|(column, text)| ColumnFilter {
+                    column,
+                    condition: FilterCondition::parse(text).unwrap(),
+                })
+                .collect();
+
+            let visible = filter_rows(&result, &filters);
+            let local: Vec<String> = sort_order(&result, &sort)
+                .into_iter()
+                .filter(|row| visible.contains(row))
+                .map(|row| row_text(&result, row))
+                .collect();
+
+            let sql = rewrite_query(&DatabaseType::PostgreSQL, &result, &filters, &sort).unwrap();
+            let server = run_query(&connection, &sql).await.unwrap();
+            let server: Vec<String> = (0..server.rows.len())
+                .map(|row| row_text(&server, row))
+                .collect();
+            assert_eq!(local, server, "{}", sql);
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use rbeaver::database::{DatabaseConnection, PostgreSQLConnection, QueryExecutor};
+use std::time::Instant;
+
+#[path = "common/mod.rs"]
+mod common;
+
+/// 100 million rows produced as they are read; a single large
+/// `generate_series` would be materialized by the server first
+const LARGE_RESULT_SQL: &str = "SELECT a.n * 10000 + b.n AS n, md5(b.n::text) AS hash \
+     FROM generate_series(0, 9999) AS a(n), generate_series(1, 10000) AS b(n)";
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Result Streaming");
+    println!("===================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run these tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+
+    // Test 1: First page of a large result
+    println!("📋 Test 1: First 100 of 100 million rows");
+    let started = Instant::now();
+    let mut cursor = connection.open_cursor(LARGE_RESULT_SQL).await?;
+    let rows = cursor.fetch(100).await?;
+    println!("  ✓ Fetched {} rows in {:?}", rows.len(), started.elapsed());
+
+    // Test 2: Further pages
+    println!("\n📋 Test 2: Fetching more pages");
+    for _ in 0..3 {
+        let rows = cursor.fetch(100).await?;
+        println!(
+            "  ✓ Rows {}..{}",
+            rows[0].values[0].to_display_string(),
+            rows[rows.len() - 1].values[0].to_display_string()
+        );
+    }
+
+    // Test 3: Dropping the cursor releases the session
+    println!("\n📋 Test 3: Closing the cursor");
+    drop(cursor);
+    let result = connection.execute_query("SELECT 1 AS one").await?;
+    println!("  ✓ Connection usable, {} row(s)", result.row_count());
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 Result streaming tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::DatabaseError;
+    use std::time::Duration;
+
+    async fn connect() -> Option<PostgreSQLConnection> {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return None;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        Some(connection)
+    }
+
+    #[tokio::test]
+    async fn test_cursor_pages_through_result() {
+        let Some(connection) = connect().await else {
+            return;
+        };
+
+        let mut cursor = connection
+            .open_cursor("SELECT n FROM generate_series(1, 250) AS n")
+            .await
+            .unwrap();
+
+        let first = cursor.fetch(100).await.unwrap();
+        assert_eq!(first.len(), 100);
+        assert!(!cursor.is_exhausted());
+
+        let result = cursor.result_with_rows(first);
+        assert_eq!(result.columns.len(), 1);
+        assert_eq!(result.columns[0].name, "n");
+
+        let second = cursor.fetch(100).await.unwrap();
+        assert_eq!(second[0].values[0].to_display_string(), "101");
+
+        // The last page is known to be the last without another round trip
+        let last = cursor.fetch(100).await.unwrap();
+        assert_eq!(last.len(), 50);
+        assert!(cursor.is_exhausted());
+        assert_eq!(cursor.rows_fetched(), 250);
+        assert!(cursor.fetch(100).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_first_page_of_large_result_is_quick() {
+        let Some(connection) = connect().await else {
+            return;
+        };
+
+        let started = Instant::now();
+        let mut cursor = connection.open_cursor(LARGE_RESULT_SQL).await.unwrap();
+        let rows = cursor.fetch(100).await.unwrap();
+        assert_eq!(rows.len(), 100);
+        assert!(started.elapsed() < Duration::from_secs(5));
+
+        // Closing the cursor part way returns the session to the pool
+        drop(cursor);
+        let result = connection.execute_query("SELECT 1 AS one").await.unwrap();
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_statement_errors_surface_on_first_fetch() {
+        let Some(connection) = connect().await else {
+            return;
+        };
+
+        let mut cursor = connection
+            .open_cursor("SELECT * FROM rbeaver_missing_table")
+            .await
+            .unwrap();
+        assert!(matches!(
+            cursor.fetch(100).await,
+            Err(DatabaseError::QueryFailed(_))
+        ));
+        assert!(cursor.is_exhausted());
+    }
+
+    #[tokio::test]
+    async fn test_open_cursor_requires_connection() {
+        let connection = PostgreSQLConnection::new();
+        assert!(matches!(
+            connection.open_cursor("SELECT 1").await,
+            Err(DatabaseError::NotConnected)
+        ));
+    }
+}
@@ -17,12 +17,14 @@ fn main() {
             data_type: "integer".to_string(),
             ordinal: 0,
             nullable: false,
+            ..Default::default()
         },
         QueryColumn {
             name: "name".to_string(),
             data_type: "varchar".to_string(),
             ordinal: 1,
             nullable: true,
+            ..Default::default()
         },
     ];
 
@@ -0,0 +1,438 @@
+use rbeaver::database::schema_diff::{compare_schemas, ChangeKind};
+use rbeaver::database::{DatabaseConnection, DatabaseType, PostgreSQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+/// Schema as it should be
+const SOURCE: &str = "rbeaver_diff_staging";
+/// Schema the migration script changes
+const TARGET: &str = "rbeaver_diff_production";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP SCHEMA IF EXISTS rbeaver_diff_staging CASCADE",
+    "DROP SCHEMA IF EXISTS rbeaver_diff_production CASCADE",
+    "CREATE SCHEMA rbeaver_diff_staging",
+    "CREATE SCHEMA rbeaver_diff_production",
+    // Production, as it was last released
+    "CREATE TABLE rbeaver_diff_production.customers (
+        id serial PRIMARY KEY,
+        email text,
+        legacy_code text
+    )",
+    "CREATE TABLE rbeaver_diff_production.orders (
+        id integer PRIMARY KEY,
+        customer_id integer,
+        total numeric(8,2)
+    )",
+    "CREATE TABLE rbeaver_diff_production.order_log (
+        id integer PRIMARY KEY,
+        order_id integer REFERENCES rbeaver_diff_production.orders
+    )",
+    "CREATE INDEX orders_total_idx ON rbeaver_diff_production.orders (total)",
+    "CREATE VIEW rbeaver_diff_production.order_summary AS
+        SELECT id, total FROM rbeaver_diff_production.orders",
+    "CREATE VIEW rbeaver_diff_production.big_orders AS
+        SELECT id, total FROM rbeaver_diff_production.order_summary WHERE total > 100",
+    "CREATE FUNCTION rbeaver_diff_production.order_total(order_id integer) RETURNS numeric
+        LANGUAGE sql AS 'SELECT total FROM rbeaver_diff_production.orders WHERE id = order_id'",
+    "CREATE FUNCTION rbeaver_diff_production.obsolete() RETURNS integer
+        LANGUAGE sql AS 'SELECT 1'",
+    "CREATE SEQUENCE rbeaver_diff_production.unused_numbers",
+    "CREATE FUNCTION rbeaver_diff_production.stamp() RETURNS trigger LANGUAGE plpgsql
+        AS $$ BEGIN RETURN NEW; END $$",
+    // Trigger names are per table; this one already matches staging
+    "CREATE TRIGGER orders_stamp BEFORE UPDATE ON rbeaver_diff_production.customers
+        FOR EACH ROW EXECUTE FUNCTION rbeaver_diff_production.stamp()",
+    // Staging, with the changes of the next release
+    "CREATE TABLE rbeaver_diff_staging.customers (
+        id serial PRIMARY KEY,
+        email text NOT NULL UNIQUE,
+        name text DEFAULT 'anonymous'
+    )",
+    "CREATE TABLE rbeaver_diff_staging.orders (
+        id integer PRIMARY KEY,
+        customer_id integer REFERENCES rbeaver_diff_staging.customers ON DELETE CASCADE,
+        total numeric(10,2) CONSTRAINT total_not_negative CHECK (total >= 0)
+    )",
+    "CREATE TABLE rbeaver_diff_staging.invoices (
+        id serial PRIMARY KEY,
+        order_id integer NOT NULL REFERENCES rbeaver_diff_staging.orders
+    )",
+    "CREATE INDEX orders_total_idx ON rbeaver_diff_staging.orders (total DESC)",
+    "CREATE INDEX invoices_order_idx ON rbeaver_diff_staging.invoices (order_id)",
+    "CREATE VIEW rbeaver_diff_staging.order_summary AS
+        SELECT id, customer_id, total FROM rbeaver_diff_staging.orders",
+    "CREATE VIEW rbeaver_diff_staging.big_orders AS
+        SELECT id, total FROM rbeaver_diff_staging.order_summary WHERE total > 100",
+    "CREATE FUNCTION rbeaver_diff_staging.order_total(order_id integer) RETURNS numeric
+        LANGUAGE sql AS 'SELECT coalesce(total, 0) FROM rbeaver_diff_staging.orders WHERE id = order_id'",
+    "CREATE FUNCTION rbeaver_diff_staging.stamp() RETURNS trigger LANGUAGE plpgsql
+        AS $$ BEGIN RETURN NEW; END $$",
+    "CREATE TRIGGER orders_stamp BEFORE UPDATE ON rbeaver_diff_staging.orders
+        FOR EACH ROW EXECUTE FUNCTION rbeaver_diff_staging.stamp()",
+    "CREATE TRIGGER orders_stamp BEFORE UPDATE ON rbeaver_diff_staging.customers
+        FOR EACH ROW EXECUTE FUNCTION rbeaver_diff_staging.stamp()",
+    "CREATE SEQUENCE rbeaver_diff_staging.invoice_numbers START 1000 INCREMENT 10",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Schema Comparison");
+    println!("====================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    let (diff, script) = compare_schemas(
+        &connection,
+        SOURCE,
+        &connection,
+        TARGET,
+        &DatabaseType::PostgreSQL,
+    )
+    .await?;
+    for object in &diff.objects {
+        let icon = match object.change {
+            ChangeKind::Added => "➕",
+            ChangeKind::Removed => "➖",
+            ChangeKind::Changed => "✏️",
+        };
+        println!("{} {:?} {}", icon, object.category, object.name);
+        for detail in &object.details {
+            println!("    {}", detail);
+        }
+    }
+    println!("\n📜 Migration script:\n{}", script);
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_diff_staging CASCADE")
+        .await?;
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_diff_production CASCADE")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("🎉 Schema comparison tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::schema_diff::{
+        compare, migration_script, Definitions, SchemaSnapshot, TableChange, TableSnapshot,
+    };
+    use rbeaver::database::{
+        split_statements, Column, Constraint, ConstraintType, ForeignKey, ForeignKeyAction,
+        ObjectCategory,
+    };
+
+    fn column(name: &str, data_type: &str, is_nullable: bool) -> Column {
+        Column {
+            name: name.to_string(),
+            data_type: data_type.to_string(),
+            is_nullable,
+            default_value: None,
+            is_primary_key: false,
+            comment: None,
+        }
+    }
+
+    fn primary_key(schema: &str, table: &str) -> Constraint {
+        Constraint {
+            name: format!("{}_pkey", table),
+            schema: schema.to_string(),
+            table_name: table.to_string(),
+            constraint_type: ConstraintType::PrimaryKey,
+            columns: vec!["id".to_string()],
+            definition: "PRIMARY KEY (id)".to_string(),
+            comment: None,
+        }
+    }
+
+    /// `shop.orders` in the source and `shop_live.orders` in the target
+    fn snapshots() -> (SchemaSnapshot, SchemaSnapshot) {
+        let source = SchemaSnapshot {
+            schema: "shop".to_string(),
+            tables: vec![
+                TableSnapshot {
+                    name: "customers".to_string(),
+                    columns: vec![column("id", "integer", false)],
+                    constraints: vec![primary_key("shop", "customers")],
+                    foreign_keys: Vec::new(),
+                },
+                TableSnapshot {
+                    name: "orders".to_string(),
+                    columns: vec![
+                        column("id", "integer", false),
+                        Column {
+                            default_value: Some("nextval('shop.order_refs'::regclass)".to_string()),
+                            ..column("reference", "bigint", false)
+                        },
+                        column("customer_id", "integer", true),
+                    ],
+                    constraints: vec![primary_key("shop", "orders")],
+                    foreign_keys: vec![ForeignKey {
+                        name: "orders_customer_id_fkey".to_string(),
+                        schema: "shop".to_string(),
+                        table_name: "orders".to_string(),
+                        columns: vec!["customer_id".to_string()],
+                        referenced_schema: "shop".to_string(),
+                        referenced_table: "customers".to_string(),
+                        referenced_columns: vec!["id".to_string()],
+                        on_update: ForeignKeyAction::NoAction,
+                        on_delete: ForeignKeyAction::Cascade,
+                        comment: None,
+                    }],
+                },
+            ],
+            ..Default::default()
+        };
+        let target = SchemaSnapshot {
+            schema: "shop_live".to_string(),
+            tables: vec![
+                TableSnapshot {
+                    name: "orders".to_string(),
+                    columns: vec![
+                        column("id", "integer", false),
+                        Column {
+                            default_value: Some(
+                                "nextval('shop_live.order_refs'::regclass)".to_string(),
+                            ),
+                            ..column("reference", "integer", true)
+                        },
+                        column("note", "text", true),
+                    ],
+                    constraints: vec![primary_key("shop_live", "orders")],
+                    foreign_keys: Vec::new(),
+                },
+                TableSnapshot {
+                    name: "archive".to_string(),
+                    columns: vec![column("id", "integer", false)],
+                    constraints: Vec::new(),
+                    foreign_keys: Vec::new(),
+                },
+            ],
+            ..Default::default()
+        };
+        (source, target)
+    }
+
+    #[test]
+    fn test_compare_tables() {
+        let (source, target) = snapshots();
+        let diff = compare(source, target);
+
+        let changes: Vec<(&str, ChangeKind)> = diff
+            .objects
+            .iter()
+            .map(|o| (o.name.as_str(), o.change))
+            .collect();
+        assert_eq!(
+            changes,
+            vec![
+                ("customers", ChangeKind::Added),
+                ("orders", ChangeKind::Changed),
+                ("archive", ChangeKind::Removed),
+            ]
+        );
+
+        // The default differs only by its schema, which does not count
+        let orders = &diff.objects[1];
+        assert_eq!(
+            orders.details,
+            vec![
+                "alter column reference: type integer → bigint, not null",
+                "add column customer_id",
+                "drop column note",
+                "add foreign key orders_customer_id_fkey",
+            ]
+        );
+        assert!(matches!(
+            orders.table_changes[0],
+            TableChange::AlterColumn { .. }
+        ));
+    }
+
+    #[test]
+    fn test_migration_script() {
+        let (source, target) = snapshots();
+        let diff = compare(source, target);
+
+        let script = migration_script(&diff, &DatabaseType::PostgreSQL, &Definitions::new());
+        assert_eq!(
+            script,
+            "-- Changes schema shop_live to match schema shop
+
+-- Drop tables
+DROP TABLE \"shop_live\".\"archive\";
+
+-- Create tables
+CREATE TABLE \"shop_live\".\"customers\" (
+    \"id\" integer NOT NULL,
+    CONSTRAINT \"customers_pkey\" PRIMARY KEY (id)
+);
+
+-- Alter columns
+ALTER TABLE \"shop_live\".\"orders\" ALTER COLUMN \"reference\" TYPE bigint;
+ALTER TABLE \"shop_live\".\"orders\" ALTER COLUMN \"reference\" SET NOT NULL;
+ALTER TABLE \"shop_live\".\"orders\" ADD COLUMN \"customer_id\" integer;
+ALTER TABLE \"shop_live\".\"orders\" DROP COLUMN \"note\";
+
+-- Add foreign keys
+ALTER TABLE \"shop_live\".\"orders\" ADD CONSTRAINT \"orders_customer_id_fkey\" FOREIGN KEY (\"customer_id\") REFERENCES \"shop_live\".\"customers\" (\"id\") ON DELETE CASCADE;
+"
+        );
+
+        // MySQL restates the whole column and SQLite cannot alter it at all
+        let script = migration_script(&diff, &DatabaseType::MySQL, &Definitions::new());
+        assert!(script.contains(
+            "ALTER TABLE `shop_live`.`orders` MODIFY COLUMN `reference` bigint \
+             DEFAULT nextval('shop_live.order_refs'::regclass) NOT NULL;"
+        ));
+        let script = migration_script(&diff, &DatabaseType::SQLite, &Definitions::new());
+        assert!(script.contains(
+            "-- SQLite cannot alter column reference of orders: rebuild the table \
+             (type integer → bigint, not null)"
+        ));
+        assert!(script.contains("-- SQLite cannot add constraint orders_customer_id_fkey"));
+
+        let identical = compare(diff.source.clone(), diff.source.clone());
+        assert!(identical.is_empty());
+        assert_eq!(
+            migration_script(&identical, &DatabaseType::PostgreSQL, &Definitions::new()),
+            "-- The schemas are identical\n"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compare_and_migrate_schemas() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The schemas are shared with the demo in main, which tests may run beside
+        let (source, target) = ("rbeaver_diff_test_staging", "rbeaver_diff_test_production");
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in FIXTURE_SQL {
+            connection
+                .execute_non_query(&sql.replace(SOURCE, source).replace(TARGET, target))
+                .await
+                .unwrap();
+        }
+
+        let (diff, script) = compare_schemas(
+            &connection,
+            source,
+            &connection,
+            target,
+            &DatabaseType::PostgreSQL,
+        )
+        .await
+        .unwrap();
+        let change = |category: ObjectCategory, name: &str| {
+            diff.objects
+                .iter()
+                .find(|o| o.category == category && o.name == name)
+                .map(|o| o.change)
+        };
+        assert_eq!(
+            change(ObjectCategory::Tables, "invoices"),
+            Some(ChangeKind::Added)
+        );
+        assert_eq!(
+            change(ObjectCategory::Tables, "order_log"),
+            Some(ChangeKind::Removed)
+        );
+        assert_eq!(
+            change(ObjectCategory::Tables, "customers"),
+            Some(ChangeKind::Changed)
+        );
+        assert_eq!(
+            change(ObjectCategory::Views, "order_summary"),
+            Some(ChangeKind::Changed)
+        );
+        // Unchanged, but built on a changed view
+        assert_eq!(change(ObjectCategory::Views, "big_orders"), None);
+        assert!(script.contains(&format!("DROP VIEW \"{}\".\"big_orders\";", target)));
+        assert_eq!(
+            change(ObjectCategory::Functions, "order_total(integer)"),
+            Some(ChangeKind::Changed)
+        );
+        assert_eq!(
+            change(ObjectCategory::Functions, "obsolete()"),
+            Some(ChangeKind::Removed)
+        );
+        assert_eq!(
+            change(ObjectCategory::Triggers, "orders.orders_stamp"),
+            Some(ChangeKind::Added)
+        );
+        // Only the trigger of that name on the table it was added to
+        assert_eq!(
+            change(ObjectCategory::Triggers, "customers.orders_stamp"),
+            None
+        );
+        assert_eq!(script.matches("CREATE TRIGGER orders_stamp").count(), 1);
+        assert_eq!(
+            change(ObjectCategory::Indexes, "orders.orders_total_idx"),
+            Some(ChangeKind::Changed)
+        );
+        assert_eq!(
+            change(ObjectCategory::Sequences, "invoice_numbers"),
+            Some(ChangeKind::Added)
+        );
+        // Serial columns match despite naming their schema in the default
+        assert_eq!(change(ObjectCategory::Sequences, "customers_id_seq"), None);
+        // Every statement is moved to the target schema
+        assert!(!script.contains(&format!("{}.", source)));
+        assert!(!script.contains(&format!("\"{}\".", source)));
+
+        // Views go before the tables they read and come back after them
+        let position = |text: &str| script.find(text).unwrap();
+        assert!(position("DROP VIEW") < position("TYPE numeric(10,2)"));
+        assert!(position("TYPE numeric(10,2)") < position("CREATE OR REPLACE VIEW"));
+
+        // Running the script leaves nothing to migrate
+        for statement in split_statements(&script) {
+            connection
+                .execute_non_query(&statement.sql)
+                .await
+                .unwrap_or_else(|err| panic!("{}: {}", statement.sql, err));
+        }
+        let (diff, _) = compare_schemas(
+            &connection,
+            source,
+            &connection,
+            target,
+            &DatabaseType::PostgreSQL,
+        )
+        .await
+        .unwrap();
+        let left: Vec<String> = diff
+            .objects
+            .iter()
+            .map(|o| format!("{:?} {} {:?}", o.category, o.name, o.details))
+            .collect();
+        assert!(left.is_empty(), "{:?}", left);
+
+        for schema in [source, target] {
+            connection
+                .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+                .await
+                .unwrap();
+        }
+        connection.disconnect().await.unwrap();
+    }
+}
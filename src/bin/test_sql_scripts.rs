@@ -0,0 +1,295 @@
+use rbeaver::database::{
+    split_statements, DatabaseConnection, PostgreSQLConnection, QueryExecutor,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const MIGRATION_SCRIPT: &str = r#"
+-- Accounts table; the semicolon in this comment does not split anything
+CREATE TEMP TABLE accounts (
+    id serial PRIMARY KEY,
+    name text NOT NULL DEFAULT 'n/a; unnamed'
+);
+
+/* Keep the name in sync; /* nested */ comments are fine */
+CREATE FUNCTION pg_temp.touch_name() RETURNS trigger AS $body$
+BEGIN
+    NEW.name := trim(NEW.name);
+    RETURN NEW;
+END;
+$body$ LANGUAGE plpgsql;
+
+BEGIN;
+INSERT INTO accounts (name) VALUES ('Alice'), ('Bob');
+UPDATE accounts SET name = upper(name) WHERE name = 'Bob';
+COMMIT;
+
+SELECT id, name FROM accounts ORDER BY id;
+"#;
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver SQL Scripts");
+    println!("==============================\n");
+
+    // Test 1: Splitting a script
+    println!("📋 Test 1: Splitting a migration script");
+    let statements = split_statements(MIGRATION_SCRIPT);
+    for statement in &statements {
+        println!(
+            "  ✓ Line {}: {}",
+            statement.line,
+            statement.sql.lines().next().unwrap_or_default()
+        );
+    }
+
+    let Some(params) = common::server_params() else {
+        println!("\n⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    // Test 2: Running the statements on one session
+    println!("\n📋 Test 2: Running the script");
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+
+    let session = connection.open_session().await?;
+    for statement in &statements {
+        let mut cursor = session.open_cursor(&statement.sql).await?;
+        let rows = cursor.fetch(100).await?;
+        let result = cursor.result_with_rows(rows);
+        match result.rows_affected {
+            Some(rows_affected) => println!("  ✓ {} row(s) affected", rows_affected),
+            None => println!("  ✓ {} row(s) returned", result.row_count()),
+        }
+    }
+    drop(session);
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 SQL script tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::{is_read_only_query, statement_at, DatabaseError};
+
+    fn sql_of(script: &str) -> Vec<String> {
+        split_statements(script)
+            .into_iter()
+            .map(|statement| statement.sql)
+            .collect()
+    }
+
+    #[test]
+    fn test_split_simple_statements() {
+        assert_eq!(
+            sql_of("SELECT 1; SELECT 2;\n\nSELECT 3"),
+            vec!["SELECT 1", "SELECT 2", "SELECT 3"]
+        );
+        assert!(sql_of("  ;; \n-- only a comment\n/* and another */").is_empty());
+    }
+
+    #[test]
+    fn test_split_ignores_semicolons_in_literals_and_comments() {
+        assert_eq!(
+            sql_of(
+                "SELECT 'a;b', \"odd;name\", `tick;`, E'it\\'s;' -- trailing; comment\nFROM t;\
+                 SELECT 'it''s; fine' /* ; */"
+            ),
+            vec![
+                "SELECT 'a;b', \"odd;name\", `tick;`, E'it\\'s;' -- trailing; comment\nFROM t",
+                "SELECT 'it''s; fine'",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_split_dollar_quoted_bodies() {
+        let statements = sql_of(
+            "CREATE FUNCTION f() RETURNS int AS $$ BEGIN RETURN 1; END; $$ LANGUAGE plpgsql;\
+             DO $do$ BEGIN PERFORM 1; END $do$;\
+             SELECT $1::int; SELECT a$b FROM t",
+        );
+        assert_eq!(statements.len(), 4);
+        assert!(statements[0].ends_with("LANGUAGE plpgsql"));
+        assert!(statements[1].ends_with("END $do$"));
+        assert_eq!(statements[2], "SELECT $1::int");
+        assert_eq!(statements[3], "SELECT a$b FROM t");
+    }
+
+    #[test]
+    fn test_split_begin_end_blocks() {
+        let statements = sql_of(
+            "CREATE TRIGGER log_insert AFTER INSERT ON users
+             BEGIN
+                 INSERT INTO log VALUES (CASE WHEN NEW.id > 0 THEN 'a;' ELSE 'b' END);
+                 UPDATE stats SET n = n + 1;
+             END;
+             CREATE PROCEDURE p() BEGIN
+                 IF 1 THEN SELECT 1; END IF;
+                 CASE WHEN 1 THEN SELECT 2; END CASE;
+             END;
+             BEGIN;
+             SELECT 1;
+             COMMIT;",
+        );
+        assert_eq!(statements.len(), 5);
+        assert!(statements[0].starts_with("CREATE TRIGGER") && statements[0].ends_with("END"));
+        assert!(statements[1].starts_with("CREATE PROCEDURE") && statements[1].ends_with("END"));
+        assert_eq!(&statements[2..], ["BEGIN", "SELECT 1", "COMMIT"]);
+    }
+
+    #[test]
+    fn test_begin_as_a_name_opens_no_block() {
+        assert_eq!(
+            sql_of("CREATE TABLE events (id int, begin timestamptz); SELECT 1;"),
+            vec![
+                "CREATE TABLE events (id int, begin timestamptz)",
+                "SELECT 1"
+            ]
+        );
+        assert_eq!(
+            sql_of("CREATE VIEW spans AS SELECT begin, finish FROM events; SELECT 2;"),
+            vec![
+                "CREATE VIEW spans AS SELECT begin, finish FROM events",
+                "SELECT 2"
+            ]
+        );
+
+        let statements = sql_of(
+            "CREATE PROCEDURE log_start(begin int) BEGIN
+                 INSERT INTO starts (begin) VALUES (begin);
+             END;
+             SELECT 3;",
+        );
+        assert_eq!(statements.len(), 2);
+        assert!(statements[0].ends_with("END"));
+        assert_eq!(statements[1], "SELECT 3");
+    }
+
+    #[test]
+    fn test_split_reports_positions() {
+        let script = "-- header\nSELECT 1;\n\n  SELECT\n  2 ;";
+        let statements = split_statements(script);
+
+        assert_eq!(statements[0].line, 2);
+        assert_eq!(&script[statements[0].range.clone()], "SELECT 1");
+        assert_eq!(statements[1].line, 4);
+        assert_eq!(&script[statements[1].range.clone()], "SELECT\n  2");
+    }
+
+    #[test]
+    fn test_split_unterminated_quotes_run_to_the_end() {
+        assert_eq!(
+            sql_of("SELECT 1; SELECT 'open;"),
+            vec!["SELECT 1", "SELECT 'open;"]
+        );
+        assert_eq!(
+            sql_of("SELECT $x$ never closed;"),
+            vec!["SELECT $x$ never closed;"]
+        );
+    }
+
+    #[test]
+    fn test_statement_at_cursor() {
+        let editor = "SELECT 1;  SELECT 2;\nSELECT 'a;\n\nb'\nFROM t\n\nSELECT 3\n\n-- note\n";
+        let at = |needle: &str| {
+            let offset = editor.find(needle).unwrap();
+            statement_at(editor, offset).map(|statement| statement.sql)
+        };
+
+        assert_eq!(at("1;").as_deref(), Some("SELECT 1"));
+        assert_eq!(at("SELECT 2").as_deref(), Some("SELECT 2"));
+        // A blank line inside a string does not end the statement
+        assert_eq!(at("FROM t").as_deref(), Some("SELECT 'a;\n\nb'\nFROM t"));
+        // Unterminated statements are separated by blank lines
+        assert_eq!(at("3").as_deref(), Some("SELECT 3"));
+        assert_eq!(at("-- note"), None);
+
+        // Right after the semicolon, on the same line
+        let offset = editor.find("2;").unwrap() + 2;
+        assert_eq!(statement_at(editor, offset).unwrap().sql, "SELECT 2");
+        // The statement range points back into the editor
+        let statement = statement_at(editor, offset).unwrap();
+        assert_eq!(&editor[statement.range], "SELECT 2");
+    }
+
+    #[test]
+    fn test_blank_lines_do_not_split_scripts() {
+        assert_eq!(
+            sql_of("SELECT 1\n\nFROM t;\n\nSELECT 2"),
+            vec!["SELECT 1\n\nFROM t", "SELECT 2"]
+        );
+    }
+
+    #[test]
+    fn test_read_only_queries() {
+        assert!(is_read_only_query("SELECT * FROM t WHERE note = 'insert'"));
+        assert!(is_read_only_query(
+            "WITH a AS (SELECT 1) -- delete later\nSELECT * FROM a"
+        ));
+        assert!(!is_read_only_query(
+            "WITH gone AS (DELETE FROM t RETURNING *) SELECT * FROM gone"
+        ));
+        assert!(!is_read_only_query("SELECT * INTO copy FROM t"));
+        assert!(!is_read_only_query("INSERT INTO t VALUES (1) RETURNING *"));
+        assert!(!is_read_only_query("SELECT 1; DELETE FROM t"));
+    }
+
+    #[tokio::test]
+    async fn test_script_runs_on_one_session() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+
+        let mut outcomes = Vec::new();
+        for statement in split_statements(MIGRATION_SCRIPT) {
+            let mut cursor = session.open_cursor(&statement.sql).await.unwrap();
+            let rows = cursor.fetch(100).await.unwrap();
+            outcomes.push(cursor.result_with_rows(rows));
+        }
+
+        // The temporary table and the transaction span statements
+        assert_eq!(outcomes.len(), 7);
+        assert_eq!(outcomes[3].rows_affected, Some(2));
+        assert_eq!(outcomes[4].rows_affected, Some(1));
+        let names: Vec<String> = outcomes[6]
+            .rows
+            .iter()
+            .map(|row| row.values[1].to_display_string())
+            .collect();
+        assert_eq!(names, vec!["Alice", "BOB"]);
+    }
+
+    #[tokio::test]
+    async fn test_failed_statement_leaves_session_usable() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+
+        let mut cursor = session.open_cursor("SELECT 1/0").await.unwrap();
+        assert!(matches!(
+            cursor.fetch(10).await,
+            Err(DatabaseError::QueryFailed(_))
+        ));
+
+        let mut cursor = session.open_cursor("SELECT 2 AS two").await.unwrap();
+        assert_eq!(cursor.fetch(10).await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,512 @@
+use rbeaver::database::{
+    ConnectionParams, DatabaseConnection, DatabaseType, QueryExecutor, SQLiteConnection,
+};
+
+const FIXTURE_SQL: &[&str] = &[
+    "CREATE TABLE users (
+        id INTEGER PRIMARY KEY,
+        name TEXT NOT NULL,
+        active BOOLEAN DEFAULT 1,
+        score REAL,
+        avatar BLOB
+    )",
+    "CREATE TABLE audit_log (id INTEGER PRIMARY KEY AUTOINCREMENT, message TEXT)",
+    "CREATE UNIQUE INDEX idx_users_name ON users (name)",
+    "CREATE INDEX idx_users_active ON users (score) WHERE active = 1",
+    "CREATE VIEW active_users AS SELECT id, name FROM users WHERE active = 1",
+    "CREATE TRIGGER users_audit AFTER INSERT ON users
+     BEGIN
+         INSERT INTO audit_log (message) VALUES ('inserted ' || NEW.name);
+     END",
+    "INSERT INTO users (name, active, score, avatar) VALUES ('Alice', 1, 9.5, x'89504E47')",
+    "INSERT INTO users (name, active, score, avatar) VALUES ('Bob', 0, NULL, NULL)",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver SQLite Support");
+    println!("=================================\n");
+
+    let mut connection = SQLiteConnection::new();
+    connection.connect(&memory_params()).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    // Test 1: Schemas
+    println!("📋 Test 1: Schemas");
+    let schemas = connection.get_schemas().await?;
+    assert_eq!(schemas.len(), 1);
+    assert_eq!(schemas[0].name, "main");
+    println!("  ✓ Found schema: {}", schemas[0].name);
+
+    // Test 2: Tables and columns
+    println!("\n📋 Test 2: Tables and columns");
+    let tables = connection.get_tables("main").await?;
+    for table in &tables {
+        println!("  ✓ {} ({})", table.name, table.table_type);
+    }
+    assert!(!tables.iter().any(|t| t.name == "sqlite_sequence"));
+
+    let columns = connection.get_columns("main", "users").await?;
+    for column in &columns {
+        println!(
+            "  ✓ {} {} nullable={} pk={}",
+            column.name, column.data_type, column.is_nullable, column.is_primary_key
+        );
+    }
+
+    // Test 3: Triggers and indexes
+    println!("\n📋 Test 3: Triggers and indexes");
+    let triggers = connection.get_triggers("main").await?;
+    for trigger in &triggers {
+        println!(
+            "  ✓ {} on {}: {:?} {:?}",
+            trigger.name, trigger.table_name, trigger.timing, trigger.events
+        );
+    }
+
+    let indexes = connection.get_indexes("main").await?;
+    for index in &indexes {
+        println!(
+            "  ✓ {} on {} unique={} partial={} condition={:?}",
+            index.name, index.table_name, index.is_unique, index.is_partial, index.condition
+        );
+    }
+
+    // Test 4: Value conversion
+    println!("\n📋 Test 4: Value conversion");
+    let result = connection
+        .execute_query("SELECT id, name, active, score, avatar FROM users ORDER BY id")
+        .await?;
+    for row in &result.rows {
+        let values: Vec<String> = row.values.iter().map(|v| v.to_display_string()).collect();
+        println!("  ✓ {}", values.join(" | "));
+    }
+
+    // Test 5: Attached databases
+    println!("\n📋 Test 5: Attached databases");
+    connection
+        .execute_non_query("ATTACH DATABASE ':memory:' AS scratch")
+        .await?;
+    let schemas = connection.get_schemas().await?;
+    let names: Vec<&str> = schemas.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["main", "scratch"]);
+    println!("  ✓ Schemas after ATTACH: {}", names.join(", "));
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 All SQLite tests completed successfully!");
+    Ok(())
+}
+
+fn memory_params() -> ConnectionParams {
+    let mut params = ConnectionParams::new("SQLite Memory".to_string(), DatabaseType::SQLite);
+    params.database = ":memory:".to_string();
+    params
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::schema_diff::compare_schemas;
+    use rbeaver::database::{
+        ConstraintType, DatabaseError, ForeignKeyAction, ObjectCategory, PostgreSQLConnection,
+        QueryValue, TriggerEvent, TriggerTiming,
+    };
+
+    async fn fixture_connection() -> SQLiteConnection {
+        let mut connection = SQLiteConnection::new();
+        connection.connect(&memory_params()).await.unwrap();
+        for sql in FIXTURE_SQL {
+            connection.execute_non_query(sql).await.unwrap();
+        }
+        connection
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_metadata() {
+        let connection = fixture_connection().await;
+
+        let tables = connection.get_tables("main").await.unwrap();
+        let names: Vec<&str> = tables.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, vec!["active_users", "audit_log", "users"]);
+        assert_eq!(tables[0].table_type, "VIEW");
+        assert_eq!(tables[2].table_type, "BASE TABLE");
+
+        let columns = connection.get_columns("main", "users").await.unwrap();
+        assert_eq!(columns.len(), 5);
+        assert!(columns[0].is_primary_key);
+        assert!(!columns[1].is_nullable);
+        assert_eq!(columns[2].default_value.as_deref(), Some("1"));
+
+        let triggers = connection.get_triggers("main").await.unwrap();
+        assert_eq!(triggers.len(), 1);
+        assert_eq!(triggers[0].timing, TriggerTiming::After);
+        assert_eq!(triggers[0].events, vec![TriggerEvent::Insert]);
+        assert_eq!(triggers[0].condition, None);
+
+        let indexes = connection.get_indexes("main").await.unwrap();
+        let partial = indexes
+            .iter()
+            .find(|i| i.name == "idx_users_active")
+            .unwrap();
+        assert!(partial.is_partial);
+        assert_eq!(partial.condition.as_deref(), Some("active = 1"));
+        assert_eq!(partial.columns[0].name, "score");
+        let unique = indexes.iter().find(|i| i.name == "idx_users_name").unwrap();
+        assert!(unique.is_unique);
+
+        let counts = connection.get_object_counts("main").await.unwrap();
+        assert_eq!(counts.tables, 2);
+        assert_eq!(counts.views, 1);
+        assert_eq!(counts.triggers, 1);
+        assert_eq!(counts.indexes, 2);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_trigger_condition() {
+        let connection = fixture_connection().await;
+        connection
+            .execute_non_query(
+                "CREATE TRIGGER users_score AFTER UPDATE OF score ON users
+                 WHEN NEW.score > (SELECT 0) AND NEW.name <> 'begin'
+                 BEGIN
+                     INSERT INTO audit_log (message) VALUES ('score');
+                 END",
+            )
+            .await
+            .unwrap();
+
+        // Only the WHEN clause is the condition; the statement is the DDL
+        let triggers = connection.get_triggers("main").await.unwrap();
+        let scored = triggers.iter().find(|t| t.name == "users_score").unwrap();
+        assert_eq!(
+            scored.condition.as_deref(),
+            Some("NEW.score > (SELECT 0) AND NEW.name <> 'begin'")
+        );
+        let ddl = connection
+            .get_object_ddl(&ObjectCategory::Triggers, "main", "users_score")
+            .await
+            .unwrap();
+        assert!(ddl.starts_with("CREATE TRIGGER users_score AFTER UPDATE OF score ON users"));
+        assert!(ddl.contains("INSERT INTO audit_log"));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_object_ddl() {
+        let connection = fixture_connection().await;
+
+        // A table comes with its indexes and triggers
+        let users = connection
+            .get_object_ddl(&ObjectCategory::Tables, "main", "users")
+            .await
+            .unwrap();
+        let sections: Vec<&str> = users.split("\n\n").collect();
+        assert_eq!(sections.len(), 4);
+        assert!(sections[0].starts_with("CREATE TABLE users ("));
+        assert_eq!(
+            sections[1],
+            "CREATE INDEX idx_users_active ON users (score) WHERE active = 1;"
+        );
+        assert_eq!(
+            sections[2],
+            "CREATE UNIQUE INDEX idx_users_name ON users (name);"
+        );
+        assert!(sections[3].starts_with("CREATE TRIGGER users_audit AFTER INSERT ON users"));
+
+        assert_eq!(
+            connection
+                .get_object_ddl(&ObjectCategory::Views, "main", "active_users")
+                .await
+                .unwrap(),
+            "CREATE VIEW active_users AS SELECT id, name FROM users WHERE active = 1;\n"
+        );
+        assert!(connection
+            .get_object_ddl(&ObjectCategory::Sequences, "main", "users")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_keys_and_constraints() {
+        let connection = fixture_connection().await;
+        for sql in [
+            "CREATE TABLE orders (
+                id INTEGER PRIMARY KEY,
+                user_id INTEGER REFERENCES users ON DELETE CASCADE,
+                code TEXT UNIQUE,
+                total REAL CHECK (total >= 0),
+                CONSTRAINT \"sane code\" CHECK (length(code) < 10 AND code <> ')')
+            )",
+            "CREATE TABLE order_lines (
+                order_id INTEGER,
+                line INTEGER,
+                PRIMARY KEY (order_id, line),
+                FOREIGN KEY (order_id) REFERENCES orders (id)
+            )",
+        ] {
+            connection.execute_non_query(sql).await.unwrap();
+        }
+
+        let keys = connection.get_foreign_keys("main", "orders").await.unwrap();
+        assert_eq!(keys.len(), 2);
+        let incoming = keys.iter().find(|k| k.table_name == "order_lines").unwrap();
+        assert!(incoming.is_incoming("main", "orders"));
+        assert_eq!(incoming.columns, vec!["order_id"]);
+        let outgoing = keys.iter().find(|k| k.table_name == "orders").unwrap();
+        assert!(outgoing.is_outgoing("main", "orders"));
+        assert_eq!(outgoing.referenced_table, "users");
+        // A key naming no columns references the primary key
+        assert_eq!(outgoing.referenced_columns, vec!["id"]);
+        assert_eq!(outgoing.on_delete, ForeignKeyAction::Cascade);
+        assert_eq!(outgoing.on_update, ForeignKeyAction::NoAction);
+
+        let constraints = connection.get_constraints("main", "orders").await.unwrap();
+        let summary: Vec<(&str, &ConstraintType, &str)> = constraints
+            .iter()
+            .map(|c| (c.name.as_str(), &c.constraint_type, c.definition.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (
+                    "orders_pkey",
+                    &ConstraintType::PrimaryKey,
+                    "PRIMARY KEY (\"id\")"
+                ),
+                (
+                    "orders_code_key",
+                    &ConstraintType::Unique,
+                    "UNIQUE (\"code\")"
+                ),
+                (
+                    "orders_check1",
+                    &ConstraintType::Check,
+                    "CHECK (total >= 0)"
+                ),
+                (
+                    "sane code",
+                    &ConstraintType::Check,
+                    "CHECK (length(code) < 10 AND code <> ')')"
+                ),
+            ]
+        );
+
+        let line_key = connection
+            .get_constraints("main", "order_lines")
+            .await
+            .unwrap();
+        assert_eq!(line_key[0].columns, vec!["order_id", "line"]);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_value_conversion() {
+        let connection = fixture_connection().await;
+
+        let result = connection
+            .execute_query("SELECT id, name, active, score, avatar FROM users ORDER BY id")
+            .await
+            .unwrap();
+        assert_eq!(result.row_count(), 2);
+
+        let alice = &result.rows[0].values;
+        assert!(matches!(alice[0], QueryValue::Int64(1)));
+        assert!(matches!(&alice[1], QueryValue::String(s) if s == "Alice"));
+        assert!(matches!(alice[2], QueryValue::Bool(true)));
+        assert!(matches!(alice[3], QueryValue::Float64(f) if f == 9.5));
+        assert!(matches!(&alice[4], QueryValue::Bytes(b) if b == &[0x89, 0x50, 0x4E, 0x47]));
+
+        let bob = &result.rows[1].values;
+        assert!(matches!(bob[2], QueryValue::Bool(false)));
+        assert!(bob[3].is_null());
+        assert!(bob[4].is_null());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_attached_schemas() {
+        let connection = fixture_connection().await;
+
+        connection
+            .execute_non_query("ATTACH DATABASE ':memory:' AS scratch")
+            .await
+            .unwrap();
+        connection
+            .execute_non_query("CREATE TABLE scratch.notes (body TEXT)")
+            .await
+            .unwrap();
+
+        let schemas = connection.get_schemas().await.unwrap();
+        assert_eq!(schemas.len(), 2);
+        assert_eq!(schemas[1].name, "scratch");
+
+        let tables = connection.get_tables("scratch").await.unwrap();
+        assert_eq!(tables.len(), 1);
+        assert!(connection.table_exists("scratch", "notes").await.unwrap());
+        assert!(!connection.table_exists("main", "notes").await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cursor_reads_attached_files() {
+        // Attached to an in-memory database, a file is held in memory as well
+        let dir = std::env::temp_dir();
+        let main = dir.join(format!("rbeaver-main-{}.db", std::process::id()));
+        let archive = dir.join(format!("rbeaver-archive-{}.db", std::process::id()));
+        std::fs::File::create(&main).unwrap();
+        std::fs::File::create(&archive).unwrap();
+
+        let mut params = memory_params();
+        params.database = main.display().to_string();
+        let mut connection = SQLiteConnection::new();
+        connection.connect(&params).await.unwrap();
+        connection
+            .execute_non_query(&format!(
+                "ATTACH DATABASE '{}' AS archive",
+                archive.display()
+            ))
+            .await
+            .unwrap();
+        connection
+            .execute_non_query("CREATE TABLE archive.notes AS SELECT 'kept' AS body")
+            .await
+            .unwrap();
+
+        // The cursor's own connection attaches the file again
+        let result = connection
+            .execute_query("SELECT body FROM archive.notes")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[0].to_display_string(), "kept");
+
+        connection.disconnect().await.unwrap();
+        let _ = std::fs::remove_file(&main);
+        let _ = std::fs::remove_file(&archive);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_missing_file_is_rejected() {
+        let mut params = memory_params();
+        params.database = "/nonexistent/rbeaver/missing.db".to_string();
+
+        let connection = SQLiteConnection::new();
+        assert!(connection.test_connection(&params).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cancel_is_unsupported() {
+        let connection = fixture_connection().await;
+        assert!(matches!(
+            connection.cancel_query().await,
+            Err(rbeaver::database::DatabaseError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cursor_fetches_in_batches() {
+        let connection = fixture_connection().await;
+
+        let mut cursor = connection
+            .open_cursor("SELECT name FROM users ORDER BY id")
+            .await
+            .unwrap();
+        let rows = cursor.fetch(1).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(!cursor.is_exhausted());
+
+        let result = cursor.result_with_rows(rows);
+        assert_eq!(result.columns[0].name, "name");
+        assert_eq!(result.rows[0].values[0].to_display_string(), "Alice");
+
+        let rows = cursor.fetch(10).await.unwrap();
+        assert_eq!(rows.len(), 1);
+        assert!(cursor.is_exhausted());
+        assert_eq!(cursor.rows_fetched(), 2);
+        assert!(cursor.fetch(10).await.unwrap().is_empty());
+
+        // The connection stays usable while a cursor is open
+        let result = connection.execute_query("SELECT 1").await.unwrap();
+        assert_eq!(result.row_count(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_cursor_reads_large_results_in_pages() {
+        let connection = fixture_connection().await;
+
+        let mut cursor = connection
+            .open_cursor(
+                "WITH RECURSIVE n(x) AS (SELECT 1 UNION ALL SELECT x + 1 FROM n WHERE x < 2500) \
+                 SELECT x FROM n",
+            )
+            .await
+            .unwrap();
+        let rows = cursor.fetch(10).await.unwrap();
+        assert_eq!(rows.len(), 10);
+        assert!(!cursor.is_exhausted());
+
+        // The single pooled connection is free while the cursor streams
+        let tables = connection.get_tables("main").await.unwrap();
+        assert!(!tables.is_empty());
+
+        let mut values: Vec<String> = rows
+            .iter()
+            .map(|row| row.values[0].to_display_string())
+            .collect();
+        while !cursor.is_exhausted() {
+            let rows = cursor.fetch(700).await.unwrap();
+            values.extend(rows.iter().map(|row| row.values[0].to_display_string()));
+        }
+        assert_eq!(values.len(), 2500);
+        assert!(values
+            .iter()
+            .enumerate()
+            .all(|(index, value)| *value == (index + 1).to_string()));
+        assert_eq!(cursor.rows_fetched(), 2500);
+    }
+
+    #[tokio::test]
+    async fn test_schemas_of_other_drivers_are_not_compared() {
+        let connection = fixture_connection().await;
+        let postgres = PostgreSQLConnection::new();
+
+        let result = compare_schemas(
+            &connection,
+            "main",
+            &postgres,
+            "public",
+            &DatabaseType::PostgreSQL,
+        )
+        .await;
+        assert!(matches!(
+            result,
+            Err(DatabaseError::UnsupportedOperation(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_session_keeps_transaction() {
+        let connection = fixture_connection().await;
+        let session = connection.open_session().await.unwrap();
+
+        for sql in [
+            "BEGIN",
+            "DELETE FROM users",
+            "SELECT COUNT(*) FROM users",
+            "ROLLBACK",
+        ] {
+            let mut cursor = session.open_cursor(sql).await.unwrap();
+            let rows = cursor.fetch(10).await.unwrap();
+            if sql == "DELETE FROM users" {
+                assert_eq!(cursor.result_with_rows(rows).rows_affected, Some(2));
+            } else if sql.starts_with("SELECT") {
+                assert_eq!(rows[0].values[0].to_display_string(), "0");
+            }
+        }
+        drop(session);
+
+        let result = connection
+            .execute_query("SELECT COUNT(*) FROM users")
+            .await
+            .unwrap();
+        assert_eq!(result.rows[0].values[0].to_display_string(), "2");
+    }
+}
@@ -0,0 +1,189 @@
+use rbeaver::database::{DatabaseConnection, PostgreSQLConnection, QueryExecutor};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const SCHEMA: &str = "rbeaver_keys_demo";
+
+const FIXTURE_SQL: &[&str] = &[
+    "DROP SCHEMA IF EXISTS rbeaver_keys_demo CASCADE",
+    "CREATE SCHEMA rbeaver_keys_demo",
+    "CREATE EXTENSION IF NOT EXISTS btree_gist",
+    "CREATE TABLE rbeaver_keys_demo.customers (
+        id integer PRIMARY KEY,
+        email text UNIQUE,
+        CONSTRAINT email_has_at CHECK (email LIKE '%@%')
+    )",
+    "CREATE TABLE rbeaver_keys_demo.orders (
+        id integer PRIMARY KEY,
+        customer_id integer REFERENCES rbeaver_keys_demo.customers ON DELETE CASCADE,
+        \"Total, gross\" numeric CHECK (\"Total, gross\" >= 0)
+    )",
+    "CREATE TABLE rbeaver_keys_demo.bookings (
+        room integer,
+        during tsrange,
+        customer_id integer,
+        order_id integer,
+        EXCLUDE USING gist (room WITH =, during WITH &&),
+        FOREIGN KEY (order_id) REFERENCES rbeaver_keys_demo.orders (id) ON UPDATE SET NULL
+    )",
+    "COMMENT ON CONSTRAINT email_has_at ON rbeaver_keys_demo.customers IS 'Looks like an address'",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Table Keys and Constraints");
+    println!("=============================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    for sql in FIXTURE_SQL {
+        connection.execute_non_query(sql).await?;
+    }
+
+    for table in ["customers", "orders", "bookings"] {
+        println!("📋 {}", table);
+        for key in connection.get_foreign_keys(SCHEMA, table).await? {
+            let arrow = if key.is_outgoing(SCHEMA, table) {
+                "→"
+            } else {
+                "←"
+            };
+            println!(
+                "  {} {} {}({}) {}({}) ON DELETE {}",
+                arrow,
+                key.name,
+                key.table_name,
+                key.columns.join(", "),
+                key.referenced_table,
+                key.referenced_columns.join(", "),
+                key.on_delete.to_sql()
+            );
+        }
+        for constraint in connection.get_constraints(SCHEMA, table).await? {
+            println!("  ✓ {}: {}", constraint.name, constraint.definition);
+        }
+        println!();
+    }
+
+    connection
+        .execute_non_query("DROP SCHEMA rbeaver_keys_demo CASCADE")
+        .await?;
+    connection.disconnect().await?;
+
+    println!("🎉 Table key tests completed!");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::result_filter::lookup_query;
+    use rbeaver::database::{ConstraintType, DatabaseType, ForeignKeyAction, QueryValue};
+
+    #[test]
+    fn test_lookup_queries() {
+        let id = QueryValue::Int32(7);
+        let code = QueryValue::String("O'Neil".to_string());
+        assert_eq!(
+            lookup_query(
+                &DatabaseType::PostgreSQL,
+                "shop",
+                "order lines",
+                &[("order_id", &id), ("code", &code)]
+            ),
+            "SELECT * FROM \"shop\".\"order lines\" WHERE \"order_id\" = 7 AND \"code\" = 'O''Neil'"
+        );
+        assert_eq!(
+            lookup_query(&DatabaseType::MySQL, "shop", "orders", &[("id", &id)]),
+            "SELECT * FROM `shop`.`orders` WHERE `id` = 7"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_keys_and_constraints_from_catalog() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        // The schema is shared with the demo in main, which tests may run beside
+        let schema = "rbeaver_keys_test";
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        for sql in FIXTURE_SQL {
+            connection
+                .execute_non_query(&sql.replace(SCHEMA, schema))
+                .await
+                .unwrap();
+        }
+
+        // Orders reference customers and are referenced by bookings
+        let keys = connection.get_foreign_keys(schema, "orders").await.unwrap();
+        assert_eq!(keys.len(), 2);
+        let outgoing = keys
+            .iter()
+            .find(|k| k.is_outgoing(schema, "orders"))
+            .unwrap();
+        assert_eq!(outgoing.name, "orders_customer_id_fkey");
+        assert_eq!(outgoing.columns, vec!["customer_id"]);
+        assert_eq!(outgoing.referenced_table, "customers");
+        assert_eq!(outgoing.referenced_columns, vec!["id"]);
+        assert_eq!(outgoing.on_delete, ForeignKeyAction::Cascade);
+        let incoming = keys
+            .iter()
+            .find(|k| k.is_incoming(schema, "orders"))
+            .unwrap();
+        assert_eq!(incoming.table_name, "bookings");
+        assert_eq!(incoming.columns, vec!["order_id"]);
+        assert_eq!(incoming.on_update, ForeignKeyAction::SetNull);
+        assert_eq!(incoming.on_delete, ForeignKeyAction::NoAction);
+
+        let constraints = connection
+            .get_constraints(schema, "customers")
+            .await
+            .unwrap();
+        let types: Vec<&ConstraintType> = constraints.iter().map(|c| &c.constraint_type).collect();
+        assert_eq!(
+            types,
+            vec![
+                &ConstraintType::PrimaryKey,
+                &ConstraintType::Unique,
+                &ConstraintType::Check
+            ]
+        );
+        assert_eq!(constraints[1].columns, vec!["email"]);
+        assert_eq!(constraints[2].definition, "CHECK ((email ~~ '%@%'::text))");
+        assert_eq!(
+            constraints[2].comment.as_deref(),
+            Some("Looks like an address")
+        );
+
+        let orders = connection.get_constraints(schema, "orders").await.unwrap();
+        let check = orders
+            .iter()
+            .find(|c| c.constraint_type == ConstraintType::Check)
+            .unwrap();
+        assert_eq!(check.columns, vec!["Total, gross"]);
+
+        let bookings = connection
+            .get_constraints(schema, "bookings")
+            .await
+            .unwrap();
+        assert_eq!(bookings.len(), 1);
+        assert_eq!(bookings[0].constraint_type, ConstraintType::Exclusion);
+        assert_eq!(bookings[0].columns, vec!["room", "during"]);
+
+        connection
+            .execute_non_query(&format!("DROP SCHEMA {} CASCADE", schema))
+            .await
+            .unwrap();
+        connection.disconnect().await.unwrap();
+    }
+}
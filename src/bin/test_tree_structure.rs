@@ -17,8 +17,16 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
 
     // Test 1: Add multiple connections
     println!("\n📋 Test 1: Adding multiple connections");
-    tree.add_connection("conn1".to_string(), "PostgreSQL Local".to_string());
-    tree.add_connection("conn2".to_string(), "PostgreSQL Remote".to_string());
+    tree.add_connection(
+        "conn1".to_string(),
+        "PostgreSQL Local".to_string(),
+        PostgreSQLConnection::capabilities(),
+    );
+    tree.add_connection(
+        "conn2".to_string(),
+        "PostgreSQL Remote".to_string(),
+        PostgreSQLConnection::capabilities(),
+    );
     tree.set_connection_status("conn1", true);
     tree.set_connection_status("conn2", false);
     println!("✓ Added 2 connections to tree");
@@ -127,7 +135,11 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
             println!("✓ Connected to real database");
 
             // Add real connection to tree
-            tree.add_connection(params.id.clone(), params.name.clone());
+            tree.add_connection(
+                params.id.clone(),
+                params.name.clone(),
+                PostgreSQLConnection::capabilities(),
+            );
             tree.set_connection_status(&params.id, true);
 
             // Load real schemas
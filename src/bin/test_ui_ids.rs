@@ -1,4 +1,6 @@
-use rbeaver::database::{Column, QueryColumn, QueryResult, QueryRow, QueryValue, Schema, Table};
+use rbeaver::database::{
+    Column, DriverCapabilities, QueryColumn, QueryResult, QueryRow, QueryValue, Schema, Table,
+};
 use rbeaver::ui::{DatabaseTree, QueryEditor, ResultTable};
 
 fn main() {
@@ -13,9 +15,21 @@ fn main() {
 
     // Test 2: Add multiple connections to database tree
     println!("\n📋 Test 2: Adding multiple connections with unique IDs");
-    database_tree.add_connection("conn1".to_string(), "PostgreSQL Local".to_string());
-    database_tree.add_connection("conn2".to_string(), "PostgreSQL Remote".to_string());
-    database_tree.add_connection("conn3".to_string(), "PostgreSQL Test".to_string());
+    database_tree.add_connection(
+        "conn1".to_string(),
+        "PostgreSQL Local".to_string(),
+        DriverCapabilities::default(),
+    );
+    database_tree.add_connection(
+        "conn2".to_string(),
+        "PostgreSQL Remote".to_string(),
+        DriverCapabilities::default(),
+    );
+    database_tree.add_connection(
+        "conn3".to_string(),
+        "PostgreSQL Test".to_string(),
+        DriverCapabilities::default(),
+    );
     println!("✓ Multiple connections added with unique IDs");
 
     // Test 3: Add schemas with same names to different connections
@@ -74,18 +88,21 @@ fn main() {
             data_type: "integer".to_string(),
             ordinal: 0,
             nullable: false,
+            ..Default::default()
         },
         QueryColumn {
             name: "name".to_string(),
             data_type: "varchar".to_string(),
             ordinal: 1,
             nullable: true,
+            ..Default::default()
         },
         QueryColumn {
             name: "email".to_string(),
             data_type: "varchar".to_string(),
             ordinal: 2,
             nullable: true,
+            ..Default::default()
         },
     ];
 
@@ -0,0 +1,536 @@
+use rbeaver::database::{
+    DatabaseConnection, PostgreSQLConnection, QueryExecutor, QueryResult, QuerySession,
+};
+
+#[path = "common/mod.rs"]
+mod common;
+
+const VALUES_QUERY: &str = "SELECT
+        12.50::numeric(10, 2) AS price,
+        '123456789012345678901234567890.123456789'::numeric AS huge,
+        'NaN'::numeric AS not_a_number,
+        DATE '2024-02-29' AS due,
+        'infinity'::date AS open_end,
+        TIME '08:30:15.25' AS starts,
+        TIMETZ '08:30:00+05:30' AS starts_local,
+        TIMESTAMP '2024-03-01 12:30:00' AS booked,
+        TIMESTAMPTZ '2024-07-01 12:00:00+00' AS paid,
+        INTERVAL '1 year 2 months -3 days 04:05:06.5' AS duration";
+
+const CONTAINERS_QUERY: &str = "SELECT
+        ARRAY[1, 2, 3] AS numbers,
+        ARRAY['plain', 'with space', NULL, 'a,b', 'say \"hi\"', ''] AS words,
+        ARRAY[[1, 2], [3, 4]] AS matrix,
+        '{}'::int4[] AS nothing,
+        int4range(2, 5) AS seats,
+        daterange('2024-01-01', '2024-02-01') AS january,
+        tstzrange('2024-01-01 00:00+00', NULL) AS since,
+        'empty'::numrange AS never,
+        ROW(7, 'Alice', ARRAY[1.5, 2.25]::numeric[])::pg_temp.person AS who,
+        ARRAY[ROW(1, 'x', NULL)::pg_temp.person] AS crowd";
+
+const GADGETS_SETUP: &[&str] = &[
+    "CREATE TYPE pg_temp.size AS ENUM ('small', 'medium', 'large')",
+    "CREATE TEMP TABLE gadgets (
+        id uuid,
+        address inet,
+        network cidr,
+        mac macaddr,
+        price money,
+        flags bit(4),
+        mask varbit,
+        size pg_temp.size,
+        note text
+    )",
+    "INSERT INTO gadgets VALUES
+        ('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11', '192.168.0.10', '10.1.0.0/16',
+         '08:00:2b:01:02:03', 1234.5, B'1010', B'011', 'medium', 'it''s'),
+        ('00000000-0000-0000-0000-000000000001', '10.0.0.1/8', '10.0.0.0/8',
+         '08:00:2b:01:02:04', -0.75, B'0001', B'', 'large', NULL),
+        ('ffffffff-0000-0000-0000-000000000000', '::1', '2001:db8::/32',
+         '00:00:00:00:00:01', 0, B'1111', B'1', 'small', 'back\\slash'),
+        (NULL, '10.0.0.0/8', '192.168.0.0/24', NULL, NULL, NULL, NULL, NULL, '')",
+];
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    println!("🔧 Testing RBeaver Value Decoding");
+    println!("=================================\n");
+
+    let Some(params) = common::server_params() else {
+        println!("⚠️  RBEAVER_PG_HOST is not set; start a PostgreSQL server and export");
+        println!("   RBEAVER_PG_HOST, RBEAVER_PG_PORT, RBEAVER_PG_USER, RBEAVER_PG_PASSWORD");
+        println!("   and RBEAVER_PG_DATABASE to run the server tests.");
+        return Ok(());
+    };
+
+    let mut connection = PostgreSQLConnection::new();
+    connection.connect(&params).await?;
+    let session = connection.open_session().await?;
+
+    // Test 1: Numeric, date and time values
+    println!("📋 Test 1: Decoding numeric, date and time values");
+    let result = query_values(&session, "Europe/Berlin").await?;
+    for (column, value) in result.columns.iter().zip(&result.rows[0].values) {
+        println!("  ✓ {}: {}", column.name, value.to_display_string());
+    }
+
+    // Test 2: Arrays, ranges and composites
+    println!("\n📋 Test 2: Decoding arrays, ranges and composites");
+    let result = query_containers(&session).await?;
+    for (column, value) in result.columns.iter().zip(&result.rows[0].values) {
+        println!("  ✓ {}: {}", column.name, value.to_display_string());
+    }
+
+    // Test 3: UUID, network, money, bit and enum values
+    println!("\n📋 Test 3: Decoding UUID, network, money, bit and enum values");
+    let result = query_gadgets(&session).await?;
+    for row in &result.rows {
+        let values: Vec<String> = row.values.iter().map(|v| v.to_display_string()).collect();
+        println!("  ✓ {}", values.join(" | "));
+    }
+    drop(session);
+
+    connection.disconnect().await?;
+
+    println!("\n🎉 Value decoding tests completed!");
+    Ok(())
+}
+
+/// Set the session time zone and run the values query
+async fn query_values(
+    session: &QuerySession,
+    time_zone: &str,
+) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    let mut cursor = session
+        .open_cursor(&format!("SET TIME ZONE '{}'", time_zone))
+        .await?;
+    cursor.fetch(1).await?;
+
+    let mut cursor = session.open_cursor(VALUES_QUERY).await?;
+    let rows = cursor.fetch(1).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+/// Create the composite type on the session and run the containers query
+async fn query_containers(
+    session: &QuerySession,
+) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    let mut cursor = session
+        .open_cursor("CREATE TYPE pg_temp.person AS (id int4, name text, scores numeric[])")
+        .await?;
+    cursor.fetch(1).await?;
+
+    let mut cursor = session.open_cursor(CONTAINERS_QUERY).await?;
+    let rows = cursor.fetch(1).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+/// Fill the gadgets table on the session and read it back
+async fn query_gadgets(session: &QuerySession) -> Result<QueryResult, Box<dyn std::error::Error>> {
+    for sql in GADGETS_SETUP {
+        let mut cursor = session.open_cursor(sql).await?;
+        cursor.fetch(1).await?;
+    }
+
+    let mut cursor = session.open_cursor("SELECT * FROM gadgets").await?;
+    let rows = cursor.fetch(100).await?;
+    Ok(cursor.result_with_rows(rows))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rbeaver::database::{EnumValue, IntervalValue, NetworkAddress, QueryValue, RangeValue};
+    use std::cmp::Ordering;
+
+    fn displayed(result: &QueryResult, name: &str) -> String {
+        let index = result.get_column_index(name).unwrap();
+        result.rows[0].values[index].to_display_string()
+    }
+
+    #[test]
+    fn test_interval_display_matches_postgres() {
+        let hour = 3_600_000_000;
+        assert_eq!(IntervalValue::new(0, 0, 0).to_display_string(), "00:00:00");
+        assert_eq!(
+            IntervalValue::new(14, 1, 0).to_display_string(),
+            "1 year 2 mons 1 day"
+        );
+        assert_eq!(
+            IntervalValue::new(0, -1, 2 * hour).to_display_string(),
+            "-1 days +02:00:00"
+        );
+        assert_eq!(
+            IntervalValue::new(-13, 0, -(hour + 500_000)).to_display_string(),
+            "-1 years -1 mons -01:00:00.5"
+        );
+        assert_eq!(
+            IntervalValue::new(0, 0, 30 * hour + 1).to_display_string(),
+            "30:00:00.000001"
+        );
+    }
+
+    #[test]
+    fn test_time_values_show_their_offset() {
+        let time = chrono::NaiveTime::from_hms_opt(8, 30, 0).unwrap();
+        assert_eq!(
+            QueryValue::Time(time, Some(5 * 3600 + 1800)).to_display_string(),
+            "08:30:00+05:30"
+        );
+        assert_eq!(QueryValue::Time(time, None).to_display_string(), "08:30:00");
+
+        let offset = chrono::FixedOffset::west_opt(3 * 3600).unwrap();
+        let dt = chrono::DateTime::parse_from_rfc3339("2024-01-02T03:04:05.5Z")
+            .unwrap()
+            .with_timezone(&offset);
+        assert_eq!(
+            QueryValue::DateTime(dt).to_display_string(),
+            "2024-01-02 00:04:05.500-03"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_numeric_values_are_exact() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        let result = query_values(&session, "UTC").await.unwrap();
+
+        assert!(matches!(result.rows[0].values[0], QueryValue::Decimal(_)));
+        assert_eq!(displayed(&result, "price"), "12.50");
+        assert_eq!(
+            displayed(&result, "huge"),
+            "123456789012345678901234567890.123456789"
+        );
+        assert_eq!(displayed(&result, "not_a_number"), "NaN");
+    }
+
+    #[tokio::test]
+    async fn test_dates_and_times() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        let result = query_values(&session, "UTC").await.unwrap();
+
+        assert_eq!(displayed(&result, "due"), "2024-02-29");
+        assert_eq!(displayed(&result, "open_end"), "infinity");
+        assert_eq!(displayed(&result, "starts"), "08:30:15.250");
+        assert_eq!(displayed(&result, "starts_local"), "08:30:00+05:30");
+        assert_eq!(displayed(&result, "booked"), "2024-03-01 12:30:00");
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 12:00:00+00");
+        assert_eq!(
+            displayed(&result, "duration"),
+            "1 year 2 mons -3 days +04:05:06.5"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_timestamptz_follows_session_time_zone() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+
+        // Summer time in Berlin, standard time in Kolkata
+        let result = query_values(&session, "Europe/Berlin").await.unwrap();
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 14:00:00+02");
+        let result = query_values(&session, "Asia/Kolkata").await.unwrap();
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 17:30:00+05:30");
+        // Naive timestamps stay as they are
+        assert_eq!(displayed(&result, "booked"), "2024-03-01 12:30:00");
+    }
+
+    #[tokio::test]
+    async fn test_time_zone_follows_undone_settings() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+
+        let result = query_values(&session, "Europe/Berlin").await.unwrap();
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 14:00:00+02");
+
+        // A transaction's time zone goes away with it
+        for sql in [
+            "BEGIN",
+            "SELECT set_config('TimeZone', 'Asia/Kolkata', true)",
+        ] {
+            let mut cursor = session.open_cursor(sql).await.unwrap();
+            cursor.fetch(1).await.unwrap();
+        }
+        let mut cursor = session.open_cursor(VALUES_QUERY).await.unwrap();
+        let rows = cursor.fetch(1).await.unwrap();
+        let result = cursor.result_with_rows(rows);
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 17:30:00+05:30");
+        drop(cursor);
+
+        let mut cursor = session.open_cursor("ROLLBACK").await.unwrap();
+        cursor.fetch(1).await.unwrap();
+        let mut cursor = session.open_cursor(VALUES_QUERY).await.unwrap();
+        let rows = cursor.fetch(1).await.unwrap();
+        let result = cursor.result_with_rows(rows);
+        assert_eq!(displayed(&result, "paid"), "2024-07-01 14:00:00+02");
+    }
+
+    #[test]
+    fn test_container_literals_quote_like_postgres() {
+        let array = QueryValue::Array(vec![
+            QueryValue::Int32(1),
+            QueryValue::Null,
+            QueryValue::String("a b".to_string()),
+            QueryValue::String("null".to_string()),
+            QueryValue::Array(vec![QueryValue::String(r#"x"y"#.to_string())]),
+        ]);
+        assert_eq!(
+            array.to_display_string(),
+            r#"{1,NULL,"a b","null",{"x\"y"}}"#
+        );
+
+        let composite = QueryValue::Composite(vec![
+            ("id".to_string(), QueryValue::Int32(1)),
+            ("tags".to_string(), array),
+            ("note".to_string(), QueryValue::Null),
+        ]);
+        assert!(composite.is_nested());
+        assert_eq!(
+            composite.to_display_string(),
+            r#"(1,"{1,NULL,\"a b\",\"null\",{\"x\\\"y\"}}",)"#
+        );
+
+        let range = RangeValue {
+            lower: Some(Box::new(QueryValue::Int32(2))),
+            upper: None,
+            lower_inclusive: true,
+            upper_inclusive: false,
+            empty: false,
+        };
+        assert_eq!(QueryValue::Range(range).to_display_string(), "[2,)");
+        assert_eq!(RangeValue::empty().to_display_string(), "empty");
+    }
+
+    #[tokio::test]
+    async fn test_arrays_ranges_and_composites() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        query_values(&session, "UTC").await.unwrap();
+        let result = query_containers(&session).await.unwrap();
+
+        assert_eq!(displayed(&result, "numbers"), "{1,2,3}");
+        assert_eq!(
+            displayed(&result, "words"),
+            r#"{plain,"with space",NULL,"a,b","say \"hi\"",""}"#
+        );
+        assert_eq!(displayed(&result, "matrix"), "{{1,2},{3,4}}");
+        assert_eq!(displayed(&result, "nothing"), "{}");
+        assert_eq!(displayed(&result, "seats"), "[2,5)");
+        assert_eq!(displayed(&result, "january"), "[2024-01-01,2024-02-01)");
+        assert_eq!(
+            displayed(&result, "since"),
+            r#"["2024-01-01 00:00:00+00",)"#
+        );
+        assert_eq!(displayed(&result, "never"), "empty");
+        assert_eq!(displayed(&result, "who"), r#"(7,Alice,"{1.5,2.25}")"#);
+        assert_eq!(displayed(&result, "crowd"), r#"{"(1,x,)"}"#);
+
+        // Composite fields keep their names and types
+        let index = result.get_column_index("who").unwrap();
+        let QueryValue::Composite(fields) = &result.rows[0].values[index] else {
+            panic!("not a composite");
+        };
+        assert_eq!(fields[1].0, "name");
+        assert!(matches!(&fields[2].1, QueryValue::Array(scores)
+            if matches!(scores[0], QueryValue::Decimal(_))));
+    }
+
+    #[test]
+    fn test_network_addresses_sort_like_postgres() {
+        let address = |text: &str| {
+            let (address, prefix_len) = text.split_once('/').unwrap_or((text, "32"));
+            QueryValue::Network(NetworkAddress {
+                address: address.parse().unwrap(),
+                prefix_len: prefix_len.parse().unwrap(),
+                is_cidr: false,
+            })
+        };
+        let mut values = [
+            address("192.168.0.10"),
+            address("10.0.0.1/8"),
+            address("10.0.0.0/8"),
+            address("9.255.255.255"),
+            QueryValue::Null,
+        ];
+        values.sort_by(|a, b| a.compare(b));
+
+        let sorted: Vec<String> = values.iter().map(|v| v.to_display_string()).collect();
+        assert_eq!(
+            sorted,
+            vec![
+                "NULL",
+                "9.255.255.255",
+                "10.0.0.0/8",
+                "10.0.0.1/8",
+                "192.168.0.10"
+            ]
+        );
+    }
+
+    #[test]
+    fn test_values_compare_by_type() {
+        let size = |label: &str, position| {
+            QueryValue::Enum(EnumValue {
+                type_name: "size".to_string(),
+                label: label.to_string(),
+                position: Some(position),
+            })
+        };
+        assert_eq!(size("small", 0).compare(&size("large", 2)), Ordering::Less);
+        assert_eq!(
+            QueryValue::Int32(10).compare(&QueryValue::Int64(9)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            QueryValue::Float64(f64::NAN).compare(&QueryValue::Float64(f64::INFINITY)),
+            Ordering::Greater
+        );
+        assert_eq!(
+            QueryValue::Interval(IntervalValue::new(1, 0, 0))
+                .compare(&QueryValue::Interval(IntervalValue::new(0, 31, 0))),
+            Ordering::Less
+        );
+        assert_eq!(
+            QueryValue::Bits("101".to_string()).compare(&QueryValue::Bits("1010".to_string())),
+            Ordering::Less
+        );
+    }
+
+    #[test]
+    fn test_sql_literals() {
+        assert_eq!(QueryValue::Null.to_sql_literal(), "NULL");
+        assert_eq!(QueryValue::Bool(true).to_sql_literal(), "TRUE");
+        assert_eq!(QueryValue::Int64(-5).to_sql_literal(), "-5");
+        assert_eq!(QueryValue::Float64(f64::NAN).to_sql_literal(), "'NaN'");
+        assert_eq!(
+            QueryValue::String("it's".to_string()).to_sql_literal(),
+            "'it''s'"
+        );
+        assert_eq!(QueryValue::Money(-75).to_sql_literal(), "'-0.75'");
+        assert_eq!(
+            QueryValue::MacAddress(vec![8, 0, 0x2b, 1, 2, 3]).to_sql_literal(),
+            "'08:00:2b:01:02:03'"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_special_types_decode_and_sort() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        let result = query_gadgets(&session).await.unwrap();
+
+        let row = &result.rows[0];
+        assert!(matches!(row.values[0], QueryValue::Uuid(_)));
+        let displayed: Vec<String> = row.values.iter().map(|v| v.to_display_string()).collect();
+        assert_eq!(
+            displayed,
+            vec![
+                "a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11",
+                "192.168.0.10",
+                "10.1.0.0/16",
+                "08:00:2b:01:02:03",
+                "1234.50",
+                "1010",
+                "011",
+                "medium",
+                "it's",
+            ]
+        );
+
+        // Local sorting agrees with the server's ORDER BY
+        for column in [
+            "id", "address", "network", "mac", "price", "flags", "mask", "size",
+        ] {
+            let index = result.get_column_index(column).unwrap();
+            let mut local: Vec<&QueryValue> =
+                result.rows.iter().map(|row| &row.values[index]).collect();
+            local.sort_by(|a, b| a.compare(b));
+            let local: Vec<String> = local.iter().map(|v| v.to_display_string()).collect();
+
+            let sql = format!(
+                "SELECT {} FROM gadgets ORDER BY {} NULLS FIRST",
+                column, column
+            );
+            let mut cursor = session.open_cursor(&sql).await.unwrap();
+            let rows = cursor.fetch(100).await.unwrap();
+            let server: Vec<String> = rows
+                .iter()
+                .map(|row| row.values[0].to_display_string())
+                .collect();
+            assert_eq!(local, server, "order of {}", column);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sql_literals_round_trip() {
+        let Some(params) = common::server_params() else {
+            eprintln!("skipping: RBEAVER_PG_HOST is not set");
+            return;
+        };
+        let mut connection = PostgreSQLConnection::new();
+        connection.connect(&params).await.unwrap();
+        let session = connection.open_session().await.unwrap();
+        let result = query_gadgets(&session).await.unwrap();
+
+        let rows: Vec<String> = result
+            .rows
+            .iter()
+            .map(|row| {
+                let literals: Vec<String> = row.values.iter().map(|v| v.to_sql_literal()).collect();
+                format!("({})", literals.join(", "))
+            })
+            .collect();
+        for sql in [
+            "CREATE TEMP TABLE gadgets_copy (LIKE gadgets)".to_string(),
+            format!("INSERT INTO gadgets_copy VALUES {}", rows.join(", ")),
+        ] {
+            let mut cursor = session.open_cursor(&sql).await.unwrap();
+            cursor.fetch(1).await.unwrap();
+        }
+
+        let mut cursor = session
+            .open_cursor(
+                "SELECT count(*) FROM (
+                    (TABLE gadgets EXCEPT ALL TABLE gadgets_copy)
+                    UNION ALL
+                    (TABLE gadgets_copy EXCEPT ALL TABLE gadgets)
+                ) AS differences",
+            )
+            .await
+            .unwrap();
+        let rows = cursor.fetch(1).await.unwrap();
+        assert_eq!(rows[0].values[0].to_display_string(), "0");
+    }
+}
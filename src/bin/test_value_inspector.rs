@@ -0,0 +1,55 @@
+use rbeaver::ui::value_inspector::{hex_dump, image_kind, visualize_whitespace};
+
+fn main() {
+    println!("🔧 Testing RBeaver Value Inspector");
+    println!("==================================\n");
+
+    let bytes = b"\x89PNG\r\n\x1a\n\x00\x00\x00\rIHDR\x00\x00\x00\x01";
+    println!("📋 Image kind: {:?}", image_kind(bytes));
+    println!("📋 Hex dump:");
+    for line in hex_dump(bytes, 1024).lines() {
+        println!("  {}", line);
+    }
+
+    println!("\n📋 Whitespace:");
+    for line in visualize_whitespace("first line \n\tindented\r\nlast").lines() {
+        println!("  {}", line);
+    }
+
+    println!("\n🎉 Value inspector tests completed!");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_image_kind() {
+        assert_eq!(image_kind(b"\x89PNG\r\n\x1a\n\x00"), Some("PNG"));
+        assert_eq!(image_kind(&[0xFF, 0xD8, 0xFF, 0xE0]), Some("JPEG"));
+        assert_eq!(image_kind(b"GIF89a"), None);
+        assert_eq!(image_kind(&[]), None);
+    }
+
+    #[test]
+    fn test_hex_dump() {
+        let bytes: Vec<u8> = (0x3e..0x52).collect();
+        assert_eq!(
+            hex_dump(&bytes, 1024),
+            "00000000  3e 3f 40 41 42 43 44 45 46 47 48 49 4a 4b 4c 4d  >?@ABCDEFGHIJKLM\n\
+             00000010  4e 4f 50 51                                      NOPQ"
+        );
+        // Control bytes show as dots, and the limit cuts the dump short
+        assert_eq!(
+            hex_dump(b"a\x00\nb", 3),
+            "00000000  61 00 0a                                         a.."
+        );
+        assert_eq!(hex_dump(&[], 1024), "");
+    }
+
+    #[test]
+    fn test_visualize_whitespace() {
+        assert_eq!(visualize_whitespace("a b\tc\nd\r\ne"), "a·b→c↵\nd␍↵\ne");
+        assert_eq!(visualize_whitespace("plain"), "plain");
+    }
+}
@@ -1,5 +1,6 @@
 use crate::database::ConnectionParams;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::PathBuf;
 
 /// Application settings
@@ -9,6 +10,9 @@ pub struct AppSettings {
     pub ui_settings: UiSettings,
     pub editor_settings: EditorSettings,
     pub general_settings: GeneralSettings,
+    /// Table positions of ER diagrams, keyed by `diagram_key`
+    #[serde(default)]
+    pub diagram_layouts: HashMap<String, HashMap<String, (f32, f32)>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -45,6 +49,7 @@ impl Default for AppSettings {
             ui_settings: UiSettings::default(),
             editor_settings: EditorSettings::default(),
             general_settings: GeneralSettings::default(),
+            diagram_layouts: HashMap::new(),
         }
     }
 }
@@ -114,6 +119,11 @@ impl AppSettings {
         Ok(())
     }
 
+    /// Key of the saved layout of a schema's ER diagram
+    pub fn diagram_key(connection_id: &str, schema: &str) -> String {
+        format!("{}/{}", connection_id, schema)
+    }
+
     /// Get the configuration file path
     fn get_config_path() -> Result<PathBuf, Box<dyn std::error::Error>> {
         let config_dir = dirs::config_dir()
@@ -15,16 +15,30 @@ pub struct ConnectionParams {
     pub password: String,
     pub ssl_mode: SslMode,
     pub connection_timeout: Option<u32>,
+    /// Statement timeout in seconds, overriding `GeneralSettings::query_timeout`
+    /// (0 disables the limit)
+    pub query_timeout: Option<u32>,
     pub additional_params: HashMap<String, String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum DatabaseType {
     PostgreSQL,
     MySQL,
     SQLite,
 }
 
+impl DatabaseType {
+    /// Default server port for the database type (0 for file-based databases)
+    pub fn default_port(&self) -> u16 {
+        match self {
+            DatabaseType::PostgreSQL => 5432,
+            DatabaseType::MySQL => 3306,
+            DatabaseType::SQLite => 0, // Not applicable for SQLite
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub enum SslMode {
     Disable,
@@ -48,6 +62,7 @@ impl Default for ConnectionParams {
             password: String::new(),
             ssl_mode: SslMode::Prefer,
             connection_timeout: Some(30),
+            query_timeout: None,
             additional_params: HashMap::new(),
         }
     }
@@ -55,11 +70,7 @@ impl Default for ConnectionParams {
 
 impl ConnectionParams {
     pub fn new(name: String, database_type: DatabaseType) -> Self {
-        let port = match database_type {
-            DatabaseType::PostgreSQL => 5432,
-            DatabaseType::MySQL => 3306,
-            DatabaseType::SQLite => 0, // Not applicable for SQLite
-        };
+        let port = database_type.default_port();
 
         Self {
             id: Uuid::new_v4().to_string(),
@@ -85,22 +96,31 @@ impl ConnectionParams {
             password: self.password.clone(),
             ssl_mode: self.ssl_mode.clone(),
             connection_timeout: self.connection_timeout,
+            query_timeout: self.query_timeout,
             additional_params: self.additional_params.clone(),
         }
     }
 
+    /// Statement timeout in seconds for this connection, given the global default
+    pub fn effective_query_timeout(&self, default_timeout: u32) -> u32 {
+        self.query_timeout.unwrap_or(default_timeout)
+    }
+
     /// Validate connection parameters
     pub fn validate(&self) -> Result<(), String> {
         if self.name.trim().is_empty() {
             return Err("Connection name cannot be empty".to_string());
         }
 
-        if self.host.trim().is_empty() {
+        if self.host.trim().is_empty() && self.database_type != DatabaseType::SQLite {
             return Err("Host cannot be empty".to_string());
         }
 
-        if self.database.trim().is_empty() && self.database_type != DatabaseType::SQLite {
-            return Err("Database name cannot be empty".to_string());
+        if self.database.trim().is_empty() {
+            return Err(match self.database_type {
+                DatabaseType::SQLite => "Database file cannot be empty".to_string(),
+                _ => "Database name cannot be empty".to_string(),
+            });
         }
 
         if self.username.trim().is_empty() && self.database_type != DatabaseType::SQLite {
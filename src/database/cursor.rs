@@ -0,0 +1,231 @@
+use crate::database::{DatabaseError, QueryColumn, QueryResult, QueryRow};
+use futures_util::{Stream, TryStreamExt};
+use sqlx::Either;
+use std::future::Future;
+use tokio::sync::{mpsc, oneshot};
+
+/// Request from a cursor to the task that owns the result stream
+pub(crate) struct FetchRequest {
+    max_rows: usize,
+    reply: oneshot::Sender<Result<RowBatch, DatabaseError>>,
+}
+
+/// Rows returned by one fetch
+struct RowBatch {
+    columns: Option<Vec<QueryColumn>>,
+    rows: Vec<QueryRow>,
+    exhausted: bool,
+    rows_affected: Option<u64>,
+}
+
+/// Result set of a running query, read incrementally
+///
+/// The rows stay on the server (or in the socket buffer) until they are
+/// fetched, so only the part of a large result that is actually looked at is
+/// held in memory. Dropping the cursor closes the result set and frees its
+/// session for the next statement.
+pub struct QueryCursor {
+    sql: String,
+    requests: mpsc::Sender<FetchRequest>,
+    columns: Vec<QueryColumn>,
+    rows_fetched: usize,
+    exhausted: bool,
+    rows_affected: Option<u64>,
+}
+
+impl QueryCursor {
+    /// Create a cursor together with the request channel a driver task serves
+    /// with [`serve_cursor`]
+    pub(crate) fn channel(sql: &str) -> (Self, mpsc::Receiver<FetchRequest>) {
+        let (requests, receiver) = mpsc::channel(1);
+        let cursor = Self {
+            sql: sql.to_string(),
+            requests,
+            columns: Vec::new(),
+            rows_fetched: 0,
+            exhausted: false,
+            rows_affected: None,
+        };
+        (cursor, receiver)
+    }
+
+    /// Fetch up to `max_rows` further rows
+    pub async fn fetch(&mut self, max_rows: usize) -> Result<Vec<QueryRow>, DatabaseError> {
+        if self.exhausted || max_rows == 0 {
+            return Ok(Vec::new());
+        }
+
+        let (reply, response) = oneshot::channel();
+        let request = FetchRequest { max_rows, reply };
+        let closed = || DatabaseError::Internal("Result stream closed unexpectedly".to_string());
+
+        self.requests.send(request).await.map_err(|_| closed())?;
+        let batch = match response.await.map_err(|_| closed())? {
+            Ok(batch) => batch,
+            Err(err) => {
+                self.exhausted = true;
+                return Err(err);
+            }
+        };
+
+        if let Some(columns) = batch.columns {
+            self.columns = columns;
+        }
+        self.rows_fetched += batch.rows.len();
+        self.exhausted = batch.exhausted;
+        self.rows_affected = batch.rows_affected;
+        Ok(batch.rows)
+    }
+
+    /// Build a `QueryResult` for this cursor's statement holding the given rows
+    ///
+    /// A statement that returned no rows reports how many rows it changed.
+    pub fn result_with_rows(&self, rows: Vec<QueryRow>) -> QueryResult {
+        let result = QueryResult::new(self.sql.clone());
+        if !rows.is_empty() {
+            result.with_columns(self.columns.clone()).with_rows(rows)
+        } else if let (true, Some(rows_affected)) = (self.columns.is_empty(), self.rows_affected) {
+            result.with_rows_affected(rows_affected)
+        } else {
+            result
+        }
+    }
+
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    pub fn rows_fetched(&self) -> usize {
+        self.rows_fetched
+    }
+
+    /// Whether every row has been fetched
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+}
+
+/// Statement sent to the task that owns a session's connection
+pub(crate) struct SessionStatement {
+    pub(crate) sql: String,
+    pub(crate) requests: mpsc::Receiver<FetchRequest>,
+}
+
+/// One server session reserved for running statements in order
+///
+/// Statements that depend on session state, such as `BEGIN` ... `COMMIT` or
+/// temporary tables, see each other's effects. Each statement starts once the
+/// cursor of the one before it is dropped or exhausted. The connection goes
+/// back to the pool when the session and its last cursor are dropped.
+#[derive(Clone)]
+pub struct QuerySession {
+    statements: mpsc::Sender<SessionStatement>,
+}
+
+impl QuerySession {
+    /// Create a session together with the statement channel its driver task serves
+    pub(crate) fn channel() -> (Self, mpsc::Receiver<SessionStatement>) {
+        let (statements, receiver) = mpsc::channel(1);
+        (Self { statements }, receiver)
+    }
+
+    /// Run a statement on this session and return a cursor over its rows
+    pub async fn open_cursor(&self, sql: &str) -> Result<QueryCursor, DatabaseError> {
+        let (cursor, requests) = QueryCursor::channel(sql);
+        let statement = SessionStatement {
+            sql: sql.to_string(),
+            requests,
+        };
+        self.statements
+            .send(statement)
+            .await
+            .map_err(|_| DatabaseError::Internal("Session closed unexpectedly".to_string()))?;
+        Ok(cursor)
+    }
+}
+
+/// Answer fetch requests from a result stream until the rows run out or the
+/// cursor is dropped
+///
+/// The stream yields rows on the right and the number of rows affected by
+/// each finished statement on the left. One row is read ahead after each
+/// batch so the cursor knows whether more rows follow. `describe_columns`
+/// gets a chance to add to the column descriptions before the first batch
+/// goes out.
+pub(crate) async fn serve_cursor<S, R, E, F>(
+    mut requests: mpsc::Receiver<FetchRequest>,
+    mut stream: S,
+    convert_columns: impl Fn(&R) -> Vec<QueryColumn>,
+    describe_columns: impl Fn(Vec<QueryColumn>) -> F,
+    convert_row: impl Fn(&R) -> Result<QueryRow, DatabaseError>,
+) where
+    S: Stream<Item = Result<Either<u64, R>, E>> + Unpin,
+    DatabaseError: From<E>,
+    F: Future<Output = Vec<QueryColumn>>,
+{
+    let mut lookahead: Option<R> = None;
+    let mut columns_sent = false;
+    let mut rows_affected: Option<u64> = None;
+
+    while let Some(request) = requests.recv().await {
+        let mut batch = RowBatch {
+            columns: None,
+            rows: Vec::new(),
+            exhausted: false,
+            rows_affected: None,
+        };
+
+        let outcome = async {
+            while batch.rows.len() < request.max_rows {
+                let row = match lookahead.take() {
+                    Some(row) => row,
+                    None => match next_row(&mut stream, &mut rows_affected).await? {
+                        Some(row) => row,
+                        None => {
+                            batch.exhausted = true;
+                            return Ok(());
+                        }
+                    },
+                };
+
+                if !columns_sent {
+                    batch.columns = Some(describe_columns(convert_columns(&row)).await);
+                    columns_sent = true;
+                }
+                batch.rows.push(convert_row(&row)?);
+            }
+
+            lookahead = next_row(&mut stream, &mut rows_affected).await?;
+            batch.exhausted = lookahead.is_none();
+            Ok::<_, DatabaseError>(())
+        }
+        .await;
+
+        if batch.exhausted {
+            batch.rows_affected = rows_affected;
+        }
+        let done = outcome.is_err() || batch.exhausted;
+        let _ = request.reply.send(outcome.map(|()| batch));
+        if done {
+            break;
+        }
+    }
+}
+
+/// Next row of the stream, adding up the counts of statements finished on the way
+async fn next_row<S, R, E>(
+    stream: &mut S,
+    rows_affected: &mut Option<u64>,
+) -> Result<Option<R>, DatabaseError>
+where
+    S: Stream<Item = Result<Either<u64, R>, E>> + Unpin,
+    DatabaseError: From<E>,
+{
+    while let Some(step) = stream.try_next().await? {
+        match step {
+            Either::Left(count) => *rows_affected = Some(rows_affected.unwrap_or(0) + count),
+            Either::Right(row) => return Ok(Some(row)),
+        }
+    }
+    Ok(None)
+}
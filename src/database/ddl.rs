@@ -0,0 +1,383 @@
+//! CREATE statements that rebuild database objects
+//!
+//! Drivers read the parts of an object from their catalogs and hand them to
+//! the writers here, or use the server's own definition where it has one.
+
+use crate::database::dml::quote_identifier;
+use crate::database::query::quote_literal;
+use crate::database::{Constraint, DatabaseType, ForeignKey, ForeignKeyAction, Sequence};
+
+/// What a relation is, which decides the statement that creates it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RelationKind {
+    Table,
+    UnloggedTable,
+    View,
+    MaterializedView,
+}
+
+impl RelationKind {
+    /// Object type as it appears in `ALTER` and `COMMENT ON`
+    pub fn object_type(self) -> &'static str {
+        match self {
+            RelationKind::Table | RelationKind::UnloggedTable => "TABLE",
+            RelationKind::View => "VIEW",
+            RelationKind::MaterializedView => "MATERIALIZED VIEW",
+        }
+    }
+}
+
+/// Everything needed to recreate a table or view
+#[derive(Debug, Clone)]
+pub struct TableDefinition {
+    pub schema: String,
+    pub name: String,
+    pub kind: RelationKind,
+    pub columns: Vec<ColumnDefinition>,
+    /// Query of a view or materialized view
+    pub query: Option<String>,
+    pub constraints: Vec<Constraint>,
+    /// Foreign keys the table owns; keys referencing it are left out
+    pub foreign_keys: Vec<ForeignKey>,
+    /// Indexes not created by a constraint
+    pub indexes: Vec<IndexDefinition>,
+    pub comment: Option<String>,
+    pub owner: Option<String>,
+    pub grants: Vec<Grant>,
+}
+
+/// One column as it is declared in `CREATE TABLE`
+#[derive(Debug, Clone)]
+pub struct ColumnDefinition {
+    pub name: String,
+    /// Type with its modifiers, e.g. `character varying(40)`
+    pub data_type: String,
+    /// Collation, when it differs from the type's, already quoted
+    pub collation: Option<String>,
+    pub not_null: bool,
+    pub default: Option<ColumnDefault>,
+    pub comment: Option<String>,
+}
+
+/// Where a column's value comes from when an insert leaves it out
+#[derive(Debug, Clone, PartialEq)]
+pub enum ColumnDefault {
+    /// `DEFAULT expression`
+    Value(String),
+    /// `GENERATED ALWAYS AS IDENTITY` or `GENERATED BY DEFAULT AS IDENTITY`
+    Identity { always: bool },
+    /// `GENERATED ALWAYS AS (expression) STORED`
+    Generated(String),
+}
+
+/// An index with the statement that creates it
+#[derive(Debug, Clone)]
+pub struct IndexDefinition {
+    pub name: String,
+    /// Complete `CREATE INDEX` statement, without the semicolon
+    pub definition: String,
+    pub comment: Option<String>,
+}
+
+/// A privilege held on an object by a role other than its owner
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grant {
+    /// Role holding the privilege; `None` is PUBLIC
+    pub grantee: Option<String>,
+    /// Privilege name, e.g. `SELECT`
+    pub privilege: String,
+    /// Whether the grantee may grant the privilege to others
+    pub grantable: bool,
+}
+
+/// Schema-qualified name of an object
+pub fn qualified_name(database_type: &DatabaseType, schema: &str, name: &str) -> String {
+    format!(
+        "{}.{}",
+        quote_identifier(database_type, schema),
+        quote_identifier(database_type, name)
+    )
+}
+
+/// The statements creating a table or view, followed by its indexes,
+/// comments, owner and privileges
+pub fn table_ddl(database_type: &DatabaseType, table: &TableDefinition) -> String {
+    let name = qualified_name(database_type, &table.schema, &table.name);
+    let object_type = table.kind.object_type();
+    let mut sections = Vec::new();
+
+    sections.push(match (table.kind, &table.query) {
+        (RelationKind::View, Some(query)) => {
+            format!(
+                "CREATE OR REPLACE VIEW {} AS\n{};",
+                name,
+                trim_statement(query)
+            )
+        }
+        (RelationKind::MaterializedView, Some(query)) => format!(
+            "CREATE MATERIALIZED VIEW {} AS\n{}\nWITH DATA;",
+            name,
+            trim_statement(query)
+        ),
+        _ => create_table(database_type, &name, table),
+    });
+
+    sections.push(
+        table
+            .indexes
+            .iter()
+            .map(|index| format!("{};", trim_statement(&index.definition)))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    );
+
+    let mut comments = Vec::new();
+    if let Some(comment) = &table.comment {
+        comments.push(comment_statement(
+            &format!("{} {}", object_type, name),
+            comment,
+        ));
+    }
+    for column in &table.columns {
+        if let Some(comment) = &column.comment {
+            let column_name = format!("{}.{}", name, quote_identifier(database_type, &column.name));
+            comments.push(comment_statement(
+                &format!("COLUMN {}", column_name),
+                comment,
+            ));
+        }
+    }
+    for constraint in &table.constraints {
+        if let Some(comment) = &constraint.comment {
+            let object = format!(
+                "CONSTRAINT {} ON {}",
+                quote_identifier(database_type, &constraint.name),
+                name
+            );
+            comments.push(comment_statement(&object, comment));
+        }
+    }
+    for key in &table.foreign_keys {
+        if let Some(comment) = &key.comment {
+            let object = format!(
+                "CONSTRAINT {} ON {}",
+                quote_identifier(database_type, &key.name),
+                name
+            );
+            comments.push(comment_statement(&object, comment));
+        }
+    }
+    for index in &table.indexes {
+        if let Some(comment) = &index.comment {
+            let index_name = qualified_name(database_type, &table.schema, &index.name);
+            comments.push(comment_statement(&format!("INDEX {}", index_name), comment));
+        }
+    }
+    sections.push(comments.join("\n"));
+
+    if let Some(owner) = &table.owner {
+        sections.push(owner_statement(database_type, object_type, &name, owner));
+    }
+    // GRANT takes TABLE for views and sequences alike
+    sections.push(
+        grant_statements(database_type, &format!("TABLE {}", name), &table.grants).join("\n"),
+    );
+
+    join_sections(sections)
+}
+
+fn create_table(database_type: &DatabaseType, name: &str, table: &TableDefinition) -> String {
+    let mut lines: Vec<String> = table
+        .columns
+        .iter()
+        .map(|column| column_definition(database_type, column))
+        .collect();
+    for constraint in &table.constraints {
+        lines.push(format!(
+            "CONSTRAINT {} {}",
+            quote_identifier(database_type, &constraint.name),
+            constraint.definition
+        ));
+    }
+    for key in &table.foreign_keys {
+        lines.push(format!(
+            "CONSTRAINT {} {}",
+            quote_identifier(database_type, &key.name),
+            foreign_key_definition(database_type, key)
+        ));
+    }
+
+    let unlogged = match table.kind {
+        RelationKind::UnloggedTable => "UNLOGGED ",
+        _ => "",
+    };
+    format!(
+        "CREATE {}TABLE {} (\n    {}\n);",
+        unlogged,
+        name,
+        lines.join(",\n    ")
+    )
+}
+
+/// A column's line in `CREATE TABLE`
+pub fn column_definition(database_type: &DatabaseType, column: &ColumnDefinition) -> String {
+    let mut line = format!(
+        "{} {}",
+        quote_identifier(database_type, &column.name),
+        column.data_type
+    );
+    if let Some(collation) = &column.collation {
+        line.push_str(&format!(" COLLATE {}", collation));
+    }
+    match &column.default {
+        Some(ColumnDefault::Value(expression)) => {
+            line.push_str(&format!(" DEFAULT {}", expression));
+        }
+        Some(ColumnDefault::Identity { always: true }) => {
+            line.push_str(" GENERATED ALWAYS AS IDENTITY");
+        }
+        Some(ColumnDefault::Identity { always: false }) => {
+            line.push_str(" GENERATED BY DEFAULT AS IDENTITY");
+        }
+        Some(ColumnDefault::Generated(expression)) => {
+            line.push_str(&format!(" GENERATED ALWAYS AS ({}) STORED", expression));
+        }
+        None => {}
+    }
+    if column.not_null {
+        line.push_str(" NOT NULL");
+    }
+    line
+}
+
+/// `FOREIGN KEY (...) REFERENCES ...`, leaving out the default actions
+pub fn foreign_key_definition(database_type: &DatabaseType, key: &ForeignKey) -> String {
+    let mut definition = format!(
+        "FOREIGN KEY ({}) REFERENCES {} ({})",
+        column_list(database_type, &key.columns),
+        qualified_name(database_type, &key.referenced_schema, &key.referenced_table),
+        column_list(database_type, &key.referenced_columns)
+    );
+    if key.on_update != ForeignKeyAction::NoAction {
+        definition.push_str(&format!(" ON UPDATE {}", key.on_update.to_sql()));
+    }
+    if key.on_delete != ForeignKeyAction::NoAction {
+        definition.push_str(&format!(" ON DELETE {}", key.on_delete.to_sql()));
+    }
+    definition
+}
+
+/// `CREATE SEQUENCE` with every option spelled out, and the column owning it
+pub fn sequence_ddl(database_type: &DatabaseType, sequence: &Sequence) -> String {
+    let name = qualified_name(database_type, &sequence.schema, &sequence.name);
+    let mut statement = format!("CREATE SEQUENCE {}", name);
+    if !sequence.data_type.is_empty() {
+        statement.push_str(&format!("\n    AS {}", sequence.data_type));
+    }
+    statement.push_str(&format!("\n    INCREMENT BY {}", sequence.increment));
+    statement.push_str(&match sequence.min_value {
+        Some(min) => format!("\n    MINVALUE {}", min),
+        None => "\n    NO MINVALUE".to_string(),
+    });
+    statement.push_str(&match sequence.max_value {
+        Some(max) => format!("\n    MAXVALUE {}", max),
+        None => "\n    NO MAXVALUE".to_string(),
+    });
+    statement.push_str(&format!("\n    START WITH {}", sequence.start_value));
+    statement.push_str(&format!("\n    CACHE {}", sequence.cache_size));
+    statement.push_str(if sequence.cycle {
+        "\n    CYCLE;"
+    } else {
+        "\n    NO CYCLE;"
+    });
+
+    // A sequence can only be owned by a column of a table in its own schema
+    if let (Some(table), Some(column)) = (&sequence.owner_table, &sequence.owner_column) {
+        statement.push_str(&format!(
+            "\n\nALTER SEQUENCE {} OWNED BY {}.{};",
+            name,
+            qualified_name(database_type, &sequence.schema, table),
+            quote_identifier(database_type, column)
+        ));
+    }
+    statement
+}
+
+/// `COMMENT ON object IS '...';`
+pub fn comment_statement(object: &str, comment: &str) -> String {
+    format!("COMMENT ON {} IS {};", object, quote_literal(comment))
+}
+
+/// `ALTER object_type name OWNER TO owner;`
+pub fn owner_statement(
+    database_type: &DatabaseType,
+    object_type: &str,
+    name: &str,
+    owner: &str,
+) -> String {
+    format!(
+        "ALTER {} {} OWNER TO {};",
+        object_type,
+        name,
+        quote_identifier(database_type, owner)
+    )
+}
+
+/// One `GRANT` per grantee, with the privileges they may pass on in a
+/// statement of their own
+pub fn grant_statements(
+    database_type: &DatabaseType,
+    object: &str,
+    grants: &[Grant],
+) -> Vec<String> {
+    let mut groups: Vec<(&Option<String>, bool, Vec<&str>)> = Vec::new();
+    for grant in grants {
+        match groups.iter_mut().find(|(grantee, grantable, _)| {
+            *grantee == &grant.grantee && *grantable == grant.grantable
+        }) {
+            Some((_, _, privileges)) => privileges.push(&grant.privilege),
+            None => groups.push((&grant.grantee, grant.grantable, vec![&grant.privilege])),
+        }
+    }
+
+    groups
+        .into_iter()
+        .map(|(grantee, grantable, privileges)| {
+            let grantee = match grantee {
+                Some(role) => quote_identifier(database_type, role),
+                None => "PUBLIC".to_string(),
+            };
+            format!(
+                "GRANT {} ON {} TO {}{};",
+                privileges.join(", "),
+                object,
+                grantee,
+                if grantable { " WITH GRANT OPTION" } else { "" }
+            )
+        })
+        .collect()
+}
+
+/// Sections separated by blank lines, leaving out empty ones
+pub fn join_sections(sections: Vec<String>) -> String {
+    let mut ddl = sections
+        .into_iter()
+        .filter(|section| !section.is_empty())
+        .collect::<Vec<_>>()
+        .join("\n\n");
+    ddl.push('\n');
+    ddl
+}
+
+fn column_list(database_type: &DatabaseType, columns: &[String]) -> String {
+    columns
+        .iter()
+        .map(|column| quote_identifier(database_type, column))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A definition from the server without its trailing semicolon and spaces
+pub fn trim_statement(definition: &str) -> &str {
+    definition.trim_end().trim_end_matches(';').trim_end()
+}
@@ -0,0 +1,281 @@
+//! Statements that write changes made in the result grid back to the table
+//! the result was read from
+//!
+//! Only PostgreSQL results name the table columns they were read from, so the
+//! statements are written for PostgreSQL alone (see
+//! `DriverCapabilities::supports_result_editing`).
+
+use crate::database::query::quote_literal;
+use crate::database::{
+    is_read_only_query, Column, DatabaseError, DatabaseType, QueryResult, QueryValue,
+};
+
+/// A table a result can be edited in, and how the result's columns map onto it
+#[derive(Debug, Clone)]
+pub struct EditTarget {
+    pub schema: String,
+    pub table: String,
+    /// Table column shown by each result column; `None` for computed columns
+    pub columns: Vec<Option<TargetColumn>>,
+    /// Result columns holding the table's primary key
+    pub key_columns: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TargetColumn {
+    pub name: String,
+    /// Type parameters are cast to
+    pub cast_type: Option<String>,
+}
+
+/// One row's worth of changes
+#[derive(Debug, Clone)]
+pub enum RowChange {
+    /// New text for some columns of an existing row; `None` writes NULL
+    Update {
+        key: Vec<QueryValue>,
+        values: Vec<(usize, Option<String>)>,
+    },
+    /// A new row; columns left out take their default
+    Insert {
+        values: Vec<(usize, Option<String>)>,
+    },
+    Delete {
+        key: Vec<QueryValue>,
+    },
+}
+
+/// A statement with its parameters, which are passed as text for the server
+/// to convert to the column types
+#[derive(Debug, Clone, PartialEq)]
+pub struct DmlStatement {
+    pub sql: String,
+    pub params: Vec<Option<String>>,
+    /// The statement with the parameters written out as literals
+    pub preview: String,
+}
+
+impl DmlStatement {
+    /// Every generated statement addresses exactly one row by its key; any
+    /// other count means the row changed or vanished since it was read
+    pub fn check_rows_affected(&self, rows_affected: u64) -> Result<(), DatabaseError> {
+        if rows_affected == 1 {
+            return Ok(());
+        }
+        Err(DatabaseError::QueryFailed(format!(
+            "Expected to change one row but {} rows matched: {}",
+            rows_affected, self.preview
+        )))
+    }
+}
+
+impl EditTarget {
+    /// Schema and name of the one table all of a result's table columns
+    /// were read from
+    pub fn source_table(result: &QueryResult) -> Option<(String, String)> {
+        let mut tables = result
+            .columns
+            .iter()
+            .filter(|column| column.table_oid.is_some());
+        let first = tables.next()?;
+        if tables.any(|column| column.table_oid != first.table_oid) {
+            return None;
+        }
+        Some((first.source_schema.clone()?, first.source_table.clone()?))
+    }
+
+    /// The result's source table, if the result includes its whole primary key
+    ///
+    /// Only query results qualify: saved changes are shown by running the
+    /// result's statement again, which must not repeat the writes of e.g. an
+    /// `INSERT ... RETURNING *`.
+    pub fn new(result: &QueryResult, table_columns: &[Column]) -> Option<Self> {
+        if !is_read_only_query(&result.query) {
+            return None;
+        }
+        let (schema, table) = Self::source_table(result)?;
+
+        let columns: Vec<Option<TargetColumn>> = result
+            .columns
+            .iter()
+            .map(|column| {
+                column.table_oid?;
+                Some(TargetColumn {
+                    name: column.source_column.clone()?,
+                    cast_type: column.unmodified_type.clone(),
+                })
+            })
+            .collect();
+
+        let key_columns = table_columns
+            .iter()
+            .filter(|column| column.is_primary_key)
+            .map(|key| {
+                columns
+                    .iter()
+                    .position(|column| column.as_ref().is_some_and(|c| c.name == key.name))
+            })
+            .collect::<Option<Vec<usize>>>()?;
+        if key_columns.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            schema,
+            table,
+            columns,
+            key_columns,
+        })
+    }
+
+    pub fn is_editable(&self, column: usize) -> bool {
+        matches!(self.columns.get(column), Some(Some(_)))
+    }
+
+    /// Key values of a result row, which identify it in the table
+    pub fn row_key(&self, result: &QueryResult, row: usize) -> Option<Vec<QueryValue>> {
+        let row = result.rows.get(row)?;
+        self.key_columns
+            .iter()
+            .map(|column| row.get(*column).cloned())
+            .collect()
+    }
+
+    pub fn statement(&self, change: &RowChange) -> DmlStatement {
+        let mut builder = StatementBuilder::new();
+        let table = format!(
+            "{}.{}",
+            builder.identifier(&self.schema),
+            builder.identifier(&self.table)
+        );
+
+        match change {
+            RowChange::Update { key, values } => {
+                builder.push(&format!("UPDATE {} SET ", table));
+                for (i, (column, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(", ");
+                    }
+                    self.push_column(&mut builder, *column);
+                    builder.push(" = ");
+                    self.push_param(&mut builder, *column, value.clone());
+                }
+                self.push_key_condition(&mut builder, key);
+            }
+            RowChange::Insert { values } if values.is_empty() => {
+                builder.push(&format!("INSERT INTO {} DEFAULT VALUES", table));
+            }
+            RowChange::Insert { values } => {
+                builder.push(&format!("INSERT INTO {} (", table));
+                for (i, (column, _)) in values.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(", ");
+                    }
+                    self.push_column(&mut builder, *column);
+                }
+                builder.push(") VALUES (");
+                for (i, (column, value)) in values.iter().enumerate() {
+                    if i > 0 {
+                        builder.push(", ");
+                    }
+                    self.push_param(&mut builder, *column, value.clone());
+                }
+                builder.push(")");
+            }
+            RowChange::Delete { key } => {
+                builder.push(&format!("DELETE FROM {}", table));
+                self.push_key_condition(&mut builder, key);
+            }
+        }
+        builder.finish()
+    }
+
+    fn target_column(&self, column: usize) -> &TargetColumn {
+        self.columns[column]
+            .as_ref()
+            .expect("changes are only made to table columns")
+    }
+
+    fn push_column(&self, builder: &mut StatementBuilder, column: usize) {
+        let name = builder.identifier(&self.target_column(column).name);
+        builder.push(&name);
+    }
+
+    fn push_param(&self, builder: &mut StatementBuilder, column: usize, value: Option<String>) {
+        let cast_type = self.target_column(column).cast_type.clone();
+        builder.param(value, cast_type.as_deref());
+    }
+
+    fn push_key_condition(&self, builder: &mut StatementBuilder, key: &[QueryValue]) {
+        builder.push(" WHERE ");
+        for (i, (column, value)) in self.key_columns.iter().zip(key).enumerate() {
+            if i > 0 {
+                builder.push(" AND ");
+            }
+            self.push_column(builder, *column);
+            builder.push(" = ");
+            self.push_param(builder, *column, value.to_sql_text());
+        }
+    }
+}
+
+/// Quote a table or column name for the server's SQL dialect
+pub(crate) fn quote_identifier(database_type: &DatabaseType, name: &str) -> String {
+    match database_type {
+        DatabaseType::MySQL => format!("`{}`", name.replace('`', "``")),
+        _ => format!("\"{}\"", name.replace('"', "\"\"")),
+    }
+}
+
+/// Writes a statement and its literal preview side by side
+struct StatementBuilder {
+    sql: String,
+    preview: String,
+    params: Vec<Option<String>>,
+}
+
+impl StatementBuilder {
+    fn new() -> Self {
+        Self {
+            sql: String::new(),
+            preview: String::new(),
+            params: Vec::new(),
+        }
+    }
+
+    fn identifier(&self, name: &str) -> String {
+        quote_identifier(&DatabaseType::PostgreSQL, name)
+    }
+
+    fn push(&mut self, text: &str) {
+        self.sql.push_str(text);
+        self.preview.push_str(text);
+    }
+
+    /// Add a parameter, cast as the server would otherwise take the text for
+    /// a `text` value
+    fn param(&mut self, value: Option<String>, cast_type: Option<&str>) {
+        let literal = value.as_deref().map_or("NULL".to_string(), quote_literal);
+        self.params.push(value);
+        match cast_type {
+            Some(cast_type) => {
+                self.sql
+                    .push_str(&format!("${}::{}", self.params.len(), cast_type));
+                self.preview
+                    .push_str(&format!("{}::{}", literal, cast_type));
+            }
+            None => {
+                self.sql.push_str(&format!("${}", self.params.len()));
+                self.preview.push_str(&literal);
+            }
+        }
+    }
+
+    fn finish(self) -> DmlStatement {
+        DmlStatement {
+            sql: self.sql,
+            params: self.params,
+            preview: self.preview,
+        }
+    }
+}
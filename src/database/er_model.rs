@@ -0,0 +1,342 @@
+//! Entity-relationship model of a schema, and its text diagram formats
+//!
+//! The model holds what an ER diagram shows: the tables with their columns
+//! and key markers, and a relationship per foreign key. It is read through
+//! the metadata methods of `QueryExecutor`, so every driver can draw one.
+
+use crate::database::{Column, DatabaseError, ForeignKey, QueryExecutor};
+use std::fmt::Write;
+
+/// Tables of a schema and the foreign keys between them
+#[derive(Debug, Clone, Default)]
+pub struct ErModel {
+    pub schema: String,
+    pub tables: Vec<ErTable>,
+    /// Foreign keys between tables of the model
+    pub relationships: Vec<Relationship>,
+}
+
+/// A table box of the diagram
+#[derive(Debug, Clone)]
+pub struct ErTable {
+    pub name: String,
+    pub columns: Vec<ErColumn>,
+}
+
+/// A column row of a table box
+#[derive(Debug, Clone)]
+pub struct ErColumn {
+    pub name: String,
+    pub data_type: String,
+    pub is_nullable: bool,
+    pub is_primary_key: bool,
+    /// Whether the column is part of a foreign key
+    pub is_foreign_key: bool,
+}
+
+/// A foreign key, drawn as an edge from the referencing table (the "many"
+/// side) to the referenced one
+#[derive(Debug, Clone)]
+pub struct Relationship {
+    pub name: String,
+    pub from_table: String,
+    pub from_columns: Vec<String>,
+    pub to_table: String,
+    pub to_columns: Vec<String>,
+    /// Whether a referencing row may have no referenced row, i.e. a
+    /// referencing column is nullable
+    pub optional: bool,
+}
+
+impl ErModel {
+    /// Read the tables and foreign keys of a schema
+    pub async fn load<E: QueryExecutor + ?Sized>(
+        executor: &E,
+        schema: &str,
+    ) -> Result<Self, DatabaseError> {
+        let mut tables = Vec::new();
+        for table in executor.get_tables(schema).await? {
+            if table.table_type != "BASE TABLE" {
+                continue;
+            }
+            let columns = executor.get_columns(schema, &table.name).await?;
+            let foreign_keys = executor.get_foreign_keys(schema, &table.name).await?;
+            tables.push((table.name, columns, foreign_keys));
+        }
+        Ok(Self::from_parts(schema, tables))
+    }
+
+    /// Build the model from each table's columns and foreign keys
+    ///
+    /// Keys may be listed on both of their tables, and keys referencing
+    /// tables outside the model are left out.
+    pub fn from_parts(schema: &str, tables: Vec<(String, Vec<Column>, Vec<ForeignKey>)>) -> Self {
+        let names: Vec<String> = tables.iter().map(|(name, _, _)| name.clone()).collect();
+        let mut model = ErModel {
+            schema: schema.to_string(),
+            ..Default::default()
+        };
+
+        for (name, columns, foreign_keys) in tables {
+            let outgoing: Vec<ForeignKey> = foreign_keys
+                .into_iter()
+                .filter(|key| key.is_outgoing(schema, &name))
+                .collect();
+            let in_key = |column: &str| {
+                outgoing
+                    .iter()
+                    .any(|key| key.columns.iter().any(|c| c == column))
+            };
+            let columns: Vec<ErColumn> = columns
+                .into_iter()
+                .map(|column| ErColumn {
+                    is_foreign_key: in_key(&column.name),
+                    name: column.name,
+                    data_type: column.data_type,
+                    is_nullable: column.is_nullable,
+                    is_primary_key: column.is_primary_key,
+                })
+                .collect();
+
+            for key in outgoing {
+                if key.referenced_schema != schema || !names.contains(&key.referenced_table) {
+                    continue;
+                }
+                let optional = key.columns.iter().any(|name| {
+                    columns
+                        .iter()
+                        .any(|column| &column.name == name && column.is_nullable)
+                });
+                model.relationships.push(Relationship {
+                    name: key.name,
+                    from_table: name.clone(),
+                    from_columns: key.columns,
+                    to_table: key.referenced_table,
+                    to_columns: key.referenced_columns,
+                    optional,
+                });
+            }
+            model.tables.push(ErTable { name, columns });
+        }
+        model
+    }
+
+    /// Position of a table in `tables`
+    pub fn table_index(&self, name: &str) -> Option<usize> {
+        self.tables.iter().position(|table| table.name == name)
+    }
+
+    /// The diagram as a Mermaid `erDiagram`
+    pub fn to_mermaid(&self) -> String {
+        let mut out = String::from("erDiagram\n");
+        for table in &self.tables {
+            let _ = writeln!(out, "    {} {{", mermaid_name(&table.name));
+            for column in &table.columns {
+                let keys: Vec<&str> =
+                    [(column.is_primary_key, "PK"), (column.is_foreign_key, "FK")]
+                        .into_iter()
+                        .filter_map(|(is_key, key)| is_key.then_some(key))
+                        .collect();
+                let _ = write!(
+                    out,
+                    "        {} {}",
+                    mermaid_token(&column.data_type),
+                    mermaid_token(&column.name)
+                );
+                if !keys.is_empty() {
+                    let _ = write!(out, " {}", keys.join(", "));
+                }
+                out.push('\n');
+            }
+            out.push_str("    }\n");
+        }
+        for relationship in &self.relationships {
+            let _ = writeln!(
+                out,
+                "    {} {}--o{{ {} : \"{}\"",
+                mermaid_name(&relationship.to_table),
+                if relationship.optional { "|o" } else { "||" },
+                mermaid_name(&relationship.from_table),
+                relationship.name.replace('"', "'")
+            );
+        }
+        out
+    }
+
+    /// The diagram as a PlantUML entity diagram
+    pub fn to_plantuml(&self) -> String {
+        let mut out = String::from("@startuml\nhide circle\nskinparam linetype ortho\n\n");
+        for table in &self.tables {
+            let _ = writeln!(
+                out,
+                "entity \"{}\" as {} {{",
+                table.name.replace('"', "'"),
+                alias(&table.name)
+            );
+            // Key columns go above the separator
+            let (keys, others): (Vec<&ErColumn>, Vec<&ErColumn>) = table
+                .columns
+                .iter()
+                .partition(|column| column.is_primary_key);
+            for column in &keys {
+                let _ = writeln!(out, "  {}", plantuml_column(column));
+            }
+            if !keys.is_empty() {
+                out.push_str("  --\n");
+            }
+            for column in &others {
+                let _ = writeln!(out, "  {}", plantuml_column(column));
+            }
+            out.push_str("}\n\n");
+        }
+        for relationship in &self.relationships {
+            let _ = writeln!(
+                out,
+                "{} }}o--{} {} : {}",
+                alias(&relationship.from_table),
+                if relationship.optional { "o|" } else { "||" },
+                alias(&relationship.to_table),
+                relationship.name
+            );
+        }
+        out.push_str("@enduml\n");
+        out
+    }
+
+    /// The diagram as a Graphviz digraph, with an HTML table label per table
+    /// and crow's foot edges between the key columns
+    pub fn to_dot(&self) -> String {
+        let mut out = String::new();
+        let _ = writeln!(out, "digraph {} {{", dot_id(&self.schema));
+        out.push_str("    graph [rankdir=LR];\n");
+        out.push_str("    node [shape=plaintext, fontname=\"Helvetica\"];\n");
+        out.push_str("    edge [dir=both, arrowtail=crow, arrowhead=tee];\n\n");
+        for table in &self.tables {
+            let _ = write!(
+                out,
+                "    {} [label=<<table border=\"0\" cellborder=\"1\" cellspacing=\"0\">\
+                 <tr><td bgcolor=\"lightblue\"><b>{}</b></td></tr>",
+                dot_id(&table.name),
+                html_escape(&table.name)
+            );
+            for column in &table.columns {
+                let _ = write!(
+                    out,
+                    "<tr><td align=\"left\" port={}>{}{} : {}</td></tr>",
+                    dot_id(&column.name),
+                    key_markers(column),
+                    html_escape(&column.name),
+                    html_escape(&column.data_type)
+                );
+            }
+            out.push_str("</table>>];\n");
+        }
+        if !self.relationships.is_empty() {
+            out.push('\n');
+        }
+        for relationship in &self.relationships {
+            let port = |columns: &[String]| {
+                columns
+                    .first()
+                    .map(|column| format!(":{}", dot_id(column)))
+                    .unwrap_or_default()
+            };
+            let _ = writeln!(
+                out,
+                "    {}{} -> {}{} [label={}{}];",
+                dot_id(&relationship.from_table),
+                port(&relationship.from_columns),
+                dot_id(&relationship.to_table),
+                port(&relationship.to_columns),
+                dot_id(&relationship.name),
+                if relationship.optional {
+                    ", arrowhead=teeodot"
+                } else {
+                    ""
+                }
+            );
+        }
+        out.push_str("}\n");
+        out
+    }
+}
+
+/// "PK", "FK" or both in front of a column name, for plain-text labels
+pub fn key_markers(column: &ErColumn) -> String {
+    let mut markers = String::new();
+    if column.is_primary_key {
+        markers.push_str("PK ");
+    }
+    if column.is_foreign_key {
+        markers.push_str("FK ");
+    }
+    markers
+}
+
+/// Entity names are bare words in Mermaid unless quoted
+fn mermaid_name(name: &str) -> String {
+    if is_plain_identifier(name) {
+        name.to_string()
+    } else {
+        format!("\"{}\"", name.replace('"', "'"))
+    }
+}
+
+/// Attribute types and names cannot be quoted, so anything but word
+/// characters and brackets becomes an underscore
+fn mermaid_token(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_alphanumeric() || "_-()[]".contains(c) {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn plantuml_column(column: &ErColumn) -> String {
+    let mut line = format!(
+        "{}{} : {}",
+        if column.is_nullable { "" } else { "* " },
+        column.name,
+        column.data_type
+    );
+    if column.is_primary_key {
+        line.push_str(" <<PK>>");
+    }
+    if column.is_foreign_key {
+        line.push_str(" <<FK>>");
+    }
+    line
+}
+
+/// PlantUML alias of an entity, which must be a single word
+fn alias(name: &str) -> String {
+    let alias: String = name
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    if alias.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", alias)
+    } else {
+        alias
+    }
+}
+
+fn dot_id(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn is_plain_identifier(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_alphanumeric() || c == '_')
+}
@@ -0,0 +1,690 @@
+//! Writing a result's rows out as CSV, TSV, JSON, NDJSON, Markdown, HTML or
+//! SQL INSERT statements
+//!
+//! Each format has a [`ResultFormatter`]; [`export`] runs the one chosen in
+//! the options and encodes its text for the file. The grid copies its
+//! selected cells with the same formatters, see [`CopyFormat`].
+
+use crate::database::dml::quote_identifier;
+use crate::database::query::quote_literal;
+use crate::database::{DatabaseType, QueryColumn, QueryResult, QueryValue};
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use std::collections::HashSet;
+
+/// File formats a result can be exported to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Tsv,
+    Json,
+    /// One JSON object per line
+    Ndjson,
+    Markdown,
+    Html,
+    SqlInsert,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 7] = [
+        ExportFormat::Csv,
+        ExportFormat::Tsv,
+        ExportFormat::Json,
+        ExportFormat::Ndjson,
+        ExportFormat::Markdown,
+        ExportFormat::Html,
+        ExportFormat::SqlInsert,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "CSV",
+            ExportFormat::Tsv => "TSV",
+            ExportFormat::Json => "JSON",
+            ExportFormat::Ndjson => "NDJSON",
+            ExportFormat::Markdown => "Markdown",
+            ExportFormat::Html => "HTML",
+            ExportFormat::SqlInsert => "SQL INSERT",
+        }
+    }
+
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Tsv => "tsv",
+            ExportFormat::Json => "json",
+            ExportFormat::Ndjson => "ndjson",
+            ExportFormat::Markdown => "md",
+            ExportFormat::Html => "html",
+            ExportFormat::SqlInsert => "sql",
+        }
+    }
+
+    pub fn formatter(&self) -> Box<dyn ResultFormatter> {
+        match self {
+            ExportFormat::Csv => Box::new(DelimitedFormatter { tabs: false }),
+            ExportFormat::Tsv => Box::new(DelimitedFormatter { tabs: true }),
+            ExportFormat::Json => Box::new(JsonFormatter { lines: false }),
+            ExportFormat::Ndjson => Box::new(JsonFormatter { lines: true }),
+            ExportFormat::Markdown => Box::new(MarkdownFormatter),
+            ExportFormat::Html => Box::new(HtmlFormatter),
+            ExportFormat::SqlInsert => Box::new(InsertFormatter),
+        }
+    }
+
+    /// Whether the format writes NULLs as the configurable NULL text
+    pub fn uses_null_text(&self) -> bool {
+        !matches!(
+            self,
+            ExportFormat::Json | ExportFormat::Ndjson | ExportFormat::SqlInsert
+        )
+    }
+}
+
+/// Formats the grid's selected cells can be copied to the clipboard as
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyFormat {
+    /// Tab-separated, which spreadsheet programs paste into cells
+    Tsv,
+    Csv,
+    /// A JSON array of row objects
+    Json,
+    Markdown,
+    /// The values as an SQL `IN (...)` list
+    InList,
+    Insert,
+}
+
+impl CopyFormat {
+    pub const ALL: [CopyFormat; 6] = [
+        CopyFormat::Tsv,
+        CopyFormat::Csv,
+        CopyFormat::Json,
+        CopyFormat::Markdown,
+        CopyFormat::InList,
+        CopyFormat::Insert,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            CopyFormat::Tsv => "TSV",
+            CopyFormat::Csv => "CSV",
+            CopyFormat::Json => "JSON",
+            CopyFormat::Markdown => "Markdown",
+            CopyFormat::InList => "SQL IN List",
+            CopyFormat::Insert => "SQL INSERT",
+        }
+    }
+
+    pub fn formatter(&self) -> Box<dyn ResultFormatter> {
+        match self {
+            CopyFormat::Tsv => ExportFormat::Tsv.formatter(),
+            CopyFormat::Csv => ExportFormat::Csv.formatter(),
+            CopyFormat::Json => ExportFormat::Json.formatter(),
+            CopyFormat::Markdown => ExportFormat::Markdown.formatter(),
+            CopyFormat::InList => Box::new(InListFormatter),
+            CopyFormat::Insert => ExportFormat::SqlInsert.formatter(),
+        }
+    }
+}
+
+/// When CSV and TSV values are put in double quotes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Every value except NULL
+    Always,
+    /// Values containing the delimiter, a quote or a line break, and text
+    /// that would otherwise read as NULL
+    AsNeeded,
+    Never,
+}
+
+impl QuoteStyle {
+    pub const ALL: [QuoteStyle; 3] = [QuoteStyle::Always, QuoteStyle::AsNeeded, QuoteStyle::Never];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            QuoteStyle::Always => "Always",
+            QuoteStyle::AsNeeded => "As needed",
+            QuoteStyle::Never => "Never",
+        }
+    }
+}
+
+/// Character encodings an export can be written in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 3] = [
+        TextEncoding::Utf8,
+        TextEncoding::Utf16Le,
+        TextEncoding::Utf16Be,
+    ];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+        }
+    }
+
+    /// The text as bytes, after a byte order mark if `bom` is set
+    pub fn encode(&self, text: &str, bom: bool) -> Vec<u8> {
+        match self {
+            TextEncoding::Utf8 => {
+                let mut bytes = Vec::with_capacity(text.len() + 3);
+                if bom {
+                    bytes.extend_from_slice(&[0xEF, 0xBB, 0xBF]);
+                }
+                bytes.extend_from_slice(text.as_bytes());
+                bytes
+            }
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+                let little_endian = *self == TextEncoding::Utf16Le;
+                let units = bom.then_some(0xFEFF).into_iter().chain(text.encode_utf16());
+                let mut bytes = Vec::with_capacity(text.len() * 2 + 2);
+                for unit in units {
+                    if little_endian {
+                        bytes.extend_from_slice(&unit.to_le_bytes());
+                    } else {
+                        bytes.extend_from_slice(&unit.to_be_bytes());
+                    }
+                }
+                bytes
+            }
+        }
+    }
+}
+
+/// How a result is exported
+#[derive(Debug, Clone)]
+pub struct ExportOptions {
+    pub format: ExportFormat,
+    /// Separator between CSV values; TSV always uses a tab
+    pub delimiter: char,
+    pub quoting: QuoteStyle,
+    /// Text written for NULL in the text formats
+    pub null_text: String,
+    /// Write the column names first in CSV and TSV
+    pub include_header: bool,
+    pub encoding: TextEncoding,
+    /// Start the file with a byte order mark, which spreadsheet programs use
+    /// to recognize UTF-8
+    pub bom: bool,
+    /// Table the INSERT statements write to, optionally schema-qualified
+    pub table_name: String,
+    /// Dialect of the INSERT statements
+    pub database_type: DatabaseType,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: ExportFormat::Csv,
+            delimiter: ',',
+            quoting: QuoteStyle::AsNeeded,
+            null_text: String::new(),
+            include_header: true,
+            encoding: TextEncoding::Utf8,
+            bom: false,
+            table_name: "exported_rows".to_string(),
+            database_type: DatabaseType::PostgreSQL,
+        }
+    }
+}
+
+/// The columns and rows of a result to export, by position
+pub struct ExportSource<'a> {
+    pub result: &'a QueryResult,
+    pub columns: Vec<usize>,
+    pub rows: Vec<usize>,
+}
+
+impl<'a> ExportSource<'a> {
+    pub fn new(result: &'a QueryResult, columns: Vec<usize>, rows: Vec<usize>) -> Self {
+        Self {
+            result,
+            columns,
+            rows,
+        }
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = &'a QueryColumn> + '_ {
+        self.columns
+            .iter()
+            .filter_map(|column| self.result.columns.get(*column))
+    }
+
+    /// Values of each exported row, with NULL for cells the row lacks
+    pub fn rows(&self) -> impl Iterator<Item = Vec<&'a QueryValue>> + '_ {
+        self.rows.iter().filter_map(|row| {
+            let row = self.result.rows.get(*row)?;
+            Some(
+                self.columns
+                    .iter()
+                    .map(|column| row.get(*column).unwrap_or(&QueryValue::Null))
+                    .collect(),
+            )
+        })
+    }
+}
+
+/// Writes rows as the text of one export format
+pub trait ResultFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String;
+}
+
+/// The rows as the chosen format, encoded for writing to a file
+pub fn export(source: &ExportSource, options: &ExportOptions) -> Vec<u8> {
+    let text = options.format.formatter().format(source, options);
+    options.encoding.encode(&text, options.bom)
+}
+
+/// CSV, or TSV with tabs in place of the delimiter
+struct DelimitedFormatter {
+    tabs: bool,
+}
+
+impl DelimitedFormatter {
+    fn field(&self, text: &str, delimiter: char, options: &ExportOptions) -> String {
+        let quote = match options.quoting {
+            QuoteStyle::Always => true,
+            QuoteStyle::Never => false,
+            QuoteStyle::AsNeeded => {
+                text.contains([delimiter, '"', '\n', '\r'])
+                    || (!options.null_text.is_empty() && text == options.null_text)
+                    || (options.null_text.is_empty() && text.is_empty())
+            }
+        };
+        if quote {
+            format!("\"{}\"", text.replace('"', "\"\""))
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+impl ResultFormatter for DelimitedFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String {
+        let delimiter = if self.tabs { '\t' } else { options.delimiter };
+        let separator = delimiter.to_string();
+        let mut text = String::new();
+
+        if options.include_header {
+            let names: Vec<String> = source
+                .columns()
+                .map(|column| self.field(&column.name, delimiter, options))
+                .collect();
+            text.push_str(&names.join(&separator));
+            text.push_str("\r\n");
+        }
+        for row in source.rows() {
+            let fields: Vec<String> = row
+                .iter()
+                .map(|value| match value.to_sql_text() {
+                    Some(value) => self.field(&value, delimiter, options),
+                    None => options.null_text.clone(),
+                })
+                .collect();
+            text.push_str(&fields.join(&separator));
+            text.push_str("\r\n");
+        }
+        text
+    }
+}
+
+/// A JSON array of row objects, or with `lines` one object per line
+struct JsonFormatter {
+    lines: bool,
+}
+
+impl ResultFormatter for JsonFormatter {
+    fn format(&self, source: &ExportSource, _options: &ExportOptions) -> String {
+        // Objects are written by hand to keep the columns in result order
+        let names: Vec<String> = source
+            .columns()
+            .map(|column| Value::String(column.name.clone()).to_string())
+            .collect();
+        let objects: Vec<String> = source
+            .rows()
+            .map(|row| {
+                let fields: Vec<String> = names
+                    .iter()
+                    .zip(row)
+                    .map(|(name, value)| format!("{}:{}", name, json_value(value)))
+                    .collect();
+                format!("{{{}}}", fields.join(","))
+            })
+            .collect();
+
+        if self.lines {
+            objects
+                .iter()
+                .map(|object| format!("{}\n", object))
+                .collect()
+        } else if objects.is_empty() {
+            "[]\n".to_string()
+        } else {
+            format!("[\n  {}\n]\n", objects.join(",\n  "))
+        }
+    }
+}
+
+/// A value as JSON of the matching type; numbers too large for a double and
+/// values JSON has no type for are written as strings
+pub fn json_value(value: &QueryValue) -> Value {
+    match value {
+        QueryValue::Null => Value::Null,
+        QueryValue::Bool(b) => Value::Bool(*b),
+        QueryValue::Int32(i) => Value::from(*i),
+        QueryValue::Int64(i) => Value::from(*i),
+        QueryValue::Float32(f) => serde_json::Number::from_f64(*f as f64)
+            .map_or_else(|| Value::String(value.to_display_string()), Value::Number),
+        QueryValue::Float64(f) => serde_json::Number::from_f64(*f)
+            .map_or_else(|| Value::String(value.to_display_string()), Value::Number),
+        QueryValue::Decimal(d) => {
+            let text = d.to_plain_string();
+            // Only numbers a double holds exactly stay numbers
+            match text
+                .parse::<f64>()
+                .ok()
+                .and_then(serde_json::Number::from_f64)
+            {
+                Some(number)
+                    if number.to_string().parse::<BigDecimal>().ok().as_ref() == Some(d) =>
+                {
+                    Value::Number(number)
+                }
+                _ => Value::String(text),
+            }
+        }
+        QueryValue::Json(json) => json.clone(),
+        QueryValue::Geometry(geometry) => geometry
+            .to_geojson()
+            .unwrap_or_else(|| Value::String(value.to_sql_text().unwrap_or_default())),
+        QueryValue::Array(elements) => Value::Array(elements.iter().map(json_value).collect()),
+        QueryValue::Composite(fields) => Value::Object(
+            fields
+                .iter()
+                .map(|(name, value)| (name.clone(), json_value(value)))
+                .collect(),
+        ),
+        _ => Value::String(value.to_sql_text().unwrap_or_default()),
+    }
+}
+
+/// A GitHub-flavored Markdown table
+struct MarkdownFormatter;
+
+impl ResultFormatter for MarkdownFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String {
+        let cell = |text: &str| {
+            text.replace('\\', "\\\\")
+                .replace('|', "\\|")
+                .replace("\r\n", "<br>")
+                .replace(['\n', '\r'], "<br>")
+        };
+        let line = |cells: Vec<String>| format!("| {} |\n", cells.join(" | "));
+
+        // A Markdown table always has a header row
+        let names: Vec<String> = source.columns().map(|column| cell(&column.name)).collect();
+        let mut text = line(names.clone());
+        text.push_str(&line(names.iter().map(|_| "---".to_string()).collect()));
+        for row in source.rows() {
+            text.push_str(&line(
+                row.iter()
+                    .map(|value| match value.to_sql_text() {
+                        Some(value) => cell(&value),
+                        None => cell(&options.null_text),
+                    })
+                    .collect(),
+            ));
+        }
+        text
+    }
+}
+
+/// A standalone HTML document holding the rows in a table
+struct HtmlFormatter;
+
+impl ResultFormatter for HtmlFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String {
+        let charset = match options.encoding {
+            TextEncoding::Utf8 => "utf-8",
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => "utf-16",
+        };
+        let mut text = format!(
+            "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"{}\">\n<title>{}</title>\n\
+             <style>table {{ border-collapse: collapse; }} th, td {{ border: 1px solid #ccc; \
+             padding: 2px 6px; }} td.null {{ color: #999; }}</style>\n</head>\n<body>\n<table>\n",
+            charset,
+            escape_html(source.result.query.trim())
+        );
+
+        if options.include_header {
+            text.push_str("<thead>\n<tr>");
+            for column in source.columns() {
+                text.push_str(&format!("<th>{}</th>", escape_html(&column.name)));
+            }
+            text.push_str("</tr>\n</thead>\n");
+        }
+        text.push_str("<tbody>\n");
+        for row in source.rows() {
+            text.push_str("<tr>");
+            for value in row {
+                match value.to_sql_text() {
+                    Some(value) => text.push_str(&format!("<td>{}</td>", escape_html(&value))),
+                    None => text.push_str(&format!(
+                        "<td class=\"null\">{}</td>",
+                        escape_html(&options.null_text)
+                    )),
+                }
+            }
+            text.push_str("</tr>\n");
+        }
+        text.push_str("</tbody>\n</table>\n</body>\n</html>\n");
+        text
+    }
+}
+
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// One INSERT statement per row
+struct InsertFormatter;
+
+impl ResultFormatter for InsertFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String {
+        let database_type = &options.database_type;
+        let columns: Vec<&QueryColumn> = source.columns().collect();
+        let names: Vec<String> = columns
+            .iter()
+            .map(|column| quote_identifier(database_type, &column.name))
+            .collect();
+        let prefix = format!(
+            "INSERT INTO {} ({}) VALUES (",
+            qualified_name(database_type, &options.table_name),
+            names.join(", ")
+        );
+
+        let mut text = String::new();
+        for row in source.rows() {
+            let values: Vec<String> = row
+                .iter()
+                .zip(&columns)
+                .map(|(value, column)| sql_literal(value, Some(column), database_type))
+                .collect();
+            text.push_str(&prefix);
+            text.push_str(&values.join(", "));
+            text.push_str(");\n");
+        }
+        text
+    }
+}
+
+/// The rows as the list of an `IN (...)` condition: single values for one
+/// column, row values for several; repeated entries are left out
+struct InListFormatter;
+
+impl ResultFormatter for InListFormatter {
+    fn format(&self, source: &ExportSource, options: &ExportOptions) -> String {
+        let columns: Vec<&QueryColumn> = source.columns().collect();
+        let mut seen = HashSet::new();
+        let mut entries = Vec::new();
+        for row in source.rows() {
+            let literals: Vec<String> = row
+                .iter()
+                .zip(&columns)
+                .map(|(value, column)| sql_literal(value, Some(column), &options.database_type))
+                .collect();
+            let entry = match literals.as_slice() {
+                [literal] => literal.clone(),
+                _ => format!("({})", literals.join(", ")),
+            };
+            if seen.insert(entry.clone()) {
+                entries.push(entry);
+            }
+        }
+        format!("IN ({})", entries.join(", "))
+    }
+}
+
+/// Quote each dot-separated part of a table name
+fn qualified_name(database_type: &DatabaseType, name: &str) -> String {
+    name.split('.')
+        .map(|part| quote_identifier(database_type, part.trim()))
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// A value as a literal of its own type in the server's dialect
+///
+/// On PostgreSQL, values whose type the variant does not tell apart, such as
+/// `json` and `jsonb` or an enum, are cast to the column's server type when
+/// it is known.
+pub fn sql_literal(
+    value: &QueryValue,
+    column: Option<&QueryColumn>,
+    database_type: &DatabaseType,
+) -> String {
+    let Some(text) = value.to_sql_text() else {
+        return "NULL".to_string();
+    };
+    match database_type {
+        DatabaseType::PostgreSQL => postgresql_literal(value, column, text),
+        DatabaseType::MySQL => mysql_literal(value, text),
+        DatabaseType::SQLite => sqlite_literal(value, text),
+    }
+}
+
+fn postgresql_literal(value: &QueryValue, column: Option<&QueryColumn>, text: String) -> String {
+    let cast = |type_name: &str| format!("{}::{}", quote_literal(&text), type_name);
+    let column_cast = |fallback: Option<&str>| match column
+        .and_then(|column| column.type_name.as_deref())
+        .or(fallback)
+    {
+        Some(type_name) => cast(type_name),
+        None => quote_literal(&text),
+    };
+    match value {
+        QueryValue::Float32(f) if !f.is_finite() => cast("real"),
+        QueryValue::Float64(f) if !f.is_finite() => cast("double precision"),
+        QueryValue::Bool(_)
+        | QueryValue::Int32(_)
+        | QueryValue::Int64(_)
+        | QueryValue::Float32(_)
+        | QueryValue::Float64(_)
+        | QueryValue::Decimal(_) => value.to_sql_literal(),
+        QueryValue::Bytes(_) => cast("bytea"),
+        QueryValue::Date(_) => format!("DATE {}", quote_literal(&text)),
+        QueryValue::Time(_, None) => format!("TIME {}", quote_literal(&text)),
+        QueryValue::Time(_, Some(_)) => format!("TIME WITH TIME ZONE {}", quote_literal(&text)),
+        QueryValue::NaiveDateTime(_) => format!("TIMESTAMP {}", quote_literal(&text)),
+        QueryValue::DateTime(_) => format!("TIMESTAMP WITH TIME ZONE {}", quote_literal(&text)),
+        QueryValue::Interval(_) => format!("INTERVAL {}", quote_literal(&text)),
+        QueryValue::Uuid(_) => cast("uuid"),
+        QueryValue::Network(network) => cast(if network.is_cidr { "cidr" } else { "inet" }),
+        QueryValue::MacAddress(bytes) => cast(if bytes.len() == 8 {
+            "macaddr8"
+        } else {
+            "macaddr"
+        }),
+        QueryValue::Money(_) => cast("money"),
+        QueryValue::Bits(bits) => format!("B'{}'", bits),
+        QueryValue::Json(_) => column_cast(Some("jsonb")),
+        QueryValue::Geometry(geometry) => column_cast(Some(if geometry.geography {
+            "geography"
+        } else {
+            "geometry"
+        })),
+        QueryValue::Enum(e) => column_cast(Some(&e.type_name)),
+        _ => column_cast(None),
+    }
+}
+
+/// Text as a string literal of the database
+pub(crate) fn text_literal(database_type: &DatabaseType, text: &str) -> String {
+    match database_type {
+        // Backslashes escape in MySQL strings unless NO_BACKSLASH_ESCAPES is set
+        DatabaseType::MySQL => quote_literal(&text.replace('\\', "\\\\")),
+        DatabaseType::PostgreSQL | DatabaseType::SQLite => quote_literal(text),
+    }
+}
+
+fn mysql_literal(value: &QueryValue, text: String) -> String {
+    let quoted = || text_literal(&DatabaseType::MySQL, &text);
+    match value {
+        // MySQL has no infinite or NaN floats
+        QueryValue::Float32(f) if !f.is_finite() => "NULL".to_string(),
+        QueryValue::Float64(f) if !f.is_finite() => "NULL".to_string(),
+        QueryValue::Bool(_)
+        | QueryValue::Int32(_)
+        | QueryValue::Int64(_)
+        | QueryValue::Float32(_)
+        | QueryValue::Float64(_)
+        | QueryValue::Decimal(_) => value.to_sql_literal(),
+        QueryValue::Bytes(bytes) => format!("X'{}'", hex::encode(bytes)),
+        QueryValue::Date(_) => format!("DATE {}", quoted()),
+        QueryValue::Time(_, None) => format!("TIME {}", quoted()),
+        QueryValue::NaiveDateTime(_) => format!("TIMESTAMP {}", quoted()),
+        QueryValue::DateTime(dt) => {
+            quote_literal(&dt.format("%Y-%m-%d %H:%M:%S%.f%:z").to_string())
+        }
+        QueryValue::Json(_) => format!("CAST({} AS JSON)", quoted()),
+        QueryValue::Bits(bits) => format!("b'{}'", bits),
+        _ => quoted(),
+    }
+}
+
+fn sqlite_literal(value: &QueryValue, text: String) -> String {
+    match value {
+        QueryValue::Bool(b) => if *b { "1" } else { "0" }.to_string(),
+        // SQLite reads a float literal too large for a double as infinity
+        QueryValue::Float32(f) if f.is_nan() => "NULL".to_string(),
+        QueryValue::Float64(f) if f.is_nan() => "NULL".to_string(),
+        QueryValue::Float32(f) if f.is_infinite() => infinity_literal(f.is_sign_negative()),
+        QueryValue::Float64(f) if f.is_infinite() => infinity_literal(f.is_sign_negative()),
+        QueryValue::Int32(_)
+        | QueryValue::Int64(_)
+        | QueryValue::Float32(_)
+        | QueryValue::Float64(_)
+        | QueryValue::Decimal(_) => value.to_sql_literal(),
+        QueryValue::Bytes(bytes) => format!("X'{}'", hex::encode(bytes)),
+        _ => quote_literal(&text),
+    }
+}
+
+fn infinity_literal(negative: bool) -> String {
+    if negative { "-9e999" } else { "9e999" }.to_string()
+}
@@ -0,0 +1,422 @@
+//! Decoding of spatial values from (E)WKB
+//!
+//! PostGIS sends `geometry` and `geography` values as EWKB: WKB whose type
+//! code may carry Z/M flags and an embedded SRID. MySQL stores plain WKB
+//! behind its own SRID prefix. Both forms, as well as the ISO WKB type codes
+//! for Z/M geometries, are read here.
+
+use crate::database::GeometryValue;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+// Flags PostGIS sets on the type code, from liblwgeom.h
+const WKBZOFFSET: u32 = 0x8000_0000;
+const WKBMOFFSET: u32 = 0x4000_0000;
+const WKBSRIDFLAG: u32 = 0x2000_0000;
+
+/// Collections nested deeper than this are treated as malformed
+const MAX_NESTING: usize = 32;
+
+/// Extent of a geometry in its own coordinates
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct BoundingBox {
+    pub min_x: f64,
+    pub min_y: f64,
+    pub max_x: f64,
+    pub max_y: f64,
+}
+
+impl BoundingBox {
+    fn around(x: f64, y: f64) -> Self {
+        Self {
+            min_x: x,
+            min_y: y,
+            max_x: x,
+            max_y: y,
+        }
+    }
+
+    fn extend(&mut self, x: f64, y: f64) {
+        self.min_x = self.min_x.min(x);
+        self.min_y = self.min_y.min(y);
+        self.max_x = self.max_x.max(x);
+        self.max_y = self.max_y.max(y);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Coordinate {
+    pub x: f64,
+    pub y: f64,
+    pub z: Option<f64>,
+    pub m: Option<f64>,
+}
+
+/// A decoded geometry; empty geometries have no coordinates or parts
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Option<Coordinate>),
+    LineString(Vec<Coordinate>),
+    /// Exterior ring followed by the interior rings
+    Polygon(Vec<Vec<Coordinate>>),
+    MultiPoint(Vec<Geometry>),
+    MultiLineString(Vec<Geometry>),
+    MultiPolygon(Vec<Geometry>),
+    Collection(Vec<Geometry>),
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Dimensions {
+    has_z: bool,
+    has_m: bool,
+}
+
+impl Dimensions {
+    /// Dimension tag written after the type name in ISO WKT
+    fn wkt_tag(&self) -> Option<&'static str> {
+        match (self.has_z, self.has_m) {
+            (true, true) => Some("ZM"),
+            (true, false) => Some("Z"),
+            (false, true) => Some("M"),
+            (false, false) => None,
+        }
+    }
+}
+
+impl GeometryValue {
+    /// Decode an EWKB or WKB value
+    ///
+    /// Returns `None` for malformed input and for curved and surface types,
+    /// which have no WKT rendering here.
+    pub fn from_ewkb(bytes: &[u8]) -> Option<Self> {
+        let mut reader = Reader::new(bytes);
+        let (geometry, dimensions, srid) = read_geometry(&mut reader, 0)?;
+        if !reader.bytes.is_empty() {
+            return None;
+        }
+
+        let mut value = Self::new(
+            geometry.type_name().to_string(),
+            // SRID 0 stands for an unknown reference system
+            srid.filter(|srid| *srid != 0),
+            geometry.to_wkt(dimensions),
+            Some(bytes.to_vec()),
+        );
+        value.has_z = dimensions.has_z;
+        value.has_m = dimensions.has_m;
+        value.bbox = geometry.bounding_box();
+        value.shape = Some(Arc::new(geometry));
+        Some(value)
+    }
+
+    /// GeoJSON geometry object, for values whose coordinates are known
+    pub fn to_geojson(&self) -> Option<Value> {
+        self.shape.as_ref().map(|shape| shape.to_geojson())
+    }
+}
+
+impl Geometry {
+    fn type_name(&self) -> &'static str {
+        match self {
+            Geometry::Point(_) => "POINT",
+            Geometry::LineString(_) => "LINESTRING",
+            Geometry::Polygon(_) => "POLYGON",
+            Geometry::MultiPoint(_) => "MULTIPOINT",
+            Geometry::MultiLineString(_) => "MULTILINESTRING",
+            Geometry::MultiPolygon(_) => "MULTIPOLYGON",
+            Geometry::Collection(_) => "GEOMETRYCOLLECTION",
+        }
+    }
+
+    /// ISO WKT, e.g. `POINT Z (1 2 3)` or `LINESTRING EMPTY`
+    fn to_wkt(&self, dimensions: Dimensions) -> String {
+        let mut wkt = self.type_name().to_string();
+        if let Some(tag) = dimensions.wkt_tag() {
+            wkt.push(' ');
+            wkt.push_str(tag);
+            wkt.push(' ');
+        }
+        match self.wkt_body(dimensions) {
+            Some(body) => wkt.push_str(&body),
+            None => {
+                if dimensions.wkt_tag().is_none() {
+                    wkt.push(' ');
+                }
+                wkt.push_str("EMPTY");
+            }
+        }
+        wkt
+    }
+
+    /// The parenthesized part of the WKT; `None` for an empty geometry
+    fn wkt_body(&self, dimensions: Dimensions) -> Option<String> {
+        let body = match self {
+            Geometry::Point(coordinate) => coordinate_text(coordinate.as_ref()?),
+            Geometry::LineString(points) => {
+                if points.is_empty() {
+                    return None;
+                }
+                join(points.iter().map(coordinate_text))
+            }
+            Geometry::Polygon(rings) => {
+                if rings.is_empty() {
+                    return None;
+                }
+                join(
+                    rings
+                        .iter()
+                        .map(|ring| coordinate_list(ring).unwrap_or_else(|| "EMPTY".to_string())),
+                )
+            }
+            Geometry::MultiPoint(parts)
+            | Geometry::MultiLineString(parts)
+            | Geometry::MultiPolygon(parts) => {
+                if parts.is_empty() {
+                    return None;
+                }
+                join(parts.iter().map(|part| {
+                    part.wkt_body(dimensions)
+                        .unwrap_or_else(|| "EMPTY".to_string())
+                }))
+            }
+            Geometry::Collection(parts) => {
+                if parts.is_empty() {
+                    return None;
+                }
+                join(parts.iter().map(|part| part.to_wkt(dimensions)))
+            }
+        };
+        Some(format!("({})", body))
+    }
+
+    /// GeoJSON geometry object; M values have no place in GeoJSON and are dropped
+    pub fn to_geojson(&self) -> Value {
+        let (kind, coordinates) = match self {
+            Geometry::Point(coordinate) => (
+                "Point",
+                coordinate.as_ref().map_or(json!([]), geojson_position),
+            ),
+            Geometry::LineString(points) => ("LineString", geojson_positions(points)),
+            Geometry::Polygon(rings) => (
+                "Polygon",
+                rings.iter().map(|ring| geojson_positions(ring)).collect(),
+            ),
+            Geometry::MultiPoint(parts) => ("MultiPoint", geojson_parts(parts)),
+            Geometry::MultiLineString(parts) => ("MultiLineString", geojson_parts(parts)),
+            Geometry::MultiPolygon(parts) => ("MultiPolygon", geojson_parts(parts)),
+            Geometry::Collection(parts) => {
+                let geometries: Vec<Value> = parts.iter().map(|part| part.to_geojson()).collect();
+                return json!({ "type": "GeometryCollection", "geometries": geometries });
+            }
+        };
+        json!({ "type": kind, "coordinates": coordinates })
+    }
+
+    pub fn for_each_coordinate(&self, visit: &mut impl FnMut(&Coordinate)) {
+        match self {
+            Geometry::Point(coordinate) => coordinate.iter().for_each(visit),
+            Geometry::LineString(points) => points.iter().for_each(visit),
+            Geometry::Polygon(rings) => rings.iter().flatten().for_each(visit),
+            Geometry::MultiPoint(parts)
+            | Geometry::MultiLineString(parts)
+            | Geometry::MultiPolygon(parts)
+            | Geometry::Collection(parts) => {
+                for part in parts {
+                    part.for_each_coordinate(visit);
+                }
+            }
+        }
+    }
+
+    fn bounding_box(&self) -> Option<BoundingBox> {
+        let mut bbox: Option<BoundingBox> = None;
+        self.for_each_coordinate(&mut |coordinate| match &mut bbox {
+            Some(bbox) => bbox.extend(coordinate.x, coordinate.y),
+            None => bbox = Some(BoundingBox::around(coordinate.x, coordinate.y)),
+        });
+        bbox
+    }
+}
+
+fn geojson_position(coordinate: &Coordinate) -> Value {
+    match coordinate.z {
+        Some(z) => json!([coordinate.x, coordinate.y, z]),
+        None => json!([coordinate.x, coordinate.y]),
+    }
+}
+
+fn geojson_positions(points: &[Coordinate]) -> Value {
+    points.iter().map(geojson_position).collect()
+}
+
+/// Coordinates of the members of a multi-geometry
+fn geojson_parts(parts: &[Geometry]) -> Value {
+    parts
+        .iter()
+        .map(|part| part.to_geojson()["coordinates"].take())
+        .collect()
+}
+
+fn coordinate_text(coordinate: &Coordinate) -> String {
+    [
+        Some(coordinate.x),
+        Some(coordinate.y),
+        coordinate.z,
+        coordinate.m,
+    ]
+    .into_iter()
+    .flatten()
+    .map(|ordinate| ordinate.to_string())
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+fn coordinate_list(points: &[Coordinate]) -> Option<String> {
+    if points.is_empty() {
+        return None;
+    }
+    Some(format!("({})", join(points.iter().map(coordinate_text))))
+}
+
+fn join(parts: impl Iterator<Item = String>) -> String {
+    parts.collect::<Vec<_>>().join(",")
+}
+
+/// Read one geometry, including its byte order and type header
+fn read_geometry(reader: &mut Reader, depth: usize) -> Option<(Geometry, Dimensions, Option<i32>)> {
+    reader.little_endian = match reader.take(1)?[0] {
+        0 => false,
+        1 => true,
+        _ => return None,
+    };
+
+    let type_code = reader.u32()?;
+    let srid = if type_code & WKBSRIDFLAG != 0 {
+        Some(reader.u32()? as i32)
+    } else {
+        None
+    };
+
+    // ISO WKB adds 1000 for Z, 2000 for M and 3000 for ZM to the type
+    let iso_code = type_code & 0x0FFF_FFFF;
+    let mut dimensions = Dimensions {
+        has_z: type_code & WKBZOFFSET != 0,
+        has_m: type_code & WKBMOFFSET != 0,
+    };
+    match iso_code / 1000 {
+        0 => {}
+        1 => dimensions.has_z = true,
+        2 => dimensions.has_m = true,
+        3 => {
+            dimensions.has_z = true;
+            dimensions.has_m = true;
+        }
+        _ => return None,
+    }
+
+    let geometry = match iso_code % 1000 {
+        1 => {
+            let point = read_coordinate(reader, dimensions)?;
+            // Empty points are written with NaN coordinates
+            Geometry::Point(Some(point).filter(|point| !point.x.is_nan() || !point.y.is_nan()))
+        }
+        2 => Geometry::LineString(read_coordinates(reader, dimensions)?),
+        3 => {
+            let count = reader.count()?;
+            let rings = (0..count)
+                .map(|_| read_coordinates(reader, dimensions))
+                .collect::<Option<Vec<_>>>()?;
+            Geometry::Polygon(rings)
+        }
+        kind @ 4..=7 => {
+            if depth >= MAX_NESTING {
+                return None;
+            }
+            let count = reader.count()?;
+            let parts = (0..count)
+                .map(|_| read_geometry(reader, depth + 1).map(|(part, _, _)| part))
+                .collect::<Option<Vec<_>>>()?;
+            match kind {
+                4 => Geometry::MultiPoint(parts),
+                5 => Geometry::MultiLineString(parts),
+                6 => Geometry::MultiPolygon(parts),
+                _ => Geometry::Collection(parts),
+            }
+        }
+        _ => return None,
+    };
+    Some((geometry, dimensions, srid))
+}
+
+fn read_coordinate(reader: &mut Reader, dimensions: Dimensions) -> Option<Coordinate> {
+    Some(Coordinate {
+        x: reader.f64()?,
+        y: reader.f64()?,
+        z: if dimensions.has_z {
+            Some(reader.f64()?)
+        } else {
+            None
+        },
+        m: if dimensions.has_m {
+            Some(reader.f64()?)
+        } else {
+            None
+        },
+    })
+}
+
+fn read_coordinates(reader: &mut Reader, dimensions: Dimensions) -> Option<Vec<Coordinate>> {
+    let count = reader.count()?;
+    (0..count)
+        .map(|_| read_coordinate(reader, dimensions))
+        .collect()
+}
+
+/// Reader over WKB bytes in the byte order of the geometry being read
+struct Reader<'a> {
+    bytes: &'a [u8],
+    little_endian: bool,
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            little_endian: false,
+        }
+    }
+
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < count {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Some(taken)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        let bytes = self.take(4)?.try_into().ok()?;
+        Some(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn f64(&mut self) -> Option<f64> {
+        let bytes = self.take(8)?.try_into().ok()?;
+        Some(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// An element count, rejected when the remaining bytes cannot hold it
+    fn count(&mut self) -> Option<usize> {
+        let count = self.u32()? as usize;
+        (count <= self.bytes.len()).then_some(count)
+    }
+}
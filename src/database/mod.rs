@@ -1,15 +1,48 @@
 pub mod connection;
+pub mod cursor;
+pub mod ddl;
+pub mod dml;
+pub mod er_model;
+pub mod export;
+mod geometry;
+#[cfg(feature = "mysql")]
+pub mod mysql;
+#[cfg(feature = "mysql")]
+pub mod mysql_queries;
 pub mod postgresql;
 pub mod postgresql_queries;
+mod postgresql_values;
 pub mod query;
+pub mod registry;
+pub mod result_filter;
+pub mod schema_diff;
+pub mod script;
+pub(crate) mod session;
+#[cfg(feature = "sqlite")]
+pub mod sqlite;
 pub mod traits;
 
 // Re-export main types
 pub use connection::{ConnectionManager, ConnectionParams, DatabaseType, SslMode};
+pub use cursor::{QueryCursor, QuerySession};
+pub use dml::{DmlStatement, EditTarget, RowChange};
+pub use export::{CopyFormat, ExportFormat, ExportOptions, ExportSource, QuoteStyle, TextEncoding};
+pub use geometry::{BoundingBox, Coordinate, Geometry};
+#[cfg(feature = "mysql")]
+pub use mysql::MySQLConnection;
 pub use postgresql::PostgreSQLConnection;
-pub use query::{GeometryValue, QueryColumn, QueryResult, QueryRow, QueryType, QueryValue};
+pub use query::{
+    EnumValue, GeometryValue, IntervalValue, NetworkAddress, QueryColumn, QueryResult, QueryRow,
+    QueryType, QueryValue, RangeValue,
+};
+pub use registry::{DriverCapabilities, DriverRegistry, ExplainFormat};
+pub use result_filter::{ColumnFilter, FilterCondition, SortKey};
+pub use script::{is_read_only_query, split_statements, statement_at, ScriptStatement};
+#[cfg(feature = "sqlite")]
+pub use sqlite::SQLiteConnection;
 pub use traits::{
-    ArgumentMode, Column, Database, DatabaseConnection, DatabaseObjectCounts, Function,
+    ArgumentMode, Column, Constraint, ConstraintType, Database, DatabaseConnection,
+    DatabaseObjectCounts, Dependency, DependencyKind, ForeignKey, ForeignKeyAction, Function,
     FunctionArgument, FunctionType, Index, IndexColumn, IndexType, NullsOrder, ObjectCategory,
     ObjectCounts, QueryExecutor, Schema, Sequence, SortDirection, Table, Trigger, TriggerEvent,
     TriggerTiming, TriggerType, View, ViewType,
@@ -35,6 +68,12 @@ pub enum DatabaseError {
     #[error("Unsupported operation: {0}")]
     UnsupportedOperation(String),
 
+    #[error("Query was cancelled")]
+    Cancelled,
+
+    #[error("Query timed out: {0}")]
+    Timeout(String),
+
     #[error("Internal error: {0}")]
     Internal(String),
 }
@@ -42,7 +81,7 @@ pub enum DatabaseError {
 impl From<sqlx::Error> for DatabaseError {
     fn from(err: sqlx::Error) -> Self {
         match err {
-            sqlx::Error::Database(db_err) => DatabaseError::QueryFailed(db_err.to_string()),
+            sqlx::Error::Database(db_err) => classify_database_error(db_err.as_ref()),
             sqlx::Error::Io(io_err) => DatabaseError::ConnectionFailed(io_err.to_string()),
             sqlx::Error::Configuration(config_err) => {
                 DatabaseError::InvalidParams(config_err.to_string())
@@ -51,3 +90,24 @@ impl From<sqlx::Error> for DatabaseError {
         }
     }
 }
+
+/// Map server errors that have a dedicated variant; everything else is a query failure
+fn classify_database_error(db_err: &dyn sqlx::error::DatabaseError) -> DatabaseError {
+    #[cfg(feature = "mysql")]
+    if let Some(mysql_err) = db_err.try_downcast_ref::<sqlx::mysql::MySqlDatabaseError>() {
+        match mysql_err.number() {
+            // ER_QUERY_TIMEOUT (MySQL) and ER_STATEMENT_TIMEOUT (MariaDB)
+            3024 | 1969 => return DatabaseError::Timeout(db_err.message().to_string()),
+            // ER_QUERY_INTERRUPTED, raised by KILL QUERY
+            1317 => return DatabaseError::Cancelled,
+            _ => {}
+        }
+    }
+
+    match db_err.code().as_deref() {
+        // PostgreSQL reports both statement_timeout and cancel requests as
+        // query_canceled; sessions that know their timeout tell them apart
+        Some("57014") => DatabaseError::Cancelled,
+        _ => DatabaseError::QueryFailed(db_err.to_string()),
+    }
+}
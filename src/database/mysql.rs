@@ -0,0 +1,1136 @@
+use crate::database::cursor::{serve_cursor, SessionStatement};
+use crate::database::ddl;
+use crate::database::mysql_queries;
+use crate::database::session::SessionTracker;
+use crate::database::{
+    ArgumentMode, Column as DbColumn, ConnectionParams, Constraint, ConstraintType, Database,
+    DatabaseConnection, DatabaseError, DatabaseObjectCounts, DatabaseType, Dependency,
+    DmlStatement, DriverCapabilities, ExplainFormat, ForeignKey, ForeignKeyAction, Function,
+    FunctionArgument, FunctionType, GeometryValue, Index, IndexColumn, IndexType, ObjectCategory,
+    ObjectCounts, QueryColumn, QueryCursor, QueryExecutor, QueryResult, QueryRow, QuerySession,
+    QueryValue, Schema, Sequence, SortDirection, SslMode, Table, Trigger, TriggerEvent,
+    TriggerTiming, TriggerType, View, ViewType,
+};
+use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use sqlx::mysql::{MySqlConnectOptions, MySqlPool, MySqlPoolOptions, MySqlRow, MySqlSslMode};
+use sqlx::{Column, Executor, Row, TypeInfo, ValueRef};
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// How long the server waits for a session to read the next rows of an open
+/// cursor, in seconds
+const CURSOR_IDLE_SECONDS: u32 = 24 * 60 * 60;
+
+/// MySQL/MariaDB database connection implementation
+///
+/// Every database on the server is exposed as a schema; `ConnectionParams::database`
+/// only selects the default database of the session.
+pub struct MySQLConnection {
+    pool: Option<MySqlPool>,
+    connection_info: Option<String>,
+    /// Thread ID of the session running the current statement
+    running_thread: SessionTracker<u64>,
+    /// Execution time limit in seconds set on each session as it opens; 0
+    /// lifts it
+    query_timeout: Arc<AtomicU32>,
+}
+
+impl MySQLConnection {
+    pub fn new() -> Self {
+        Self {
+            pool: None,
+            connection_info: None,
+            running_thread: SessionTracker::new(),
+            query_timeout: Arc::new(AtomicU32::new(0)),
+        }
+    }
+
+    /// Get the connection pool (internal use)
+    fn get_pool(&self) -> Result<&MySqlPool, DatabaseError> {
+        self.pool.as_ref().ok_or(DatabaseError::NotConnected)
+    }
+
+    /// Features of MySQL exposed through this driver
+    pub fn capabilities() -> DriverCapabilities {
+        DriverCapabilities {
+            // Databases take the place of schemas
+            supports_schemas: false,
+            supports_sequences: false,
+            supports_materialized_views: false,
+            supports_routines: true,
+            // Through KILL QUERY on a second connection
+            supports_cancellation: true,
+            // max_execution_time only limits SELECT statements
+            supports_statement_timeout: false,
+            supports_dependencies: false,
+            // Result columns do not name the table columns they were read from
+            supports_result_editing: false,
+            explain_formats: vec![
+                ExplainFormat::Text,
+                ExplainFormat::Json,
+                ExplainFormat::Tree,
+            ],
+        }
+    }
+
+    /// The statement in column `column` of a `SHOW CREATE` result, which is
+    /// NULL for a routine whose body the user may not see
+    async fn show_create(&self, sql: &str, column: usize) -> Result<String, DatabaseError> {
+        let pool = self.get_pool()?;
+        // Without arguments the statement goes over the text protocol, which
+        // takes every SHOW CREATE form
+        let row = pool.fetch_one(sql).await?;
+        let statement = match row.try_get::<Option<String>, _>(column) {
+            Ok(statement) => statement,
+            Err(_) => row
+                .try_get::<Option<Vec<u8>>, _>(column)?
+                .map(|bytes| String::from_utf8_lossy(&bytes).into_owned()),
+        };
+        statement.ok_or_else(|| {
+            DatabaseError::QueryFailed(format!("The definition is not visible: {}", sql))
+        })
+    }
+
+    /// Open a pool for the server named in the connection parameters
+    async fn open_pool(params: &ConnectionParams) -> Result<MySqlPool, DatabaseError> {
+        let ssl_mode = match params.ssl_mode {
+            SslMode::Disable => MySqlSslMode::Disabled,
+            SslMode::Allow | SslMode::Prefer => MySqlSslMode::Preferred,
+            SslMode::Require => MySqlSslMode::Required,
+            SslMode::VerifyCa => MySqlSslMode::VerifyCa,
+            SslMode::VerifyFull => MySqlSslMode::VerifyIdentity,
+        };
+
+        // Options are set field by field so credentials never need URL escaping
+        let mut options = MySqlConnectOptions::new()
+            .host(&params.host)
+            .port(params.port)
+            .username(&params.username)
+            .ssl_mode(ssl_mode)
+            .charset("utf8mb4");
+
+        if !params.password.is_empty() {
+            options = options.password(&params.password);
+        }
+        if !params.database.trim().is_empty() {
+            options = options.database(params.database.trim());
+        }
+
+        let mut pool_options = MySqlPoolOptions::new();
+        if let Some(timeout) = params.connection_timeout {
+            pool_options = pool_options.acquire_timeout(Duration::from_secs(timeout as u64));
+        }
+
+        Ok(pool_options.connect_with(options).await?)
+    }
+}
+
+impl Default for MySQLConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DatabaseConnection for MySQLConnection {
+    async fn connect(&mut self, params: &ConnectionParams) -> Result<(), DatabaseError> {
+        let pool = Self::open_pool(params).await?;
+
+        // Test the connection
+        sqlx::query("SELECT 1").fetch_one(&pool).await?;
+
+        self.pool = Some(pool);
+        self.set_query_timeout(params.query_timeout.unwrap_or(0));
+        self.connection_info = Some(format!(
+            "{}@{}:{}/{}",
+            params.username, params.host, params.port, params.database
+        ));
+
+        log::info!(
+            "Connected to MySQL database: {}",
+            self.connection_info.as_ref().unwrap()
+        );
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), DatabaseError> {
+        if let Some(pool) = self.pool.take() {
+            pool.close().await;
+            log::info!("Disconnected from MySQL database");
+        }
+        self.connection_info = None;
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        if let Some(pool) = &self.pool {
+            !pool.is_closed()
+        } else {
+            false
+        }
+    }
+
+    async fn test_connection(&self, params: &ConnectionParams) -> Result<(), DatabaseError> {
+        let pool = Self::open_pool(params).await?;
+        let mut conn = pool.acquire().await?;
+        apply_execution_time_limit(&mut conn, params.query_timeout.unwrap_or(0)).await?;
+        sqlx::query("SELECT 1").fetch_one(&mut *conn).await?;
+        drop(conn);
+        pool.close().await;
+        Ok(())
+    }
+
+    fn database_type(&self) -> &'static str {
+        "MySQL"
+    }
+
+    fn connection_info(&self) -> Option<String> {
+        self.connection_info.clone()
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for MySQLConnection {
+    async fn execute_query(&self, sql: &str) -> Result<QueryResult, DatabaseError> {
+        let start_time = Instant::now();
+        let mut cursor = self.open_cursor(sql).await?;
+        let rows = cursor.fetch(usize::MAX).await?;
+        Ok(cursor
+            .result_with_rows(rows)
+            .with_execution_time(start_time.elapsed()))
+    }
+
+    async fn open_cursor(&self, sql: &str) -> Result<QueryCursor, DatabaseError> {
+        // The session ends once the cursor is done with
+        self.open_session().await?.open_cursor(sql).await
+    }
+
+    async fn open_session(&self) -> Result<QuerySession, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        // Run on a dedicated session so it can be targeted by KILL QUERY
+        let mut conn = pool.acquire().await?;
+        let thread_id: u64 = sqlx::query_scalar("SELECT CONNECTION_ID()")
+            .fetch_one(&mut *conn)
+            .await?;
+        // Read now so settings changes reach connections that are already open
+        apply_execution_time_limit(&mut conn, self.query_timeout.load(Ordering::Relaxed)).await?;
+        // The server gives up on a client that stops reading for
+        // net_write_timeout seconds, 60 by default, which a cursor left open
+        // between fetches easily does
+        conn.execute(format!("SET SESSION net_write_timeout = {}", CURSOR_IDLE_SECONDS).as_str())
+            .await?;
+
+        let (session, mut statements) = QuerySession::channel();
+        let running_thread = self.running_thread.clone();
+        tokio::spawn(async move {
+            while let Some(SessionStatement { sql, requests }) = statements.recv().await {
+                // Forget the thread ID once the statement is done with
+                let _running = running_thread.track(thread_id);
+                let stream = conn
+                    .fetch_many(sqlx::query(&sql))
+                    .map_ok(|step| step.map_left(|done| done.rows_affected()));
+                serve_cursor(
+                    requests,
+                    stream,
+                    mysql_columns,
+                    std::future::ready,
+                    convert_mysql_row,
+                )
+                .await;
+            }
+        });
+
+        Ok(session)
+    }
+
+    async fn execute_non_query(&self, sql: &str) -> Result<u64, DatabaseError> {
+        let pool = self.get_pool()?;
+        let result = sqlx::query(sql).execute(pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn execute_dml(&self, _statements: &[DmlStatement]) -> Result<u64, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "MySQL results cannot be edited".to_string(),
+        ))
+    }
+
+    async fn cancel_query(&self) -> Result<(), DatabaseError> {
+        let pool = self.get_pool()?;
+        let Some(thread_id) = self.running_thread.cancel() else {
+            return Ok(());
+        };
+
+        // KILL cannot take placeholders; the ID is a plain integer
+        sqlx::raw_sql(&format!("KILL QUERY {}", thread_id))
+            .execute(pool)
+            .await?;
+
+        log::info!("Sent KILL QUERY for MySQL thread {}", thread_id);
+        Ok(())
+    }
+
+    fn set_query_timeout(&self, seconds: u32) {
+        self.query_timeout.store(seconds, Ordering::Relaxed);
+    }
+
+    async fn get_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_USER_SCHEMAS_QUERY)
+            .fetch_all(pool)
+            .await?;
+
+        let schemas = rows
+            .iter()
+            .map(|row| Schema {
+                name: row.get::<String, _>("schema_name"),
+                owner: None, // MySQL databases have no owner
+            })
+            .collect();
+
+        Ok(schemas)
+    }
+
+    async fn get_tables(&self, schema: &str) -> Result<Vec<Table>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_TABLES_QUERY)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let tables = rows
+            .iter()
+            .map(|row| {
+                let table_type = row.get::<String, _>("table_type");
+
+                // MySQL fills the comment of every view with the literal "VIEW"
+                let comment = row
+                    .try_get::<String, _>("table_comment")
+                    .ok()
+                    .filter(|s| !s.is_empty() && table_type != "VIEW");
+
+                Table {
+                    name: row.get::<String, _>("table_name"),
+                    schema: schema.to_string(),
+                    table_type,
+                    comment,
+                }
+            })
+            .collect();
+
+        Ok(tables)
+    }
+
+    async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<DbColumn>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_COLUMNS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let columns = rows
+            .iter()
+            .map(|row| {
+                let comment = row
+                    .try_get::<String, _>("column_comment")
+                    .ok()
+                    .filter(|s| !s.is_empty());
+
+                // MariaDB reports a missing default as the literal NULL; a string
+                // default of 'NULL' keeps its quotes
+                let default_value = row
+                    .try_get::<String, _>("column_default")
+                    .ok()
+                    .filter(|s| s != "NULL");
+
+                DbColumn {
+                    name: row.get::<String, _>("column_name"),
+                    data_type: row.get::<String, _>("data_type"),
+                    is_nullable: row.get::<String, _>("is_nullable") == "YES",
+                    default_value,
+                    is_primary_key: row.get::<String, _>("column_key") == "PRI",
+                    comment,
+                }
+            })
+            .collect();
+
+        Ok(columns)
+    }
+
+    async fn get_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        limit: Option<u32>,
+    ) -> Result<QueryResult, DatabaseError> {
+        let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+        let sql = format!(
+            "SELECT * FROM {}.{}{}",
+            quote_identifier(schema),
+            quote_identifier(table),
+            limit_clause
+        );
+        let mut result = self.execute_query(&sql).await?;
+
+        // Result rows do not name the table they come from, but here it is known
+        let columns = self.get_columns(schema, table).await?;
+        for column in &mut result.columns {
+            if let Some(source) = columns.iter().find(|c| c.name == column.name) {
+                column.nullable = source.is_nullable;
+            }
+        }
+        Ok(result)
+    }
+
+    async fn table_exists(&self, schema: &str, table: &str) -> Result<bool, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let row = sqlx::query(mysql_queries::TABLE_EXISTS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(row.get::<i64, _>("table_count") > 0)
+    }
+
+    async fn get_views(&self, schema: &str) -> Result<Vec<View>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_VIEWS_QUERY)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let views = rows
+            .iter()
+            .map(|row| View {
+                name: row.get::<String, _>("name"),
+                schema: row.get::<String, _>("schema"),
+                view_type: ViewType::Regular,
+                definition: row.try_get::<String, _>("definition").ok(),
+                comment: None,
+                owner: row.try_get::<String, _>("owner").ok(),
+                is_updatable: row.get::<String, _>("is_updatable") == "YES",
+            })
+            .collect();
+
+        Ok(views)
+    }
+
+    async fn get_functions(&self, schema: &str) -> Result<Vec<Function>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_ROUTINES_QUERY)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let functions = rows
+            .iter()
+            .map(|row| {
+                let function_type = match row.get::<String, _>("function_type").as_str() {
+                    "PROCEDURE" => FunctionType::Procedure,
+                    _ => FunctionType::Function,
+                };
+
+                let comment = row
+                    .try_get::<String, _>("comment")
+                    .ok()
+                    .filter(|s| !s.is_empty());
+
+                let arguments = parse_routine_parameters(
+                    &row.try_get::<String, _>("arguments").unwrap_or_default(),
+                );
+
+                Function {
+                    name: row.get::<String, _>("name"),
+                    schema: row.get::<String, _>("schema"),
+                    function_type,
+                    return_type: row.get::<String, _>("return_type"),
+                    arguments,
+                    language: row.get::<String, _>("language"),
+                    definition: row.try_get::<String, _>("definition").ok(),
+                    comment,
+                    owner: row.try_get::<String, _>("owner").ok(),
+                }
+            })
+            .collect();
+
+        Ok(functions)
+    }
+
+    async fn get_triggers(&self, schema: &str) -> Result<Vec<Trigger>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_TRIGGERS_QUERY)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let triggers = rows
+            .iter()
+            .map(|row| {
+                let trigger_type = match row.get::<String, _>("trigger_type").as_str() {
+                    "STATEMENT" => TriggerType::Statement,
+                    _ => TriggerType::Row,
+                };
+
+                let timing = match row.get::<String, _>("timing").as_str() {
+                    "BEFORE" => TriggerTiming::Before,
+                    _ => TriggerTiming::After,
+                };
+
+                // MySQL triggers fire on exactly one event
+                let events = match row.get::<String, _>("events").as_str() {
+                    "INSERT" => vec![TriggerEvent::Insert],
+                    "UPDATE" => vec![TriggerEvent::Update],
+                    "DELETE" => vec![TriggerEvent::Delete],
+                    _ => Vec::new(),
+                };
+
+                Trigger {
+                    name: row.get::<String, _>("name"),
+                    schema: row.get::<String, _>("schema"),
+                    table_name: row.get::<String, _>("table_name"),
+                    trigger_type,
+                    events,
+                    timing,
+                    // The trigger body is inline SQL rather than a separate function
+                    function_name: String::new(),
+                    function_schema: schema.to_string(),
+                    // MySQL triggers have no WHEN clause
+                    condition: None,
+                    comment: None,
+                }
+            })
+            .collect();
+
+        Ok(triggers)
+    }
+
+    async fn get_sequences(&self, _schema: &str) -> Result<Vec<Sequence>, DatabaseError> {
+        // MySQL uses AUTO_INCREMENT columns instead of sequence objects
+        Ok(Vec::new())
+    }
+
+    async fn get_indexes(&self, schema: &str) -> Result<Vec<Index>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_INDEXES_QUERY)
+            .bind(schema)
+            .fetch_all(pool)
+            .await?;
+
+        let indexes = rows
+            .iter()
+            .map(|row| {
+                let index_type = match row.get::<String, _>("index_type").as_str() {
+                    "HASH" => IndexType::Hash,
+                    "FULLTEXT" => IndexType::FullText,
+                    "SPATIAL" | "RTREE" => IndexType::Spatial,
+                    _ => IndexType::BTree,
+                };
+
+                let columns =
+                    parse_index_columns(&row.try_get::<String, _>("columns").unwrap_or_default());
+
+                let comment = row
+                    .try_get::<String, _>("comment")
+                    .ok()
+                    .filter(|s| !s.is_empty());
+
+                let name = row.get::<String, _>("name");
+                let is_primary = name == "PRIMARY";
+
+                Index {
+                    name,
+                    schema: row.get::<String, _>("schema"),
+                    table_name: row.get::<String, _>("table_name"),
+                    index_type,
+                    columns,
+                    is_unique: row.get::<i64, _>("non_unique") == 0,
+                    is_primary,
+                    // MySQL has no partial indexes
+                    is_partial: false,
+                    condition: None,
+                    size: None,
+                    comment,
+                }
+            })
+            .collect();
+
+        Ok(indexes)
+    }
+
+    async fn get_foreign_keys(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKey>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_FOREIGN_KEYS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let foreign_keys = rows
+            .iter()
+            .map(|row| ForeignKey {
+                name: row.get::<String, _>("name"),
+                schema: row.get::<String, _>("schema"),
+                table_name: row.get::<String, _>("table_name"),
+                columns: split_column_list(&row.get::<String, _>("columns")),
+                referenced_schema: row.get::<String, _>("referenced_schema"),
+                referenced_table: row.get::<String, _>("referenced_table"),
+                referenced_columns: split_column_list(&row.get::<String, _>("referenced_columns")),
+                on_update: ForeignKeyAction::from_rule(&row.get::<String, _>("on_update")),
+                on_delete: ForeignKeyAction::from_rule(&row.get::<String, _>("on_delete")),
+                comment: None,
+            })
+            .collect();
+
+        Ok(foreign_keys)
+    }
+
+    async fn get_constraints(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<Constraint>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_CONSTRAINTS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let constraints = rows
+            .iter()
+            .map(|row| {
+                let columns = split_column_list(&row.get::<String, _>("columns"));
+                let column_list = columns
+                    .iter()
+                    .map(|column| quote_identifier(column))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                let (constraint_type, definition) =
+                    match row.get::<String, _>("constraint_type").as_str() {
+                        "PRIMARY KEY" => (
+                            ConstraintType::PrimaryKey,
+                            format!("PRIMARY KEY ({})", column_list),
+                        ),
+                        "UNIQUE" => (ConstraintType::Unique, format!("UNIQUE ({})", column_list)),
+                        _ => {
+                            let clause =
+                                row.try_get::<String, _>("check_clause").unwrap_or_default();
+                            (ConstraintType::Check, format!("CHECK ({})", clause))
+                        }
+                    };
+
+                Constraint {
+                    name: row.get::<String, _>("name"),
+                    schema: row.get::<String, _>("schema"),
+                    table_name: row.get::<String, _>("table_name"),
+                    constraint_type,
+                    columns,
+                    definition,
+                    comment: None,
+                }
+            })
+            .collect();
+
+        Ok(constraints)
+    }
+
+    async fn get_object_ddl(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        let qualified = format!("{}.{}", quote_identifier(schema), quote_identifier(name));
+        let statements = match category {
+            // SHOW CREATE TABLE answers for views as well
+            ObjectCategory::Tables | ObjectCategory::Views => {
+                vec![
+                    self.show_create(&format!("SHOW CREATE TABLE {}", qualified), 1)
+                        .await?,
+                ]
+            }
+            ObjectCategory::Functions => {
+                let pool = self.get_pool()?;
+                let rows = sqlx::query(mysql_queries::GET_ROUTINE_TYPES_QUERY)
+                    .bind(schema)
+                    .bind(name)
+                    .fetch_all(pool)
+                    .await?;
+                let mut statements = Vec::new();
+                for row in &rows {
+                    let routine_type = row.get::<String, _>("routine_type");
+                    let sql = format!("SHOW CREATE {} {}", routine_type, qualified);
+                    statements.push(self.show_create(&sql, 2).await?);
+                }
+                statements
+            }
+            ObjectCategory::Triggers => {
+                vec![
+                    self.show_create(&format!("SHOW CREATE TRIGGER {}", qualified), 2)
+                        .await?,
+                ]
+            }
+            ObjectCategory::Indexes => self
+                .get_indexes(schema)
+                .await?
+                .iter()
+                .filter(|index| index.name == name)
+                .map(index_statement)
+                .collect(),
+            ObjectCategory::Sequences | ObjectCategory::SystemCatalog => {
+                return Err(DatabaseError::UnsupportedOperation(format!(
+                    "DDL for {:?} on MySQL",
+                    category
+                )))
+            }
+        };
+
+        if statements.is_empty() {
+            return Err(DatabaseError::QueryFailed(format!(
+                "{}.{} does not exist",
+                schema, name
+            )));
+        }
+        Ok(ddl::join_sections(
+            statements
+                .iter()
+                .map(|statement| format!("{};", ddl::trim_statement(statement)))
+                .collect(),
+        ))
+    }
+
+    async fn get_trigger_ddl(
+        &self,
+        schema: &str,
+        _table: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        // Trigger names are unique within a schema
+        self.get_object_ddl(&ObjectCategory::Triggers, schema, name)
+            .await
+    }
+
+    async fn get_dependencies(
+        &self,
+        _category: &ObjectCategory,
+        _schema: &str,
+        _name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "MySQL does not record object dependencies".to_string(),
+        ))
+    }
+
+    async fn get_dependents(
+        &self,
+        _category: &ObjectCategory,
+        _schema: &str,
+        _name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "MySQL does not record object dependencies".to_string(),
+        ))
+    }
+
+    async fn get_all_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(mysql_queries::GET_ALL_SCHEMAS_QUERY)
+            .fetch_all(pool)
+            .await?;
+
+        let schemas = rows
+            .iter()
+            .map(|row| Schema {
+                name: row.get::<String, _>("schema_name"),
+                owner: None,
+            })
+            .collect();
+
+        Ok(schemas)
+    }
+
+    async fn get_object_counts(&self, schema: &str) -> Result<ObjectCounts, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let row = sqlx::query(mysql_queries::GET_OBJECT_COUNTS_QUERY)
+            .bind(schema)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(ObjectCounts {
+            tables: row.get::<i64, _>("tables") as usize,
+            views: row.get::<i64, _>("views") as usize,
+            functions: row.get::<i64, _>("functions") as usize,
+            procedures: row.get::<i64, _>("procedures") as usize,
+            triggers: row.get::<i64, _>("triggers") as usize,
+            indexes: row.get::<i64, _>("indexes") as usize,
+            ..Default::default()
+        })
+    }
+
+    async fn get_database_object_counts(&self) -> Result<DatabaseObjectCounts, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let row = sqlx::query(mysql_queries::GET_DATABASE_OBJECT_COUNTS_QUERY)
+            .fetch_one(pool)
+            .await?;
+
+        Ok(DatabaseObjectCounts {
+            schemas: row.get::<i64, _>("schemas") as usize,
+            user_schemas: row.get::<i64, _>("user_schemas") as usize,
+            system_schemas: row.get::<i64, _>("system_schemas") as usize,
+            total_tables: row.get::<i64, _>("total_tables") as usize,
+            total_views: row.get::<i64, _>("total_views") as usize,
+            total_functions: row.get::<i64, _>("total_functions") as usize,
+            total_procedures: row.get::<i64, _>("total_procedures") as usize,
+            total_triggers: row.get::<i64, _>("total_triggers") as usize,
+            total_indexes: row.get::<i64, _>("total_indexes") as usize,
+            ..Default::default()
+        })
+    }
+}
+
+impl Database for MySQLConnection {
+    fn clone_connection(&self) -> Box<dyn Database> {
+        Box::new(MySQLConnection::new())
+    }
+}
+
+/// Column descriptions of a MySQL result row
+///
+/// sqlx does not say which table a column comes from, so every column counts
+/// as nullable.
+fn mysql_columns(row: &MySqlRow) -> Vec<QueryColumn> {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            QueryColumn::new(
+                col.name().to_string(),
+                col.type_info().name().to_string(),
+                i,
+                true,
+            )
+        })
+        .collect()
+}
+
+/// Limit the statements of a session to `seconds`, or lift the limit for 0
+async fn apply_execution_time_limit(
+    conn: &mut sqlx::mysql::MySqlConnection,
+    seconds: u32,
+) -> Result<(), DatabaseError> {
+    // MySQL takes milliseconds; MariaDB only knows max_statement_time
+    let mysql = format!(
+        "SET SESSION max_execution_time = {}",
+        u64::from(seconds) * 1000
+    );
+    if conn.execute(mysql.as_str()).await.is_err() {
+        let mariadb = format!("SET SESSION max_statement_time = {}", seconds);
+        conn.execute(mariadb.as_str()).await?;
+    }
+    Ok(())
+}
+
+/// Convert a MySQL row to QueryRow format
+fn convert_mysql_row(row: &MySqlRow) -> Result<QueryRow, DatabaseError> {
+    let values: Result<Vec<QueryValue>, DatabaseError> = (0..row.len())
+        .map(|i| convert_mysql_value(row, i))
+        .collect();
+    values.map(QueryRow::new)
+}
+
+/// Convert MySQL values to QueryValue
+///
+/// ENUM and SET have no dedicated QueryValue variant and are kept as their
+/// server text, as are TIME values outside a time of day.
+fn convert_mysql_value(row: &MySqlRow, index: usize) -> Result<QueryValue, DatabaseError> {
+    let column = &row.columns()[index];
+    let type_name = column.type_info().name();
+
+    // Handle NULL values first
+    if row.try_get_raw(index)?.is_null() {
+        return Ok(QueryValue::Null);
+    }
+
+    // Convert based on the type name sqlx derives from the column definition
+    match type_name {
+        "NULL" => Ok(QueryValue::Null),
+        "BOOLEAN" => Ok(QueryValue::Bool(row.try_get(index)?)),
+        "TINYINT" | "SMALLINT" | "MEDIUMINT" | "INT" => {
+            Ok(QueryValue::Int32(row.try_get::<i32, _>(index)?))
+        }
+        "TINYINT UNSIGNED" | "SMALLINT UNSIGNED" | "MEDIUMINT UNSIGNED" => {
+            Ok(QueryValue::Int32(row.try_get::<u32, _>(index)? as i32))
+        }
+        "INT UNSIGNED" => Ok(QueryValue::Int64(row.try_get::<u32, _>(index)? as i64)),
+        "BIGINT" => Ok(QueryValue::Int64(row.try_get(index)?)),
+        "BIGINT UNSIGNED" => {
+            let value: u64 = row.try_get(index)?;
+            // Values above i64::MAX are kept exact as text
+            Ok(i64::try_from(value)
+                .map(QueryValue::Int64)
+                .unwrap_or_else(|_| QueryValue::String(value.to_string())))
+        }
+        "YEAR" => Ok(QueryValue::Int32(
+            row.try_get_unchecked::<u16, _>(index)? as i32
+        )),
+        "FLOAT" => Ok(QueryValue::Float32(row.try_get(index)?)),
+        "DOUBLE" => Ok(QueryValue::Float64(row.try_get(index)?)),
+        // Parsed from the exact server text, keeping its scale
+        "DECIMAL" => Ok(QueryValue::Decimal(row.try_get(index)?)),
+        "ENUM" | "SET" => {
+            // Both arrive as text in both protocols
+            Ok(QueryValue::String(
+                row.try_get_unchecked::<String, _>(index)?,
+            ))
+        }
+        "BIT" => Ok(decode_bit_value(
+            &row.try_get_unchecked::<Vec<u8>, _>(index)?,
+        )),
+        // DATETIME carries no zone, so it is shown as stored
+        "DATETIME" => match row.try_get::<chrono::NaiveDateTime, _>(index) {
+            Ok(dt) => Ok(QueryValue::NaiveDateTime(dt)),
+            Err(error) => zero_date(row, index, "0000-00-00 00:00:00").ok_or(error.into()),
+        },
+        "TIMESTAMP" => match row.try_get::<chrono::DateTime<chrono::Utc>, _>(index) {
+            Ok(dt) => Ok(QueryValue::DateTime(dt.fixed_offset())),
+            Err(error) => zero_date(row, index, "0000-00-00 00:00:00").ok_or(error.into()),
+        },
+        "DATE" => match row.try_get::<chrono::NaiveDate, _>(index) {
+            Ok(date) => Ok(QueryValue::Date(date)),
+            Err(error) => zero_date(row, index, "0000-00-00").ok_or(error.into()),
+        },
+        "TIME" => {
+            // TIME covers intervals up to 838 hours, including negative ones
+            let time: sqlx::mysql::types::MySqlTime = row.try_get(index)?;
+            let time_of_day = time
+                .is_valid_time_of_day()
+                .then(|| {
+                    chrono::NaiveTime::from_hms_micro_opt(
+                        time.hours(),
+                        time.minutes() as u32,
+                        time.seconds() as u32,
+                        time.microseconds(),
+                    )
+                })
+                .flatten();
+            Ok(match time_of_day {
+                Some(time_of_day) => QueryValue::Time(time_of_day, None),
+                None => QueryValue::String(time.to_string()),
+            })
+        }
+        "JSON" => {
+            let json: serde_json::Value = row.try_get(index)?;
+            Ok(QueryValue::Json(json))
+        }
+        "GEOMETRY" => {
+            let bytes: Vec<u8> = row.try_get_unchecked(index)?;
+            Ok(decode_geometry_value(bytes))
+        }
+        "BINARY" | "VARBINARY" | "TINYBLOB" | "BLOB" | "MEDIUMBLOB" | "LONGBLOB" => {
+            Ok(QueryValue::Bytes(row.try_get(index)?))
+        }
+        _ => {
+            // Fallback to string representation
+            match row.try_get_unchecked::<String, _>(index) {
+                Ok(s) => Ok(QueryValue::String(s)),
+                Err(_) => Ok(QueryValue::String(format!("<{}>", type_name))),
+            }
+        }
+    }
+}
+
+/// The zero date MySQL allows in place of a date, which has no chrono
+/// equivalent; `None` when the raw value is not a zero date
+fn zero_date(row: &MySqlRow, index: usize, zero: &str) -> Option<QueryValue> {
+    // Zero dates are empty in the binary protocol and text in the text protocol
+    let raw = row.try_get_unchecked::<&[u8], _>(index).ok()?;
+    if raw.is_empty() {
+        Some(QueryValue::String(zero.to_string()))
+    } else if raw.starts_with(b"0000-00-00") {
+        Some(QueryValue::String(
+            String::from_utf8_lossy(raw).into_owned(),
+        ))
+    } else {
+        None
+    }
+}
+
+/// Decode a BIT(n) value, sent as big-endian bytes, into an integer
+fn decode_bit_value(bytes: &[u8]) -> QueryValue {
+    if bytes.len() > 8 {
+        return QueryValue::Bytes(bytes.to_vec());
+    }
+
+    let value = bytes
+        .iter()
+        .fold(0u64, |acc, byte| (acc << 8) | *byte as u64);
+
+    // BIT(64) values with the top bit set do not fit an i64
+    i64::try_from(value)
+        .map(QueryValue::Int64)
+        .unwrap_or_else(|_| QueryValue::String(format!("b'{:064b}'", value)))
+}
+
+/// Decode MySQL's internal geometry format: a little-endian SRID followed by WKB
+fn decode_geometry_value(bytes: Vec<u8>) -> QueryValue {
+    if bytes.len() < 4 {
+        return QueryValue::Bytes(bytes);
+    }
+
+    let srid = i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+    let wkb = bytes[4..].to_vec();
+
+    let mut geometry = GeometryValue::from_ewkb(&wkb).unwrap_or_else(|| {
+        GeometryValue::new(
+            "GEOMETRY".to_string(),
+            None,
+            format!("<Binary Geometry Data: {} bytes>", wkb.len()),
+            Some(wkb),
+        )
+    });
+    geometry.srid = Some(srid).filter(|srid| *srid != 0);
+    QueryValue::Geometry(geometry)
+}
+
+/// The statement creating an index, which for the primary key is an
+/// `ALTER TABLE`
+fn index_statement(index: &Index) -> String {
+    let table = ddl::qualified_name(&DatabaseType::MySQL, &index.schema, &index.table_name);
+    let columns = index
+        .columns
+        .iter()
+        .map(|column| match column.direction {
+            Some(SortDirection::Descending) => format!("{} DESC", quote_identifier(&column.name)),
+            _ => quote_identifier(&column.name),
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    if index.is_primary {
+        return format!("ALTER TABLE {} ADD PRIMARY KEY ({})", table, columns);
+    }
+    let kind = match index.index_type {
+        IndexType::FullText => "FULLTEXT ",
+        IndexType::Spatial => "SPATIAL ",
+        _ if index.is_unique => "UNIQUE ",
+        _ => "",
+    };
+    let mut statement = format!(
+        "CREATE {}INDEX {} ON {} ({})",
+        kind,
+        quote_identifier(&index.name),
+        table,
+        columns
+    );
+    if let Some(comment) = &index.comment {
+        statement.push_str(&format!(
+            " COMMENT {}",
+            crate::database::query::quote_literal(comment)
+        ));
+    }
+    statement
+}
+
+/// Quote an identifier for interpolation into MySQL statements
+fn quote_identifier(name: &str) -> String {
+    format!("`{}`", name.replace('`', "``"))
+}
+
+/// Parse routine parameters from the `[MODE] name type, ...` list built by the query
+fn parse_routine_parameters(params_str: &str) -> Vec<FunctionArgument> {
+    if params_str.is_empty() {
+        return Vec::new();
+    }
+
+    params_str
+        .split(", ")
+        .map(|param| {
+            let mut parts: Vec<&str> = param.split_whitespace().collect();
+
+            // Only procedure parameters carry a mode
+            let mode = match parts.first().copied() {
+                Some("IN") => Some(ArgumentMode::In),
+                Some("OUT") => Some(ArgumentMode::Out),
+                Some("INOUT") => Some(ArgumentMode::InOut),
+                _ => None,
+            };
+            if mode.is_some() {
+                parts.remove(0);
+            }
+
+            let (name, data_type) = match parts.split_first() {
+                Some((name, rest)) if !rest.is_empty() => (Some(name.to_string()), rest.join(" ")),
+                Some((data_type, _)) => (None, data_type.to_string()),
+                None => (None, "unknown".to_string()),
+            };
+
+            FunctionArgument {
+                name,
+                data_type,
+                mode: mode.unwrap_or(ArgumentMode::In),
+                default_value: None,
+            }
+        })
+        .collect()
+}
+
+/// Split a comma-separated list of column names
+fn split_column_list(columns_str: &str) -> Vec<String> {
+    columns_str
+        .split(',')
+        .map(str::trim)
+        .filter(|column| !column.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Parse index columns from comma-separated string
+fn parse_index_columns(columns_str: &str) -> Vec<IndexColumn> {
+    if columns_str.is_empty() {
+        return Vec::new();
+    }
+
+    columns_str
+        .split(',')
+        .enumerate()
+        .map(|(i, column)| {
+            let trimmed = column.trim();
+            let (name, direction) = match trimmed.strip_suffix(" DESC") {
+                Some(name) => (name, SortDirection::Descending),
+                None => (trimmed, SortDirection::Ascending),
+            };
+
+            IndexColumn {
+                name: name.to_string(),
+                position: i as i32 + 1,
+                direction: Some(direction),
+                nulls_order: None,
+            }
+        })
+        .collect()
+}
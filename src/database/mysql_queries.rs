@@ -0,0 +1,249 @@
+//! MySQL/MariaDB metadata queries for database object categorization
+//!
+//! MySQL has no schema level below the database, so every database on the server
+//! is presented as a schema. All metadata comes from `information_schema`; text
+//! columns are cast to CHAR because MySQL 8 reports several of them (and every
+//! GROUP_CONCAT result) as binary strings.
+
+/// Query to get all schemas including system schemas
+pub const GET_ALL_SCHEMAS_QUERY: &str = r#"
+SELECT
+    CAST(SCHEMA_NAME AS CHAR) as schema_name
+FROM information_schema.SCHEMATA
+ORDER BY
+    CASE
+        WHEN SCHEMA_NAME IN ('information_schema', 'mysql', 'performance_schema', 'sys') THEN 1
+        ELSE 0
+    END,
+    SCHEMA_NAME
+"#;
+
+/// Query to get user schemas (excluding system schemas)
+pub const GET_USER_SCHEMAS_QUERY: &str = r#"
+SELECT
+    CAST(SCHEMA_NAME AS CHAR) as schema_name
+FROM information_schema.SCHEMATA
+WHERE SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')
+ORDER BY SCHEMA_NAME
+"#;
+
+/// Query to get tables and views in a specific schema
+pub const GET_TABLES_QUERY: &str = r#"
+SELECT
+    CAST(TABLE_NAME AS CHAR) as table_name,
+    CAST(TABLE_TYPE AS CHAR) as table_type,
+    CAST(COALESCE(TABLE_COMMENT, '') AS CHAR) as table_comment
+FROM information_schema.TABLES
+WHERE TABLE_SCHEMA = ?
+ORDER BY TABLE_NAME
+"#;
+
+/// Query to get columns of a specific table
+pub const GET_COLUMNS_QUERY: &str = r#"
+SELECT
+    CAST(COLUMN_NAME AS CHAR) as column_name,
+    CAST(COLUMN_TYPE AS CHAR) as data_type,
+    CAST(IS_NULLABLE AS CHAR) as is_nullable,
+    CAST(COLUMN_DEFAULT AS CHAR) as column_default,
+    CAST(COLUMN_KEY AS CHAR) as column_key,
+    CAST(COALESCE(COLUMN_COMMENT, '') AS CHAR) as column_comment
+FROM information_schema.COLUMNS
+WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+ORDER BY ORDINAL_POSITION
+"#;
+
+/// Query to check whether a table exists
+pub const TABLE_EXISTS_QUERY: &str = r#"
+SELECT COUNT(*) as table_count
+FROM information_schema.TABLES
+WHERE TABLE_SCHEMA = ? AND TABLE_NAME = ?
+"#;
+
+/// Query to get whether a routine is a function, a procedure or both
+pub const GET_ROUTINE_TYPES_QUERY: &str = r#"
+SELECT CAST(ROUTINE_TYPE AS CHAR) as routine_type
+FROM information_schema.ROUTINES
+WHERE ROUTINE_SCHEMA = ? AND ROUTINE_NAME = ?
+ORDER BY ROUTINE_TYPE
+"#;
+
+/// Query to get views in a specific schema
+pub const GET_VIEWS_QUERY: &str = r#"
+SELECT
+    CAST(TABLE_NAME AS CHAR) as name,
+    CAST(TABLE_SCHEMA AS CHAR) as `schema`,
+    CAST(VIEW_DEFINITION AS CHAR) as definition,
+    CAST(DEFINER AS CHAR) as owner,
+    CAST(IS_UPDATABLE AS CHAR) as is_updatable
+FROM information_schema.VIEWS
+WHERE TABLE_SCHEMA = ?
+ORDER BY TABLE_NAME
+"#;
+
+/// Query to get stored functions and procedures in a specific schema
+pub const GET_ROUTINES_QUERY: &str = r#"
+SELECT
+    CAST(r.ROUTINE_NAME AS CHAR) as name,
+    CAST(r.ROUTINE_SCHEMA AS CHAR) as `schema`,
+    CAST(r.ROUTINE_TYPE AS CHAR) as function_type,
+    CAST(COALESCE(r.DTD_IDENTIFIER, '') AS CHAR) as return_type,
+    CAST((
+        SELECT GROUP_CONCAT(
+            CONCAT_WS(' ', p.PARAMETER_MODE, p.PARAMETER_NAME, p.DTD_IDENTIFIER)
+            ORDER BY p.ORDINAL_POSITION
+            SEPARATOR ', '
+        )
+        FROM information_schema.PARAMETERS p
+        WHERE p.SPECIFIC_SCHEMA = r.ROUTINE_SCHEMA
+            AND p.SPECIFIC_NAME = r.SPECIFIC_NAME
+            AND p.ROUTINE_TYPE = r.ROUTINE_TYPE
+            AND p.ORDINAL_POSITION > 0
+    ) AS CHAR) as arguments,
+    CAST(r.ROUTINE_BODY AS CHAR) as language,
+    CAST(r.ROUTINE_DEFINITION AS CHAR) as definition,
+    CAST(COALESCE(r.ROUTINE_COMMENT, '') AS CHAR) as comment,
+    CAST(r.DEFINER AS CHAR) as owner
+FROM information_schema.ROUTINES r
+WHERE r.ROUTINE_SCHEMA = ?
+ORDER BY r.ROUTINE_NAME
+"#;
+
+/// Query to get triggers in a specific schema
+pub const GET_TRIGGERS_QUERY: &str = r#"
+SELECT
+    CAST(TRIGGER_NAME AS CHAR) as name,
+    CAST(TRIGGER_SCHEMA AS CHAR) as `schema`,
+    CAST(EVENT_OBJECT_TABLE AS CHAR) as table_name,
+    CAST(ACTION_ORIENTATION AS CHAR) as trigger_type,
+    CAST(EVENT_MANIPULATION AS CHAR) as events,
+    CAST(ACTION_TIMING AS CHAR) as timing
+FROM information_schema.TRIGGERS
+WHERE TRIGGER_SCHEMA = ?
+ORDER BY EVENT_OBJECT_TABLE, TRIGGER_NAME
+"#;
+
+/// Query to get indexes in a specific schema
+///
+/// STATISTICS has one row per indexed column, so rows are grouped per index and
+/// the columns folded into a list in key order. Descending columns carry a
+/// ` DESC` suffix.
+pub const GET_INDEXES_QUERY: &str = r#"
+SELECT
+    CAST(INDEX_NAME AS CHAR) as name,
+    CAST(TABLE_SCHEMA AS CHAR) as `schema`,
+    CAST(TABLE_NAME AS CHAR) as table_name,
+    CAST(MAX(INDEX_TYPE) AS CHAR) as index_type,
+    MIN(NON_UNIQUE) as non_unique,
+    CAST(MAX(COALESCE(INDEX_COMMENT, '')) AS CHAR) as comment,
+    CAST(GROUP_CONCAT(
+        CONCAT(COLUMN_NAME, IF(COLLATION = 'D', ' DESC', ''))
+        ORDER BY SEQ_IN_INDEX
+        SEPARATOR ','
+    ) AS CHAR) as columns
+FROM information_schema.STATISTICS
+WHERE TABLE_SCHEMA = ?
+GROUP BY TABLE_SCHEMA, TABLE_NAME, INDEX_NAME
+ORDER BY TABLE_NAME, INDEX_NAME
+"#;
+
+/// Query to get the foreign keys of a table and those referencing it
+///
+/// Bound as (schema, table, schema, table). KEY_COLUMN_USAGE has one row per
+/// key column, folded into lists in key order.
+pub const GET_FOREIGN_KEYS_QUERY: &str = r#"
+SELECT
+    CAST(k.CONSTRAINT_NAME AS CHAR) as name,
+    CAST(k.TABLE_SCHEMA AS CHAR) as `schema`,
+    CAST(k.TABLE_NAME AS CHAR) as table_name,
+    CAST(GROUP_CONCAT(k.COLUMN_NAME ORDER BY k.ORDINAL_POSITION SEPARATOR ',') AS CHAR) as columns,
+    CAST(k.REFERENCED_TABLE_SCHEMA AS CHAR) as referenced_schema,
+    CAST(k.REFERENCED_TABLE_NAME AS CHAR) as referenced_table,
+    CAST(GROUP_CONCAT(k.REFERENCED_COLUMN_NAME ORDER BY k.ORDINAL_POSITION SEPARATOR ',') AS CHAR)
+        as referenced_columns,
+    CAST(r.UPDATE_RULE AS CHAR) as on_update,
+    CAST(r.DELETE_RULE AS CHAR) as on_delete
+FROM information_schema.KEY_COLUMN_USAGE k
+JOIN information_schema.REFERENTIAL_CONSTRAINTS r
+    ON r.CONSTRAINT_SCHEMA = k.CONSTRAINT_SCHEMA
+    AND r.CONSTRAINT_NAME = k.CONSTRAINT_NAME
+    AND r.TABLE_NAME = k.TABLE_NAME
+WHERE k.REFERENCED_TABLE_NAME IS NOT NULL
+    AND ((k.TABLE_SCHEMA = ? AND k.TABLE_NAME = ?)
+        OR (k.REFERENCED_TABLE_SCHEMA = ? AND k.REFERENCED_TABLE_NAME = ?))
+GROUP BY k.TABLE_SCHEMA, k.TABLE_NAME, k.CONSTRAINT_NAME, k.REFERENCED_TABLE_SCHEMA,
+    k.REFERENCED_TABLE_NAME, r.UPDATE_RULE, r.DELETE_RULE
+ORDER BY k.TABLE_SCHEMA, k.TABLE_NAME, k.CONSTRAINT_NAME
+"#;
+
+/// Query to get the primary key, unique and check constraints of a table
+///
+/// Bound as (schema, table). CHECK_CONSTRAINTS exists from MySQL 8.0.16 and
+/// MariaDB 10.2.
+pub const GET_CONSTRAINTS_QUERY: &str = r#"
+SELECT
+    CAST(tc.CONSTRAINT_NAME AS CHAR) as name,
+    CAST(tc.TABLE_SCHEMA AS CHAR) as `schema`,
+    CAST(tc.TABLE_NAME AS CHAR) as table_name,
+    CAST(tc.CONSTRAINT_TYPE AS CHAR) as constraint_type,
+    CAST(COALESCE(GROUP_CONCAT(k.COLUMN_NAME ORDER BY k.ORDINAL_POSITION SEPARATOR ','), '')
+        AS CHAR) as columns,
+    CAST(MAX(cc.CHECK_CLAUSE) AS CHAR) as check_clause
+FROM information_schema.TABLE_CONSTRAINTS tc
+LEFT JOIN information_schema.KEY_COLUMN_USAGE k
+    ON k.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+    AND k.CONSTRAINT_NAME = tc.CONSTRAINT_NAME
+    AND k.TABLE_NAME = tc.TABLE_NAME
+LEFT JOIN information_schema.CHECK_CONSTRAINTS cc
+    ON cc.CONSTRAINT_SCHEMA = tc.CONSTRAINT_SCHEMA
+    AND cc.CONSTRAINT_NAME = tc.CONSTRAINT_NAME
+WHERE tc.CONSTRAINT_TYPE IN ('PRIMARY KEY', 'UNIQUE', 'CHECK')
+    AND tc.TABLE_SCHEMA = ? AND tc.TABLE_NAME = ?
+GROUP BY tc.TABLE_SCHEMA, tc.TABLE_NAME, tc.CONSTRAINT_NAME, tc.CONSTRAINT_TYPE
+ORDER BY FIELD(tc.CONSTRAINT_TYPE, 'PRIMARY KEY', 'UNIQUE', 'CHECK'), tc.CONSTRAINT_NAME
+"#;
+
+/// Query to get object counts for a specific schema
+pub const GET_OBJECT_COUNTS_QUERY: &str = r#"
+SELECT
+    (SELECT COUNT(*) FROM information_schema.TABLES t
+        WHERE t.TABLE_SCHEMA = s.name AND t.TABLE_TYPE = 'BASE TABLE') as tables,
+    (SELECT COUNT(*) FROM information_schema.VIEWS v
+        WHERE v.TABLE_SCHEMA = s.name) as views,
+    (SELECT COUNT(*) FROM information_schema.ROUTINES r
+        WHERE r.ROUTINE_SCHEMA = s.name AND r.ROUTINE_TYPE = 'FUNCTION') as functions,
+    (SELECT COUNT(*) FROM information_schema.ROUTINES r
+        WHERE r.ROUTINE_SCHEMA = s.name AND r.ROUTINE_TYPE = 'PROCEDURE') as procedures,
+    (SELECT COUNT(*) FROM information_schema.TRIGGERS tr
+        WHERE tr.TRIGGER_SCHEMA = s.name) as triggers,
+    (SELECT COUNT(DISTINCT st.TABLE_NAME, st.INDEX_NAME) FROM information_schema.STATISTICS st
+        WHERE st.TABLE_SCHEMA = s.name) as indexes
+FROM (SELECT ? as name) s
+"#;
+
+/// Query to get database-wide object counts
+///
+/// Object totals only cover user schemas; the system databases hold hundreds of
+/// server tables and views that would drown out the user's own objects.
+pub const GET_DATABASE_OBJECT_COUNTS_QUERY: &str = r#"
+SELECT
+    (SELECT COUNT(*) FROM information_schema.SCHEMATA) as schemas,
+    (SELECT COUNT(*) FROM information_schema.SCHEMATA
+        WHERE SCHEMA_NAME NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as user_schemas,
+    (SELECT COUNT(*) FROM information_schema.SCHEMATA
+        WHERE SCHEMA_NAME IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as system_schemas,
+    (SELECT COUNT(*) FROM information_schema.TABLES
+        WHERE TABLE_TYPE = 'BASE TABLE'
+            AND TABLE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_tables,
+    (SELECT COUNT(*) FROM information_schema.VIEWS
+        WHERE TABLE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_views,
+    (SELECT COUNT(*) FROM information_schema.ROUTINES
+        WHERE ROUTINE_TYPE = 'FUNCTION'
+            AND ROUTINE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_functions,
+    (SELECT COUNT(*) FROM information_schema.ROUTINES
+        WHERE ROUTINE_TYPE = 'PROCEDURE'
+            AND ROUTINE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_procedures,
+    (SELECT COUNT(*) FROM information_schema.TRIGGERS
+        WHERE TRIGGER_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_triggers,
+    (SELECT COUNT(DISTINCT TABLE_SCHEMA, TABLE_NAME, INDEX_NAME) FROM information_schema.STATISTICS
+        WHERE TABLE_SCHEMA NOT IN ('information_schema', 'mysql', 'performance_schema', 'sys')) as total_indexes
+"#;
@@ -1,18 +1,48 @@
+use crate::database::cursor::{serve_cursor, SessionStatement};
+use crate::database::ddl::{
+    self, ColumnDefault, ColumnDefinition, Grant, IndexDefinition, RelationKind, TableDefinition,
+};
+use crate::database::dml::quote_identifier;
+use crate::database::postgresql_values::decode_value;
+use crate::database::script::first_keyword;
+use crate::database::session::SessionTracker;
 use crate::database::{
-    ArgumentMode, Column as DbColumn, ConnectionParams, Database, DatabaseConnection,
-    DatabaseError, DatabaseObjectCounts, Function, FunctionArgument, FunctionType, GeometryValue,
-    Index, IndexColumn, IndexType, NullsOrder, ObjectCounts, QueryColumn, QueryExecutor,
-    QueryResult, QueryRow, QueryValue, Schema, Sequence, SortDirection, Table, Trigger,
-    TriggerEvent, TriggerTiming, TriggerType, View, ViewType,
+    is_read_only_query, ArgumentMode, Column as DbColumn, ConnectionParams, Constraint,
+    ConstraintType, Database, DatabaseConnection, DatabaseError, DatabaseObjectCounts,
+    DatabaseType, Dependency, DependencyKind, DmlStatement, DriverCapabilities, ExplainFormat,
+    ForeignKey, ForeignKeyAction, Function, FunctionArgument, FunctionType, GeometryValue, Index,
+    IndexColumn, IndexType, NullsOrder, ObjectCategory, ObjectCounts, QueryColumn, QueryCursor,
+    QueryExecutor, QueryResult, QueryRow, QuerySession, QueryValue, Schema, Sequence,
+    SortDirection, Table, Trigger, TriggerEvent, TriggerTiming, TriggerType, View, ViewType,
 };
 use async_trait::async_trait;
-use sqlx::{Column, PgPool, Row, TypeInfo, ValueRef};
+use chrono_tz::Tz;
+use futures_util::stream::{self, BoxStream};
+use futures_util::{Stream, StreamExt, TryStreamExt};
+use sqlx::postgres::types::Oid;
+use sqlx::postgres::{PgConnectOptions, PgRow, PgTypeKind};
+use sqlx::{Column, Either, Executor, PgPool, Row, TypeInfo, ValueRef};
+use std::future;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
 use std::time::Instant;
 
+/// Server-side cursor queries of a session are read through
+const CURSOR_NAME: &str = "rbeaver_cursor";
+
+/// Rows fetched from a query's server-side cursor at a time
+const CURSOR_PAGE_ROWS: usize = 1000;
+
 /// PostgreSQL database connection implementation
 pub struct PostgreSQLConnection {
     pool: Option<PgPool>,
     connection_info: Option<String>,
+    /// Backend PID of the session running the current statement
+    running_backend: SessionTracker<i32>,
+    /// statement_timeout in seconds set on each session as it opens; 0 keeps
+    /// the server's default
+    query_timeout: Arc<AtomicU32>,
 }
 
 impl PostgreSQLConnection {
@@ -20,6 +50,8 @@ impl PostgreSQLConnection {
         Self {
             pool: None,
             connection_info: None,
+            running_backend: SessionTracker::new(),
+            query_timeout: Arc::new(AtomicU32::new(0)),
         }
     }
 
@@ -27,6 +59,392 @@ impl PostgreSQLConnection {
     fn get_pool(&self) -> Result<&PgPool, DatabaseError> {
         self.pool.as_ref().ok_or(DatabaseError::NotConnected)
     }
+
+    /// CREATE statements for a table, view or materialized view, or None
+    /// when there is no such relation
+    async fn relation_ddl(
+        &self,
+        schema: &str,
+        name: &str,
+    ) -> Result<Option<String>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let Some(row) = sqlx::query(crate::database::postgresql_queries::GET_RELATION_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_optional(pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let kind = match row.get::<String, _>("kind").as_str() {
+            "v" => RelationKind::View,
+            "m" => RelationKind::MaterializedView,
+            "S" => return self.sequence_ddl(schema, name).await,
+            "r" | "p" | "f" if row.get::<String, _>("persistence") == "u" => {
+                RelationKind::UnloggedTable
+            }
+            "r" | "p" | "f" => RelationKind::Table,
+            other => {
+                return Err(DatabaseError::UnsupportedOperation(format!(
+                    "DDL for relations of kind '{}'",
+                    other
+                )))
+            }
+        };
+
+        let columns = sqlx::query(crate::database::postgresql_queries::GET_COLUMNS_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| {
+                let default_value = row.try_get::<String, _>("default_value").ok();
+                let default = match (
+                    row.get::<String, _>("identity").as_str(),
+                    row.get::<String, _>("generated").as_str(),
+                ) {
+                    ("a", _) => Some(ColumnDefault::Identity { always: true }),
+                    ("d", _) => Some(ColumnDefault::Identity { always: false }),
+                    (_, "s") => default_value.map(ColumnDefault::Generated),
+                    _ => default_value.map(ColumnDefault::Value),
+                };
+                ColumnDefinition {
+                    name: row.get::<String, _>("name"),
+                    data_type: row.get::<String, _>("data_type"),
+                    collation: row.try_get::<String, _>("collation").ok(),
+                    not_null: row.get::<bool, _>("not_null"),
+                    default,
+                    comment: row
+                        .try_get::<String, _>("comment")
+                        .ok()
+                        .filter(|s| !s.is_empty()),
+                }
+            })
+            .collect();
+
+        let indexes = sqlx::query(crate::database::postgresql_queries::GET_TABLE_INDEXES_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(index_definition)
+            .collect();
+
+        let (constraints, foreign_keys) = match kind {
+            RelationKind::Table | RelationKind::UnloggedTable => (
+                self.get_constraints(schema, name).await?,
+                self.get_foreign_keys(schema, name)
+                    .await?
+                    .into_iter()
+                    .filter(|key| key.is_outgoing(schema, name))
+                    .collect(),
+            ),
+            _ => (Vec::new(), Vec::new()),
+        };
+
+        let table = TableDefinition {
+            schema: schema.to_string(),
+            name: name.to_string(),
+            kind,
+            columns,
+            query: row.try_get::<String, _>("query").ok(),
+            constraints,
+            foreign_keys,
+            indexes,
+            comment: row
+                .try_get::<String, _>("comment")
+                .ok()
+                .filter(|s| !s.is_empty()),
+            owner: row.try_get::<String, _>("owner").ok(),
+            grants: self.relation_grants(schema, name).await?,
+        };
+        Ok(Some(ddl::table_ddl(&DatabaseType::PostgreSQL, &table)))
+    }
+
+    /// CREATE SEQUENCE with the sequence's comment, owner and privileges
+    async fn sequence_ddl(
+        &self,
+        schema: &str,
+        name: &str,
+    ) -> Result<Option<String>, DatabaseError> {
+        let pool = self.get_pool()?;
+        let Some(sequence) = self
+            .get_sequences(schema)
+            .await?
+            .into_iter()
+            .find(|sequence| sequence.name == name)
+        else {
+            return Ok(None);
+        };
+
+        let database_type = DatabaseType::PostgreSQL;
+        let qualified = ddl::qualified_name(&database_type, schema, name);
+        let mut sections = vec![ddl::sequence_ddl(&database_type, &sequence)];
+        if let Some(comment) = &sequence.comment {
+            sections.push(ddl::comment_statement(
+                &format!("SEQUENCE {}", qualified),
+                comment,
+            ));
+        }
+        let owner = sqlx::query(crate::database::postgresql_queries::GET_RELATION_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_one(pool)
+            .await?
+            .try_get::<String, _>("owner")
+            .ok();
+        if let Some(owner) = owner {
+            sections.push(ddl::owner_statement(
+                &database_type,
+                "SEQUENCE",
+                &qualified,
+                &owner,
+            ));
+        }
+        let grants = self.relation_grants(schema, name).await?;
+        sections.push(
+            ddl::grant_statements(&database_type, &format!("SEQUENCE {}", qualified), &grants)
+                .join("\n"),
+        );
+        Ok(Some(ddl::join_sections(sections)))
+    }
+
+    /// Privileges on a relation held by roles other than its owner
+    async fn relation_grants(&self, schema: &str, name: &str) -> Result<Vec<Grant>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(crate::database::postgresql_queries::GET_RELATION_PRIVILEGES_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(pool)
+            .await?;
+
+        Ok(rows.iter().map(grant).collect())
+    }
+
+    /// The definitions of every overload of a function or procedure
+    async fn routine_ddl(&self, schema: &str, name: &str) -> Result<Option<String>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(crate::database::postgresql_queries::GET_FUNCTIONS_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(pool)
+            .await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+        let privileges =
+            sqlx::query(crate::database::postgresql_queries::GET_FUNCTION_PRIVILEGES_QUERY)
+                .bind(schema)
+                .bind(name)
+                .fetch_all(pool)
+                .await?;
+
+        let database_type = DatabaseType::PostgreSQL;
+        let mut sections = Vec::new();
+        for row in &rows {
+            let arguments = row.get::<String, _>("arguments");
+            let signature = format!(
+                "{}({})",
+                ddl::qualified_name(&database_type, schema, name),
+                arguments
+            );
+            let object_type = match row.get::<String, _>("kind").as_str() {
+                "p" => "PROCEDURE",
+                "a" => "AGGREGATE",
+                _ => "FUNCTION",
+            };
+
+            match row.try_get::<String, _>("definition") {
+                Ok(definition) => sections.push(format!("{};", ddl::trim_statement(&definition))),
+                Err(_) => sections.push(format!(
+                    "-- {} {} is an aggregate; its definition is not available",
+                    object_type, signature
+                )),
+            }
+            if let Some(comment) = row
+                .try_get::<String, _>("comment")
+                .ok()
+                .filter(|s| !s.is_empty())
+            {
+                sections.push(ddl::comment_statement(
+                    &format!("{} {}", object_type, signature),
+                    &comment,
+                ));
+            }
+            if let Ok(owner) = row.try_get::<String, _>("owner") {
+                sections.push(ddl::owner_statement(
+                    &database_type,
+                    object_type,
+                    &signature,
+                    &owner,
+                ));
+            }
+            let grants: Vec<Grant> = privileges
+                .iter()
+                .filter(|privilege| privilege.get::<String, _>("arguments") == arguments)
+                .map(grant)
+                .collect();
+            // GRANT takes FUNCTION for aggregates
+            let grant_type = if object_type == "PROCEDURE" {
+                "PROCEDURE"
+            } else {
+                "FUNCTION"
+            };
+            sections.push(
+                ddl::grant_statements(
+                    &database_type,
+                    &format!("{} {}", grant_type, signature),
+                    &grants,
+                )
+                .join("\n"),
+            );
+        }
+        Ok(Some(ddl::join_sections(sections)))
+    }
+
+    /// CREATE TRIGGER for the trigger of that name on `table`, or on every
+    /// table with one when `table` is None
+    async fn trigger_ddl(
+        &self,
+        schema: &str,
+        table: Option<&str>,
+        name: &str,
+    ) -> Result<Option<String>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(crate::database::postgresql_queries::GET_TRIGGERS_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        let database_type = DatabaseType::PostgreSQL;
+        let mut sections = Vec::new();
+        for row in &rows {
+            sections.push(format!(
+                "{};",
+                ddl::trim_statement(&row.get::<String, _>("definition"))
+            ));
+            if let Some(comment) = row
+                .try_get::<String, _>("comment")
+                .ok()
+                .filter(|s| !s.is_empty())
+            {
+                let object = format!(
+                    "TRIGGER {} ON {}",
+                    quote_identifier(&database_type, name),
+                    ddl::qualified_name(
+                        &database_type,
+                        schema,
+                        &row.get::<String, _>("table_name")
+                    )
+                );
+                sections.push(ddl::comment_statement(&object, &comment));
+            }
+        }
+        Ok(Some(ddl::join_sections(sections)))
+    }
+
+    /// CREATE INDEX with the index's comment
+    async fn index_ddl(&self, schema: &str, name: &str) -> Result<Option<String>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let Some(row) = sqlx::query(crate::database::postgresql_queries::GET_INDEX_DDL_QUERY)
+            .bind(schema)
+            .bind(name)
+            .fetch_optional(pool)
+            .await?
+        else {
+            return Ok(None);
+        };
+
+        let index = index_definition(&row);
+        let mut sections = vec![format!("{};", ddl::trim_statement(&index.definition))];
+        if let Some(comment) = &index.comment {
+            let object = format!(
+                "INDEX {}",
+                ddl::qualified_name(&DatabaseType::PostgreSQL, schema, name)
+            );
+            sections.push(ddl::comment_statement(&object, comment));
+        }
+        Ok(Some(ddl::join_sections(sections)))
+    }
+
+    /// Run one of the `pg_depend` queries for an object
+    async fn dependency_query(
+        &self,
+        query: &str,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        let catalog = match category {
+            ObjectCategory::Tables
+            | ObjectCategory::Views
+            | ObjectCategory::Sequences
+            | ObjectCategory::Indexes => "relation",
+            ObjectCategory::Functions => "function",
+            ObjectCategory::Triggers => "trigger",
+            ObjectCategory::SystemCatalog => {
+                return Err(DatabaseError::UnsupportedOperation(
+                    "Dependencies of the system catalog".to_string(),
+                ))
+            }
+        };
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(query)
+            .bind(catalog)
+            .bind(schema)
+            .bind(name)
+            .fetch_all(pool)
+            .await?;
+
+        let dependencies = rows
+            .iter()
+            .map(|row| Dependency {
+                kind: DependencyKind::from_name(&row.get::<String, _>("kind")),
+                schema: row.get::<String, _>("schema_name"),
+                name: row.get::<String, _>("name"),
+                table: row.get::<Option<String>, _>("table_name"),
+                columns: row.get::<Vec<String>, _>("columns"),
+                depth: row.get::<i32, _>("depth") as u32,
+                automatic: row.get::<bool, _>("automatic"),
+            })
+            .collect();
+
+        Ok(dependencies)
+    }
+
+    /// Features of PostgreSQL exposed through this driver
+    pub fn capabilities() -> DriverCapabilities {
+        DriverCapabilities {
+            supports_schemas: true,
+            supports_sequences: true,
+            supports_materialized_views: true,
+            supports_routines: true,
+            supports_cancellation: true,
+            supports_statement_timeout: true,
+            supports_dependencies: true,
+            supports_result_editing: true,
+            explain_formats: vec![
+                ExplainFormat::Text,
+                ExplainFormat::Json,
+                ExplainFormat::Xml,
+                ExplainFormat::Yaml,
+            ],
+        }
+    }
 }
 
 impl Default for PostgreSQLConnection {
@@ -38,15 +456,16 @@ impl Default for PostgreSQLConnection {
 #[async_trait]
 impl DatabaseConnection for PostgreSQLConnection {
     async fn connect(&mut self, params: &ConnectionParams) -> Result<(), DatabaseError> {
-        let connection_string = params.build_connection_string();
+        let options = PgConnectOptions::from_str(&params.build_connection_string())?;
 
         // Create connection pool
-        let pool = PgPool::connect(&connection_string).await?;
+        let pool = PgPool::connect_with(options).await?;
 
         // Test the connection
         sqlx::query("SELECT 1").fetch_one(&pool).await?;
 
         self.pool = Some(pool);
+        self.set_query_timeout(params.query_timeout.unwrap_or(0));
         self.connection_info = Some(format!(
             "{}@{}:{}/{}",
             params.username, params.host, params.port, params.database
@@ -79,7 +498,11 @@ impl DatabaseConnection for PostgreSQLConnection {
     async fn test_connection(&self, params: &ConnectionParams) -> Result<(), DatabaseError> {
         let connection_string = params.build_connection_string();
         let pool = PgPool::connect(&connection_string).await?;
-        sqlx::query("SELECT 1").fetch_one(&pool).await?;
+        let mut conn = pool.acquire().await?;
+        // An invalid timeout fails here rather than on the first query
+        apply_statement_timeout(&mut conn, params.query_timeout.unwrap_or(0)).await?;
+        sqlx::query("SELECT 1").fetch_one(&mut *conn).await?;
+        drop(conn);
         pool.close().await;
         Ok(())
     }
@@ -96,54 +519,137 @@ impl DatabaseConnection for PostgreSQLConnection {
 #[async_trait]
 impl QueryExecutor for PostgreSQLConnection {
     async fn execute_query(&self, sql: &str) -> Result<QueryResult, DatabaseError> {
-        let pool = self.get_pool()?;
         let start_time = Instant::now();
+        let mut cursor = self.open_cursor(sql).await?;
+        let rows = cursor.fetch(usize::MAX).await?;
+        Ok(cursor
+            .result_with_rows(rows)
+            .with_execution_time(start_time.elapsed()))
+    }
 
-        let rows = sqlx::query(sql).fetch_all(pool).await?;
-        let execution_time = start_time.elapsed();
+    async fn open_cursor(&self, sql: &str) -> Result<QueryCursor, DatabaseError> {
+        // The session ends once the cursor is done with
+        self.open_session().await?.open_cursor(sql).await
+    }
 
-        let mut result = QueryResult::new(sql.to_string()).with_execution_time(execution_time);
+    async fn open_session(&self) -> Result<QuerySession, DatabaseError> {
+        let pool = self.get_pool()?;
 
-        if !rows.is_empty() {
-            // Extract column information from the first row
-            let first_row = &rows[0];
-            let columns: Vec<QueryColumn> = first_row
-                .columns()
-                .iter()
-                .enumerate()
-                .map(|(i, col)| {
-                    QueryColumn::new(
-                        col.name().to_string(),
-                        col.type_info().name().to_string(),
-                        i,
-                        true, // TODO: Get actual nullable info
-                    )
-                })
-                .collect();
+        // Run on a dedicated session so its backend can be cancelled
+        let mut conn = pool.acquire().await?;
+        let backend_pid: i32 = sqlx::query_scalar("SELECT pg_backend_pid()")
+            .fetch_one(&mut *conn)
+            .await?;
+        // Read now so settings changes reach connections that are already open
+        let timeout = self.query_timeout.load(Ordering::Relaxed);
+        apply_statement_timeout(&mut conn, timeout).await?;
+        let mut time_zone = session_time_zone(&mut conn).await?;
+
+        let (session, mut statements) = QuerySession::channel();
+        let running_backend = self.running_backend.clone();
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            let mut time_zone_changed = false;
+            while let Some(SessionStatement { sql, requests }) = statements.recv().await {
+                // The statement before may have changed the time zone
+                if time_zone_changed {
+                    match session_time_zone(&mut conn).await {
+                        Ok(zone) => time_zone = zone,
+                        Err(e) => log::warn!("Failed to read the session time zone: {}", e),
+                    }
+                }
+                time_zone_changed = may_change_time_zone(&sql);
+
+                // Forget the PID once the statement is done with
+                let running = running_backend.track(backend_pid);
+                let error = |err| statement_error(err, timeout, running.cancel_sent());
+
+                // Queries are read through a server-side cursor a page at a
+                // time, so the statement timeout covers fetching each page
+                // rather than the pauses between fetches
+                let mut cursor_transaction = None;
+                let stream: BoxStream<'_, Result<Either<u64, PgRow>, DatabaseError>> =
+                    if !is_read_only_query(&sql) {
+                        conn.fetch_many(sqlx::query(&sql))
+                            .map_ok(|step| step.map_left(|done| done.rows_affected()))
+                            .map_err(error)
+                            .boxed()
+                    } else {
+                        match declare_cursor(&mut conn, &sql).await {
+                            Ok(own_transaction) => {
+                                cursor_transaction = Some(own_transaction);
+                                cursor_pages(&mut conn).map_err(error).boxed()
+                            }
+                            Err(err) => stream::once(future::ready(Err(error(err)))).boxed(),
+                        }
+                    };
+                serve_cursor(
+                    requests,
+                    stream,
+                    postgres_columns,
+                    |columns| describe_postgres_columns(pool.clone(), columns),
+                    |row| convert_postgres_row(row, time_zone),
+                )
+                .await;
+
+                if let Some(own_transaction) = cursor_transaction {
+                    close_cursor(&mut conn, own_transaction).await;
+                }
+            }
+        });
 
-            result = result.with_columns(columns);
+        Ok(session)
+    }
 
-            // Convert rows to QueryRow format
-            let query_rows: Result<Vec<QueryRow>, DatabaseError> = rows
-                .iter()
-                .map(|row| {
-                    let values: Result<Vec<QueryValue>, DatabaseError> = (0..row.len())
-                        .map(|i| convert_postgres_value(row, i))
-                        .collect();
-                    values.map(QueryRow::new)
-                })
-                .collect();
+    async fn execute_non_query(&self, sql: &str) -> Result<u64, DatabaseError> {
+        let pool = self.get_pool()?;
+        let result = sqlx::query(sql).execute(pool).await?;
+        Ok(result.rows_affected())
+    }
 
-            result = result.with_rows(query_rows?);
+    async fn execute_dml(&self, statements: &[DmlStatement]) -> Result<u64, DatabaseError> {
+        let pool = self.get_pool()?;
+        let mut transaction = pool.begin().await?;
+
+        // Returning early drops the transaction, which rolls it back
+        let mut rows_affected = 0;
+        for statement in statements {
+            let mut query = sqlx::query(&statement.sql);
+            for param in &statement.params {
+                query = query.bind(param.clone());
+            }
+            let rows = query.execute(&mut *transaction).await?.rows_affected();
+            statement.check_rows_affected(rows)?;
+            rows_affected += rows;
         }
 
-        Ok(result)
+        transaction.commit().await?;
+        Ok(rows_affected)
     }
 
-    async fn execute_non_query(&self, sql: &str) -> Result<u64, DatabaseError> {
+    async fn cancel_query(&self) -> Result<(), DatabaseError> {
         let pool = self.get_pool()?;
-        let result = sqlx::query(sql).execute(pool).await?;
-        Ok(result.rows_affected())
+        let Some(backend_pid) = self.running_backend.cancel() else {
+            return Ok(());
+        };
+
+        let signalled: bool = sqlx::query_scalar("SELECT pg_cancel_backend($1)")
+            .bind(backend_pid)
+            .fetch_one(pool)
+            .await?;
+        if !signalled {
+            return Err(DatabaseError::QueryFailed(format!(
+                "Could not signal backend {}",
+                backend_pid
+            )));
+        }
+
+        log::info!("Sent cancel request to PostgreSQL backend {}", backend_pid);
+        Ok(())
+    }
+
+    fn set_query_timeout(&self, seconds: u32) {
+        self.query_timeout.store(seconds, Ordering::Relaxed);
     }
 
     async fn get_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
@@ -178,8 +684,8 @@ impl QueryExecutor for PostgreSQLConnection {
                 t.table_type,
                 COALESCE(obj_description(c.oid), '') as table_comment
              FROM information_schema.tables t
-             LEFT JOIN pg_class c ON c.relname = t.table_name
-             LEFT JOIN pg_namespace n ON n.oid = c.relnamespace AND n.nspname = t.table_schema
+             LEFT JOIN pg_namespace n ON n.nspname = t.table_schema
+             LEFT JOIN pg_class c ON c.relnamespace = n.oid AND c.relname = t.table_name
              WHERE t.table_schema = $1
              ORDER BY t.table_name",
         )
@@ -213,14 +719,15 @@ impl QueryExecutor for PostgreSQLConnection {
         let rows = sqlx::query(
             "SELECT
                 c.column_name,
-                c.data_type,
+                COALESCE(format_type(a.atttypid, a.atttypmod), c.data_type) as data_type,
                 c.is_nullable,
                 c.column_default,
                 CASE WHEN pk.column_name IS NOT NULL THEN true ELSE false END as is_primary_key,
                 COALESCE(col_description(pgc.oid, c.ordinal_position), '') as column_comment
              FROM information_schema.columns c
-             LEFT JOIN pg_class pgc ON pgc.relname = c.table_name
-             LEFT JOIN pg_namespace pgn ON pgn.oid = pgc.relnamespace AND pgn.nspname = c.table_schema
+             LEFT JOIN pg_namespace pgn ON pgn.nspname = c.table_schema
+             LEFT JOIN pg_class pgc ON pgc.relnamespace = pgn.oid AND pgc.relname = c.table_name
+             LEFT JOIN pg_attribute a ON a.attrelid = pgc.oid AND a.attname = c.column_name
              LEFT JOIN (
                  SELECT ku.column_name
                  FROM information_schema.table_constraints tc
@@ -232,11 +739,12 @@ impl QueryExecutor for PostgreSQLConnection {
                      AND tc.table_name = $2
              ) pk ON c.column_name = pk.column_name
              WHERE c.table_schema = $1 AND c.table_name = $2
-             ORDER BY c.ordinal_position"
+             ORDER BY c.ordinal_position",
         )
         .bind(schema)
         .bind(table)
-        .fetch_all(pool).await?;
+        .fetch_all(pool)
+        .await?;
 
         let columns = rows
             .iter()
@@ -347,7 +855,6 @@ impl QueryExecutor for PostgreSQLConnection {
                     .ok()
                     .filter(|s| !s.is_empty());
 
-                // Parse arguments - simplified for now
                 let arguments = parse_function_arguments(
                     &row.try_get::<String, _>("arguments").unwrap_or_default(),
                 );
@@ -501,6 +1008,147 @@ impl QueryExecutor for PostgreSQLConnection {
         Ok(indexes)
     }
 
+    async fn get_foreign_keys(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKey>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(crate::database::postgresql_queries::GET_FOREIGN_KEYS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let foreign_keys = rows
+            .iter()
+            .map(|row| ForeignKey {
+                name: row.get::<String, _>("name"),
+                schema: row.get::<String, _>("schema"),
+                table_name: row.get::<String, _>("table_name"),
+                columns: row.get::<Vec<String>, _>("columns"),
+                referenced_schema: row.get::<String, _>("referenced_schema"),
+                referenced_table: row.get::<String, _>("referenced_table"),
+                referenced_columns: row.get::<Vec<String>, _>("referenced_columns"),
+                on_update: ForeignKeyAction::from_rule(&row.get::<String, _>("on_update")),
+                on_delete: ForeignKeyAction::from_rule(&row.get::<String, _>("on_delete")),
+                comment: row
+                    .try_get::<String, _>("comment")
+                    .ok()
+                    .filter(|s| !s.is_empty()),
+            })
+            .collect();
+
+        Ok(foreign_keys)
+    }
+
+    async fn get_constraints(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<Constraint>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(crate::database::postgresql_queries::GET_CONSTRAINTS_QUERY)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        let constraints = rows
+            .iter()
+            .map(|row| {
+                let constraint_type = match row.get::<String, _>("constraint_type").as_str() {
+                    "PRIMARY KEY" => ConstraintType::PrimaryKey,
+                    "UNIQUE" => ConstraintType::Unique,
+                    "CHECK" => ConstraintType::Check,
+                    _ => ConstraintType::Exclusion,
+                };
+
+                Constraint {
+                    name: row.get::<String, _>("name"),
+                    schema: row.get::<String, _>("schema"),
+                    table_name: row.get::<String, _>("table_name"),
+                    constraint_type,
+                    columns: row.get::<Vec<String>, _>("columns"),
+                    definition: row.get::<String, _>("definition"),
+                    comment: row
+                        .try_get::<String, _>("comment")
+                        .ok()
+                        .filter(|s| !s.is_empty()),
+                }
+            })
+            .collect();
+
+        Ok(constraints)
+    }
+
+    async fn get_object_ddl(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        let ddl = match category {
+            ObjectCategory::Tables | ObjectCategory::Views | ObjectCategory::Sequences => {
+                self.relation_ddl(schema, name).await?
+            }
+            ObjectCategory::Functions => self.routine_ddl(schema, name).await?,
+            ObjectCategory::Triggers => self.trigger_ddl(schema, None, name).await?,
+            ObjectCategory::Indexes => self.index_ddl(schema, name).await?,
+            ObjectCategory::SystemCatalog => {
+                return Err(DatabaseError::UnsupportedOperation(
+                    "DDL for the system catalog".to_string(),
+                ))
+            }
+        };
+        ddl.ok_or_else(|| DatabaseError::QueryFailed(format!("{}.{} does not exist", schema, name)))
+    }
+
+    async fn get_trigger_ddl(
+        &self,
+        schema: &str,
+        table: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        self.trigger_ddl(schema, Some(table), name)
+            .await?
+            .ok_or_else(|| {
+                DatabaseError::QueryFailed(format!("{}.{}.{} does not exist", schema, table, name))
+            })
+    }
+
+    async fn get_dependencies(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        self.dependency_query(
+            crate::database::postgresql_queries::GET_DEPENDENCIES_QUERY,
+            category,
+            schema,
+            name,
+        )
+        .await
+    }
+
+    async fn get_dependents(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        self.dependency_query(
+            crate::database::postgresql_queries::GET_DEPENDENTS_QUERY,
+            category,
+            schema,
+            name,
+        )
+        .await
+    }
+
     async fn get_all_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
         let pool = self.get_pool()?;
 
@@ -569,10 +1217,287 @@ impl Database for PostgreSQLConnection {
     }
 }
 
+/// Column descriptions of a PostgreSQL result row
+fn postgres_columns(row: &sqlx::postgres::PgRow) -> Vec<QueryColumn> {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            let type_info = col.type_info();
+            let element_type = match type_info.kind() {
+                PgTypeKind::Array(element) => Some(element.name().to_string()),
+                _ => None,
+            };
+            QueryColumn {
+                table_oid: col.relation_id().map(|oid| oid.0),
+                column_number: col.relation_attribute_no(),
+                type_oid: type_info.oid().map(|oid| oid.0),
+                element_type,
+                ..QueryColumn::new(
+                    col.name().to_string(),
+                    type_info.name().to_string(),
+                    i,
+                    true,
+                )
+            }
+        })
+        .collect()
+}
+
+/// Fill in what the row description leaves out from the system catalogs:
+/// nullability and names of base table columns, and type names with their
+/// modifiers
+///
+/// The row description carries the base type of a domain column, so the
+/// declared type of a base table column wins over it. The lookup runs on
+/// another pooled connection, as the session's own is busy streaming the
+/// result. Columns are returned as they are if it fails.
+async fn describe_postgres_columns(
+    pool: PgPool,
+    mut columns: Vec<QueryColumn>,
+) -> Vec<QueryColumn> {
+    let type_oids: Vec<Oid> = columns
+        .iter()
+        .map(|column| Oid(column.type_oid.unwrap_or(0)))
+        .collect();
+    let table_oids: Vec<Oid> = columns
+        .iter()
+        .map(|column| Oid(column.table_oid.unwrap_or(0)))
+        .collect();
+    let column_numbers: Vec<i16> = columns
+        .iter()
+        .map(|column| column.column_number.unwrap_or(0))
+        .collect();
+
+    let query = r#"
+        SELECT
+            c.ordinal::int8 AS ordinal,
+            format_type(t.oid, a.atttypmod) AS type_name,
+            format_type(t.oid, NULL) AS unmodified_type,
+            CASE WHEN t.typcategory = 'A' THEN format_type(t.typelem, NULL) END AS element_type,
+            COALESCE(a.attnotnull, false) AS not_null,
+            n.nspname::text AS source_schema,
+            cl.relname::text AS source_table,
+            a.attname::text AS source_column,
+            bt.typname::text AS base_type,
+            CASE WHEN t.typtype = 'd' THEN t.typtypmod ELSE a.atttypmod END AS type_modifier
+        FROM unnest($1::oid[], $2::oid[], $3::int2[]) WITH ORDINALITY AS c(type_oid, table_oid, column_number, ordinal)
+        LEFT JOIN pg_attribute a
+            ON a.attrelid = c.table_oid AND a.attnum = c.column_number AND c.column_number > 0
+        LEFT JOIN pg_class cl ON cl.oid = a.attrelid
+        LEFT JOIN pg_namespace n ON n.oid = cl.relnamespace
+        JOIN pg_type t ON t.oid = COALESCE(a.atttypid, c.type_oid)
+        JOIN pg_type bt ON bt.oid = COALESCE(NULLIF(t.typbasetype, 0), t.oid)
+    "#;
+
+    let rows = match sqlx::query(query)
+        .bind(type_oids)
+        .bind(table_oids)
+        .bind(column_numbers)
+        .fetch_all(&pool)
+        .await
+    {
+        Ok(rows) => rows,
+        Err(e) => {
+            log::warn!("Failed to look up result column details: {}", e);
+            return columns;
+        }
+    };
+
+    for row in rows {
+        let Some(column) = usize::try_from(row.get::<i64, _>("ordinal") - 1)
+            .ok()
+            .and_then(|index| columns.get_mut(index))
+        else {
+            continue;
+        };
+
+        column.type_name = row.get("type_name");
+        column.unmodified_type = row.get("unmodified_type");
+        if let Some(element_type) = row.get::<Option<String>, _>("element_type") {
+            column.element_type = Some(element_type);
+        }
+        column.nullable = !row.get::<bool, _>("not_null");
+        column.source_schema = row.get("source_schema");
+        column.source_table = row.get("source_table");
+        column.source_column = row.get("source_column");
+
+        let base_type: String = row.get("base_type");
+        let type_modifier: Option<i32> = row.get("type_modifier");
+        (column.precision, column.scale) = type_modifier_precision(&base_type, type_modifier);
+    }
+
+    columns
+}
+
+/// Precision and scale encoded in a type modifier (`atttypmod`)
+fn type_modifier_precision(
+    base_type: &str,
+    type_modifier: Option<i32>,
+) -> (Option<u32>, Option<u32>) {
+    let Some(modifier) = type_modifier.filter(|modifier| *modifier >= 0) else {
+        return (None, None);
+    };
+    let modifier = modifier as u32;
+
+    match base_type {
+        // ((precision << 16) | scale) + VARHDRSZ
+        "numeric" if modifier >= 4 => (
+            Some(((modifier - 4) >> 16) & 0xffff),
+            Some((modifier - 4) & 0xffff),
+        ),
+        // Length + VARHDRSZ
+        "varchar" | "bpchar" if modifier >= 4 => (Some(modifier - 4), None),
+        "bit" | "varbit" => (Some(modifier), None),
+        "time" | "timetz" | "timestamp" | "timestamptz" => (Some(modifier), None),
+        // The low 16 bits hold the fractional-second digits
+        "interval" => Some(modifier & 0xffff)
+            .filter(|precision| *precision != 0xffff)
+            .map_or((None, None), |precision| (Some(precision), None)),
+        _ => (None, None),
+    }
+}
+
+/// Limit the statements of a session to `seconds`, or restore the server's
+/// default for 0
+async fn apply_statement_timeout(
+    conn: &mut sqlx::postgres::PgConnection,
+    seconds: u32,
+) -> Result<(), DatabaseError> {
+    let sql = if seconds > 0 {
+        format!("SET statement_timeout = '{}s'", seconds)
+    } else {
+        "RESET statement_timeout".to_string()
+    };
+    conn.execute(sql.as_str()).await?;
+    Ok(())
+}
+
+/// Open [`CURSOR_NAME`] over a query, returning whether it got a transaction
+/// of its own
+///
+/// Cursors only live inside a transaction block. A session that is not in
+/// one gets a transaction for the cursor, which [`close_cursor`] commits.
+async fn declare_cursor(
+    conn: &mut sqlx::postgres::PgConnection,
+    sql: &str,
+) -> Result<bool, sqlx::Error> {
+    let declare = format!("DECLARE {} NO SCROLL CURSOR FOR {}", CURSOR_NAME, sql);
+    match conn.execute(declare.as_str()).await {
+        Ok(_) => Ok(false),
+        Err(err)
+            if err
+                .as_database_error()
+                .and_then(|db_err| db_err.code())
+                .as_deref()
+                == Some("25P01") =>
+        {
+            conn.execute("BEGIN").await?;
+            if let Err(err) = conn.execute(declare.as_str()).await {
+                let _ = conn.execute("ROLLBACK").await;
+                return Err(err);
+            }
+            Ok(true)
+        }
+        Err(err) => Err(err),
+    }
+}
+
+/// Rows of [`CURSOR_NAME`], fetched [`CURSOR_PAGE_ROWS`] at a time
+fn cursor_pages(
+    conn: &mut sqlx::postgres::PgConnection,
+) -> impl Stream<Item = Result<Either<u64, PgRow>, sqlx::Error>> + '_ {
+    stream::try_unfold(Some(conn), |conn| async move {
+        let Some(conn) = conn else {
+            return Ok(None);
+        };
+
+        // Not kept as a prepared statement: its columns change with the cursor
+        let fetch = format!("FETCH {} FROM {}", CURSOR_PAGE_ROWS, CURSOR_NAME);
+        let steps: Vec<_> = (&mut *conn)
+            .fetch_many(sqlx::query(&fetch).persistent(false))
+            .map_ok(|step| step.map_left(|done| done.rows_affected()))
+            .try_collect()
+            .await?;
+
+        // A short page is the last one
+        let rows = steps.iter().filter(|step| step.is_right()).count();
+        let next = (rows == CURSOR_PAGE_ROWS).then_some(conn);
+        Ok::<_, sqlx::Error>(Some((steps, next)))
+    })
+    .map_ok(|steps| stream::iter(steps.into_iter().map(Ok)))
+    .try_flatten()
+}
+
+/// Close [`CURSOR_NAME`], committing the transaction it was opened in if
+/// that was its own
+async fn close_cursor(conn: &mut sqlx::postgres::PgConnection, own_transaction: bool) {
+    let close = if own_transaction {
+        "COMMIT".to_string()
+    } else {
+        format!("CLOSE {}", CURSOR_NAME)
+    };
+    if let Err(e) = conn.execute(close.as_str()).await {
+        log::warn!("Failed to close the query cursor: {}", e);
+    }
+}
+
+/// Error of a statement run on a session with the given statement timeout
+///
+/// The server reports a timeout like a cancel request, so a statement
+/// cancelled without this client asking for it timed out.
+fn statement_error(err: sqlx::Error, timeout: u32, cancel_sent: bool) -> DatabaseError {
+    if let Some(db_err) = err.as_database_error() {
+        if db_err.code().as_deref() == Some("57014") && timeout > 0 && !cancel_sent {
+            return DatabaseError::Timeout(db_err.message().to_string());
+        }
+    }
+    err.into()
+}
+
+/// Whether a statement may change the session's TimeZone setting
+///
+/// Besides SET and RESET in their spellings, `set_config()` changes it, and
+/// ROLLBACK and DISCARD undo changes. The connection keeps the server's
+/// ParameterStatus reports to itself, so the setting is read again after any
+/// of them.
+fn may_change_time_zone(sql: &str) -> bool {
+    matches!(
+        first_keyword(sql).as_deref(),
+        Some("SET" | "RESET" | "DISCARD" | "ROLLBACK" | "ABORT")
+    ) || sql.to_ascii_lowercase().contains("set_config")
+}
+
+/// Time zone the session shows `timestamptz` values in
+///
+/// Falls back to UTC for settings that are not IANA zone names, such as POSIX
+/// offset strings.
+async fn session_time_zone(conn: &mut sqlx::postgres::PgConnection) -> Result<Tz, DatabaseError> {
+    let name: String = sqlx::query_scalar("SELECT current_setting('TimeZone')")
+        .fetch_one(conn)
+        .await?;
+    Ok(name.parse().unwrap_or_else(|_| {
+        log::warn!("Unknown session time zone {}, showing times in UTC", name);
+        Tz::UTC
+    }))
+}
+
+/// Convert a PostgreSQL row to QueryRow format
+fn convert_postgres_row(
+    row: &sqlx::postgres::PgRow,
+    time_zone: Tz,
+) -> Result<QueryRow, DatabaseError> {
+    let values: Result<Vec<QueryValue>, DatabaseError> = (0..row.len())
+        .map(|i| convert_postgres_value(row, i, time_zone))
+        .collect();
+    values.map(QueryRow::new)
+}
+
 /// Convert PostgreSQL values to QueryValue
 fn convert_postgres_value(
     row: &sqlx::postgres::PgRow,
     index: usize,
+    time_zone: Tz,
 ) -> Result<QueryValue, DatabaseError> {
     let column = &row.columns()[index];
     let type_name = column.type_info().name();
@@ -584,6 +1509,18 @@ fn convert_postgres_value(
         }
     }
 
+    // Arrays, ranges and composites hold values of other types, and enums
+    // need their type's labels
+    if matches!(
+        column.type_info().kind(),
+        PgTypeKind::Array(_)
+            | PgTypeKind::Range(_)
+            | PgTypeKind::Composite(_)
+            | PgTypeKind::Enum(_)
+    ) {
+        return Ok(decode_raw_value(row, index, time_zone));
+    }
+
     // Convert based on PostgreSQL type
     match type_name {
         "BOOL" => Ok(QueryValue::Bool(row.get(index))),
@@ -605,15 +1542,16 @@ fn convert_postgres_value(
             }
         }
         "BYTEA" => Ok(QueryValue::Bytes(row.get(index))),
-        "TIMESTAMP" | "TIMESTAMPTZ" => {
-            let dt: chrono::DateTime<chrono::Utc> = row.get(index);
-            Ok(QueryValue::DateTime(dt))
+        // Decoded from the wire format, which keeps numeric scale and infinities
+        "NUMERIC" | "DATE" | "TIME" | "TIMETZ" | "TIMESTAMP" | "TIMESTAMPTZ" | "INTERVAL"
+        | "UUID" | "INET" | "CIDR" | "MACADDR" | "MACADDR8" | "MONEY" | "BIT" | "VARBIT" => {
+            Ok(decode_raw_value(row, index, time_zone))
         }
         "JSON" | "JSONB" => {
             let json: serde_json::Value = row.get(index);
             Ok(QueryValue::Json(json))
         }
-        "USER-DEFINED" => {
+        "USER-DEFINED" | "geometry" | "geography" => {
             // Handle PostGIS geometry types
             handle_user_defined_type(row, index, column)
         }
@@ -639,6 +1577,16 @@ fn convert_postgres_value(
     }
 }
 
+/// Decode a value from its wire format bytes
+fn decode_raw_value(row: &sqlx::postgres::PgRow, index: usize, time_zone: Tz) -> QueryValue {
+    let type_info = row.columns()[index].type_info();
+    let bytes = row
+        .try_get_raw(index)
+        .ok()
+        .and_then(|value| value.as_bytes().ok());
+    decode_value(type_info, bytes, time_zone)
+}
+
 /// Handle USER-DEFINED types, particularly PostGIS geometry types
 fn handle_user_defined_type(
     row: &sqlx::postgres::PgRow,
@@ -648,29 +1596,27 @@ fn handle_user_defined_type(
     // Try to get the actual type name from the column type info
     let type_info = column.type_info();
     let type_name = type_info.name();
-
-    // For PostGIS geometry types, we need to extract the geometry information
-    // First, try to get the raw bytes
-    if let Ok(bytes) = row.try_get::<Vec<u8>, _>(index) {
-        // This is likely a geometry in binary format (WKB)
-        return Ok(QueryValue::Geometry(GeometryValue::new(
-            "GEOMETRY".to_string(),
-            None, // We'll need to parse SRID from WKB if needed
-            format!("<Binary Geometry Data: {} bytes>", bytes.len()),
-            Some(bytes),
-        )));
+    let bytes = row
+        .try_get_raw(index)
+        .ok()
+        .and_then(|value| value.as_bytes().ok());
+
+    // geometry and geography values are sent as EWKB
+    if let Some(mut geometry) = bytes.and_then(GeometryValue::from_ewkb) {
+        geometry.geography = type_name == "geography";
+        return Ok(QueryValue::Geometry(geometry));
     }
 
     // Try to get as string (might be WKT format)
-    if let Ok(wkt_string) = row.try_get::<String, _>(index) {
+    if let Some(wkt_string) = bytes.and_then(|bytes| std::str::from_utf8(bytes).ok()) {
         // Parse the geometry type from WKT
-        let geometry_type = extract_geometry_type_from_wkt(&wkt_string);
-        let srid = extract_srid_from_wkt(&wkt_string);
+        let geometry_type = extract_geometry_type_from_wkt(wkt_string);
+        let srid = extract_srid_from_wkt(wkt_string);
 
         return Ok(QueryValue::Geometry(GeometryValue::new(
             geometry_type,
             srid,
-            wkt_string,
+            wkt_string.to_string(),
             None,
         )));
     }
@@ -719,38 +1665,90 @@ fn extract_srid_from_wkt(wkt: &str) -> Option<i32> {
     }
 }
 
-/// Parse function arguments from PostgreSQL function signature
+/// Parse function arguments from the output of `pg_get_function_arguments`,
+/// e.g. `a integer, OUT total numeric(10,2), VARIADIC rest text[] DEFAULT '{}'`
 fn parse_function_arguments(args_str: &str) -> Vec<FunctionArgument> {
-    if args_str.is_empty() {
-        return Vec::new();
-    }
+    // Words that start a type name of more than one word
+    const MULTI_WORD_TYPES: &[&str] = &[
+        "bit",
+        "character",
+        "double",
+        "interval",
+        "national",
+        "time",
+        "timestamp",
+    ];
+
+    split_top_level(args_str)
+        .into_iter()
+        .filter(|arg| !arg.is_empty())
+        .map(|arg| {
+            let (declaration, default_value) = match arg.find(" DEFAULT ") {
+                Some(at) => (&arg[..at], Some(arg[at + 9..].trim().to_string())),
+                None => (arg, None),
+            };
 
-    // Simple parsing - in a real implementation, this would be more sophisticated
-    args_str
-        .split(',')
-        .enumerate()
-        .map(|(i, arg)| {
-            let trimmed = arg.trim();
-            let parts: Vec<&str> = trimmed.split_whitespace().collect();
-
-            let (name, data_type) = if parts.len() >= 2 {
-                (Some(parts[0].to_string()), parts[1].to_string())
-            } else if parts.len() == 1 {
-                (None, parts[0].to_string())
-            } else {
-                (None, "unknown".to_string())
+            let mut rest = declaration.trim();
+            let mut mode = ArgumentMode::In;
+            for (keyword, keyword_mode) in [
+                ("INOUT ", ArgumentMode::InOut),
+                ("IN ", ArgumentMode::In),
+                ("OUT ", ArgumentMode::Out),
+                ("VARIADIC ", ArgumentMode::Variadic),
+            ] {
+                if let Some(stripped) = rest.strip_prefix(keyword) {
+                    mode = keyword_mode;
+                    rest = stripped.trim_start();
+                    break;
+                }
+            }
+
+            // The name comes first when there is one; a type never ends a
+            // quoted word and a multi-word type starts with a known word
+            let (name, data_type) = match rest.split_once(' ') {
+                Some((first, type_name))
+                    if first.starts_with('"')
+                        || !MULTI_WORD_TYPES.contains(&first.to_lowercase().as_str()) =>
+                {
+                    (Some(first.to_string()), type_name.trim().to_string())
+                }
+                _ => (None, rest.to_string()),
             };
 
             FunctionArgument {
                 name,
                 data_type,
-                mode: ArgumentMode::In, // Default to IN mode
-                default_value: None,
+                mode,
+                default_value,
             }
         })
         .collect()
 }
 
+/// Split a list on the commas outside parentheses, quotes and brackets
+fn split_top_level(list: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut quote: Option<char> = None;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '\'' | '"') => quote = Some(c),
+            (None, '(' | '[' | '{') => depth += 1,
+            (None, ')' | ']' | '}') => depth = depth.saturating_sub(1),
+            (None, ',') if depth == 0 => {
+                parts.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    parts.push(list[start..].trim());
+    parts
+}
+
 /// Parse trigger events from comma-separated string
 fn parse_trigger_events(events_str: &str) -> Vec<TriggerEvent> {
     if events_str.is_empty() {
@@ -769,23 +1767,61 @@ fn parse_trigger_events(events_str: &str) -> Vec<TriggerEvent> {
         .collect()
 }
 
+/// An index from a row of the DDL index queries
+fn index_definition(row: &sqlx::postgres::PgRow) -> IndexDefinition {
+    IndexDefinition {
+        name: row.get::<String, _>("name"),
+        definition: row.get::<String, _>("definition"),
+        comment: row
+            .try_get::<String, _>("comment")
+            .ok()
+            .filter(|s| !s.is_empty()),
+    }
+}
+
+/// A privilege from a row of the privilege queries
+fn grant(row: &sqlx::postgres::PgRow) -> Grant {
+    Grant {
+        grantee: row.try_get::<String, _>("grantee").ok(),
+        privilege: row.get::<String, _>("privilege"),
+        grantable: row.get::<bool, _>("grantable"),
+    }
+}
+
 /// Parse index columns from comma-separated string
 fn parse_index_columns(columns_str: &str) -> Vec<IndexColumn> {
     if columns_str.is_empty() {
         return Vec::new();
     }
 
-    columns_str
-        .split(',')
+    // Each key is a column or expression, followed by its sort order when
+    // that is not the default
+    split_top_level(columns_str)
+        .into_iter()
         .enumerate()
         .map(|(i, column)| {
-            let trimmed = column.trim();
+            let mut key = column.trim();
+            let mut nulls_order = None;
+            if let Some(stripped) = key.strip_suffix(" NULLS FIRST") {
+                key = stripped;
+                nulls_order = Some(NullsOrder::First);
+            } else if let Some(stripped) = key.strip_suffix(" NULLS LAST") {
+                key = stripped;
+                nulls_order = Some(NullsOrder::Last);
+            }
+            let direction = match key.strip_suffix(" DESC") {
+                Some(stripped) => {
+                    key = stripped;
+                    SortDirection::Descending
+                }
+                None => SortDirection::Ascending,
+            };
 
             IndexColumn {
-                name: trimmed.to_string(),
+                name: key.to_string(),
                 position: i as i32 + 1,
-                direction: Some(SortDirection::Ascending), // Default
-                nulls_order: None,
+                direction: Some(direction),
+                nulls_order,
             }
         })
         .collect()
@@ -41,8 +41,8 @@ SELECT
     pg_get_userbyid(c.relowner) as owner,
     v.is_updatable = 'YES' as is_updatable
 FROM information_schema.views v
-LEFT JOIN pg_class c ON c.relname = v.table_name
-LEFT JOIN pg_namespace n ON n.oid = c.relnamespace AND n.nspname = v.table_schema
+LEFT JOIN pg_namespace n ON n.nspname = v.table_schema
+LEFT JOIN pg_class c ON c.relnamespace = n.oid AND c.relname = v.table_name
 WHERE v.table_schema = $1
 UNION ALL
 SELECT 
@@ -171,11 +171,15 @@ SELECT
     COALESCE(obj_description(i.oid), '') as comment,
     array_to_string(
         ARRAY(
-            SELECT a.attname
-            FROM pg_attribute a
-            WHERE a.attrelid = t.oid
-                AND a.attnum = ANY(ix.indkey)
-            ORDER BY array_position(ix.indkey, a.attnum)
+            SELECT pg_get_indexdef(i.oid, k.n, true)
+                || CASE WHEN ix.indoption[k.n - 1] & 1 = 1 THEN ' DESC' ELSE '' END
+                || CASE
+                    WHEN ix.indoption[k.n - 1] & 3 = 2 THEN ' NULLS FIRST'
+                    WHEN ix.indoption[k.n - 1] & 3 = 1 THEN ' NULLS LAST'
+                    ELSE ''
+                END
+            FROM generate_series(1, ix.indnkeyatts::int) k(n)
+            ORDER BY k.n
         ),
         ','
     ) as columns
@@ -188,6 +192,207 @@ WHERE i.relkind = 'i' AND n.nspname = $1
 ORDER BY t.relname, i.relname
 "#;
 
+/// Query to get the foreign keys of a table ($1.$2) and those referencing it
+pub const GET_FOREIGN_KEYS_QUERY: &str = r#"
+SELECT 
+    con.conname as name,
+    n.nspname as schema,
+    c.relname as table_name,
+    ARRAY(
+        SELECT a.attname::text
+        FROM unnest(con.conkey) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) as columns,
+    rn.nspname as referenced_schema,
+    rc.relname as referenced_table,
+    ARRAY(
+        SELECT a.attname::text
+        FROM unnest(con.confkey) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_attribute a ON a.attrelid = con.confrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) as referenced_columns,
+    CASE con.confupdtype
+        WHEN 'r' THEN 'RESTRICT'
+        WHEN 'c' THEN 'CASCADE'
+        WHEN 'n' THEN 'SET NULL'
+        WHEN 'd' THEN 'SET DEFAULT'
+        ELSE 'NO ACTION'
+    END as on_update,
+    CASE con.confdeltype
+        WHEN 'r' THEN 'RESTRICT'
+        WHEN 'c' THEN 'CASCADE'
+        WHEN 'n' THEN 'SET NULL'
+        WHEN 'd' THEN 'SET DEFAULT'
+        ELSE 'NO ACTION'
+    END as on_delete,
+    COALESCE(obj_description(con.oid, 'pg_constraint'), '') as comment
+FROM pg_constraint con
+JOIN pg_class c ON c.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_class rc ON rc.oid = con.confrelid
+JOIN pg_namespace rn ON rn.oid = rc.relnamespace
+WHERE con.contype = 'f'
+    AND ((n.nspname = $1 AND c.relname = $2) OR (rn.nspname = $1 AND rc.relname = $2))
+ORDER BY n.nspname, c.relname, con.conname
+"#;
+
+/// Query to get the primary key, unique, check and exclusion constraints of
+/// a table ($1.$2)
+pub const GET_CONSTRAINTS_QUERY: &str = r#"
+SELECT 
+    con.conname as name,
+    n.nspname as schema,
+    c.relname as table_name,
+    CASE con.contype
+        WHEN 'p' THEN 'PRIMARY KEY'
+        WHEN 'u' THEN 'UNIQUE'
+        WHEN 'c' THEN 'CHECK'
+        ELSE 'EXCLUDE'
+    END as constraint_type,
+    ARRAY(
+        SELECT a.attname::text
+        FROM unnest(con.conkey) WITH ORDINALITY k(attnum, ord)
+        JOIN pg_attribute a ON a.attrelid = con.conrelid AND a.attnum = k.attnum
+        ORDER BY k.ord
+    ) as columns,
+    pg_get_constraintdef(con.oid) as definition,
+    COALESCE(obj_description(con.oid, 'pg_constraint'), '') as comment
+FROM pg_constraint con
+JOIN pg_class c ON c.oid = con.conrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE con.contype IN ('p', 'u', 'c', 'x')
+    AND n.nspname = $1 AND c.relname = $2
+ORDER BY position(con.contype in 'pucx'), con.conname
+"#;
+
+/// Query to get what kind of relation $1.$2 is, with its owner, comment and
+/// the query of a view
+pub const GET_RELATION_DDL_QUERY: &str = r#"
+SELECT 
+    c.relkind::text as kind,
+    c.relpersistence::text as persistence,
+    pg_get_userbyid(c.relowner) as owner,
+    COALESCE(obj_description(c.oid, 'pg_class'), '') as comment,
+    CASE WHEN c.relkind IN ('v', 'm') THEN pg_get_viewdef(c.oid, true) END as query
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1 AND c.relname = $2
+"#;
+
+/// Query to get the column declarations of a relation ($1.$2)
+pub const GET_COLUMNS_DDL_QUERY: &str = r#"
+SELECT 
+    a.attname as name,
+    format_type(a.atttypid, a.atttypmod) as data_type,
+    a.attnotnull as not_null,
+    pg_get_expr(d.adbin, d.adrelid) as default_value,
+    a.attidentity::text as identity,
+    a.attgenerated::text as generated,
+    CASE WHEN a.attcollation <> t.typcollation
+        THEN quote_ident(cn.nspname) || '.' || quote_ident(co.collname)
+    END as collation,
+    COALESCE(col_description(a.attrelid, a.attnum), '') as comment
+FROM pg_attribute a
+JOIN pg_class c ON c.oid = a.attrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+JOIN pg_type t ON t.oid = a.atttypid
+LEFT JOIN pg_attrdef d ON d.adrelid = a.attrelid AND d.adnum = a.attnum
+LEFT JOIN pg_collation co ON co.oid = a.attcollation
+LEFT JOIN pg_namespace cn ON cn.oid = co.collnamespace
+WHERE n.nspname = $1 AND c.relname = $2
+    AND a.attnum > 0 AND NOT a.attisdropped
+ORDER BY a.attnum
+"#;
+
+/// Query to get the indexes of a relation ($1.$2) that no constraint created
+pub const GET_TABLE_INDEXES_DDL_QUERY: &str = r#"
+SELECT 
+    ic.relname as name,
+    pg_get_indexdef(i.indexrelid) as definition,
+    COALESCE(obj_description(i.indexrelid, 'pg_class'), '') as comment
+FROM pg_index i
+JOIN pg_class ic ON ic.oid = i.indexrelid
+JOIN pg_class c ON c.oid = i.indrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1 AND c.relname = $2
+    AND NOT EXISTS (
+        SELECT 1 FROM pg_constraint con
+        WHERE con.conindid = i.indexrelid AND con.contype IN ('p', 'u', 'x')
+    )
+ORDER BY ic.relname
+"#;
+
+/// Query to get the definition of an index ($1.$2)
+pub const GET_INDEX_DDL_QUERY: &str = r#"
+SELECT 
+    ic.relname as name,
+    pg_get_indexdef(ic.oid) as definition,
+    COALESCE(obj_description(ic.oid, 'pg_class'), '') as comment
+FROM pg_class ic
+JOIN pg_namespace n ON n.oid = ic.relnamespace
+WHERE n.nspname = $1 AND ic.relname = $2 AND ic.relkind IN ('i', 'I')
+"#;
+
+/// Query to get the privileges on a relation ($1.$2) held by roles other
+/// than its owner; a NULL grantee is PUBLIC
+pub const GET_RELATION_PRIVILEGES_QUERY: &str = r#"
+SELECT 
+    CASE WHEN a.grantee <> 0 THEN pg_get_userbyid(a.grantee) END as grantee,
+    a.privilege_type as privilege,
+    a.is_grantable as grantable
+FROM pg_class c
+JOIN pg_namespace n ON n.oid = c.relnamespace
+CROSS JOIN LATERAL aclexplode(c.relacl) a
+WHERE n.nspname = $1 AND c.relname = $2 AND a.grantee <> c.relowner
+ORDER BY a.grantee <> 0, 1, a.privilege_type
+"#;
+
+/// Query to get the definitions of every overload of a routine ($1.$2);
+/// aggregates have none
+pub const GET_FUNCTIONS_DDL_QUERY: &str = r#"
+SELECT 
+    pg_get_function_identity_arguments(p.oid) as arguments,
+    p.prokind::text as kind,
+    CASE WHEN p.prokind <> 'a' THEN pg_get_functiondef(p.oid) END as definition,
+    pg_get_userbyid(p.proowner) as owner,
+    COALESCE(obj_description(p.oid, 'pg_proc'), '') as comment
+FROM pg_proc p
+JOIN pg_namespace n ON n.oid = p.pronamespace
+WHERE n.nspname = $1 AND p.proname = $2
+ORDER BY 1
+"#;
+
+/// Query to get the privileges on the overloads of a routine ($1.$2) held by
+/// roles other than its owner; a NULL grantee is PUBLIC
+pub const GET_FUNCTION_PRIVILEGES_QUERY: &str = r#"
+SELECT 
+    pg_get_function_identity_arguments(p.oid) as arguments,
+    CASE WHEN a.grantee <> 0 THEN pg_get_userbyid(a.grantee) END as grantee,
+    a.privilege_type as privilege,
+    a.is_grantable as grantable
+FROM pg_proc p
+JOIN pg_namespace n ON n.oid = p.pronamespace
+CROSS JOIN LATERAL aclexplode(p.proacl) a
+WHERE n.nspname = $1 AND p.proname = $2 AND a.grantee <> p.proowner
+ORDER BY 1, a.grantee <> 0, 2, a.privilege_type
+"#;
+
+/// Query to get the definitions of the triggers named $2 in schema $1, on
+/// table $3 or on any table when it is NULL
+pub const GET_TRIGGERS_DDL_QUERY: &str = r#"
+SELECT 
+    c.relname as table_name,
+    pg_get_triggerdef(t.oid, true) as definition,
+    COALESCE(obj_description(t.oid, 'pg_trigger'), '') as comment
+FROM pg_trigger t
+JOIN pg_class c ON c.oid = t.tgrelid
+JOIN pg_namespace n ON n.oid = c.relnamespace
+WHERE n.nspname = $1 AND t.tgname = $2 AND NOT t.tgisinternal
+    AND ($3::text IS NULL OR c.relname = $3)
+ORDER BY c.relname
+"#;
+
 /// Query to get object counts for a specific schema
 pub const GET_OBJECT_COUNTS_QUERY: &str = r#"
 SELECT 
@@ -225,3 +430,245 @@ LEFT JOIN pg_class c ON c.relnamespace = n.oid
 LEFT JOIN pg_proc p ON p.pronamespace = n.oid
 LEFT JOIN pg_trigger t ON t.tgrelid = c.oid
 "#;
+
+/// Query to get the objects that depend on an object, following `pg_depend`
+/// up to ten links away
+///
+/// $1 says where the object lives (`relation`, `function` or `trigger`),
+/// $2.$3 names it; functions match by name or by `name(argument types)`.
+/// View rules count as their view and column defaults as their column, and
+/// the dependents of a default are left out since dropping what the default
+/// uses drops only the default. `columns` are the object's columns a
+/// dependent goes through.
+pub const GET_DEPENDENTS_QUERY: &str = r#"
+WITH RECURSIVE roots AS (
+    SELECT 'pg_class'::regclass::oid AS classid, c.oid AS objid
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE $1::text = 'relation' AND n.nspname = $2 AND c.relname = $3
+    UNION ALL
+    -- Functions returning or taking the table's row type
+    SELECT 'pg_type'::regclass::oid, c.reltype
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE $1::text = 'relation' AND n.nspname = $2 AND c.relname = $3 AND c.reltype <> 0
+    UNION ALL
+    SELECT 'pg_proc'::regclass::oid, p.oid
+    FROM pg_proc p
+    JOIN pg_namespace n ON n.oid = p.pronamespace
+    WHERE $1::text = 'function' AND n.nspname = $2
+        AND $3::text IN (p.proname::text, p.proname || '(' || oidvectortypes(p.proargtypes) || ')')
+    UNION ALL
+    SELECT 'pg_trigger'::regclass::oid, t.oid
+    FROM pg_trigger t
+    JOIN pg_class c ON c.oid = t.tgrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE $1::text = 'trigger' AND n.nspname = $2 AND t.tgname = $3 AND NOT t.tgisinternal
+),
+links AS (
+    SELECT
+        CASE WHEN r.oid IS NOT NULL OR ad.oid IS NOT NULL
+            THEN 'pg_class'::regclass::oid ELSE d.classid END AS classid,
+        COALESCE(r.ev_class, ad.adrelid, d.objid) AS objid,
+        CASE WHEN r.oid IS NOT NULL THEN 0 ELSE COALESCE(ad.adnum::int, d.objsubid) END AS objsubid,
+        ad.oid IS NOT NULL AS is_default,
+        d.refclassid, d.refobjid, d.refobjsubid,
+        d.deptype = 'a' AS automatic
+    FROM pg_depend d
+    LEFT JOIN pg_rewrite r ON d.classid = 'pg_rewrite'::regclass AND r.oid = d.objid
+    LEFT JOIN pg_attrdef ad ON d.classid = 'pg_attrdef'::regclass AND ad.oid = d.objid
+    WHERE d.deptype IN ('n', 'a')
+),
+dependents AS (
+    SELECT l.classid, l.objid, l.objsubid, l.is_default, l.refobjsubid AS via,
+        l.automatic, 1 AS depth,
+        ARRAY[l.classid::text || '/' || l.objid::text] AS path
+    FROM links l
+    JOIN roots ON l.refclassid = roots.classid AND l.refobjid = roots.objid
+    WHERE (l.classid, l.objid) NOT IN (SELECT classid, objid FROM roots)
+    UNION ALL
+    SELECT l.classid, l.objid, l.objsubid, l.is_default, x.via,
+        l.automatic, x.depth + 1,
+        x.path || (l.classid::text || '/' || l.objid::text)
+    FROM dependents x
+    JOIN links l ON l.refclassid = x.classid AND l.refobjid = x.objid
+        AND (x.objsubid = 0 OR l.refobjsubid = x.objsubid)
+    WHERE x.depth < 10
+        AND NOT x.is_default
+        AND (l.classid::text || '/' || l.objid::text) <> ALL (x.path)
+        AND (l.classid, l.objid) NOT IN (SELECT classid, objid FROM roots)
+),
+grouped AS (
+    SELECT classid, objid, objsubid, is_default,
+        min(depth) AS depth,
+        bool_or(automatic) AS automatic,
+        array_agg(DISTINCT via) FILTER (WHERE via <> 0) AS via
+    FROM dependents
+    GROUP BY classid, objid, objsubid, is_default
+)
+SELECT
+    CASE
+        WHEN g.is_default THEN 'default'
+        WHEN c.oid IS NOT NULL AND g.objsubid <> 0 THEN 'column'
+        WHEN c.relkind IN ('r', 'p', 'f') THEN 'table'
+        WHEN c.relkind = 'v' THEN 'view'
+        WHEN c.relkind = 'm' THEN 'materialized view'
+        WHEN c.relkind = 'S' THEN 'sequence'
+        WHEN c.relkind IN ('i', 'I') THEN 'index'
+        WHEN c.relkind = 'c' THEN 'type'
+        WHEN p.prokind = 'p' THEN 'procedure'
+        WHEN p.oid IS NOT NULL THEN 'function'
+        WHEN con.oid IS NOT NULL THEN 'constraint'
+        WHEN t.oid IS NOT NULL THEN 'trigger'
+        ELSE ident.type
+    END as kind,
+    COALESCE(n.nspname::text, ident.schema, '') as schema_name,
+    CASE
+        WHEN c.oid IS NOT NULL AND g.objsubid <> 0 THEN a.attname::text
+        WHEN c.oid IS NOT NULL THEN c.relname::text
+        WHEN p.oid IS NOT NULL THEN p.proname || '(' || oidvectortypes(p.proargtypes) || ')'
+        WHEN con.oid IS NOT NULL THEN con.conname::text
+        WHEN t.oid IS NOT NULL THEN t.tgname::text
+        ELSE ident.identity
+    END as name,
+    owner.relname::text as table_name,
+    ARRAY(
+        SELECT ra.attname::text
+        FROM pg_attribute ra
+        JOIN roots ON roots.classid = 'pg_class'::regclass AND ra.attrelid = roots.objid
+        WHERE ra.attnum = ANY(g.via)
+        ORDER BY ra.attnum
+    ) as columns,
+    g.depth,
+    g.automatic
+FROM grouped g
+CROSS JOIN LATERAL pg_identify_object(g.classid, g.objid, 0) ident
+LEFT JOIN pg_class c ON g.classid = 'pg_class'::regclass AND c.oid = g.objid
+LEFT JOIN pg_attribute a ON a.attrelid = c.oid AND a.attnum = g.objsubid AND g.objsubid <> 0
+LEFT JOIN pg_index ix ON ix.indexrelid = c.oid
+LEFT JOIN pg_proc p ON g.classid = 'pg_proc'::regclass AND p.oid = g.objid
+LEFT JOIN pg_constraint con ON g.classid = 'pg_constraint'::regclass AND con.oid = g.objid
+LEFT JOIN pg_trigger t ON g.classid = 'pg_trigger'::regclass AND t.oid = g.objid
+LEFT JOIN pg_class owner ON owner.oid = CASE
+    WHEN g.objsubid <> 0 THEN c.oid
+    ELSE COALESCE(ix.indrelid, NULLIF(con.conrelid, 0), t.tgrelid)
+END
+LEFT JOIN pg_namespace n ON n.oid = COALESCE(c.relnamespace, p.pronamespace, con.connamespace, owner.relnamespace)
+ORDER BY g.depth, schema_name, table_name NULLS FIRST, name
+"#;
+
+/// Query to get the objects an object depends on, following `pg_depend` up
+/// to ten links away
+///
+/// Takes the same parameters as `GET_DEPENDENTS_QUERY`. A relation includes
+/// what its view rule, column defaults, constraints, triggers and indexes
+/// depend on, and a row type counts as its relation. `columns` are the
+/// columns of a dependency in use.
+pub const GET_DEPENDENCIES_QUERY: &str = r#"
+WITH RECURSIVE roots AS (
+    SELECT 'pg_class'::regclass::oid AS classid, c.oid AS objid
+    FROM pg_class c
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE $1::text = 'relation' AND n.nspname = $2 AND c.relname = $3
+    UNION ALL
+    SELECT 'pg_proc'::regclass::oid, p.oid
+    FROM pg_proc p
+    JOIN pg_namespace n ON n.oid = p.pronamespace
+    WHERE $1::text = 'function' AND n.nspname = $2
+        AND $3::text IN (p.proname::text, p.proname || '(' || oidvectortypes(p.proargtypes) || ')')
+    UNION ALL
+    SELECT 'pg_trigger'::regclass::oid, t.oid
+    FROM pg_trigger t
+    JOIN pg_class c ON c.oid = t.tgrelid
+    JOIN pg_namespace n ON n.oid = c.relnamespace
+    WHERE $1::text = 'trigger' AND n.nspname = $2 AND t.tgname = $3 AND NOT t.tgisinternal
+),
+links AS (
+    SELECT
+        d.classid AS own_classid,
+        d.objid AS own_objid,
+        CASE WHEN COALESCE(r.ev_class, ad.adrelid, NULLIF(con.conrelid, 0), t.tgrelid, ix.indrelid) IS NOT NULL
+            THEN 'pg_class'::regclass::oid ELSE d.classid END AS classid,
+        COALESCE(r.ev_class, ad.adrelid, NULLIF(con.conrelid, 0), t.tgrelid, ix.indrelid, d.objid) AS objid,
+        CASE WHEN rt.oid IS NOT NULL THEN 'pg_class'::regclass::oid ELSE d.refclassid END AS refclassid,
+        COALESCE(rt.typrelid, d.refobjid) AS refobjid,
+        d.refobjsubid,
+        d.deptype = 'a' AS automatic
+    FROM pg_depend d
+    LEFT JOIN pg_rewrite r ON d.classid = 'pg_rewrite'::regclass AND r.oid = d.objid
+    LEFT JOIN pg_attrdef ad ON d.classid = 'pg_attrdef'::regclass AND ad.oid = d.objid
+    LEFT JOIN pg_constraint con ON d.classid = 'pg_constraint'::regclass AND con.oid = d.objid
+    LEFT JOIN pg_trigger t ON d.classid = 'pg_trigger'::regclass AND t.oid = d.objid
+    LEFT JOIN pg_index ix ON d.classid = 'pg_class'::regclass AND ix.indexrelid = d.objid
+    LEFT JOIN pg_type rt ON d.refclassid = 'pg_type'::regclass AND rt.oid = d.refobjid AND rt.typrelid <> 0
+    WHERE d.deptype IN ('n', 'a')
+        AND d.refclassid NOT IN ('pg_namespace'::regclass, 'pg_language'::regclass)
+),
+dependencies AS (
+    SELECT l.refclassid AS classid, l.refobjid AS objid, l.refobjsubid AS objsubid,
+        l.automatic, 1 AS depth,
+        ARRAY[l.refclassid::text || '/' || l.refobjid::text] AS path
+    FROM links l
+    JOIN roots ON (l.classid = roots.classid AND l.objid = roots.objid)
+        OR (l.own_classid = roots.classid AND l.own_objid = roots.objid)
+    WHERE (l.refclassid, l.refobjid) NOT IN (SELECT classid, objid FROM roots)
+    UNION ALL
+    SELECT l.refclassid, l.refobjid, l.refobjsubid,
+        l.automatic, x.depth + 1,
+        x.path || (l.refclassid::text || '/' || l.refobjid::text)
+    FROM dependencies x
+    JOIN links l ON l.classid = x.classid AND l.objid = x.objid
+    WHERE x.depth < 10
+        AND (l.refclassid::text || '/' || l.refobjid::text) <> ALL (x.path)
+        AND (l.refclassid, l.refobjid) NOT IN (SELECT classid, objid FROM roots)
+),
+grouped AS (
+    SELECT classid, objid,
+        min(depth) AS depth,
+        bool_or(automatic) AS automatic,
+        array_agg(DISTINCT objsubid) FILTER (WHERE objsubid <> 0) AS columns
+    FROM dependencies
+    GROUP BY classid, objid
+)
+SELECT
+    CASE
+        WHEN c.relkind IN ('r', 'p', 'f') THEN 'table'
+        WHEN c.relkind = 'v' THEN 'view'
+        WHEN c.relkind = 'm' THEN 'materialized view'
+        WHEN c.relkind = 'S' THEN 'sequence'
+        WHEN c.relkind IN ('i', 'I') THEN 'index'
+        WHEN c.relkind = 'c' THEN 'type'
+        WHEN p.prokind = 'p' THEN 'procedure'
+        WHEN p.oid IS NOT NULL THEN 'function'
+        WHEN con.oid IS NOT NULL THEN 'constraint'
+        WHEN t.oid IS NOT NULL THEN 'trigger'
+        ELSE ident.type
+    END as kind,
+    COALESCE(n.nspname::text, ident.schema, '') as schema_name,
+    CASE
+        WHEN c.oid IS NOT NULL THEN c.relname::text
+        WHEN p.oid IS NOT NULL THEN p.proname || '(' || oidvectortypes(p.proargtypes) || ')'
+        WHEN con.oid IS NOT NULL THEN con.conname::text
+        WHEN t.oid IS NOT NULL THEN t.tgname::text
+        ELSE ident.identity
+    END as name,
+    owner.relname::text as table_name,
+    ARRAY(
+        SELECT a.attname::text
+        FROM pg_attribute a
+        WHERE c.oid IS NOT NULL AND a.attrelid = c.oid AND a.attnum = ANY(g.columns)
+        ORDER BY a.attnum
+    ) as columns,
+    g.depth,
+    g.automatic
+FROM grouped g
+CROSS JOIN LATERAL pg_identify_object(g.classid, g.objid, 0) ident
+LEFT JOIN pg_class c ON g.classid = 'pg_class'::regclass AND c.oid = g.objid
+LEFT JOIN pg_index ix ON ix.indexrelid = c.oid
+LEFT JOIN pg_proc p ON g.classid = 'pg_proc'::regclass AND p.oid = g.objid
+LEFT JOIN pg_constraint con ON g.classid = 'pg_constraint'::regclass AND con.oid = g.objid
+LEFT JOIN pg_trigger t ON g.classid = 'pg_trigger'::regclass AND t.oid = g.objid
+LEFT JOIN pg_class owner ON owner.oid = COALESCE(ix.indrelid, NULLIF(con.conrelid, 0), t.tgrelid)
+LEFT JOIN pg_namespace n ON n.oid = COALESCE(c.relnamespace, p.pronamespace, con.connamespace, owner.relnamespace)
+ORDER BY g.depth, schema_name, table_name NULLS FIRST, name
+"#;
@@ -0,0 +1,342 @@
+//! Decoding of PostgreSQL values from the binary wire format
+//!
+//! sqlx can only decode a value into a Rust type chosen up front, which does
+//! not work for the elements of arrays, ranges and composites whose types are
+//! only known from the type description. Those are decoded here instead.
+
+use crate::database::{
+    EnumValue, GeometryValue, IntervalValue, NetworkAddress, QueryValue, RangeValue,
+};
+use bigdecimal::BigDecimal;
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
+use chrono_tz::Tz;
+use sqlx::postgres::{PgTypeInfo, PgTypeKind};
+use sqlx::TypeInfo;
+use std::net::IpAddr;
+use std::str::FromStr;
+
+// Address families of inet values, from inet.h
+const PGSQL_AF_INET: u8 = 2;
+const PGSQL_AF_INET6: u8 = 3;
+
+// Range flags, from rangetypes.h
+const RANGE_EMPTY: u8 = 0x01;
+const RANGE_LB_INC: u8 = 0x02;
+const RANGE_UB_INC: u8 = 0x04;
+const RANGE_LB_INF: u8 = 0x08;
+const RANGE_UB_INF: u8 = 0x10;
+
+/// Decode a value of the given type; `None` bytes stand for NULL
+///
+/// Values that cannot be decoded become a `<TYPE>` placeholder rather than
+/// failing the whole row.
+pub(crate) fn decode_value(
+    type_info: &PgTypeInfo,
+    bytes: Option<&[u8]>,
+    time_zone: Tz,
+) -> QueryValue {
+    let Some(bytes) = bytes else {
+        return QueryValue::Null;
+    };
+
+    let decoded = match type_info.kind() {
+        PgTypeKind::Array(element) => decode_array(element, bytes, time_zone),
+        PgTypeKind::Range(subtype) => decode_range(subtype, bytes, time_zone),
+        PgTypeKind::Composite(fields) => decode_composite(fields, bytes, time_zone),
+        PgTypeKind::Domain(base) => Some(decode_value(base, Some(bytes), time_zone)),
+        PgTypeKind::Enum(labels) => std::str::from_utf8(bytes).ok().map(|label| {
+            QueryValue::Enum(EnumValue {
+                type_name: type_info.name().to_string(),
+                label: label.to_string(),
+                position: labels.iter().position(|known| known == label),
+            })
+        }),
+        PgTypeKind::Simple | PgTypeKind::Pseudo => {
+            decode_scalar(type_info.name(), bytes, time_zone)
+        }
+    };
+    decoded.unwrap_or_else(|| QueryValue::String(format!("<{}>", type_info.name())))
+}
+
+/// Decode an array; dimensions after the first become nested arrays
+fn decode_array(element: &PgTypeInfo, bytes: &[u8], time_zone: Tz) -> Option<QueryValue> {
+    let mut reader = Reader::new(bytes);
+    let dimensions = reader.i32()?;
+    let _has_nulls = reader.i32()?;
+    let _element_oid = reader.u32()?;
+
+    let mut lengths = Vec::new();
+    for _ in 0..dimensions {
+        lengths.push(usize::try_from(reader.i32()?).ok()?);
+        let _lower_bound = reader.i32()?;
+    }
+    if lengths.is_empty() {
+        return Some(QueryValue::Array(Vec::new()));
+    }
+
+    let mut elements = Vec::new();
+    for _ in 0..lengths.iter().product::<usize>() {
+        elements.push(decode_value(element, reader.value()?, time_zone));
+    }
+
+    // Group the flat element list from the innermost dimension outwards
+    for length in lengths[1..].iter().rev() {
+        let mut grouped = Vec::new();
+        let mut rest = elements.into_iter();
+        loop {
+            let group: Vec<QueryValue> = rest.by_ref().take(*length).collect();
+            if group.is_empty() {
+                break;
+            }
+            grouped.push(QueryValue::Array(group));
+        }
+        elements = grouped;
+    }
+    Some(QueryValue::Array(elements))
+}
+
+fn decode_range(subtype: &PgTypeInfo, bytes: &[u8], time_zone: Tz) -> Option<QueryValue> {
+    let mut reader = Reader::new(bytes);
+    let flags = reader.take(1)?[0];
+    if flags & RANGE_EMPTY != 0 {
+        return Some(QueryValue::Range(RangeValue::empty()));
+    }
+
+    let mut bound = |infinite: u8| -> Option<Option<Box<QueryValue>>> {
+        if flags & infinite != 0 {
+            return Some(None);
+        }
+        let value = decode_value(subtype, reader.value()?, time_zone);
+        Some(Some(Box::new(value)))
+    };
+    let lower = bound(RANGE_LB_INF)?;
+    let upper = bound(RANGE_UB_INF)?;
+
+    Some(QueryValue::Range(RangeValue {
+        lower,
+        upper,
+        lower_inclusive: flags & RANGE_LB_INC != 0,
+        upper_inclusive: flags & RANGE_UB_INC != 0,
+        empty: false,
+    }))
+}
+
+fn decode_composite(
+    fields: &[(String, PgTypeInfo)],
+    bytes: &[u8],
+    time_zone: Tz,
+) -> Option<QueryValue> {
+    let mut reader = Reader::new(bytes);
+    let count = usize::try_from(reader.i32()?).ok()?;
+
+    let mut values = Vec::with_capacity(count);
+    for index in 0..count {
+        let _oid = reader.u32()?;
+        let value = reader.value()?;
+        let (name, value) = match fields.get(index) {
+            Some((name, type_info)) => (name.clone(), decode_value(type_info, value, time_zone)),
+            // Anonymous records carry no field names or type descriptions
+            None => (
+                format!("f{}", index + 1),
+                value.map_or(QueryValue::Null, |bytes| {
+                    text(bytes).unwrap_or_else(|| QueryValue::Bytes(bytes.to_vec()))
+                }),
+            ),
+        };
+        values.push((name, value));
+    }
+    Some(QueryValue::Composite(values))
+}
+
+fn decode_scalar(type_name: &str, bytes: &[u8], time_zone: Tz) -> Option<QueryValue> {
+    let mut reader = Reader::new(bytes);
+    let value = match type_name {
+        "BOOL" => QueryValue::Bool(reader.take(1)?[0] != 0),
+        "INT2" => QueryValue::Int32(i16::from_be_bytes(reader.array()?) as i32),
+        "INT4" => QueryValue::Int32(reader.i32()?),
+        "INT8" => QueryValue::Int64(reader.i64()?),
+        "OID" => QueryValue::Int64(reader.u32()? as i64),
+        "FLOAT4" => QueryValue::Float32(f32::from_be_bytes(reader.array()?)),
+        "FLOAT8" => QueryValue::Float64(f64::from_be_bytes(reader.array()?)),
+        "NUMERIC" => decode_numeric(bytes)?,
+        "BYTEA" => QueryValue::Bytes(bytes.to_vec()),
+        "DATE" => match reader.i32()? {
+            i32::MAX => QueryValue::String("infinity".to_string()),
+            i32::MIN => QueryValue::String("-infinity".to_string()),
+            days => QueryValue::Date(
+                postgres_epoch()
+                    .date()
+                    .checked_add_signed(Duration::days(days as i64))?,
+            ),
+        },
+        "TIME" => QueryValue::Time(time_of_day(reader.i64()?)?, None),
+        "TIMETZ" => {
+            let time = time_of_day(reader.i64()?)?;
+            // Stored as seconds west of UTC
+            QueryValue::Time(time, Some(-reader.i32()?))
+        }
+        "TIMESTAMP" | "TIMESTAMPTZ" => match reader.i64()? {
+            i64::MAX => QueryValue::String("infinity".to_string()),
+            i64::MIN => QueryValue::String("-infinity".to_string()),
+            microseconds => {
+                let timestamp =
+                    postgres_epoch().checked_add_signed(Duration::microseconds(microseconds))?;
+                if type_name == "TIMESTAMP" {
+                    QueryValue::NaiveDateTime(timestamp)
+                } else {
+                    let utc = timestamp.and_utc();
+                    QueryValue::DateTime(utc.with_timezone(&time_zone).fixed_offset())
+                }
+            }
+        },
+        "INTERVAL" => {
+            let microseconds = reader.i64()?;
+            let days = reader.i32()?;
+            let months = reader.i32()?;
+            QueryValue::Interval(IntervalValue::new(months, days, microseconds))
+        }
+        "JSON" => QueryValue::Json(serde_json::from_slice(bytes).ok()?),
+        // JSONB starts with a format version byte
+        "JSONB" => QueryValue::Json(serde_json::from_slice(bytes.get(1..)?).ok()?),
+        "UUID" => QueryValue::Uuid(uuid::Uuid::from_slice(bytes).ok()?),
+        "INET" | "CIDR" => {
+            let [family, prefix_len, is_cidr, length] = reader.array()?;
+            let address = reader.take(length as usize)?;
+            let address = match family {
+                PGSQL_AF_INET => IpAddr::from(<[u8; 4]>::try_from(address).ok()?),
+                PGSQL_AF_INET6 => IpAddr::from(<[u8; 16]>::try_from(address).ok()?),
+                _ => return None,
+            };
+            QueryValue::Network(NetworkAddress {
+                address,
+                prefix_len,
+                is_cidr: is_cidr != 0,
+            })
+        }
+        "MACADDR" | "MACADDR8" => QueryValue::MacAddress(bytes.to_vec()),
+        "MONEY" => QueryValue::Money(reader.i64()?),
+        "BIT" | "VARBIT" => {
+            let length = usize::try_from(reader.i32()?).ok()?;
+            let bits = (0..length)
+                .map(|bit| {
+                    let byte = reader.bytes.get(bit / 8)?;
+                    Some(if byte & (0x80 >> (bit % 8)) != 0 {
+                        '1'
+                    } else {
+                        '0'
+                    })
+                })
+                .collect::<Option<String>>()?;
+            QueryValue::Bits(bits)
+        }
+        // PostGIS types, e.g. as array elements
+        "geometry" | "geography" => {
+            let mut geometry = GeometryValue::from_ewkb(bytes)?;
+            geometry.geography = type_name == "geography";
+            QueryValue::Geometry(geometry)
+        }
+        // Text-like types send their text as is
+        _ => text(bytes)?,
+    };
+    Some(value)
+}
+
+/// Decode a numeric value, keeping the scale it was stored with
+///
+/// NaN and the infinities have no BigDecimal equivalent and become text.
+fn decode_numeric(bytes: &[u8]) -> Option<QueryValue> {
+    let mut reader = Reader::new(bytes);
+    let digit_count = usize::try_from(reader.i16()?).ok()?;
+    let weight = reader.i16()? as i64;
+    let sign = reader.u16()?;
+    let scale = reader.u16()?;
+
+    let sign = match sign {
+        0x0000 => "",
+        0x4000 => "-",
+        0xC000 => return Some(QueryValue::String("NaN".to_string())),
+        0xD000 => return Some(QueryValue::String("Infinity".to_string())),
+        0xF000 => return Some(QueryValue::String("-Infinity".to_string())),
+        _ => return None,
+    };
+
+    // Base-10000 digits, the first of which is worth 10000^weight
+    let mut digits = String::from("0");
+    for _ in 0..digit_count {
+        digits.push_str(&format!("{:04}", reader.u16()?));
+    }
+    let exponent = 4 * (weight + 1 - digit_count as i64);
+    let decimal = BigDecimal::from_str(&format!("{}{}E{}", sign, digits, exponent)).ok()?;
+    Some(QueryValue::Decimal(decimal.with_scale(scale as i64)))
+}
+
+fn text(bytes: &[u8]) -> Option<QueryValue> {
+    std::str::from_utf8(bytes)
+        .ok()
+        .map(|text| QueryValue::String(text.to_string()))
+}
+
+/// Midnight of 2000-01-01, from which dates and timestamps are counted
+fn postgres_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(2000, 1, 1)
+        .and_then(|date| date.and_hms_opt(0, 0, 0))
+        .expect("valid epoch")
+}
+
+fn time_of_day(microseconds: i64) -> Option<NaiveTime> {
+    let seconds = u32::try_from(microseconds.div_euclid(1_000_000)).ok()?;
+    let nanoseconds = u32::try_from(microseconds.rem_euclid(1_000_000) * 1000).ok()?;
+    NaiveTime::from_num_seconds_from_midnight_opt(seconds, nanoseconds)
+}
+
+/// Big-endian reader over a value's bytes
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, count: usize) -> Option<&'a [u8]> {
+        if self.bytes.len() < count {
+            return None;
+        }
+        let (taken, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Some(taken)
+    }
+
+    fn array<const N: usize>(&mut self) -> Option<[u8; N]> {
+        self.take(N)?.try_into().ok()
+    }
+
+    fn i16(&mut self) -> Option<i16> {
+        self.array().map(i16::from_be_bytes)
+    }
+
+    fn u16(&mut self) -> Option<u16> {
+        self.array().map(u16::from_be_bytes)
+    }
+
+    fn i32(&mut self) -> Option<i32> {
+        self.array().map(i32::from_be_bytes)
+    }
+
+    fn u32(&mut self) -> Option<u32> {
+        self.array().map(u32::from_be_bytes)
+    }
+
+    fn i64(&mut self) -> Option<i64> {
+        self.array().map(i64::from_be_bytes)
+    }
+
+    /// A length-prefixed value; a length of -1 stands for NULL
+    fn value(&mut self) -> Option<Option<&'a [u8]>> {
+        match self.i32()? {
+            -1 => Some(None),
+            length => self.take(usize::try_from(length).ok()?).map(Some),
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use chrono::{DateTime, Utc};
+use crate::database::{BoundingBox, Geometry};
+use bigdecimal::{BigDecimal, ToPrimitive};
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Arc;
 
 /// Represents a single value in a query result
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -13,9 +18,313 @@ pub enum QueryValue {
     Float64(f64),
     String(String),
     Bytes(Vec<u8>),
-    DateTime(DateTime<Utc>),
+    /// Exact numeric value with the scale it was stored with
+    Decimal(BigDecimal),
+    Date(NaiveDate),
+    /// Time of day, with the UTC offset in seconds for a time with time zone
+    Time(NaiveTime, Option<i32>),
+    /// Point in time, shown in the session's time zone
+    DateTime(DateTime<FixedOffset>),
+    /// Date and time without a time zone
+    NaiveDateTime(NaiveDateTime),
+    Interval(IntervalValue),
     Json(serde_json::Value),
     Geometry(GeometryValue),
+    /// Array elements; each dimension of a multi-dimensional array is a nested array
+    Array(Vec<QueryValue>),
+    Range(RangeValue),
+    /// Fields of a composite (row) value with their names
+    Composite(Vec<(String, QueryValue)>),
+    Uuid(uuid::Uuid),
+    /// `inet` or `cidr` address
+    Network(NetworkAddress),
+    /// `macaddr` (6 bytes) or `macaddr8` (8 bytes)
+    MacAddress(Vec<u8>),
+    /// `money` amount in cents
+    Money(i64),
+    /// `bit` or `bit varying` value as a string of `0` and `1`
+    Bits(String),
+    /// Label of a user-defined enum type
+    Enum(EnumValue),
+}
+
+/// An IPv4 or IPv6 host or network address
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NetworkAddress {
+    pub address: IpAddr,
+    /// Number of network bits
+    pub prefix_len: u8,
+    /// Whether this is a `cidr` network rather than an `inet` host address
+    pub is_cidr: bool,
+}
+
+impl NetworkAddress {
+    /// Format like PostgreSQL: `inet` host addresses leave out a full-length prefix
+    pub fn to_display_string(&self) -> String {
+        let full_length = match self.address {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        };
+        if self.is_cidr || self.prefix_len != full_length {
+            format!("{}/{}", self.address, self.prefix_len)
+        } else {
+            self.address.to_string()
+        }
+    }
+
+    /// Order as PostgreSQL does: IPv4 before IPv6, then by the network part
+    /// both addresses share, then by prefix length, then by the whole address
+    fn compare(&self, other: &Self) -> Ordering {
+        let (own, others) = (octets(&self.address), octets(&other.address));
+        let common_bits = self.prefix_len.min(other.prefix_len) as usize;
+        let network = |octets: &[u8]| -> Vec<u8> {
+            octets
+                .iter()
+                .enumerate()
+                .map(|(index, octet)| {
+                    let kept = common_bits.saturating_sub(index * 8).min(8);
+                    if kept == 0 {
+                        0
+                    } else {
+                        octet & (0xffu8 << (8 - kept))
+                    }
+                })
+                .collect()
+        };
+
+        own.len()
+            .cmp(&others.len())
+            .then_with(|| network(&own).cmp(&network(&others)))
+            .then_with(|| self.prefix_len.cmp(&other.prefix_len))
+            .then_with(|| own.cmp(&others))
+    }
+}
+
+fn octets(address: &IpAddr) -> Vec<u8> {
+    match address {
+        IpAddr::V4(address) => address.octets().to_vec(),
+        IpAddr::V6(address) => address.octets().to_vec(),
+    }
+}
+
+/// A label of a user-defined enum type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EnumValue {
+    pub type_name: String,
+    pub label: String,
+    /// Position of the label in the type's definition, which is its sort order
+    pub position: Option<usize>,
+}
+
+/// A PostgreSQL range such as `int4range` or `tstzrange`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RangeValue {
+    /// Lower bound, `None` when unbounded
+    pub lower: Option<Box<QueryValue>>,
+    /// Upper bound, `None` when unbounded
+    pub upper: Option<Box<QueryValue>>,
+    pub lower_inclusive: bool,
+    pub upper_inclusive: bool,
+    /// Whether the range contains no points at all
+    pub empty: bool,
+}
+
+impl RangeValue {
+    /// The range containing no points
+    pub fn empty() -> Self {
+        Self {
+            lower: None,
+            upper: None,
+            lower_inclusive: false,
+            upper_inclusive: false,
+            empty: true,
+        }
+    }
+
+    /// Order by lower bound, then upper bound; the empty range comes first
+    fn compare(&self, other: &Self) -> Ordering {
+        if self.empty || other.empty {
+            return other.empty.cmp(&self.empty);
+        }
+
+        // An unbounded lower end is the smallest, an inclusive one starts earlier
+        let lower = match (&self.lower, &other.lower) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Less,
+            (Some(_), None) => Ordering::Greater,
+            (Some(a), Some(b)) => a
+                .compare(b)
+                .then_with(|| other.lower_inclusive.cmp(&self.lower_inclusive)),
+        };
+        // An unbounded upper end is the largest, an inclusive one ends later
+        let upper = || match (&self.upper, &other.upper) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(a), Some(b)) => a
+                .compare(b)
+                .then_with(|| self.upper_inclusive.cmp(&other.upper_inclusive)),
+        };
+        lower.then_with(upper)
+    }
+
+    /// Format like a PostgreSQL range literal, e.g. `[2024-01-01,2024-02-01)`
+    pub fn to_display_string(&self) -> String {
+        if self.empty {
+            return "empty".to_string();
+        }
+
+        let bound = |value: &Option<Box<QueryValue>>| match value {
+            Some(value) => quote_element(&value.to_display_string(), "()[],"),
+            None => String::new(),
+        };
+        format!(
+            "{}{},{}{}",
+            if self.lower_inclusive { '[' } else { '(' },
+            bound(&self.lower),
+            bound(&self.upper),
+            if self.upper_inclusive { ']' } else { ')' }
+        )
+    }
+}
+
+/// A PostgreSQL interval, kept in the units it is stored in
+///
+/// Months and days are separate from the time part because their length in
+/// seconds depends on the date they are added to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IntervalValue {
+    pub months: i32,
+    pub days: i32,
+    pub microseconds: i64,
+}
+
+impl IntervalValue {
+    pub fn new(months: i32, days: i32, microseconds: i64) -> Self {
+        Self {
+            months,
+            days,
+            microseconds,
+        }
+    }
+
+    /// Length in microseconds with months of 30 days, which is how PostgreSQL
+    /// orders intervals
+    fn span(&self) -> i128 {
+        const DAY: i128 = 86_400_000_000;
+        self.months as i128 * 30 * DAY + self.days as i128 * DAY + self.microseconds as i128
+    }
+
+    /// Format like PostgreSQL's default interval style, e.g.
+    /// `1 year 2 mons -3 days +04:05:06.5`
+    pub fn to_display_string(&self) -> String {
+        let mut text = String::new();
+        // Whether the previous part was negative, which makes a positive part show its sign
+        let mut negative_before = false;
+
+        let parts = [
+            (self.months / 12, "year"),
+            (self.months % 12, "mon"),
+            (self.days, "day"),
+        ];
+        for (value, unit) in parts {
+            if value == 0 {
+                continue;
+            }
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let sign = if negative_before && value > 0 {
+                "+"
+            } else {
+                ""
+            };
+            let plural = if value != 1 { "s" } else { "" };
+            text.push_str(&format!("{}{} {}{}", sign, value, unit, plural));
+            negative_before = value < 0;
+        }
+
+        if self.microseconds != 0 || text.is_empty() {
+            if !text.is_empty() {
+                text.push(' ');
+            }
+            let sign = if self.microseconds < 0 {
+                "-"
+            } else if negative_before {
+                "+"
+            } else {
+                ""
+            };
+            let total = self.microseconds.unsigned_abs();
+            let seconds = total / 1_000_000;
+            text.push_str(&format!(
+                "{}{:02}:{:02}:{:02}",
+                sign,
+                seconds / 3600,
+                seconds / 60 % 60,
+                seconds % 60
+            ));
+            let fraction = total % 1_000_000;
+            if fraction != 0 {
+                let digits = format!("{:06}", fraction);
+                text.push('.');
+                text.push_str(digits.trim_end_matches('0'));
+            }
+        }
+
+        text
+    }
+}
+
+/// Element-wise comparison; a prefix sorts before the longer sequence
+fn compare_all<'a>(
+    a: impl Iterator<Item = &'a QueryValue>,
+    b: impl Iterator<Item = &'a QueryValue>,
+) -> Ordering {
+    let (mut a, mut b) = (a, b);
+    loop {
+        match (a.next(), b.next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(x), Some(y)) => match x.compare(y) {
+                Ordering::Equal => continue,
+                unequal => return unequal,
+            },
+        }
+    }
+}
+
+/// Single-quote text as a SQL string literal
+pub(crate) fn quote_literal(text: &str) -> String {
+    format!("'{}'", text.replace('\'', "''"))
+}
+
+/// Double-quote an element of an array, range or composite literal if it is
+/// empty or contains whitespace, quotes, backslashes or one of `delimiters`
+fn quote_element(text: &str, delimiters: &str) -> String {
+    let needs_quotes = text.is_empty()
+        || text
+            .chars()
+            .any(|c| c == '"' || c == '\\' || c.is_whitespace() || delimiters.contains(c));
+    if !needs_quotes {
+        return text.to_string();
+    }
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// UTC offset as PostgreSQL prints it: `+02`, `-03:30`
+fn format_utc_offset(seconds: i32) -> String {
+    let sign = if seconds < 0 { '-' } else { '+' };
+    let seconds = seconds.unsigned_abs();
+    let mut text = format!("{}{:02}", sign, seconds / 3600);
+    if !seconds.is_multiple_of(3600) {
+        text.push_str(&format!(":{:02}", seconds / 60 % 60));
+    }
+    if !seconds.is_multiple_of(60) {
+        text.push_str(&format!(":{:02}", seconds % 60));
+    }
+    text
 }
 
 /// Represents PostGIS geometry data
@@ -25,6 +334,19 @@ pub struct GeometryValue {
     pub srid: Option<i32>,
     pub wkt: String,
     pub binary_data: Option<Vec<u8>>,
+    #[serde(default)]
+    pub has_z: bool,
+    #[serde(default)]
+    pub has_m: bool,
+    /// Extent in the geometry's own coordinates; `None` when it is empty
+    #[serde(default)]
+    pub bbox: Option<BoundingBox>,
+    /// Whether the value is a PostGIS `geography` rather than a `geometry`
+    #[serde(default)]
+    pub geography: bool,
+    /// Decoded coordinates for drawing; `None` when only the WKT is known
+    #[serde(skip)]
+    pub shape: Option<Arc<Geometry>>,
 }
 
 impl GeometryValue {
@@ -39,6 +361,11 @@ impl GeometryValue {
             srid,
             wkt,
             binary_data,
+            has_z: false,
+            has_m: false,
+            bbox: None,
+            geography: false,
+            shape: None,
         }
     }
 
@@ -61,11 +388,18 @@ impl GeometryValue {
     }
 
     pub fn get_summary(&self) -> String {
+        let dimensions = match (self.has_z, self.has_m) {
+            (true, true) => " ZM",
+            (true, false) => " Z",
+            (false, true) => " M",
+            (false, false) => "",
+        };
         let srid_info = self
             .srid
             .map(|s| format!(" (SRID: {})", s))
             .unwrap_or_default();
-        format!("{}{}", self.geometry_type, srid_info)
+        let kind = if self.geography { "GEOGRAPHY " } else { "" };
+        format!("{}{}{}{}", kind, self.geometry_type, dimensions, srid_info)
     }
 }
 
@@ -81,12 +415,219 @@ impl QueryValue {
             QueryValue::Float64(f) => f.to_string(),
             QueryValue::String(s) => s.clone(),
             QueryValue::Bytes(b) => format!("\\x{}", hex::encode(b)),
-            QueryValue::DateTime(dt) => dt.format("%Y-%m-%d %H:%M:%S UTC").to_string(),
+            QueryValue::Decimal(d) => d.to_plain_string(),
+            QueryValue::Date(d) => d.format("%Y-%m-%d").to_string(),
+            QueryValue::Time(t, offset) => {
+                let time = t.format("%H:%M:%S%.f").to_string();
+                match offset {
+                    Some(offset) => format!("{}{}", time, format_utc_offset(*offset)),
+                    None => time,
+                }
+            }
+            QueryValue::DateTime(dt) => format!(
+                "{}{}",
+                dt.format("%Y-%m-%d %H:%M:%S%.f"),
+                format_utc_offset(dt.offset().local_minus_utc())
+            ),
+            QueryValue::NaiveDateTime(dt) => dt.format("%Y-%m-%d %H:%M:%S%.f").to_string(),
+            QueryValue::Interval(i) => i.to_display_string(),
             QueryValue::Json(j) => j.to_string(),
             QueryValue::Geometry(g) => g.to_display_string(),
+            QueryValue::Array(elements) => {
+                let elements: Vec<String> = elements
+                    .iter()
+                    .map(|element| match element {
+                        QueryValue::Null => "NULL".to_string(),
+                        QueryValue::Array(_) => element.to_display_string(),
+                        _ => {
+                            let text = element.to_display_string();
+                            if text.eq_ignore_ascii_case("NULL") {
+                                format!("\"{}\"", text)
+                            } else {
+                                quote_element(&text, "{},")
+                            }
+                        }
+                    })
+                    .collect();
+                format!("{{{}}}", elements.join(","))
+            }
+            QueryValue::Range(range) => range.to_display_string(),
+            QueryValue::Composite(fields) => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|(_, value)| match value {
+                        QueryValue::Null => String::new(),
+                        _ => quote_element(&value.to_display_string(), "(),"),
+                    })
+                    .collect();
+                format!("({})", fields.join(","))
+            }
+            QueryValue::Uuid(u) => u.to_string(),
+            QueryValue::Network(n) => n.to_display_string(),
+            QueryValue::MacAddress(bytes) => bytes
+                .iter()
+                .map(|byte| format!("{:02x}", byte))
+                .collect::<Vec<_>>()
+                .join(":"),
+            QueryValue::Money(cents) => format!(
+                "{}{}.{:02}",
+                if *cents < 0 { "-" } else { "" },
+                cents.unsigned_abs() / 100,
+                cents.unsigned_abs() % 100
+            ),
+            QueryValue::Bits(bits) => bits.clone(),
+            QueryValue::Enum(e) => e.label.clone(),
+        }
+    }
+
+    /// The value in its type's text input format; `None` for NULL
+    ///
+    /// Unlike the display string this is never shortened, so the server can
+    /// read the value back from it.
+    pub fn to_sql_text(&self) -> Option<String> {
+        let text = match self {
+            QueryValue::Null => return None,
+            QueryValue::Bool(b) => b.to_string(),
+            QueryValue::Float32(_) | QueryValue::Float64(_) => {
+                let text = self.to_display_string();
+                match text.as_str() {
+                    "inf" => "Infinity".to_string(),
+                    "-inf" => "-Infinity".to_string(),
+                    _ => text,
+                }
+            }
+            QueryValue::Geometry(g) => match g.srid {
+                Some(srid) => format!("SRID={};{}", srid, g.wkt),
+                None => g.wkt.clone(),
+            },
+            _ => self.to_display_string(),
+        };
+        Some(text)
+    }
+
+    /// The value as a PostgreSQL literal, for generated SQL
+    ///
+    /// Numbers and booleans are written as is; everything else is a quoted
+    /// string in the type's input format, which the server converts to the
+    /// column's type on assignment.
+    pub fn to_sql_literal(&self) -> String {
+        let Some(text) = self.to_sql_text() else {
+            return "NULL".to_string();
+        };
+        match self {
+            QueryValue::Bool(_) => text.to_uppercase(),
+            QueryValue::Int32(_) | QueryValue::Int64(_) | QueryValue::Decimal(_) => text,
+            QueryValue::Float32(f) if f.is_finite() => text,
+            QueryValue::Float64(f) if f.is_finite() => text,
+            _ => quote_literal(&text),
+        }
+    }
+
+    /// Compare two values of a column for sorting
+    ///
+    /// Values of the same type are ordered as the server orders them, e.g.
+    /// numbers numerically, network addresses by address and enum labels in
+    /// the order of their definition. NULL sorts before everything else.
+    pub fn compare(&self, other: &QueryValue) -> Ordering {
+        use QueryValue as V;
+
+        match (self, other) {
+            (V::Null, V::Null) => Ordering::Equal,
+            (V::Bool(a), V::Bool(b)) => a.cmp(b),
+            (V::Int32(a), V::Int32(b)) => a.cmp(b),
+            (V::Int64(a), V::Int64(b)) => a.cmp(b),
+            (V::Int32(a), V::Int64(b)) => (*a as i64).cmp(b),
+            (V::Int64(a), V::Int32(b)) => a.cmp(&(*b as i64)),
+            (V::Decimal(a), V::Decimal(b)) => a.cmp(b),
+            (V::Money(a), V::Money(b)) => a.cmp(b),
+            (V::String(a), V::String(b)) => a.cmp(b),
+            (V::Bytes(a), V::Bytes(b)) => a.cmp(b),
+            (V::Date(a), V::Date(b)) => a.cmp(b),
+            (V::Time(a, a_offset), V::Time(b, b_offset)) => {
+                // Times with time zone compare in UTC first
+                let utc = |time: &NaiveTime, offset: &Option<i32>| {
+                    time.num_seconds_from_midnight() as i64 - offset.unwrap_or(0) as i64
+                };
+                utc(a, a_offset)
+                    .cmp(&utc(b, b_offset))
+                    .then_with(|| a.cmp(b))
+                    .then_with(|| b_offset.cmp(a_offset))
+            }
+            (V::DateTime(a), V::DateTime(b)) => a.cmp(b),
+            (V::NaiveDateTime(a), V::NaiveDateTime(b)) => a.cmp(b),
+            (V::Interval(a), V::Interval(b)) => a.span().cmp(&b.span()),
+            (V::Json(a), V::Json(b)) => a.to_string().cmp(&b.to_string()),
+            (V::Geometry(a), V::Geometry(b)) => a.wkt.cmp(&b.wkt),
+            (V::Array(a), V::Array(b)) => compare_all(a.iter(), b.iter()),
+            (V::Range(a), V::Range(b)) => a.compare(b),
+            (V::Composite(a), V::Composite(b)) => compare_all(
+                a.iter().map(|(_, value)| value),
+                b.iter().map(|(_, value)| value),
+            ),
+            (V::Uuid(a), V::Uuid(b)) => a.cmp(b),
+            (V::Network(a), V::Network(b)) => a.compare(b),
+            (V::MacAddress(a), V::MacAddress(b)) => a.cmp(b),
+            // Bits compare bit by bit, a prefix before the longer string
+            (V::Bits(a), V::Bits(b)) => a.cmp(b),
+            (V::Enum(a), V::Enum(b)) => match (a.position, b.position) {
+                (Some(a), Some(b)) => a.cmp(&b),
+                _ => a.label.cmp(&b.label),
+            },
+            _ => match (self.as_f64(), other.as_f64()) {
+                // Numbers of different types, e.g. in a UNION
+                (Some(a), Some(b)) => a.total_cmp(&b),
+                _ => self.type_rank().cmp(&other.type_rank()),
+            },
+        }
+    }
+
+    fn as_f64(&self) -> Option<f64> {
+        match self {
+            QueryValue::Int32(i) => Some(*i as f64),
+            QueryValue::Int64(i) => Some(*i as f64),
+            QueryValue::Float32(f) => Some(*f as f64),
+            QueryValue::Float64(f) => Some(*f),
+            QueryValue::Decimal(d) => d.to_f64(),
+            _ => None,
         }
     }
 
+    /// Order of values of unrelated types, which only meet in untyped results
+    fn type_rank(&self) -> u8 {
+        match self {
+            QueryValue::Null => 0,
+            QueryValue::Bool(_) => 1,
+            QueryValue::Int32(_)
+            | QueryValue::Int64(_)
+            | QueryValue::Float32(_)
+            | QueryValue::Float64(_)
+            | QueryValue::Decimal(_) => 2,
+            QueryValue::Money(_) => 3,
+            QueryValue::String(_) | QueryValue::Enum(_) => 4,
+            QueryValue::Bits(_) => 5,
+            QueryValue::Bytes(_) => 6,
+            QueryValue::Date(_) | QueryValue::NaiveDateTime(_) | QueryValue::DateTime(_) => 7,
+            QueryValue::Time(_, _) => 8,
+            QueryValue::Interval(_) => 9,
+            QueryValue::Uuid(_) => 10,
+            QueryValue::Network(_) => 11,
+            QueryValue::MacAddress(_) => 12,
+            QueryValue::Json(_) => 13,
+            QueryValue::Geometry(_) => 14,
+            QueryValue::Array(_) => 15,
+            QueryValue::Range(_) => 16,
+            QueryValue::Composite(_) => 17,
+        }
+    }
+
+    /// Whether the value holds other values, as arrays, ranges and composites do
+    pub fn is_nested(&self) -> bool {
+        matches!(
+            self,
+            QueryValue::Array(_) | QueryValue::Range(_) | QueryValue::Composite(_)
+        )
+    }
+
     /// Check if the value is null
     pub fn is_null(&self) -> bool {
         matches!(self, QueryValue::Null)
@@ -94,12 +635,55 @@ impl QueryValue {
 }
 
 /// Column metadata for query results
-#[derive(Debug, Clone, Serialize, Deserialize)]
+///
+/// Besides the name and driver type, PostgreSQL results record the base table
+/// column a result column was read from, when it was read from one, and the
+/// type as the server spells it.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct QueryColumn {
     pub name: String,
     pub data_type: String,
     pub ordinal: usize,
+    /// False only when the source column is declared `NOT NULL`
     pub nullable: bool,
+    /// OID of the table the column was read from
+    #[serde(default)]
+    pub table_oid: Option<u32>,
+    /// Attribute number of the column within that table
+    #[serde(default)]
+    pub column_number: Option<i16>,
+    /// Schema of that table
+    #[serde(default)]
+    pub source_schema: Option<String>,
+    /// Name of that table
+    #[serde(default)]
+    pub source_table: Option<String>,
+    /// Name of the column within that table, which an alias may hide
+    #[serde(default)]
+    pub source_column: Option<String>,
+    /// OID of the column type as sent with the rows, which for a domain is
+    /// its base type
+    #[serde(default)]
+    pub type_oid: Option<u32>,
+    /// Type as the server spells it, e.g. `numeric(10,2)`, `integer[]` or the
+    /// name of a domain or enum
+    #[serde(default)]
+    pub type_name: Option<String>,
+    /// Type without its modifier, e.g. `character varying` for
+    /// `character varying(20)`; values cast to it still get checked against
+    /// the column's own length or precision on assignment
+    #[serde(default)]
+    pub unmodified_type: Option<String>,
+    /// Element type of an array column
+    #[serde(default)]
+    pub element_type: Option<String>,
+    /// Digits of a numeric column, length of a character or bit column, or
+    /// fractional-second digits of a time column
+    #[serde(default)]
+    pub precision: Option<u32>,
+    /// Digits after the decimal point of a numeric column
+    #[serde(default)]
+    pub scale: Option<u32>,
 }
 
 impl QueryColumn {
@@ -109,8 +693,21 @@ impl QueryColumn {
             data_type,
             ordinal,
             nullable,
+            ..Self::default()
         }
     }
+
+    /// Type name for display, preferring the server's spelling
+    pub fn display_type(&self) -> &str {
+        self.type_name.as_deref().unwrap_or(&self.data_type)
+    }
+
+    /// Base table column this column was read from, as `table.column`
+    pub fn source(&self) -> Option<String> {
+        let table = self.source_table.as_deref()?;
+        let column = self.source_column.as_deref().unwrap_or(&self.name);
+        Some(format!("{}.{}", table, column))
+    }
 }
 
 /// A single row in a query result
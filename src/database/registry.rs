@@ -0,0 +1,201 @@
+use crate::database::{Database, DatabaseError, DatabaseType, ObjectCategory, ViewType};
+use std::collections::HashMap;
+
+/// Output formats a driver can produce for EXPLAIN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExplainFormat {
+    Text,
+    Json,
+    Xml,
+    Yaml,
+    Tree,
+}
+
+impl ExplainFormat {
+    pub fn label(&self) -> &'static str {
+        match self {
+            ExplainFormat::Text => "Text",
+            ExplainFormat::Json => "JSON",
+            ExplainFormat::Xml => "XML",
+            ExplainFormat::Yaml => "YAML",
+            ExplainFormat::Tree => "Tree",
+        }
+    }
+
+    /// The statement explaining `sql` in this format on the given database
+    pub fn statement(&self, database_type: &DatabaseType, sql: &str) -> String {
+        let sql = sql.trim().trim_end_matches(';').trim_end();
+        match (database_type, self) {
+            (DatabaseType::SQLite, _) => format!("EXPLAIN QUERY PLAN {}", sql),
+            (_, ExplainFormat::Text) => format!("EXPLAIN {}", sql),
+            (DatabaseType::MySQL, format) => {
+                format!("EXPLAIN FORMAT={} {}", format.label().to_uppercase(), sql)
+            }
+            (_, format) => format!("EXPLAIN (FORMAT {}) {}", format.label().to_uppercase(), sql),
+        }
+    }
+}
+
+/// Features supported by a database driver
+///
+/// The UI consults these to hide object categories and actions that the
+/// connected database has no notion of.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DriverCapabilities {
+    /// Namespaces inside a database; drivers without them list databases instead
+    pub supports_schemas: bool,
+    pub supports_sequences: bool,
+    pub supports_materialized_views: bool,
+    /// Stored functions and procedures
+    pub supports_routines: bool,
+    /// Running statements can be cancelled from another connection
+    pub supports_cancellation: bool,
+    /// The server enforces `ConnectionParams::query_timeout` on its own
+    pub supports_statement_timeout: bool,
+    /// The server records which objects depend on which, for
+    /// `QueryExecutor::get_dependencies` and `get_dependents`
+    pub supports_dependencies: bool,
+    /// Results name the table columns they were read from, so they can be
+    /// edited and saved with `QueryExecutor::execute_dml`
+    pub supports_result_editing: bool,
+    pub explain_formats: Vec<ExplainFormat>,
+}
+
+impl DriverCapabilities {
+    /// Whether the tree should offer the given object category
+    pub fn supports_category(&self, category: &ObjectCategory) -> bool {
+        match category {
+            ObjectCategory::Sequences => self.supports_sequences,
+            ObjectCategory::Functions => self.supports_routines,
+            _ => true,
+        }
+    }
+
+    /// Whether the tree should show views of the given kind
+    pub fn supports_view_type(&self, view_type: &ViewType) -> bool {
+        match view_type {
+            ViewType::Materialized => self.supports_materialized_views,
+            ViewType::Regular => true,
+        }
+    }
+}
+
+impl Default for DriverCapabilities {
+    fn default() -> Self {
+        Self {
+            supports_schemas: true,
+            supports_sequences: true,
+            supports_materialized_views: true,
+            supports_routines: true,
+            supports_cancellation: false,
+            supports_statement_timeout: false,
+            supports_dependencies: false,
+            supports_result_editing: false,
+            explain_formats: vec![ExplainFormat::Text],
+        }
+    }
+}
+
+/// A database driver known to the registry
+#[derive(Clone)]
+pub struct DriverDescriptor {
+    pub database_type: DatabaseType,
+    pub capabilities: DriverCapabilities,
+    factory: fn() -> Box<dyn Database>,
+}
+
+impl DriverDescriptor {
+    pub fn new(
+        database_type: DatabaseType,
+        capabilities: DriverCapabilities,
+        factory: fn() -> Box<dyn Database>,
+    ) -> Self {
+        Self {
+            database_type,
+            capabilities,
+            factory,
+        }
+    }
+
+    /// Create an unconnected instance of the driver
+    pub fn create(&self) -> Box<dyn Database> {
+        (self.factory)()
+    }
+}
+
+/// Registry of the database drivers compiled into this build
+///
+/// Drivers are enabled through cargo features, so `DriverRegistry::new` only
+/// registers the ones whose feature is turned on.
+pub struct DriverRegistry {
+    drivers: HashMap<DatabaseType, DriverDescriptor>,
+}
+
+impl DriverRegistry {
+    /// Create a registry with every driver enabled in this build
+    pub fn new() -> Self {
+        let mut registry = Self {
+            drivers: HashMap::new(),
+        };
+
+        registry.register(DriverDescriptor::new(
+            DatabaseType::PostgreSQL,
+            crate::database::PostgreSQLConnection::capabilities(),
+            || Box::new(crate::database::PostgreSQLConnection::new()),
+        ));
+
+        #[cfg(feature = "mysql")]
+        registry.register(DriverDescriptor::new(
+            DatabaseType::MySQL,
+            crate::database::MySQLConnection::capabilities(),
+            || Box::new(crate::database::MySQLConnection::new()),
+        ));
+
+        #[cfg(feature = "sqlite")]
+        registry.register(DriverDescriptor::new(
+            DatabaseType::SQLite,
+            crate::database::SQLiteConnection::capabilities(),
+            || Box::new(crate::database::SQLiteConnection::new()),
+        ));
+
+        registry
+    }
+
+    /// Register a driver, replacing any previous driver for the same type
+    pub fn register(&mut self, descriptor: DriverDescriptor) {
+        self.drivers
+            .insert(descriptor.database_type.clone(), descriptor);
+    }
+
+    /// Look up the driver for a database type
+    pub fn get(&self, database_type: &DatabaseType) -> Option<&DriverDescriptor> {
+        self.drivers.get(database_type)
+    }
+
+    /// Capabilities of the driver for a database type, if it is available
+    pub fn capabilities(&self, database_type: &DatabaseType) -> Option<&DriverCapabilities> {
+        self.get(database_type)
+            .map(|descriptor| &descriptor.capabilities)
+    }
+
+    /// Create an unconnected driver for the given database type
+    pub fn create_connection(
+        &self,
+        database_type: &DatabaseType,
+    ) -> Result<Box<dyn Database>, DatabaseError> {
+        self.get(database_type)
+            .map(|descriptor| descriptor.create())
+            .ok_or_else(|| {
+                DatabaseError::UnsupportedOperation(format!(
+                    "{:?} support is not enabled in this build",
+                    database_type
+                ))
+            })
+    }
+}
+
+impl Default for DriverRegistry {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,325 @@
+//! Sorting and filtering of a fetched result, either on the rows in memory
+//! or by wrapping the result's query for the server to do it
+
+use crate::database::dml::quote_identifier;
+use crate::database::export::{sql_literal, text_literal};
+use crate::database::{DatabaseType, NullsOrder, QueryResult, QueryValue, SortDirection};
+use bigdecimal::BigDecimal;
+use std::cmp::Ordering;
+use std::str::FromStr;
+
+/// One column of a multi-column sort
+#[derive(Debug, Clone, PartialEq)]
+pub struct SortKey {
+    pub column: usize,
+    pub direction: SortDirection,
+    pub nulls: NullsOrder,
+}
+
+impl SortKey {
+    /// Ascending with NULLs last, as servers sort by default
+    pub fn ascending(column: usize) -> Self {
+        Self {
+            column,
+            direction: SortDirection::Ascending,
+            nulls: NullsOrder::Last,
+        }
+    }
+
+    /// Flip the direction, moving NULLs to the end they take by default
+    pub fn reverse(&mut self) {
+        (self.direction, self.nulls) = match self.direction {
+            SortDirection::Ascending => (SortDirection::Descending, NullsOrder::First),
+            SortDirection::Descending => (SortDirection::Ascending, NullsOrder::Last),
+        };
+    }
+
+    fn compare(&self, a: &QueryValue, b: &QueryValue) -> Ordering {
+        match (a, b) {
+            (QueryValue::Null, QueryValue::Null) => Ordering::Equal,
+            (QueryValue::Null, _) => self.null_ordering(),
+            (_, QueryValue::Null) => self.null_ordering().reverse(),
+            _ => match self.direction {
+                SortDirection::Ascending => a.compare(b),
+                SortDirection::Descending => b.compare(a),
+            },
+        }
+    }
+
+    /// Where a NULL goes relative to a value
+    fn null_ordering(&self) -> Ordering {
+        match self.nulls {
+            NullsOrder::First => Ordering::Less,
+            NullsOrder::Last => Ordering::Greater,
+        }
+    }
+}
+
+/// A condition on the values of one column
+#[derive(Debug, Clone, PartialEq)]
+pub enum FilterCondition {
+    /// The displayed value contains the text, ignoring case
+    Contains(String),
+    Equals(String),
+    AtLeast(String),
+    AtMost(String),
+    /// Inclusive on both ends
+    Between(String, String),
+    IsNull,
+    IsNotNull,
+}
+
+impl FilterCondition {
+    /// Read a condition typed into a column's filter box
+    ///
+    /// `=x`, `>=x`, `<=x`, `a..b`, `NULL` and `NOT NULL` stand for the
+    /// comparisons; any other text is searched for. Empty text is no filter.
+    pub fn parse(text: &str) -> Option<Self> {
+        let text = text.trim();
+        if text.is_empty() {
+            return None;
+        }
+        if text.eq_ignore_ascii_case("null") {
+            return Some(Self::IsNull);
+        }
+        if text.eq_ignore_ascii_case("not null") || text.eq_ignore_ascii_case("!null") {
+            return Some(Self::IsNotNull);
+        }
+
+        let operand = |rest: &str| rest.trim().to_string();
+        Some(if let Some(rest) = text.strip_prefix(">=") {
+            Self::AtLeast(operand(rest))
+        } else if let Some(rest) = text.strip_prefix("<=") {
+            Self::AtMost(operand(rest))
+        } else if let Some(rest) = text.strip_prefix('=') {
+            Self::Equals(operand(rest))
+        } else if let Some((low, high)) = text.split_once("..") {
+            Self::Between(operand(low), operand(high))
+        } else {
+            Self::Contains(text.to_string())
+        })
+    }
+
+    /// Whether a value passes; as in SQL, only the NULL tests pass NULLs
+    pub fn matches(&self, value: &QueryValue) -> bool {
+        let is_null = matches!(value, QueryValue::Null);
+        match self {
+            Self::IsNull => is_null,
+            Self::IsNotNull => !is_null,
+            _ if is_null => false,
+            Self::Contains(text) => matches_search(value, text),
+            Self::Equals(text) => compare_to_text(value, text) == Ordering::Equal,
+            Self::AtLeast(text) => compare_to_text(value, text) != Ordering::Less,
+            Self::AtMost(text) => compare_to_text(value, text) != Ordering::Greater,
+            Self::Between(low, high) => {
+                compare_to_text(value, low) != Ordering::Less
+                    && compare_to_text(value, high) != Ordering::Greater
+            }
+        }
+    }
+
+    /// The condition as SQL on a column of the wrapped query
+    fn to_sql(&self, database_type: &DatabaseType, column: &str) -> String {
+        let literal = |text: &str| text_literal(database_type, text);
+        match self {
+            Self::IsNull => format!("{} IS NULL", column),
+            Self::IsNotNull => format!("{} IS NOT NULL", column),
+            Self::Contains(text) => {
+                let pattern = format!("%{}%", escape_like(text));
+                match database_type {
+                    DatabaseType::PostgreSQL => format!(
+                        "CAST({} AS text) ILIKE {} ESCAPE '!'",
+                        column,
+                        literal(&pattern)
+                    ),
+                    // LIKE ignores case with the default collations
+                    DatabaseType::MySQL => format!(
+                        "CAST({} AS CHAR) LIKE {} ESCAPE '!'",
+                        column,
+                        literal(&pattern)
+                    ),
+                    DatabaseType::SQLite => format!(
+                        "CAST({} AS TEXT) LIKE {} ESCAPE '!'",
+                        column,
+                        literal(&pattern)
+                    ),
+                }
+            }
+            Self::Equals(text) => format!("{} = {}", column, literal(text)),
+            Self::AtLeast(text) => format!("{} >= {}", column, literal(text)),
+            Self::AtMost(text) => format!("{} <= {}", column, literal(text)),
+            Self::Between(low, high) => {
+                format!("{} BETWEEN {} AND {}", column, literal(low), literal(high))
+            }
+        }
+    }
+}
+
+/// A filter on one result column
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColumnFilter {
+    pub column: usize,
+    pub condition: FilterCondition,
+}
+
+/// Whether the displayed value contains the text, ignoring case
+pub fn matches_search(value: &QueryValue, text: &str) -> bool {
+    if matches!(value, QueryValue::Null) {
+        return false;
+    }
+    value
+        .to_display_string()
+        .to_lowercase()
+        .contains(&text.to_lowercase())
+}
+
+/// Positions of the result's rows in sorted order
+pub fn sort_order(result: &QueryResult, keys: &[SortKey]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..result.rows.len()).collect();
+    order.sort_by(|a, b| {
+        let (a, b) = (&result.rows[*a], &result.rows[*b]);
+        keys.iter()
+            .map(|key| match (a.get(key.column), b.get(key.column)) {
+                (Some(a), Some(b)) => key.compare(a, b),
+                _ => Ordering::Equal,
+            })
+            .find(|ordering| ordering.is_ne())
+            .unwrap_or(Ordering::Equal)
+    });
+    order
+}
+
+/// Positions of the rows passing every filter
+pub fn filter_rows(result: &QueryResult, filters: &[ColumnFilter]) -> Vec<usize> {
+    (0..result.rows.len())
+        .filter(|row| {
+            filters.iter().all(|filter| {
+                result
+                    .get_value(*row, filter.column)
+                    .is_some_and(|value| filter.condition.matches(value))
+            })
+        })
+        .collect()
+}
+
+/// The result's query wrapped so the server filters and sorts it
+///
+/// Returns `None` when a filtered or sorted column cannot be named because
+/// its name is shared with another column.
+pub fn rewrite_query(
+    database_type: &DatabaseType,
+    result: &QueryResult,
+    filters: &[ColumnFilter],
+    sort: &[SortKey],
+) -> Option<String> {
+    let column_name = |column: usize| -> Option<String> {
+        let name = &result.columns.get(column)?.name;
+        let uses = result.columns.iter().filter(|c| &c.name == name).count();
+        (uses == 1).then(|| quote_identifier(database_type, name))
+    };
+
+    let query = result.query.trim().trim_end_matches(';').trim_end();
+    let mut sql = format!("SELECT * FROM (\n{}\n) AS filtered", query);
+
+    let conditions = filters
+        .iter()
+        .map(|filter| {
+            let column = column_name(filter.column)?;
+            Some(filter.condition.to_sql(database_type, &column))
+        })
+        .collect::<Option<Vec<_>>>()?;
+    if !conditions.is_empty() {
+        sql.push_str(&format!("\nWHERE {}", conditions.join(" AND ")));
+    }
+
+    let mut order_by = Vec::new();
+    for key in sort {
+        let column = column_name(key.column)?;
+        let direction = match key.direction {
+            SortDirection::Ascending => "ASC",
+            SortDirection::Descending => "DESC",
+        };
+        let nulls = match key.nulls {
+            NullsOrder::First => "FIRST",
+            NullsOrder::Last => "LAST",
+        };
+        match database_type {
+            // MySQL has no NULLS FIRST/LAST; sorting on IS NULL does the same
+            DatabaseType::MySQL => {
+                let nulls_direction = match key.nulls {
+                    NullsOrder::First => "DESC",
+                    NullsOrder::Last => "ASC",
+                };
+                order_by.push(format!("{} IS NULL {}", column, nulls_direction));
+                order_by.push(format!("{} {}", column, direction));
+            }
+            _ => order_by.push(format!("{} {} NULLS {}", column, direction, nulls)),
+        }
+    }
+    if !order_by.is_empty() {
+        sql.push_str(&format!("\nORDER BY {}", order_by.join(", ")));
+    }
+    Some(sql)
+}
+
+/// Query for the rows of a table whose columns hold the given values, as
+/// when following a foreign key from one row to the rows it relates to
+pub fn lookup_query(
+    database_type: &DatabaseType,
+    schema: &str,
+    table: &str,
+    conditions: &[(&str, &QueryValue)],
+) -> String {
+    let conditions: Vec<String> = conditions
+        .iter()
+        .map(|(column, value)| {
+            let column = quote_identifier(database_type, column);
+            match value {
+                QueryValue::Null => format!("{} IS NULL", column),
+                _ => format!("{} = {}", column, sql_literal(value, None, database_type)),
+            }
+        })
+        .collect();
+    format!(
+        "SELECT * FROM {}.{} WHERE {}",
+        quote_identifier(database_type, schema),
+        quote_identifier(database_type, table),
+        conditions.join(" AND ")
+    )
+}
+
+/// Compare a value with text typed for it, reading the text as a value of
+/// the same type where it can be; other values compare by their display
+/// text, which orders ISO dates and times correctly
+fn compare_to_text(value: &QueryValue, text: &str) -> Ordering {
+    let number = || BigDecimal::from_str(text).ok();
+    let ordering = match value {
+        QueryValue::Int32(_)
+        | QueryValue::Int64(_)
+        | QueryValue::Float32(_)
+        | QueryValue::Float64(_)
+        | QueryValue::Decimal(_) => number().map(|n| value.compare(&QueryValue::Decimal(n))),
+        QueryValue::Money(cents) => {
+            number().map(|n| BigDecimal::from(*cents).cmp(&(n * BigDecimal::from(100))))
+        }
+        QueryValue::Bool(b) => match text.to_ascii_lowercase().as_str() {
+            "true" | "t" | "1" => Some(b.cmp(&true)),
+            "false" | "f" | "0" => Some(b.cmp(&false)),
+            _ => None,
+        },
+        _ => None,
+    };
+    ordering.unwrap_or_else(|| value.to_display_string().as_str().cmp(text))
+}
+
+/// Escape the LIKE wildcards in text, using `!` as the escape character
+fn escape_like(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        if matches!(c, '!' | '%' | '_') {
+            escaped.push('!');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,1644 @@
+//! Differences between two schemas and the script that removes them
+//!
+//! Both schemas are read through the metadata methods of `QueryExecutor`,
+//! so they may live on different connections. The source is the schema as
+//! it should be; the migration script changes the target to match it.
+
+use crate::database::ddl::{self, ColumnDefault, ColumnDefinition, RelationKind, TableDefinition};
+use crate::database::dml::quote_identifier;
+use crate::database::{
+    ArgumentMode, Column, Constraint, ConstraintType, Database, DatabaseError, DatabaseType,
+    Dependency, DependencyKind, ForeignKey, Function, FunctionType, Index, ObjectCategory,
+    QueryExecutor, Sequence, Trigger, View, ViewType,
+};
+use std::collections::HashMap;
+
+/// Objects of one schema, as far as they take part in a comparison
+#[derive(Debug, Clone, Default)]
+pub struct SchemaSnapshot {
+    pub schema: String,
+    pub tables: Vec<TableSnapshot>,
+    pub views: Vec<View>,
+    pub functions: Vec<Function>,
+    pub triggers: Vec<Trigger>,
+    pub sequences: Vec<Sequence>,
+    /// Indexes not created by a constraint
+    pub indexes: Vec<Index>,
+}
+
+/// A table with its columns and keys
+#[derive(Debug, Clone)]
+pub struct TableSnapshot {
+    pub name: String,
+    pub columns: Vec<Column>,
+    pub constraints: Vec<Constraint>,
+    /// Foreign keys the table owns; keys referencing it are left out
+    pub foreign_keys: Vec<ForeignKey>,
+}
+
+impl SchemaSnapshot {
+    /// Read every object of a schema
+    pub async fn load<E: QueryExecutor + ?Sized>(
+        executor: &E,
+        schema: &str,
+    ) -> Result<Self, DatabaseError> {
+        let mut tables = Vec::new();
+        for table in executor.get_tables(schema).await? {
+            if table.table_type != "BASE TABLE" {
+                continue;
+            }
+            let columns = executor.get_columns(schema, &table.name).await?;
+            let constraints = executor.get_constraints(schema, &table.name).await?;
+            let foreign_keys = executor
+                .get_foreign_keys(schema, &table.name)
+                .await?
+                .into_iter()
+                .filter(|key| key.is_outgoing(schema, &table.name))
+                .collect();
+            tables.push(TableSnapshot {
+                name: table.name,
+                columns,
+                constraints,
+                foreign_keys,
+            });
+        }
+
+        // Indexes behind keys come and go with their constraints
+        let indexes = executor
+            .get_indexes(schema)
+            .await?
+            .into_iter()
+            .filter(|index| {
+                let backs_constraint = tables
+                    .iter()
+                    .find(|table| table.name == index.table_name)
+                    .is_some_and(|table| {
+                        table.constraints.iter().any(|c| c.name == index.name)
+                            || table.foreign_keys.iter().any(|k| k.name == index.name)
+                    });
+                !index.is_primary
+                    && !backs_constraint
+                    && !index.name.starts_with("sqlite_autoindex_")
+            })
+            .collect();
+
+        Ok(Self {
+            schema: schema.to_string(),
+            tables,
+            views: executor.get_views(schema).await?,
+            functions: executor.get_functions(schema).await?,
+            triggers: executor.get_triggers(schema).await?,
+            sequences: executor.get_sequences(schema).await?,
+            indexes,
+        })
+    }
+
+    fn table(&self, name: &str) -> Option<&TableSnapshot> {
+        self.tables.iter().find(|table| table.name == name)
+    }
+}
+
+/// How an object differs between the two schemas
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// Only in the source, so the script creates it
+    Added,
+    /// Only in the target, so the script drops it
+    Removed,
+    /// In both, with different definitions
+    Changed,
+}
+
+/// One object that differs between the schemas
+#[derive(Debug, Clone)]
+pub struct ObjectDiff {
+    pub category: ObjectCategory,
+    /// Name the object is matched by: `table.name` for triggers and indexes,
+    /// `name(argument types)` for functions
+    pub name: String,
+    pub change: ChangeKind,
+    /// What differs, for display
+    pub details: Vec<String>,
+    /// Changes of a changed table
+    pub table_changes: Vec<TableChange>,
+}
+
+/// A change to a table that exists in both schemas
+#[derive(Debug, Clone)]
+pub enum TableChange {
+    AddColumn(Column),
+    DropColumn(Column),
+    AlterColumn { from: Column, to: Column },
+    AddConstraint(Constraint),
+    DropConstraint(Constraint),
+    AddForeignKey(ForeignKey),
+    DropForeignKey(ForeignKey),
+}
+
+impl TableChange {
+    /// One-line summary, e.g. `add column email`
+    pub fn describe(&self) -> String {
+        match self {
+            TableChange::AddColumn(column) => format!("add column {}", column.name),
+            TableChange::DropColumn(column) => format!("drop column {}", column.name),
+            TableChange::AlterColumn { from, to } => {
+                format!(
+                    "alter column {}: {}",
+                    to.name,
+                    column_changes(from, to).join(", ")
+                )
+            }
+            TableChange::AddConstraint(constraint) => {
+                format!("add constraint {}", constraint.name)
+            }
+            TableChange::DropConstraint(constraint) => {
+                format!("drop constraint {}", constraint.name)
+            }
+            TableChange::AddForeignKey(key) => format!("add foreign key {}", key.name),
+            TableChange::DropForeignKey(key) => format!("drop foreign key {}", key.name),
+        }
+    }
+}
+
+/// Result of comparing a source schema with a target schema
+#[derive(Debug, Clone)]
+pub struct SchemaDiff {
+    pub source: SchemaSnapshot,
+    pub target: SchemaSnapshot,
+    /// Differing objects, grouped by category
+    pub objects: Vec<ObjectDiff>,
+    /// Objects of the target in the way of the script, as found by
+    /// `load_drop_dependents`
+    pub drop_dependents: Vec<DropDependents>,
+}
+
+/// Something the script drops or alters, with the objects of the target that
+/// depend on it and that the script leaves alone
+#[derive(Debug, Clone)]
+pub struct DropDependents {
+    /// What the script changes, e.g. `view order_totals` or
+    /// `column orders.total`
+    pub object: String,
+    pub dependents: Vec<Dependency>,
+}
+
+impl SchemaDiff {
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Objects whose statements the script takes from the source server,
+    /// by category and plain name; triggers go by table and name
+    pub fn definitions_needed(&self) -> Vec<(ObjectCategory, String)> {
+        let mut needed: Vec<(ObjectCategory, String)> = Vec::new();
+        let mut push = |category: ObjectCategory, name: &str| {
+            if !needed.iter().any(|(c, n)| *c == category && n == name) {
+                needed.push((category, name.to_string()));
+            }
+        };
+
+        for object in &self.objects {
+            if object.change == ChangeKind::Removed {
+                continue;
+            }
+            match object.category {
+                ObjectCategory::Views => push(ObjectCategory::Views, &object.name),
+                ObjectCategory::Functions => {
+                    if let Some(function) = self
+                        .source
+                        .functions
+                        .iter()
+                        .find(|f| function_key(f) == object.name)
+                    {
+                        push(ObjectCategory::Functions, &function.name);
+                    }
+                }
+                ObjectCategory::Triggers => push(ObjectCategory::Triggers, &object.name),
+                ObjectCategory::Indexes => {
+                    if let Some(index) = self
+                        .source
+                        .indexes
+                        .iter()
+                        .find(|i| index_key(i) == object.name)
+                    {
+                        push(ObjectCategory::Indexes, &index.name);
+                    }
+                }
+                _ => {}
+            }
+        }
+        for view in self.rebuilt_views() {
+            push(ObjectCategory::Views, &view);
+        }
+        needed
+    }
+
+    /// Views in both schemas that are dropped and created again: the changed
+    /// ones and every view built on them
+    fn rebuilt_views(&self) -> Vec<String> {
+        let mut rebuilt: Vec<String> = self
+            .objects
+            .iter()
+            .filter(|o| o.category == ObjectCategory::Views && o.change == ChangeKind::Changed)
+            .map(|o| o.name.clone())
+            .collect();
+        loop {
+            let dependent = self.target.views.iter().find(|view| {
+                !rebuilt.contains(&view.name)
+                    && self.source.views.iter().any(|v| v.name == view.name)
+                    && rebuilt
+                        .iter()
+                        .any(|name| references(view.definition.as_deref().unwrap_or(""), name))
+            });
+            match dependent {
+                Some(view) => rebuilt.push(view.name.clone()),
+                None => return rebuilt,
+            }
+        }
+    }
+}
+
+/// Compare two snapshots; the source is the schema as it should be
+pub fn compare(source: SchemaSnapshot, target: SchemaSnapshot) -> SchemaDiff {
+    let sides = Sides {
+        source: &source.schema,
+        target: &target.schema,
+    };
+    let mut objects = Vec::new();
+
+    for table in &source.tables {
+        match target.table(&table.name) {
+            None => objects.push(ObjectDiff::new(
+                ObjectCategory::Tables,
+                &table.name,
+                ChangeKind::Added,
+            )),
+            Some(existing) => {
+                let changes = table_changes(&sides, table, existing);
+                if !changes.is_empty() {
+                    let mut object =
+                        ObjectDiff::new(ObjectCategory::Tables, &table.name, ChangeKind::Changed);
+                    object.details = changes.iter().map(TableChange::describe).collect();
+                    object.table_changes = changes;
+                    objects.push(object);
+                }
+            }
+        }
+    }
+    for table in &target.tables {
+        if source.table(&table.name).is_none() {
+            objects.push(ObjectDiff::new(
+                ObjectCategory::Tables,
+                &table.name,
+                ChangeKind::Removed,
+            ));
+        }
+    }
+
+    objects.extend(compare_objects(
+        ObjectCategory::Views,
+        &source.views,
+        &target.views,
+        |view| view.name.clone(),
+        |from, to| {
+            let mut details = Vec::new();
+            if from.view_type != to.view_type {
+                details.push(format!("type {:?} → {:?}", from.view_type, to.view_type));
+            }
+            if !sides.same_text(
+                from.definition.as_deref().unwrap_or(""),
+                to.definition.as_deref().unwrap_or(""),
+            ) {
+                details.push("query differs".to_string());
+            }
+            details
+        },
+    ));
+    objects.extend(compare_objects(
+        ObjectCategory::Functions,
+        &source.functions,
+        &target.functions,
+        function_key,
+        |from, to| {
+            let mut details = Vec::new();
+            if from.function_type != to.function_type {
+                details.push(format!(
+                    "kind {:?} → {:?}",
+                    from.function_type, to.function_type
+                ));
+            }
+            if from.return_type != to.return_type {
+                details.push(format!("returns {} → {}", from.return_type, to.return_type));
+            }
+            if from.language != to.language {
+                details.push(format!("language {} → {}", from.language, to.language));
+            }
+            if !sides.same_text(
+                from.definition.as_deref().unwrap_or(""),
+                to.definition.as_deref().unwrap_or(""),
+            ) {
+                details.push("body differs".to_string());
+            }
+            details
+        },
+    ));
+    objects.extend(compare_objects(
+        ObjectCategory::Triggers,
+        &source.triggers,
+        &target.triggers,
+        trigger_key,
+        |from, to| {
+            let mut details = Vec::new();
+            if from.timing != to.timing || from.events != to.events {
+                details.push(format!(
+                    "fires {:?} {:?} → {:?} {:?}",
+                    from.timing, from.events, to.timing, to.events
+                ));
+            }
+            if from.trigger_type != to.trigger_type {
+                details.push(format!(
+                    "for each {:?} → {:?}",
+                    from.trigger_type, to.trigger_type
+                ));
+            }
+            if from.function_name != to.function_name {
+                details.push(format!(
+                    "executes {} → {}",
+                    from.function_name, to.function_name
+                ));
+            }
+            if !sides.same_text(
+                from.condition.as_deref().unwrap_or(""),
+                to.condition.as_deref().unwrap_or(""),
+            ) {
+                details.push("definition differs".to_string());
+            }
+            details
+        },
+    ));
+    objects.extend(compare_objects(
+        ObjectCategory::Sequences,
+        &source.sequences,
+        &target.sequences,
+        |sequence| sequence.name.clone(),
+        |from, to| {
+            let mut details: Vec<String> = sequence_options(to, from)
+                .into_iter()
+                .map(|option| option.to_lowercase())
+                .collect();
+            if owner(from) != owner(to) {
+                details.push(format!(
+                    "owned by {} → {}",
+                    owner(from).unwrap_or_else(|| "none".to_string()),
+                    owner(to).unwrap_or_else(|| "none".to_string())
+                ));
+            }
+            details
+        },
+    ));
+    objects.extend(compare_objects(
+        ObjectCategory::Indexes,
+        &source.indexes,
+        &target.indexes,
+        index_key,
+        |from, to| {
+            let mut details = Vec::new();
+            if from.index_type != to.index_type {
+                details.push(format!(
+                    "method {:?} → {:?}",
+                    from.index_type, to.index_type
+                ));
+            }
+            if from.is_unique != to.is_unique {
+                details.push(if to.is_unique { "unique" } else { "not unique" }.to_string());
+            }
+            let columns = |index: &Index| {
+                index
+                    .columns
+                    .iter()
+                    .map(|c| format!("{} {:?} {:?}", c.name, c.direction, c.nulls_order))
+                    .collect::<Vec<_>>()
+            };
+            if columns(from) != columns(to) {
+                details.push("columns differ".to_string());
+            }
+            if !sides.same_text(
+                from.condition.as_deref().unwrap_or(""),
+                to.condition.as_deref().unwrap_or(""),
+            ) {
+                details.push("condition differs".to_string());
+            }
+            details
+        },
+    ));
+
+    SchemaDiff {
+        source,
+        target,
+        objects,
+        drop_dependents: Vec::new(),
+    }
+}
+
+impl ObjectDiff {
+    fn new(category: ObjectCategory, name: &str, change: ChangeKind) -> Self {
+        Self {
+            category,
+            name: name.to_string(),
+            change,
+            details: Vec::new(),
+            table_changes: Vec::new(),
+        }
+    }
+}
+
+/// Match objects by key; `differences` gets the target's object first and
+/// the source's second
+fn compare_objects<T>(
+    category: ObjectCategory,
+    source: &[T],
+    target: &[T],
+    key: impl Fn(&T) -> String,
+    differences: impl Fn(&T, &T) -> Vec<String>,
+) -> Vec<ObjectDiff> {
+    let mut objects = Vec::new();
+    for object in source {
+        let name = key(object);
+        match target.iter().find(|o| key(o) == name) {
+            None => objects.push(ObjectDiff::new(category.clone(), &name, ChangeKind::Added)),
+            Some(existing) => {
+                let details = differences(existing, object);
+                if !details.is_empty() {
+                    let mut object = ObjectDiff::new(category.clone(), &name, ChangeKind::Changed);
+                    object.details = details;
+                    objects.push(object);
+                }
+            }
+        }
+    }
+    for object in target {
+        let name = key(object);
+        if !source.iter().any(|o| key(o) == name) {
+            objects.push(ObjectDiff::new(
+                category.clone(),
+                &name,
+                ChangeKind::Removed,
+            ));
+        }
+    }
+    objects
+}
+
+fn table_changes(
+    sides: &Sides,
+    source: &TableSnapshot,
+    target: &TableSnapshot,
+) -> Vec<TableChange> {
+    let mut changes = Vec::new();
+
+    for column in &source.columns {
+        match target.columns.iter().find(|c| c.name == column.name) {
+            None => changes.push(TableChange::AddColumn(column.clone())),
+            Some(existing) => {
+                let default_changed = !sides.same_text(
+                    existing.default_value.as_deref().unwrap_or(""),
+                    column.default_value.as_deref().unwrap_or(""),
+                );
+                if !existing.data_type.eq_ignore_ascii_case(&column.data_type)
+                    || existing.is_nullable != column.is_nullable
+                    || default_changed
+                {
+                    // A default naming only its own schema stays as the target has it
+                    let mut to = column.clone();
+                    if !default_changed {
+                        to.default_value = existing.default_value.clone();
+                    }
+                    changes.push(TableChange::AlterColumn {
+                        from: existing.clone(),
+                        to,
+                    });
+                }
+            }
+        }
+    }
+    for column in &target.columns {
+        if !source.columns.iter().any(|c| c.name == column.name) {
+            changes.push(TableChange::DropColumn(column.clone()));
+        }
+    }
+
+    for constraint in &target.constraints {
+        let kept = source.constraints.iter().any(|c| {
+            c.name == constraint.name
+                && c.constraint_type == constraint.constraint_type
+                && sides.same_text(&constraint.definition, &c.definition)
+        });
+        if !kept {
+            changes.push(TableChange::DropConstraint(constraint.clone()));
+        }
+    }
+    for constraint in &source.constraints {
+        let existing = target.constraints.iter().any(|c| {
+            c.name == constraint.name
+                && c.constraint_type == constraint.constraint_type
+                && sides.same_text(&c.definition, &constraint.definition)
+        });
+        if !existing {
+            changes.push(TableChange::AddConstraint(constraint.clone()));
+        }
+    }
+
+    for key in &target.foreign_keys {
+        if !source
+            .foreign_keys
+            .iter()
+            .any(|k| k.name == key.name && sides.same_key(k, key))
+        {
+            changes.push(TableChange::DropForeignKey(key.clone()));
+        }
+    }
+    for key in &source.foreign_keys {
+        if !target
+            .foreign_keys
+            .iter()
+            .any(|k| k.name == key.name && sides.same_key(key, k))
+        {
+            changes.push(TableChange::AddForeignKey(key.clone()));
+        }
+    }
+
+    changes
+}
+
+/// What differs between two versions of a column, target first
+fn column_changes(from: &Column, to: &Column) -> Vec<String> {
+    let mut changes = Vec::new();
+    if !from.data_type.eq_ignore_ascii_case(&to.data_type) {
+        changes.push(format!("type {} → {}", from.data_type, to.data_type));
+    }
+    if from.is_nullable != to.is_nullable {
+        changes.push(
+            if to.is_nullable {
+                "nullable"
+            } else {
+                "not null"
+            }
+            .to_string(),
+        );
+    }
+    if from.default_value != to.default_value {
+        changes.push(format!(
+            "default {} → {}",
+            from.default_value.as_deref().unwrap_or("none"),
+            to.default_value.as_deref().unwrap_or("none")
+        ));
+    }
+    changes
+}
+
+/// The two schemas being compared, whose names are ignored where objects
+/// mention them
+struct Sides<'a> {
+    source: &'a str,
+    target: &'a str,
+}
+
+impl Sides<'_> {
+    /// Whether a target text and a source text say the same, apart from
+    /// their own schema names and whitespace
+    fn same_text(&self, target: &str, source: &str) -> bool {
+        normalize(target, self.target) == normalize(source, self.source)
+    }
+
+    /// Whether a source foreign key and a target one are the same
+    fn same_key(&self, source: &ForeignKey, target: &ForeignKey) -> bool {
+        let referenced_schema = if source.referenced_schema == self.source {
+            self.target
+        } else {
+            &source.referenced_schema
+        };
+        source.columns == target.columns
+            && referenced_schema == target.referenced_schema
+            && source.referenced_table == target.referenced_table
+            && source.referenced_columns == target.referenced_columns
+            && source.on_update == target.on_update
+            && source.on_delete == target.on_delete
+    }
+}
+
+fn normalize(text: &str, schema: &str) -> String {
+    replace_schema(text, schema, None)
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Replace the qualifier `schema.`, quoted or not, with `replacement.` or
+/// remove it when there is no replacement
+fn replace_schema(text: &str, schema: &str, replacement: Option<&str>) -> String {
+    let prefixes = [
+        (format!("\"{}\".", schema), '"'),
+        (format!("`{}`.", schema), '`'),
+        (format!("{}.", schema), ' '),
+    ];
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+    let mut previous: Option<char> = None;
+    'scan: while let Some(c) = rest.chars().next() {
+        if !previous.is_some_and(is_identifier_char) {
+            for (prefix, quote) in &prefixes {
+                if rest.starts_with(prefix.as_str()) {
+                    if let Some(replacement) = replacement {
+                        match quote {
+                            ' ' => result.push_str(replacement),
+                            quote => {
+                                result.push(*quote);
+                                result.push_str(replacement);
+                                result.push(*quote);
+                            }
+                        }
+                        result.push('.');
+                    }
+                    rest = &rest[prefix.len()..];
+                    previous = Some('.');
+                    continue 'scan;
+                }
+            }
+        }
+        result.push(c);
+        previous = Some(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+fn is_identifier_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '$' | '"' | '`' | '.')
+}
+
+/// Whether a definition mentions an object name as a whole word
+fn references(definition: &str, name: &str) -> bool {
+    let definition = definition.to_lowercase();
+    let name = name.to_lowercase();
+    definition.match_indices(&name).any(|(at, _)| {
+        let before = definition[..at].chars().next_back();
+        let after = definition[at + name.len()..].chars().next();
+        !before.is_some_and(|c| c.is_alphanumeric() || c == '_')
+            && !after.is_some_and(|c| c.is_alphanumeric() || c == '_')
+    })
+}
+
+/// Names ordered so that each comes after those it depends on; cycles keep
+/// their original order
+fn dependency_order(names: Vec<String>, depends_on: impl Fn(&str, &str) -> bool) -> Vec<String> {
+    let mut ordered: Vec<String> = Vec::new();
+    let mut remaining = names;
+    while !remaining.is_empty() {
+        let ready = remaining.iter().position(|name| {
+            remaining
+                .iter()
+                .all(|other| other == name || !depends_on(name, other))
+        });
+        ordered.push(remaining.remove(ready.unwrap_or(0)));
+    }
+    ordered
+}
+
+fn function_key(function: &Function) -> String {
+    format!("{}({})", function.name, identity_arguments(function))
+}
+
+/// Types of the arguments that identify a function among its overloads
+fn identity_arguments(function: &Function) -> String {
+    function
+        .arguments
+        .iter()
+        .filter(|argument| argument.mode != ArgumentMode::Out)
+        .map(|argument| argument.data_type.as_str())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn trigger_key(trigger: &Trigger) -> String {
+    format!("{}.{}", trigger.table_name, trigger.name)
+}
+
+fn index_key(index: &Index) -> String {
+    format!("{}.{}", index.table_name, index.name)
+}
+
+fn owner(sequence: &Sequence) -> Option<String> {
+    match (&sequence.owner_table, &sequence.owner_column) {
+        (Some(table), Some(column)) => Some(format!("{}.{}", table, column)),
+        _ => None,
+    }
+}
+
+/// `ALTER SEQUENCE` options that turn the target's settings into the source's
+fn sequence_options(source: &Sequence, target: &Sequence) -> Vec<String> {
+    let mut options = Vec::new();
+    if source.data_type != target.data_type && !source.data_type.is_empty() {
+        options.push(format!("AS {}", source.data_type));
+    }
+    if source.increment != target.increment {
+        options.push(format!("INCREMENT BY {}", source.increment));
+    }
+    if source.min_value != target.min_value {
+        options.push(match source.min_value {
+            Some(min) => format!("MINVALUE {}", min),
+            None => "NO MINVALUE".to_string(),
+        });
+    }
+    if source.max_value != target.max_value {
+        options.push(match source.max_value {
+            Some(max) => format!("MAXVALUE {}", max),
+            None => "NO MAXVALUE".to_string(),
+        });
+    }
+    if source.start_value != target.start_value {
+        options.push(format!("START WITH {}", source.start_value));
+    }
+    if source.cache_size != target.cache_size {
+        options.push(format!("CACHE {}", source.cache_size));
+    }
+    if source.cycle != target.cycle {
+        options.push(if source.cycle { "CYCLE" } else { "NO CYCLE" }.to_string());
+    }
+    options
+}
+
+/// Statements of views, functions, triggers and indexes, read from the
+/// source server, by category and plain name; triggers go by table and name
+pub type Definitions = HashMap<(ObjectCategory, String), String>;
+
+/// Read the statements the script needs from the source server
+pub async fn load_definitions<E: QueryExecutor + ?Sized>(
+    source: &E,
+    diff: &SchemaDiff,
+) -> Result<Definitions, DatabaseError> {
+    let mut definitions = Definitions::new();
+    for (category, name) in diff.definitions_needed() {
+        let trigger = match category {
+            ObjectCategory::Triggers => {
+                diff.source.triggers.iter().find(|t| trigger_key(t) == name)
+            }
+            _ => None,
+        };
+        let definition = match trigger {
+            Some(trigger) => {
+                source
+                    .get_trigger_ddl(&diff.source.schema, &trigger.table_name, &trigger.name)
+                    .await?
+            }
+            None => {
+                source
+                    .get_object_ddl(&category, &diff.source.schema, &name)
+                    .await?
+            }
+        };
+        definitions.insert((category, name), definition);
+    }
+    Ok(definitions)
+}
+
+/// Find the objects of the target that depend on what the script drops or
+/// alters, other than those the script drops or recreates itself
+///
+/// Only direct dependents count, since any chain of them that stops the
+/// script starts with one. Those that go away with their object, like the
+/// indexes of a dropped column, are left out. Drivers without dependency
+/// tracking find none.
+pub async fn load_drop_dependents<E: QueryExecutor + ?Sized>(
+    target: &E,
+    diff: &SchemaDiff,
+    database_type: &DatabaseType,
+) -> Result<Vec<DropDependents>, DatabaseError> {
+    let definitions = Definitions::new();
+    let migration = Migration {
+        database_type,
+        diff,
+        definitions: &definitions,
+    };
+    let mut found: HashMap<(ObjectCategory, String), Vec<Dependency>> = HashMap::new();
+    let mut warnings = Vec::new();
+    for dropped in migration.dropped_objects() {
+        let key = (dropped.category.clone(), dropped.name.clone());
+        if !found.contains_key(&key) {
+            let dependents = match target
+                .get_dependents(&dropped.category, &diff.target.schema, &dropped.name)
+                .await
+            {
+                Ok(dependents) => dependents,
+                Err(DatabaseError::UnsupportedOperation(_)) => return Ok(Vec::new()),
+                Err(error) => return Err(error),
+            };
+            found.insert(key.clone(), dependents);
+        }
+        let dependents: Vec<Dependency> = found[&key]
+            .iter()
+            .filter(|dependent| {
+                dependent.depth == 1 && !dependent.automatic && !migration.handles(dependent)
+            })
+            .filter(|dependent| {
+                dropped
+                    .column
+                    .as_ref()
+                    .is_none_or(|column| dependent.columns.contains(column))
+            })
+            .cloned()
+            .collect();
+        if !dependents.is_empty() {
+            warnings.push(DropDependents {
+                object: dropped.description,
+                dependents,
+            });
+        }
+    }
+    Ok(warnings)
+}
+
+/// Compare a schema on one connection with a schema on another, or the same,
+/// and write the script that makes the target match the source
+///
+/// Both connections must use the same driver: types, defaults and the
+/// statements read from the source are in its own dialect.
+pub async fn compare_schemas(
+    source: &dyn Database,
+    source_schema: &str,
+    target: &dyn Database,
+    target_schema: &str,
+    target_type: &DatabaseType,
+) -> Result<(SchemaDiff, String), DatabaseError> {
+    if source.database_type() != target.database_type() {
+        return Err(DatabaseError::UnsupportedOperation(format!(
+            "Comparing a {} schema with a {} schema",
+            source.database_type(),
+            target.database_type()
+        )));
+    }
+    let source_snapshot = SchemaSnapshot::load(source, source_schema).await?;
+    let target_snapshot = SchemaSnapshot::load(target, target_schema).await?;
+    let mut diff = compare(source_snapshot, target_snapshot);
+    diff.drop_dependents = load_drop_dependents(target, &diff, target_type).await?;
+    let definitions = load_definitions(source, &diff).await?;
+    let script = migration_script(&diff, target_type, &definitions);
+    Ok((diff, script))
+}
+
+/// The script that changes the target schema to match the source, in the
+/// target's dialect
+///
+/// Objects are dropped before the ones they depend on and created after
+/// them: triggers, views, indexes, keys and tables go first, and come back
+/// in the opposite order.
+pub fn migration_script(
+    diff: &SchemaDiff,
+    database_type: &DatabaseType,
+    definitions: &Definitions,
+) -> String {
+    if diff.is_empty() {
+        return "-- The schemas are identical\n".to_string();
+    }
+    let script = Migration {
+        database_type,
+        diff,
+        definitions,
+    };
+    let mut sections = vec![format!(
+        "-- Changes schema {} to match schema {}",
+        diff.target.schema, diff.source.schema
+    )];
+    if !diff.drop_dependents.is_empty() {
+        sections.push(drop_warning(&diff.drop_dependents));
+    }
+    sections.extend(script.drops());
+    sections.extend(script.creates());
+    ddl::join_sections(sections)
+}
+
+/// Comment listing the objects in the way of the script
+fn drop_warning(drop_dependents: &[DropDependents]) -> String {
+    let mut lines = vec![
+        "-- Warning: other objects depend on what this script drops or alters;".to_string(),
+        "-- it fails until they are dropped or changed first".to_string(),
+    ];
+    for warning in drop_dependents {
+        let dependents: Vec<String> = warning
+            .dependents
+            .iter()
+            .map(|dependent| format!("{} {}", dependent.kind.label(), dependent.qualified_name()))
+            .collect();
+        lines.push(format!(
+            "--   {}: {}",
+            warning.object,
+            dependents.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// An object the script drops or alters, to look its dependents up by
+struct DroppedObject {
+    category: ObjectCategory,
+    name: String,
+    /// The dropped or retyped column of a table that stays
+    column: Option<String>,
+    description: String,
+}
+
+/// Writes the statements of a migration script
+struct Migration<'a> {
+    database_type: &'a DatabaseType,
+    diff: &'a SchemaDiff,
+    definitions: &'a Definitions,
+}
+
+impl Migration<'_> {
+    fn objects(
+        &self,
+        category: ObjectCategory,
+        change: ChangeKind,
+    ) -> impl Iterator<Item = &ObjectDiff> {
+        self.diff
+            .objects
+            .iter()
+            .filter(move |o| o.category == category && o.change == change)
+    }
+
+    fn target_name(&self, name: &str) -> String {
+        ddl::qualified_name(self.database_type, &self.diff.target.schema, name)
+    }
+
+    fn quote(&self, name: &str) -> String {
+        quote_identifier(self.database_type, name)
+    }
+
+    /// A statement of the source server, moved to the target schema
+    fn definition(&self, category: ObjectCategory, name: &str) -> String {
+        match self.definitions.get(&(category.clone(), name.to_string())) {
+            Some(definition) => replace_schema(
+                definition.trim_end(),
+                &self.diff.source.schema,
+                Some(&self.diff.target.schema),
+            ),
+            None => format!("-- No definition available for {:?} {}", category, name),
+        }
+    }
+
+    fn removed_table(&self, name: &str) -> bool {
+        self.objects(ObjectCategory::Tables, ChangeKind::Removed)
+            .any(|o| o.name == name)
+    }
+
+    fn table_changes(&self) -> impl Iterator<Item = (&str, &TableChange)> {
+        self.objects(ObjectCategory::Tables, ChangeKind::Changed)
+            .flat_map(|o| o.table_changes.iter().map(|c| (o.name.as_str(), c)))
+    }
+
+    fn drops(&self) -> Vec<String> {
+        let target = &self.diff.target;
+        let mut sections = Vec::new();
+
+        let triggers = self
+            .objects(ObjectCategory::Triggers, ChangeKind::Removed)
+            .chain(self.objects(ObjectCategory::Triggers, ChangeKind::Changed))
+            .filter_map(|o| target.triggers.iter().find(|t| trigger_key(t) == o.name))
+            .map(|trigger| match self.database_type {
+                DatabaseType::PostgreSQL => format!(
+                    "DROP TRIGGER {} ON {};",
+                    self.quote(&trigger.name),
+                    self.target_name(&trigger.table_name)
+                ),
+                _ => format!("DROP TRIGGER {};", self.target_name(&trigger.name)),
+            })
+            .collect();
+        sections.push(section("Drop triggers", triggers));
+
+        let views = self.dropped_views();
+        let view_definition = |name: &str| target.views.iter().find(|v| v.name == name).cloned();
+        let mut views = dependency_order(views, |view, other| {
+            view_definition(view)
+                .and_then(|v| v.definition)
+                .is_some_and(|definition| references(&definition, other))
+        });
+        // Dependents go first
+        views.reverse();
+        let views = views
+            .iter()
+            .filter_map(|name| view_definition(name))
+            .map(|view| {
+                let kind = match view.view_type {
+                    ViewType::Materialized => "MATERIALIZED VIEW",
+                    ViewType::Regular => "VIEW",
+                };
+                format!("DROP {} {};", kind, self.target_name(&view.name))
+            })
+            .collect();
+        sections.push(section("Drop views", views));
+
+        let indexes = self
+            .objects(ObjectCategory::Indexes, ChangeKind::Removed)
+            .chain(self.objects(ObjectCategory::Indexes, ChangeKind::Changed))
+            .filter_map(|o| target.indexes.iter().find(|i| index_key(i) == o.name))
+            .filter(|index| !self.removed_table(&index.table_name))
+            .map(|index| match self.database_type {
+                DatabaseType::MySQL => format!(
+                    "DROP INDEX {} ON {};",
+                    self.quote(&index.name),
+                    self.target_name(&index.table_name)
+                ),
+                _ => format!("DROP INDEX {};", self.target_name(&index.name)),
+            })
+            .collect();
+        sections.push(section("Drop indexes", indexes));
+
+        // Keys of dropped tables go too, so the tables can go in any order
+        let mut keys: Vec<(&str, &ForeignKey)> = self
+            .objects(ObjectCategory::Tables, ChangeKind::Removed)
+            .filter_map(|o| target.table(&o.name))
+            .flat_map(|t| t.foreign_keys.iter().map(|k| (t.name.as_str(), k)))
+            .collect();
+        keys.extend(
+            self.table_changes()
+                .filter_map(|(table, change)| match change {
+                    TableChange::DropForeignKey(key) => Some((table, key)),
+                    _ => None,
+                }),
+        );
+        let keys = keys
+            .into_iter()
+            .map(|(table, key)| self.drop_foreign_key(table, key))
+            .collect();
+        sections.push(section("Drop foreign keys", keys));
+
+        let constraints = self
+            .table_changes()
+            .filter_map(|(table, change)| match change {
+                TableChange::DropConstraint(constraint) => {
+                    Some(self.drop_constraint(table, constraint))
+                }
+                _ => None,
+            })
+            .collect();
+        sections.push(section("Drop constraints", constraints));
+
+        let tables = self
+            .objects(ObjectCategory::Tables, ChangeKind::Removed)
+            .map(|o| format!("DROP TABLE {};", self.target_name(&o.name)))
+            .collect();
+        sections.push(section("Drop tables", tables));
+
+        let functions = self
+            .dropped_functions()
+            .filter_map(|o| target.functions.iter().find(|f| function_key(f) == o.name))
+            .map(|function| {
+                let kind = match function.function_type {
+                    FunctionType::Procedure => "PROCEDURE",
+                    FunctionType::Aggregate => "AGGREGATE",
+                    _ => "FUNCTION",
+                };
+                match self.database_type {
+                    DatabaseType::PostgreSQL => format!(
+                        "DROP {} {}({});",
+                        kind,
+                        self.target_name(&function.name),
+                        identity_arguments(function)
+                    ),
+                    _ => format!("DROP {} {};", kind, self.target_name(&function.name)),
+                }
+            })
+            .collect();
+        sections.push(section("Drop functions", functions));
+
+        // Sequences owned by a dropped table or column are dropped with it
+        let dropped_columns: Vec<String> = self
+            .table_changes()
+            .filter_map(|(table, change)| match change {
+                TableChange::DropColumn(column) => Some(format!("{}.{}", table, column.name)),
+                _ => None,
+            })
+            .collect();
+        let sequences = self
+            .objects(ObjectCategory::Sequences, ChangeKind::Removed)
+            .filter_map(|o| target.sequences.iter().find(|s| s.name == o.name))
+            .filter(|sequence| {
+                !sequence
+                    .owner_table
+                    .as_deref()
+                    .is_some_and(|table| self.removed_table(table))
+                    && !owner(sequence).is_some_and(|owner| dropped_columns.contains(&owner))
+            })
+            .map(|sequence| format!("DROP SEQUENCE {};", self.target_name(&sequence.name)))
+            .collect();
+        sections.push(section("Drop sequences", sequences));
+
+        sections
+    }
+
+    fn creates(&self) -> Vec<String> {
+        let source = &self.diff.source;
+        let target = &self.diff.target;
+        let mut sections = Vec::new();
+
+        // Owners are set once the tables exist
+        let mut sequences: Vec<String> = self
+            .objects(ObjectCategory::Sequences, ChangeKind::Added)
+            .filter_map(|o| source.sequences.iter().find(|s| s.name == o.name))
+            .map(|sequence| {
+                let sequence = Sequence {
+                    schema: target.schema.clone(),
+                    owner_table: None,
+                    owner_column: None,
+                    ..sequence.clone()
+                };
+                ddl::sequence_ddl(self.database_type, &sequence)
+            })
+            .collect();
+        for object in self.objects(ObjectCategory::Sequences, ChangeKind::Changed) {
+            let (Some(from), Some(to)) = (
+                target.sequences.iter().find(|s| s.name == object.name),
+                source.sequences.iter().find(|s| s.name == object.name),
+            ) else {
+                continue;
+            };
+            let options = sequence_options(to, from);
+            if !options.is_empty() {
+                sequences.push(format!(
+                    "ALTER SEQUENCE {} {};",
+                    self.target_name(&object.name),
+                    options.join(" ")
+                ));
+            }
+        }
+        sections.push(section("Create and alter sequences", sequences));
+
+        let mut names: Vec<String> = Vec::new();
+        for object in self
+            .objects(ObjectCategory::Functions, ChangeKind::Added)
+            .chain(self.objects(ObjectCategory::Functions, ChangeKind::Changed))
+        {
+            if let Some(function) = source
+                .functions
+                .iter()
+                .find(|f| function_key(f) == object.name)
+            {
+                if !names.contains(&function.name) {
+                    names.push(function.name.clone());
+                }
+            }
+        }
+        let functions = names
+            .iter()
+            .map(|name| self.definition(ObjectCategory::Functions, name))
+            .collect();
+        sections.push(section("Create functions", functions));
+
+        let tables = self
+            .objects(ObjectCategory::Tables, ChangeKind::Added)
+            .filter_map(|o| source.table(&o.name))
+            .map(|table| self.create_table(table))
+            .collect();
+        sections.push(section("Create tables", tables));
+
+        let columns = self
+            .table_changes()
+            .flat_map(|(table, change)| self.alter_columns(table, change))
+            .collect();
+        sections.push(section("Alter columns", columns));
+
+        let constraints = self
+            .table_changes()
+            .filter_map(|(table, change)| match change {
+                TableChange::AddConstraint(constraint) => {
+                    Some(self.add_constraint(table, &constraint.name, &constraint.definition))
+                }
+                _ => None,
+            })
+            .collect();
+        sections.push(section("Add constraints", constraints));
+
+        // SQLite declares the keys of new tables in CREATE TABLE
+        let mut keys: Vec<(&str, &ForeignKey)> = Vec::new();
+        if *self.database_type != DatabaseType::SQLite {
+            keys.extend(
+                self.objects(ObjectCategory::Tables, ChangeKind::Added)
+                    .filter_map(|o| source.table(&o.name))
+                    .flat_map(|t| t.foreign_keys.iter().map(|k| (t.name.as_str(), k))),
+            );
+        }
+        keys.extend(
+            self.table_changes()
+                .filter_map(|(table, change)| match change {
+                    TableChange::AddForeignKey(key) => Some((table, key)),
+                    _ => None,
+                }),
+        );
+        let keys = keys
+            .into_iter()
+            .map(|(table, key)| {
+                let definition =
+                    ddl::foreign_key_definition(self.database_type, &self.retarget_key(key));
+                self.add_constraint(table, &key.name, &definition)
+            })
+            .collect();
+        sections.push(section("Add foreign keys", keys));
+
+        let owners = self
+            .objects(ObjectCategory::Sequences, ChangeKind::Added)
+            .chain(self.objects(ObjectCategory::Sequences, ChangeKind::Changed))
+            .filter_map(|o| source.sequences.iter().find(|s| s.name == o.name))
+            .filter(|sequence| {
+                let previous = target.sequences.iter().find(|s| s.name == sequence.name);
+                owner(sequence) != previous.and_then(owner)
+            })
+            .map(|sequence| {
+                let owned_by = match (&sequence.owner_table, &sequence.owner_column) {
+                    (Some(table), Some(column)) => {
+                        format!("{}.{}", self.target_name(table), self.quote(column))
+                    }
+                    _ => "NONE".to_string(),
+                };
+                format!(
+                    "ALTER SEQUENCE {} OWNED BY {};",
+                    self.target_name(&sequence.name),
+                    owned_by
+                )
+            })
+            .collect();
+        sections.push(section("Set sequence owners", owners));
+
+        let indexes = self
+            .objects(ObjectCategory::Indexes, ChangeKind::Added)
+            .chain(self.objects(ObjectCategory::Indexes, ChangeKind::Changed))
+            .filter_map(|o| source.indexes.iter().find(|i| index_key(i) == o.name))
+            .map(|index| self.definition(ObjectCategory::Indexes, &index.name))
+            .collect();
+        sections.push(section("Create indexes", indexes));
+
+        let mut views: Vec<String> = self
+            .objects(ObjectCategory::Views, ChangeKind::Added)
+            .chain(self.objects(ObjectCategory::Views, ChangeKind::Changed))
+            .map(|o| o.name.clone())
+            .collect();
+        for view in self.diff.rebuilt_views() {
+            if !views.contains(&view) {
+                views.push(view);
+            }
+        }
+        let views = dependency_order(views, |view, other| {
+            source
+                .views
+                .iter()
+                .find(|v| v.name == view)
+                .and_then(|v| v.definition.as_deref())
+                .is_some_and(|definition| references(definition, other))
+        })
+        .iter()
+        .map(|name| self.definition(ObjectCategory::Views, name))
+        .collect();
+        sections.push(section("Create views", views));
+
+        let triggers = self
+            .objects(ObjectCategory::Triggers, ChangeKind::Added)
+            .chain(self.objects(ObjectCategory::Triggers, ChangeKind::Changed))
+            .filter(|object| {
+                source
+                    .triggers
+                    .iter()
+                    .any(|t| trigger_key(t) == object.name)
+            })
+            .map(|object| self.definition(ObjectCategory::Triggers, &object.name))
+            .collect();
+        sections.push(section("Create triggers", triggers));
+
+        sections
+    }
+
+    /// Views the script drops, whether for good or to create them again
+    fn dropped_views(&self) -> Vec<String> {
+        let mut views: Vec<String> = self
+            .objects(ObjectCategory::Views, ChangeKind::Removed)
+            .map(|o| o.name.clone())
+            .collect();
+        views.extend(self.diff.rebuilt_views());
+        views
+    }
+
+    /// Functions the script drops, whether for good or to create them again
+    fn dropped_functions(&self) -> impl Iterator<Item = &ObjectDiff> {
+        self.objects(ObjectCategory::Functions, ChangeKind::Removed)
+            .chain(
+                self.objects(ObjectCategory::Functions, ChangeKind::Changed)
+                    .filter(|o| !self.replaces_function(&o.name)),
+            )
+    }
+
+    /// Objects whose dependents can get in the way of the script: those it
+    /// drops, and the columns it drops or changes the type of
+    fn dropped_objects(&self) -> Vec<DroppedObject> {
+        let target = &self.diff.target;
+        let object = |category: ObjectCategory, name: &str, kind: &str| DroppedObject {
+            category,
+            name: name.to_string(),
+            column: None,
+            description: format!("{} {}", kind, name),
+        };
+        let mut dropped: Vec<DroppedObject> = self
+            .objects(ObjectCategory::Tables, ChangeKind::Removed)
+            .map(|o| object(ObjectCategory::Tables, &o.name, "table"))
+            .collect();
+        dropped.extend(
+            self.dropped_views()
+                .iter()
+                .map(|name| object(ObjectCategory::Views, name, "view")),
+        );
+        dropped.extend(
+            self.dropped_functions()
+                .map(|o| object(ObjectCategory::Functions, &o.name, "function")),
+        );
+        dropped.extend(
+            self.objects(ObjectCategory::Sequences, ChangeKind::Removed)
+                .map(|o| object(ObjectCategory::Sequences, &o.name, "sequence")),
+        );
+        dropped.extend(
+            self.objects(ObjectCategory::Indexes, ChangeKind::Removed)
+                .chain(self.objects(ObjectCategory::Indexes, ChangeKind::Changed))
+                .filter_map(|o| target.indexes.iter().find(|i| index_key(i) == o.name))
+                .filter(|index| !self.removed_table(&index.table_name))
+                .map(|index| object(ObjectCategory::Indexes, &index.name, "index")),
+        );
+        dropped.extend(self.table_changes().filter_map(|(table, change)| {
+            let column = match change {
+                TableChange::DropColumn(column) => column,
+                TableChange::AlterColumn { from, to } if from.data_type != to.data_type => from,
+                _ => return None,
+            };
+            Some(DroppedObject {
+                category: ObjectCategory::Tables,
+                name: table.to_string(),
+                column: Some(column.name.clone()),
+                description: format!("column {}.{}", table, column.name),
+            })
+        }));
+        dropped
+    }
+
+    /// Whether the script drops or recreates a dependent itself
+    fn handles(&self, dependent: &Dependency) -> bool {
+        if dependent.schema != self.diff.target.schema {
+            return false;
+        }
+        let name = dependent.name.as_str();
+        let Some(table) = &dependent.table else {
+            return match dependent.kind {
+                DependencyKind::Table => self.removed_table(name),
+                DependencyKind::View | DependencyKind::MaterializedView => {
+                    self.dropped_views().iter().any(|view| view == name)
+                }
+                DependencyKind::Function | DependencyKind::Procedure => {
+                    self.dropped_functions().any(|o| o.name == name)
+                }
+                DependencyKind::Sequence => self
+                    .objects(ObjectCategory::Sequences, ChangeKind::Removed)
+                    .any(|o| o.name == name),
+                _ => false,
+            };
+        };
+        if self.removed_table(table) {
+            return true;
+        }
+        let key = format!("{}.{}", table, name);
+        let dropped_or_changed = |category: ObjectCategory| {
+            self.objects(category.clone(), ChangeKind::Removed)
+                .chain(self.objects(category, ChangeKind::Changed))
+                .any(|o| o.name == key)
+        };
+        match dependent.kind {
+            DependencyKind::Index => dropped_or_changed(ObjectCategory::Indexes),
+            DependencyKind::Trigger => dropped_or_changed(ObjectCategory::Triggers),
+            DependencyKind::Constraint => self.table_changes().any(|(t, change)| {
+                t == table
+                    && match change {
+                        TableChange::DropConstraint(constraint) => constraint.name == name,
+                        TableChange::DropForeignKey(key) => key.name == name,
+                        _ => false,
+                    }
+            }),
+            DependencyKind::Column | DependencyKind::ColumnDefault => {
+                self.table_changes().any(|(t, change)| {
+                    t == table
+                        && matches!(change, TableChange::DropColumn(column) if column.name == name)
+                })
+            }
+            _ => false,
+        }
+    }
+
+    /// Whether a changed function is replaced in place rather than dropped,
+    /// which PostgreSQL allows while its kind and result stay the same
+    fn replaces_function(&self, key: &str) -> bool {
+        let find =
+            |functions: &[Function]| functions.iter().find(|f| function_key(f) == key).cloned();
+        match (
+            find(&self.diff.source.functions),
+            find(&self.diff.target.functions),
+        ) {
+            (Some(source), Some(target)) => {
+                *self.database_type == DatabaseType::PostgreSQL
+                    && source.function_type == target.function_type
+                    && source.return_type == target.return_type
+            }
+            _ => false,
+        }
+    }
+
+    fn create_table(&self, table: &TableSnapshot) -> String {
+        let foreign_keys = match self.database_type {
+            DatabaseType::SQLite => table
+                .foreign_keys
+                .iter()
+                .map(|key| self.retarget_key(key))
+                .collect(),
+            _ => Vec::new(),
+        };
+        let definition = TableDefinition {
+            schema: self.diff.target.schema.clone(),
+            name: table.name.clone(),
+            kind: RelationKind::Table,
+            columns: table
+                .columns
+                .iter()
+                .map(|column| self.column_definition(column))
+                .collect(),
+            query: None,
+            constraints: table
+                .constraints
+                .iter()
+                .map(|constraint| Constraint {
+                    definition: self.retarget(&constraint.definition),
+                    ..constraint.clone()
+                })
+                .collect(),
+            foreign_keys,
+            indexes: Vec::new(),
+            comment: None,
+            owner: None,
+            grants: Vec::new(),
+        };
+        ddl::table_ddl(self.database_type, &definition)
+            .trim_end()
+            .to_string()
+    }
+
+    fn column_definition(&self, column: &Column) -> ColumnDefinition {
+        ColumnDefinition {
+            name: column.name.clone(),
+            data_type: column.data_type.clone(),
+            collation: None,
+            not_null: !column.is_nullable,
+            default: column
+                .default_value
+                .as_deref()
+                .map(|value| ColumnDefault::Value(self.retarget(value))),
+            comment: None,
+        }
+    }
+
+    fn alter_columns(&self, table: &str, change: &TableChange) -> Vec<String> {
+        let alter = format!("ALTER TABLE {}", self.target_name(table));
+        match change {
+            TableChange::AddColumn(column) => vec![format!(
+                "{} ADD COLUMN {};",
+                alter,
+                ddl::column_definition(self.database_type, &self.column_definition(column))
+            )],
+            TableChange::DropColumn(column) => {
+                vec![format!(
+                    "{} DROP COLUMN {};",
+                    alter,
+                    self.quote(&column.name)
+                )]
+            }
+            TableChange::AlterColumn { from, to } => match self.database_type {
+                DatabaseType::PostgreSQL => {
+                    let column = format!("{} ALTER COLUMN {}", alter, self.quote(&to.name));
+                    let mut statements = Vec::new();
+                    if !from.data_type.eq_ignore_ascii_case(&to.data_type) {
+                        statements.push(format!("{} TYPE {};", column, to.data_type));
+                    }
+                    if from.default_value != to.default_value {
+                        statements.push(match &to.default_value {
+                            Some(value) => {
+                                format!("{} SET DEFAULT {};", column, self.retarget(value))
+                            }
+                            None => format!("{} DROP DEFAULT;", column),
+                        });
+                    }
+                    if from.is_nullable != to.is_nullable {
+                        statements.push(if to.is_nullable {
+                            format!("{} DROP NOT NULL;", column)
+                        } else {
+                            format!("{} SET NOT NULL;", column)
+                        });
+                    }
+                    statements
+                }
+                DatabaseType::MySQL => vec![format!(
+                    "{} MODIFY COLUMN {};",
+                    alter,
+                    ddl::column_definition(self.database_type, &self.column_definition(to))
+                )],
+                DatabaseType::SQLite => vec![format!(
+                    "-- SQLite cannot alter column {} of {}: rebuild the table ({})",
+                    to.name,
+                    table,
+                    column_changes(from, to).join(", ")
+                )],
+            },
+            _ => Vec::new(),
+        }
+    }
+
+    fn add_constraint(&self, table: &str, name: &str, definition: &str) -> String {
+        if *self.database_type == DatabaseType::SQLite {
+            return format!(
+                "-- SQLite cannot add constraint {} to {}: rebuild the table",
+                name, table
+            );
+        }
+        format!(
+            "ALTER TABLE {} ADD CONSTRAINT {} {};",
+            self.target_name(table),
+            self.quote(name),
+            self.retarget(definition)
+        )
+    }
+
+    fn drop_constraint(&self, table: &str, constraint: &Constraint) -> String {
+        let alter = format!("ALTER TABLE {}", self.target_name(table));
+        let name = self.quote(&constraint.name);
+        match (self.database_type, &constraint.constraint_type) {
+            (DatabaseType::SQLite, _) => format!(
+                "-- SQLite cannot drop constraint {} of {}: rebuild the table",
+                constraint.name, table
+            ),
+            (DatabaseType::MySQL, ConstraintType::PrimaryKey) => {
+                format!("{} DROP PRIMARY KEY;", alter)
+            }
+            (DatabaseType::MySQL, ConstraintType::Unique) => {
+                format!("{} DROP INDEX {};", alter, name)
+            }
+            (DatabaseType::MySQL, ConstraintType::Check) => {
+                format!("{} DROP CHECK {};", alter, name)
+            }
+            _ => format!("{} DROP CONSTRAINT {};", alter, name),
+        }
+    }
+
+    fn drop_foreign_key(&self, table: &str, key: &ForeignKey) -> String {
+        let alter = format!("ALTER TABLE {}", self.target_name(table));
+        match self.database_type {
+            DatabaseType::SQLite => format!(
+                "-- SQLite cannot drop foreign key {} of {}: rebuild the table",
+                key.name, table
+            ),
+            DatabaseType::MySQL => {
+                format!("{} DROP FOREIGN KEY {};", alter, self.quote(&key.name))
+            }
+            DatabaseType::PostgreSQL => {
+                format!("{} DROP CONSTRAINT {};", alter, self.quote(&key.name))
+            }
+        }
+    }
+
+    /// A foreign key referencing the source schema references the target
+    fn retarget_key(&self, key: &ForeignKey) -> ForeignKey {
+        let mut key = key.clone();
+        if key.referenced_schema == self.diff.source.schema {
+            key.referenced_schema = self.diff.target.schema.clone();
+        }
+        key
+    }
+
+    fn retarget(&self, text: &str) -> String {
+        replace_schema(
+            text,
+            &self.diff.source.schema,
+            Some(&self.diff.target.schema),
+        )
+    }
+}
+
+/// Statements under a heading, or nothing when there are none
+fn section(title: &str, statements: Vec<String>) -> String {
+    if statements.is_empty() {
+        return String::new();
+    }
+    // Definitions of several statements are kept apart by blank lines
+    let separator = if statements.iter().any(|s| s.contains('\n')) {
+        "\n\n"
+    } else {
+        "\n"
+    };
+    format!("-- {}\n{}", title, statements.join(separator))
+}
@@ -0,0 +1,357 @@
+use std::ops::Range;
+
+/// One statement of a SQL script
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptStatement {
+    /// Statement text without the terminating `;`
+    pub sql: String,
+    /// Byte range of `sql` within the script
+    pub range: Range<usize>,
+    /// Line the statement starts on, counting from 1
+    pub line: usize,
+}
+
+/// Split a script into statements at top-level semicolons
+///
+/// Semicolons inside string literals, quoted identifiers, comments,
+/// dollar-quoted bodies and the `BEGIN ... END` blocks of `CREATE` statements
+/// (triggers, procedures, `BEGIN ATOMIC` functions) do not end a statement.
+/// Comments before a statement are left out of it, and pieces holding nothing
+/// but comments are dropped.
+pub fn split_statements(script: &str) -> Vec<ScriptStatement> {
+    split(script, false)
+}
+
+/// Statement of an editor buffer at a byte offset, e.g. the text cursor
+///
+/// Unlike in [`split_statements`], a blank line also ends a statement, so
+/// unterminated queries typed one after another can be run one at a time. A
+/// cursor right after a statement, on the same line, still belongs to it.
+pub fn statement_at(script: &str, offset: usize) -> Option<ScriptStatement> {
+    let statements = split(script, true);
+    if let Some(statement) = statements
+        .iter()
+        .find(|statement| statement.range.contains(&offset) || statement.range.end == offset)
+    {
+        return Some(statement.clone());
+    }
+
+    statements.into_iter().rev().find(|statement| {
+        statement.range.end <= offset
+            && script
+                .get(statement.range.end..offset)
+                .is_some_and(|gap| !gap.contains('\n'))
+    })
+}
+
+/// Whether a single statement only reads data: a SELECT, VALUES or TABLE
+/// query, or a WITH query, without any part that writes or locks rows
+///
+/// Running such a statement again returns the same rows as long as the data
+/// stays the same.
+pub fn is_read_only_query(sql: &str) -> bool {
+    let words = words(sql);
+    matches!(
+        words.first().map(String::as_str),
+        Some("SELECT" | "VALUES" | "TABLE" | "WITH")
+    ) && !words.iter().any(|word| {
+        // SELECT ... INTO creates a table, FOR UPDATE locks rows
+        matches!(
+            word.as_str(),
+            "INSERT" | "UPDATE" | "DELETE" | "MERGE" | "INTO"
+        )
+    }) && split_statements(sql).len() == 1
+}
+
+/// First keyword of a statement, upper-cased, after any comments
+pub(crate) fn first_keyword(sql: &str) -> Option<String> {
+    words(sql).into_iter().next()
+}
+
+/// Upper-cased words of the code, leaving out literals, quoted identifiers
+/// and comments
+fn words(sql: &str) -> Vec<String> {
+    let bytes = sql.as_bytes();
+    let mut words = Vec::new();
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let byte = bytes[pos];
+        match byte {
+            b'-' if bytes.get(pos + 1) == Some(&b'-') => {
+                pos = sql[pos..].find('\n').map_or(bytes.len(), |end| pos + end);
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => pos = skip_block_comment(bytes, pos),
+            b'\'' | b'"' | b'`' => pos = skip_string(bytes, pos, false),
+            b'$' if pos == 0 || !is_identifier_byte(bytes[pos - 1]) => {
+                pos = match dollar_quote_tag(sql, pos) {
+                    Some(tag) => {
+                        let body = pos + tag.len();
+                        sql[body..]
+                            .find(tag)
+                            .map_or(bytes.len(), |end| body + end + tag.len())
+                    }
+                    None => pos + 1,
+                };
+            }
+            _ if is_identifier_start(byte) => {
+                let start = pos;
+                while pos < bytes.len() && is_identifier_byte(bytes[pos]) {
+                    pos += 1;
+                }
+                words.push(sql[start..pos].to_ascii_uppercase());
+            }
+            _ => pos += 1,
+        }
+    }
+    words
+}
+
+fn split(script: &str, blank_line_separates: bool) -> Vec<ScriptStatement> {
+    let bytes = script.as_bytes();
+    let mut statements = Vec::new();
+
+    // Byte range of the code in the current statement
+    let mut code_start: Option<usize> = None;
+    let mut code_end = 0;
+
+    // Keyword state for BEGIN ... END blocks
+    let mut first_word: Option<String> = None;
+    let mut created_kind: Option<String> = None;
+    let mut previous_word = String::new();
+    let mut depth = 0usize;
+    let mut parens = 0usize;
+
+    // Line breaks since the last token, to find blank lines
+    let mut line_breaks = 0;
+
+    let mut pos = 0;
+    while pos < bytes.len() {
+        let byte = bytes[pos];
+        let token_start = pos;
+
+        if byte == b'\n' {
+            line_breaks += 1;
+            if blank_line_separates && line_breaks == 2 && depth == 0 {
+                if let Some(start) = code_start.take() {
+                    statements.push(statement(script, start..code_end));
+                }
+                first_word = None;
+                created_kind = None;
+                previous_word.clear();
+                parens = 0;
+            }
+        } else if !byte.is_ascii_whitespace() {
+            line_breaks = 0;
+        }
+
+        match byte {
+            b'-' if bytes.get(pos + 1) == Some(&b'-') => {
+                pos = script[pos..]
+                    .find('\n')
+                    .map_or(bytes.len(), |end| pos + end);
+                continue;
+            }
+            b'/' if bytes.get(pos + 1) == Some(&b'*') => {
+                pos = skip_block_comment(bytes, pos);
+                continue;
+            }
+            b';' if depth == 0 => {
+                if let Some(start) = code_start.take() {
+                    statements.push(statement(script, start..code_end));
+                }
+                first_word = None;
+                created_kind = None;
+                previous_word.clear();
+                parens = 0;
+                pos += 1;
+                continue;
+            }
+            b'(' => {
+                parens += 1;
+                pos += 1;
+            }
+            b')' => {
+                parens = parens.saturating_sub(1);
+                pos += 1;
+            }
+            b'\'' => {
+                let backslash_escapes = pos > 0
+                    && matches!(bytes[pos - 1], b'e' | b'E')
+                    && (pos < 2 || !is_identifier_byte(bytes[pos - 2]));
+                pos = skip_string(bytes, pos, backslash_escapes);
+            }
+            b'"' | b'`' => pos = skip_string(bytes, pos, false),
+            b'$' if pos == 0 || !is_identifier_byte(bytes[pos - 1]) => {
+                pos = match dollar_quote_tag(script, pos) {
+                    Some(tag) => {
+                        let body = pos + tag.len();
+                        script[body..]
+                            .find(tag)
+                            .map_or(bytes.len(), |end| body + end + tag.len())
+                    }
+                    None => pos + 1,
+                };
+            }
+            _ if is_identifier_start(byte) => {
+                while pos < bytes.len() && is_identifier_byte(bytes[pos]) {
+                    pos += 1;
+                }
+                let word = script[token_start..pos].to_ascii_uppercase();
+
+                // Only routine, trigger and event bodies hold blocks; a BEGIN
+                // in parentheses is a column or argument name
+                if first_word.as_deref() == Some("CREATE")
+                    && created_kind.is_none()
+                    && is_object_kind(&word)
+                {
+                    created_kind = Some(word.clone());
+                }
+                let in_body = matches!(
+                    created_kind.as_deref(),
+                    Some("FUNCTION" | "PROCEDURE" | "TRIGGER" | "EVENT")
+                ) && parens == 0;
+                match word.as_str() {
+                    "BEGIN" if in_body && next_byte(bytes, pos) != Some(b';') => depth += 1,
+                    "CASE" if depth > 0 && previous_word != "END" => depth += 1,
+                    "END" if depth > 0 => {
+                        // IF, LOOP, WHILE and REPEAT do not open a block here,
+                        // so their END does not close one
+                        let next = next_word(script, pos).to_ascii_uppercase();
+                        if !matches!(next.as_str(), "IF" | "LOOP" | "WHILE" | "REPEAT") {
+                            depth -= 1;
+                        }
+                    }
+                    _ => {}
+                }
+
+                if first_word.is_none() {
+                    first_word = Some(word.clone());
+                }
+                previous_word = word;
+            }
+            _ if byte.is_ascii_whitespace() => {
+                pos += 1;
+                continue;
+            }
+            _ => pos += 1,
+        }
+
+        code_start.get_or_insert(token_start);
+        code_end = pos;
+    }
+
+    if let Some(start) = code_start {
+        statements.push(statement(script, start..code_end));
+    }
+
+    statements
+}
+
+fn statement(script: &str, range: Range<usize>) -> ScriptStatement {
+    ScriptStatement {
+        sql: script[range.clone()].to_string(),
+        line: script[..range.start].matches('\n').count() + 1,
+        range,
+    }
+}
+
+fn is_identifier_start(byte: u8) -> bool {
+    byte.is_ascii_alphabetic() || byte == b'_' || byte >= 0x80
+}
+
+fn is_identifier_byte(byte: u8) -> bool {
+    is_identifier_start(byte) || byte.is_ascii_digit() || byte == b'$'
+}
+
+/// Position after a quoted string or identifier starting at `start`
+///
+/// A doubled quote character stands for itself.
+fn skip_string(bytes: &[u8], start: usize, backslash_escapes: bool) -> usize {
+    let quote = bytes[start];
+    let mut pos = start + 1;
+    while pos < bytes.len() {
+        match bytes[pos] {
+            b'\\' if backslash_escapes => pos += 2,
+            byte if byte == quote => {
+                if bytes.get(pos + 1) == Some(&quote) {
+                    pos += 2;
+                } else {
+                    return pos + 1;
+                }
+            }
+            _ => pos += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Position after a block comment starting at `start`; comments nest as in PostgreSQL
+fn skip_block_comment(bytes: &[u8], start: usize) -> usize {
+    let mut nesting = 0;
+    let mut pos = start;
+    while pos + 1 < bytes.len() {
+        match (bytes[pos], bytes[pos + 1]) {
+            (b'/', b'*') => {
+                nesting += 1;
+                pos += 2;
+            }
+            (b'*', b'/') => {
+                nesting -= 1;
+                pos += 2;
+                if nesting == 0 {
+                    return pos;
+                }
+            }
+            _ => pos += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// `$tag$` delimiter starting at `start`, if there is one
+///
+/// `$1` style parameters are not delimiters because a tag cannot start with a digit.
+fn dollar_quote_tag(script: &str, start: usize) -> Option<&str> {
+    let bytes = script.as_bytes();
+    let mut pos = start + 1;
+    if pos < bytes.len() && is_identifier_start(bytes[pos]) {
+        while pos < bytes.len() && is_identifier_byte(bytes[pos]) && bytes[pos] != b'$' {
+            pos += 1;
+        }
+    }
+    (bytes.get(pos) == Some(&b'$')).then(|| &script[start..=pos])
+}
+
+/// Whether a word of a CREATE statement names the kind of object it makes
+fn is_object_kind(word: &str) -> bool {
+    matches!(
+        word,
+        "TABLE"
+            | "VIEW"
+            | "INDEX"
+            | "SEQUENCE"
+            | "SCHEMA"
+            | "TYPE"
+            | "DOMAIN"
+            | "FUNCTION"
+            | "PROCEDURE"
+            | "TRIGGER"
+            | "EVENT"
+    )
+}
+
+fn next_byte(bytes: &[u8], pos: usize) -> Option<u8> {
+    bytes[pos..]
+        .iter()
+        .copied()
+        .find(|byte| !byte.is_ascii_whitespace())
+}
+
+fn next_word(script: &str, pos: usize) -> &str {
+    let rest = script[pos..].trim_start();
+    let end = rest
+        .bytes()
+        .position(|byte| !is_identifier_byte(byte))
+        .unwrap_or(rest.len());
+    &rest[..end]
+}
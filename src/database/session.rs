@@ -0,0 +1,61 @@
+use std::sync::{Arc, Mutex};
+
+/// Tracks the server session that is running a statement, so another
+/// connection can cancel it
+#[derive(Debug, Clone)]
+pub(crate) struct SessionTracker<T: Copy + PartialEq> {
+    /// The running session, and whether this client asked to cancel it
+    current: Arc<Mutex<Option<(T, bool)>>>,
+}
+
+impl<T: Copy + PartialEq> SessionTracker<T> {
+    pub(crate) fn new() -> Self {
+        Self {
+            current: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    /// Session running a statement right now, recorded as cancelled by this
+    /// client before the cancel request goes out
+    pub(crate) fn cancel(&self) -> Option<T> {
+        let mut current = self.current.lock().unwrap();
+        let (session, cancel_sent) = current.as_mut()?;
+        *cancel_sent = true;
+        Some(*session)
+    }
+
+    /// Record a session until the returned guard is dropped
+    ///
+    /// The guard also clears the record when the statement's future is dropped
+    /// half way, e.g. by a client-side timeout. A newer session recorded in the
+    /// meantime is left alone.
+    pub(crate) fn track(&self, session: T) -> SessionGuard<T> {
+        *self.current.lock().unwrap() = Some((session, false));
+        SessionGuard {
+            tracker: self.clone(),
+            session,
+        }
+    }
+}
+
+pub(crate) struct SessionGuard<T: Copy + PartialEq> {
+    tracker: SessionTracker<T>,
+    session: T,
+}
+
+impl<T: Copy + PartialEq> SessionGuard<T> {
+    /// Whether this client asked to cancel the statement, as opposed to the
+    /// server stopping it on its own, e.g. for a timeout
+    pub(crate) fn cancel_sent(&self) -> bool {
+        *self.tracker.current.lock().unwrap() == Some((self.session, true))
+    }
+}
+
+impl<T: Copy + PartialEq> Drop for SessionGuard<T> {
+    fn drop(&mut self) {
+        let mut current = self.tracker.current.lock().unwrap();
+        if current.is_some_and(|(session, _)| session == self.session) {
+            *current = None;
+        }
+    }
+}
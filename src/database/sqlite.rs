@@ -0,0 +1,1121 @@
+use crate::database::cursor::{serve_cursor, SessionStatement};
+use crate::database::ddl;
+use crate::database::{
+    is_read_only_query, Column as DbColumn, ConnectionParams, Constraint, ConstraintType, Database,
+    DatabaseConnection, DatabaseError, DatabaseObjectCounts, Dependency, DmlStatement,
+    DriverCapabilities, ExplainFormat, ForeignKey, ForeignKeyAction, Function, Index, IndexColumn,
+    IndexType, ObjectCategory, ObjectCounts, QueryColumn, QueryCursor, QueryExecutor, QueryResult,
+    QueryRow, QuerySession, QueryValue, Schema, Sequence, SortDirection, Table, Trigger,
+    TriggerEvent, TriggerTiming, TriggerType, View, ViewType,
+};
+use async_trait::async_trait;
+use futures_util::TryStreamExt;
+use sqlx::sqlite::{
+    SqliteConnectOptions, SqliteConnection, SqlitePool, SqlitePoolOptions, SqliteRow,
+};
+use sqlx::{Column, ConnectOptions, Executor, Row, TypeInfo, ValueRef};
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+/// Filter that hides SQLite's own bookkeeping tables (`sqlite_sequence`, `sqlite_stat1`, ...)
+const USER_OBJECT_FILTER: &str = "name NOT LIKE 'sqlite\\_%' ESCAPE '\\'";
+
+/// SQLite database connection implementation
+///
+/// `ConnectionParams::database` holds the path of the database file, or `:memory:`
+/// for a private in-memory database. Attached databases are exposed as schemas.
+pub struct SQLiteConnection {
+    pool: Option<SqlitePool>,
+    options: Option<SqliteConnectOptions>,
+    connection_info: Option<String>,
+}
+
+impl SQLiteConnection {
+    pub fn new() -> Self {
+        Self {
+            pool: None,
+            options: None,
+            connection_info: None,
+        }
+    }
+
+    /// Get the connection pool (internal use)
+    fn get_pool(&self) -> Result<&SqlitePool, DatabaseError> {
+        self.pool.as_ref().ok_or(DatabaseError::NotConnected)
+    }
+
+    /// Features of SQLite exposed through this driver
+    pub fn capabilities() -> DriverCapabilities {
+        DriverCapabilities {
+            // Attached databases stand in for schemas
+            supports_schemas: false,
+            supports_sequences: false,
+            supports_materialized_views: false,
+            supports_routines: false,
+            supports_cancellation: false,
+            supports_statement_timeout: false,
+            supports_dependencies: false,
+            // Result columns do not name the table columns they were read from
+            supports_result_editing: false,
+            // EXPLAIN QUERY PLAN only has a tree-shaped text form
+            explain_formats: vec![ExplainFormat::Tree],
+        }
+    }
+
+    /// Open a connection of its own for a cursor, so that the pooled connection
+    /// is free for other work for as long as the cursor is open
+    ///
+    /// Database files attached on the pooled connection are attached again;
+    /// attached in-memory databases cannot be shared and are left out.
+    async fn open_cursor_connection(&self) -> Result<SqliteConnection, DatabaseError> {
+        let options = self.options.as_ref().ok_or(DatabaseError::NotConnected)?;
+        let attached = sqlx::query(
+            "SELECT name, file FROM pragma_database_list
+             WHERE name NOT IN ('main', 'temp') AND file <> ''",
+        )
+        .fetch_all(self.get_pool()?)
+        .await?;
+
+        let mut conn = options.connect().await?;
+        for row in attached {
+            let sql = format!(
+                "ATTACH DATABASE ?1 AS {}",
+                quote_identifier(&row.get::<String, _>("name"))
+            );
+            sqlx::query(&sql)
+                .bind(row.get::<String, _>("file"))
+                .execute(&mut conn)
+                .await?;
+        }
+        Ok(conn)
+    }
+
+    /// Options for the database file named in the connection parameters
+    ///
+    /// `:memory:` names a shared in-memory database, which every connection
+    /// opened with the same options sees.
+    fn connect_options(params: &ConnectionParams) -> Result<SqliteConnectOptions, DatabaseError> {
+        let database = params.database.trim();
+
+        if database == ":memory:" {
+            Ok(SqliteConnectOptions::from_str("sqlite::memory:")?)
+        } else {
+            // Never create a new file for a mistyped path
+            Ok(SqliteConnectOptions::new()
+                .filename(database)
+                .create_if_missing(false))
+        }
+    }
+
+    /// Open a pool over the database the options name
+    async fn open_pool(
+        params: &ConnectionParams,
+        options: SqliteConnectOptions,
+    ) -> Result<SqlitePool, DatabaseError> {
+        // ATTACH and in-memory databases are scoped to a single SQLite connection,
+        // so the pool keeps exactly one connection alive for its whole lifetime
+        let mut pool_options = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None);
+
+        if let Some(timeout) = params.connection_timeout {
+            pool_options = pool_options.acquire_timeout(Duration::from_secs(timeout as u64));
+        }
+
+        Ok(pool_options.connect_with(options).await?)
+    }
+}
+
+impl Default for SQLiteConnection {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl DatabaseConnection for SQLiteConnection {
+    async fn connect(&mut self, params: &ConnectionParams) -> Result<(), DatabaseError> {
+        let options = Self::connect_options(params)?;
+        let pool = Self::open_pool(params, options.clone()).await?;
+
+        // Test the connection
+        sqlx::query("SELECT 1").fetch_one(&pool).await?;
+
+        self.pool = Some(pool);
+        self.options = Some(options);
+        self.connection_info = Some(params.database.trim().to_string());
+
+        log::info!(
+            "Connected to SQLite database: {}",
+            self.connection_info.as_ref().unwrap()
+        );
+        Ok(())
+    }
+
+    async fn disconnect(&mut self) -> Result<(), DatabaseError> {
+        if let Some(pool) = self.pool.take() {
+            pool.close().await;
+            log::info!("Disconnected from SQLite database");
+        }
+        self.options = None;
+        self.connection_info = None;
+        Ok(())
+    }
+
+    async fn is_connected(&self) -> bool {
+        if let Some(pool) = &self.pool {
+            !pool.is_closed()
+        } else {
+            false
+        }
+    }
+
+    async fn test_connection(&self, params: &ConnectionParams) -> Result<(), DatabaseError> {
+        let pool = Self::open_pool(params, Self::connect_options(params)?).await?;
+        sqlx::query("SELECT 1").fetch_one(&pool).await?;
+        pool.close().await;
+        Ok(())
+    }
+
+    fn database_type(&self) -> &'static str {
+        "SQLite"
+    }
+
+    fn connection_info(&self) -> Option<String> {
+        self.connection_info.clone()
+    }
+}
+
+#[async_trait]
+impl QueryExecutor for SQLiteConnection {
+    async fn execute_query(&self, sql: &str) -> Result<QueryResult, DatabaseError> {
+        let start_time = Instant::now();
+        let mut cursor = self.open_cursor(sql).await?;
+        let rows = cursor.fetch(usize::MAX).await?;
+        Ok(cursor
+            .result_with_rows(rows)
+            .with_execution_time(start_time.elapsed()))
+    }
+
+    async fn open_cursor(&self, sql: &str) -> Result<QueryCursor, DatabaseError> {
+        let (cursor, requests) = QueryCursor::channel(sql);
+
+        // The pool holds a single connection, which must not stay busy for as
+        // long as the cursor is open. Queries stream from a connection of their
+        // own; statements that write run to the end at once on the pool's.
+        if is_read_only_query(sql) {
+            let mut conn = self.open_cursor_connection().await?;
+            let sql = sql.to_string();
+            tokio::spawn(async move {
+                let stream = conn
+                    .fetch_many(sqlx::query(&sql))
+                    .map_ok(|step| step.map_left(|done| done.rows_affected()));
+                serve_cursor(
+                    requests,
+                    stream,
+                    sqlite_columns,
+                    std::future::ready,
+                    convert_sqlite_row,
+                )
+                .await;
+            });
+        } else {
+            let steps: Vec<_> = self
+                .get_pool()?
+                .fetch_many(sqlx::query(sql))
+                .map_ok(|step| step.map_left(|done| done.rows_affected()))
+                .try_collect()
+                .await?;
+            tokio::spawn(async move {
+                let stream =
+                    futures_util::stream::iter(steps.into_iter().map(Ok::<_, sqlx::Error>));
+                serve_cursor(
+                    requests,
+                    stream,
+                    sqlite_columns,
+                    std::future::ready,
+                    convert_sqlite_row,
+                )
+                .await;
+            });
+        }
+
+        Ok(cursor)
+    }
+
+    async fn open_session(&self) -> Result<QuerySession, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        // Other work on this database waits until the session is dropped
+        let mut conn = pool.acquire().await?;
+
+        let (session, mut statements) = QuerySession::channel();
+        tokio::spawn(async move {
+            while let Some(SessionStatement { sql, requests }) = statements.recv().await {
+                let stream = conn
+                    .fetch_many(sqlx::query(&sql))
+                    .map_ok(|step| step.map_left(|done| done.rows_affected()));
+                serve_cursor(
+                    requests,
+                    stream,
+                    sqlite_columns,
+                    std::future::ready,
+                    convert_sqlite_row,
+                )
+                .await;
+            }
+        });
+
+        Ok(session)
+    }
+
+    async fn execute_non_query(&self, sql: &str) -> Result<u64, DatabaseError> {
+        let pool = self.get_pool()?;
+        let result = sqlx::query(sql).execute(pool).await?;
+        Ok(result.rows_affected())
+    }
+
+    async fn execute_dml(&self, _statements: &[DmlStatement]) -> Result<u64, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "SQLite results cannot be edited".to_string(),
+        ))
+    }
+
+    async fn cancel_query(&self) -> Result<(), DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "SQLite statements cannot be cancelled".to_string(),
+        ))
+    }
+
+    fn set_query_timeout(&self, _seconds: u32) {
+        // SQLite has no statement timeout; the client's timer enforces it
+    }
+
+    async fn get_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        // `main` plus every attached database; `temp` only exists on demand
+        let rows =
+            sqlx::query("SELECT name FROM pragma_database_list WHERE name <> 'temp' ORDER BY seq")
+                .fetch_all(pool)
+                .await?;
+
+        let schemas = rows
+            .iter()
+            .map(|row| Schema {
+                name: row.get::<String, _>("name"),
+                owner: None,
+            })
+            .collect();
+
+        Ok(schemas)
+    }
+
+    async fn get_tables(&self, schema: &str) -> Result<Vec<Table>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT name, type
+             FROM {}.sqlite_master
+             WHERE type IN ('table', 'view') AND {}
+             ORDER BY name",
+            quote_identifier(schema),
+            USER_OBJECT_FILTER
+        );
+        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+
+        let tables = rows
+            .iter()
+            .map(|row| {
+                // Use the information_schema spelling so the tree treats all drivers alike
+                let table_type = match row.get::<String, _>("type").as_str() {
+                    "view" => "VIEW".to_string(),
+                    _ => "BASE TABLE".to_string(),
+                };
+
+                Table {
+                    name: row.get::<String, _>("name"),
+                    schema: schema.to_string(),
+                    table_type,
+                    comment: None,
+                }
+            })
+            .collect();
+
+        Ok(tables)
+    }
+
+    async fn get_columns(&self, schema: &str, table: &str) -> Result<Vec<DbColumn>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query(
+            "SELECT name, type, \"notnull\", dflt_value, pk
+             FROM pragma_table_info(?1, ?2)
+             ORDER BY cid",
+        )
+        .bind(table)
+        .bind(schema)
+        .fetch_all(pool)
+        .await?;
+
+        let columns = rows
+            .iter()
+            .map(|row| DbColumn {
+                name: row.get::<String, _>("name"),
+                data_type: row.get::<String, _>("type"),
+                is_nullable: row.get::<i64, _>("notnull") == 0,
+                default_value: row.try_get::<String, _>("dflt_value").ok(),
+                is_primary_key: row.get::<i64, _>("pk") > 0,
+                comment: None,
+            })
+            .collect();
+
+        Ok(columns)
+    }
+
+    async fn get_table_data(
+        &self,
+        schema: &str,
+        table: &str,
+        limit: Option<u32>,
+    ) -> Result<QueryResult, DatabaseError> {
+        let limit_clause = limit.map(|l| format!(" LIMIT {}", l)).unwrap_or_default();
+        let sql = format!(
+            "SELECT * FROM {}.{}{}",
+            quote_identifier(schema),
+            quote_identifier(table),
+            limit_clause
+        );
+        self.execute_query(&sql).await
+    }
+
+    async fn table_exists(&self, schema: &str, table: &str) -> Result<bool, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT EXISTS (
+                 SELECT 1 FROM {}.sqlite_master WHERE type = 'table' AND name = ?1
+             )",
+            quote_identifier(schema)
+        );
+        let row = sqlx::query(&sql).bind(table).fetch_one(pool).await?;
+
+        Ok(row.get::<bool, _>(0))
+    }
+
+    async fn get_views(&self, schema: &str) -> Result<Vec<View>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT name, sql FROM {}.sqlite_master WHERE type = 'view' ORDER BY name",
+            quote_identifier(schema)
+        );
+        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+
+        let views = rows
+            .iter()
+            .map(|row| View {
+                name: row.get::<String, _>("name"),
+                schema: schema.to_string(),
+                view_type: ViewType::Regular,
+                definition: row.try_get::<String, _>("sql").ok(),
+                comment: None,
+                owner: None,
+                // SQLite views are read-only unless INSTEAD OF triggers are defined
+                is_updatable: false,
+            })
+            .collect();
+
+        Ok(views)
+    }
+
+    async fn get_functions(&self, _schema: &str) -> Result<Vec<Function>, DatabaseError> {
+        // SQLite has no stored functions or procedures
+        Ok(Vec::new())
+    }
+
+    async fn get_triggers(&self, schema: &str) -> Result<Vec<Trigger>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT name, tbl_name, sql
+             FROM {}.sqlite_master
+             WHERE type = 'trigger'
+             ORDER BY tbl_name, name",
+            quote_identifier(schema)
+        );
+        let rows = sqlx::query(&sql).fetch_all(pool).await?;
+
+        let triggers = rows
+            .iter()
+            .map(|row| {
+                let definition = row.try_get::<String, _>("sql").unwrap_or_default();
+                let (timing, events) = parse_trigger_definition(&definition);
+
+                Trigger {
+                    name: row.get::<String, _>("name"),
+                    schema: schema.to_string(),
+                    table_name: row.get::<String, _>("tbl_name"),
+                    // SQLite only supports FOR EACH ROW triggers
+                    trigger_type: TriggerType::Row,
+                    events,
+                    timing,
+                    // The trigger body is inline SQL rather than a separate function
+                    function_name: String::new(),
+                    function_schema: schema.to_string(),
+                    // The full statement is served by get_object_ddl
+                    condition: parse_trigger_condition(&definition),
+                    comment: None,
+                }
+            })
+            .collect();
+
+        Ok(triggers)
+    }
+
+    async fn get_sequences(&self, _schema: &str) -> Result<Vec<Sequence>, DatabaseError> {
+        // SQLite has no sequence objects
+        Ok(Vec::new())
+    }
+
+    async fn get_indexes(&self, schema: &str) -> Result<Vec<Index>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT
+                m.name,
+                m.tbl_name,
+                il.\"unique\" AS is_unique,
+                il.origin,
+                il.partial,
+                m.sql,
+                (SELECT group_concat(ii.name, ',')
+                 FROM pragma_index_info(m.name, ?1) ii) AS columns
+             FROM {}.sqlite_master m
+             JOIN pragma_index_list(m.tbl_name, ?1) il ON il.name = m.name
+             WHERE m.type = 'index'
+             ORDER BY m.tbl_name, m.name",
+            quote_identifier(schema)
+        );
+        let rows = sqlx::query(&sql).bind(schema).fetch_all(pool).await?;
+
+        let indexes = rows
+            .iter()
+            .map(|row| {
+                let columns =
+                    parse_index_columns(&row.try_get::<String, _>("columns").unwrap_or_default());
+
+                let is_partial = row.get::<i64, _>("partial") != 0;
+                let condition = if is_partial {
+                    row.try_get::<String, _>("sql")
+                        .ok()
+                        .and_then(|sql| extract_where_clause(&sql))
+                } else {
+                    None
+                };
+
+                Index {
+                    name: row.get::<String, _>("name"),
+                    schema: schema.to_string(),
+                    table_name: row.get::<String, _>("tbl_name"),
+                    index_type: IndexType::BTree,
+                    columns,
+                    is_unique: row.get::<i64, _>("is_unique") != 0,
+                    is_primary: row.get::<String, _>("origin") == "pk",
+                    is_partial,
+                    condition,
+                    size: None,
+                    comment: None,
+                }
+            })
+            .collect();
+
+        Ok(indexes)
+    }
+
+    async fn get_foreign_keys(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKey>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        // A key naming no parent columns references the parent's primary key
+        let sql = format!(
+            "SELECT
+                m.name AS table_name,
+                fk.id,
+                fk.\"table\" AS referenced_table,
+                fk.\"from\" AS column_name,
+                COALESCE(fk.\"to\", (SELECT p.name FROM pragma_table_info(fk.\"table\", ?1) p
+                                      WHERE p.pk = fk.seq + 1)) AS referenced_column,
+                fk.on_update,
+                fk.on_delete
+             FROM {}.sqlite_master m
+             JOIN pragma_foreign_key_list(m.name, ?1) fk
+             WHERE m.type = 'table' AND (m.name = ?2 OR fk.\"table\" = ?2 COLLATE NOCASE)
+             ORDER BY m.name, fk.id, fk.seq",
+            quote_identifier(schema)
+        );
+        let rows = sqlx::query(&sql)
+            .bind(schema)
+            .bind(table)
+            .fetch_all(pool)
+            .await?;
+
+        // One row per key column; consecutive rows with the same id form a key
+        let mut foreign_keys: Vec<(i64, ForeignKey)> = Vec::new();
+        for row in &rows {
+            let table_name = row.get::<String, _>("table_name");
+            let id = row.get::<i64, _>("id");
+            let column = row.get::<String, _>("column_name");
+            let referenced_column = row
+                .try_get::<String, _>("referenced_column")
+                .unwrap_or_default();
+
+            match foreign_keys.last_mut() {
+                Some((last_id, key)) if *last_id == id && key.table_name == table_name => {
+                    key.columns.push(column);
+                    key.referenced_columns.push(referenced_column);
+                }
+                _ => {
+                    // SQLite keeps no names for foreign keys
+                    let name = format!("{}_fkey{}", table_name, id);
+                    foreign_keys.push((
+                        id,
+                        ForeignKey {
+                            name,
+                            schema: schema.to_string(),
+                            table_name,
+                            columns: vec![column],
+                            referenced_schema: schema.to_string(),
+                            referenced_table: row.get::<String, _>("referenced_table"),
+                            referenced_columns: vec![referenced_column],
+                            on_update: ForeignKeyAction::from_rule(
+                                &row.get::<String, _>("on_update"),
+                            ),
+                            on_delete: ForeignKeyAction::from_rule(
+                                &row.get::<String, _>("on_delete"),
+                            ),
+                            comment: None,
+                        },
+                    ));
+                }
+            }
+        }
+
+        Ok(foreign_keys.into_iter().map(|(_, key)| key).collect())
+    }
+
+    async fn get_constraints(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<Constraint>, DatabaseError> {
+        let pool = self.get_pool()?;
+        let column_list = |columns: &[String]| {
+            columns
+                .iter()
+                .map(|column| quote_identifier(column))
+                .collect::<Vec<_>>()
+                .join(", ")
+        };
+        // Unnamed constraints are named as PostgreSQL would name them
+        let constraint =
+            |name: String, constraint_type, columns: Vec<String>, definition| Constraint {
+                name,
+                schema: schema.to_string(),
+                table_name: table.to_string(),
+                constraint_type,
+                columns,
+                definition,
+                comment: None,
+            };
+        let mut constraints = Vec::new();
+
+        let key_columns: Vec<String> =
+            sqlx::query("SELECT name FROM pragma_table_info(?1, ?2) WHERE pk > 0 ORDER BY pk")
+                .bind(table)
+                .bind(schema)
+                .fetch_all(pool)
+                .await?
+                .iter()
+                .map(|row| row.get::<String, _>("name"))
+                .collect();
+        if !key_columns.is_empty() {
+            let definition = format!("PRIMARY KEY ({})", column_list(&key_columns));
+            constraints.push(constraint(
+                format!("{}_pkey", table),
+                ConstraintType::PrimaryKey,
+                key_columns,
+                definition,
+            ));
+        }
+
+        let unique_rows = sqlx::query(
+            "SELECT (SELECT group_concat(ii.name, ',')
+                     FROM pragma_index_info(il.name, ?2) ii) AS columns
+             FROM pragma_index_list(?1, ?2) il
+             WHERE il.origin = 'u'
+             ORDER BY il.seq DESC",
+        )
+        .bind(table)
+        .bind(schema)
+        .fetch_all(pool)
+        .await?;
+        for row in &unique_rows {
+            let columns: Vec<String> = row
+                .try_get::<String, _>("columns")
+                .unwrap_or_default()
+                .split(',')
+                .map(str::to_string)
+                .collect();
+            let definition = format!("UNIQUE ({})", column_list(&columns));
+            constraints.push(constraint(
+                format!("{}_{}_key", table, columns.join("_")),
+                ConstraintType::Unique,
+                columns,
+                definition,
+            ));
+        }
+
+        let sql = format!(
+            "SELECT sql FROM {}.sqlite_master WHERE type = 'table' AND name = ?1",
+            quote_identifier(schema)
+        );
+        let create_sql = sqlx::query(&sql)
+            .bind(table)
+            .fetch_optional(pool)
+            .await?
+            .and_then(|row| row.try_get::<String, _>("sql").ok())
+            .unwrap_or_default();
+        for (i, (name, expression)) in extract_check_constraints(&create_sql)
+            .into_iter()
+            .enumerate()
+        {
+            constraints.push(constraint(
+                name.unwrap_or_else(|| format!("{}_check{}", table, i + 1)),
+                ConstraintType::Check,
+                Vec::new(),
+                format!("CHECK ({})", expression),
+            ));
+        }
+
+        Ok(constraints)
+    }
+
+    async fn get_object_ddl(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        // SQLite keeps each object's statement as it was written; a table
+        // comes with its indexes and triggers
+        let filter = match category {
+            ObjectCategory::Tables => "tbl_name = ?1",
+            ObjectCategory::Views => "type = 'view' AND name = ?1",
+            ObjectCategory::Triggers => "type = 'trigger' AND name = ?1",
+            ObjectCategory::Indexes => "type = 'index' AND name = ?1",
+            _ => {
+                return Err(DatabaseError::UnsupportedOperation(format!(
+                    "DDL for {:?} on SQLite",
+                    category
+                )))
+            }
+        };
+        let sql = format!(
+            "SELECT sql FROM {}.sqlite_master
+             WHERE {} AND sql IS NOT NULL
+             ORDER BY CASE type WHEN 'index' THEN 1 WHEN 'trigger' THEN 2 ELSE 0 END, name",
+            quote_identifier(schema),
+            filter
+        );
+        let statements: Vec<String> = sqlx::query(&sql)
+            .bind(name)
+            .fetch_all(pool)
+            .await?
+            .iter()
+            .map(|row| format!("{};", ddl::trim_statement(&row.get::<String, _>("sql"))))
+            .collect();
+
+        if statements.is_empty() {
+            return Err(DatabaseError::QueryFailed(format!(
+                "{}.{} does not exist",
+                schema, name
+            )));
+        }
+        Ok(ddl::join_sections(statements))
+    }
+
+    async fn get_trigger_ddl(
+        &self,
+        schema: &str,
+        _table: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError> {
+        // Trigger names are unique within a schema
+        self.get_object_ddl(&ObjectCategory::Triggers, schema, name)
+            .await
+    }
+
+    async fn get_dependencies(
+        &self,
+        _category: &ObjectCategory,
+        _schema: &str,
+        _name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "SQLite does not record object dependencies".to_string(),
+        ))
+    }
+
+    async fn get_dependents(
+        &self,
+        _category: &ObjectCategory,
+        _schema: &str,
+        _name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError> {
+        Err(DatabaseError::UnsupportedOperation(
+            "SQLite does not record object dependencies".to_string(),
+        ))
+    }
+
+    async fn get_all_schemas(&self) -> Result<Vec<Schema>, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let rows = sqlx::query("SELECT name FROM pragma_database_list ORDER BY seq")
+            .fetch_all(pool)
+            .await?;
+
+        let schemas = rows
+            .iter()
+            .map(|row| Schema {
+                name: row.get::<String, _>("name"),
+                owner: None,
+            })
+            .collect();
+
+        Ok(schemas)
+    }
+
+    async fn get_object_counts(&self, schema: &str) -> Result<ObjectCounts, DatabaseError> {
+        let pool = self.get_pool()?;
+
+        let sql = format!(
+            "SELECT
+                COUNT(CASE WHEN type = 'table' AND {filter} THEN 1 END) AS tables,
+                COUNT(CASE WHEN type = 'view' THEN 1 END) AS views,
+                COUNT(CASE WHEN type = 'trigger' THEN 1 END) AS triggers,
+                COUNT(CASE WHEN type = 'index' THEN 1 END) AS indexes
+             FROM {schema}.sqlite_master",
+            filter = USER_OBJECT_FILTER,
+            schema = quote_identifier(schema)
+        );
+        let row = sqlx::query(&sql).fetch_one(pool).await?;
+
+        Ok(ObjectCounts {
+            tables: row.get::<i64, _>("tables") as usize,
+            views: row.get::<i64, _>("views") as usize,
+            triggers: row.get::<i64, _>("triggers") as usize,
+            indexes: row.get::<i64, _>("indexes") as usize,
+            ..Default::default()
+        })
+    }
+
+    async fn get_database_object_counts(&self) -> Result<DatabaseObjectCounts, DatabaseError> {
+        let schemas = self.get_all_schemas().await?;
+
+        let mut totals = DatabaseObjectCounts {
+            schemas: schemas.len(),
+            ..Default::default()
+        };
+
+        for schema in &schemas {
+            if schema.name == "temp" {
+                totals.system_schemas += 1;
+            } else {
+                totals.user_schemas += 1;
+            }
+
+            let counts = self.get_object_counts(&schema.name).await?;
+            totals.total_tables += counts.tables;
+            totals.total_views += counts.views;
+            totals.total_triggers += counts.triggers;
+            totals.total_indexes += counts.indexes;
+        }
+
+        Ok(totals)
+    }
+}
+
+impl Database for SQLiteConnection {
+    fn clone_connection(&self) -> Box<dyn Database> {
+        Box::new(SQLiteConnection::new())
+    }
+}
+
+/// Column descriptions of a SQLite result row
+fn sqlite_columns(row: &SqliteRow) -> Vec<QueryColumn> {
+    row.columns()
+        .iter()
+        .enumerate()
+        .map(|(i, col)| {
+            QueryColumn::new(
+                col.name().to_string(),
+                col.type_info().name().to_string(),
+                i,
+                true, // SQLite does not report nullability for result columns
+            )
+        })
+        .collect()
+}
+
+/// Convert a SQLite row to QueryRow format
+fn convert_sqlite_row(row: &SqliteRow) -> Result<QueryRow, DatabaseError> {
+    let values: Result<Vec<QueryValue>, DatabaseError> = (0..row.len())
+        .map(|i| convert_sqlite_value(row, i))
+        .collect();
+    values.map(QueryRow::new)
+}
+
+/// Convert SQLite values to QueryValue
+///
+/// SQLite is dynamically typed, so the storage class of each value decides how it
+/// is decoded; the declared column type only refines booleans and datetimes.
+fn convert_sqlite_value(row: &SqliteRow, index: usize) -> Result<QueryValue, DatabaseError> {
+    let raw = row.try_get_raw(index)?;
+    if raw.is_null() {
+        return Ok(QueryValue::Null);
+    }
+
+    let storage_class = raw.type_info().name().to_string();
+    let declared_type = row.columns()[index].type_info().name();
+
+    match storage_class.as_str() {
+        "INTEGER" => {
+            let value: i64 = row.try_get(index)?;
+            if declared_type == "BOOLEAN" {
+                Ok(QueryValue::Bool(value != 0))
+            } else {
+                Ok(QueryValue::Int64(value))
+            }
+        }
+        "REAL" => Ok(QueryValue::Float64(row.try_get(index)?)),
+        "BLOB" => Ok(QueryValue::Bytes(row.try_get(index)?)),
+        _ => {
+            if declared_type == "DATETIME" {
+                if let Ok(dt) = row.try_get::<chrono::DateTime<chrono::Utc>, _>(index) {
+                    return Ok(QueryValue::DateTime(dt.fixed_offset()));
+                }
+            }
+            Ok(QueryValue::String(row.try_get(index)?))
+        }
+    }
+}
+
+/// Quote an identifier for interpolation into SQLite statements
+fn quote_identifier(name: &str) -> String {
+    format!("\"{}\"", name.replace('"', "\"\""))
+}
+
+/// Derive timing and events from a `CREATE TRIGGER` statement
+fn parse_trigger_definition(sql: &str) -> (TriggerTiming, Vec<TriggerEvent>) {
+    // Only the header up to the ON keyword describes timing and events
+    let upper = sql.to_ascii_uppercase();
+    let header: Vec<&str> = upper
+        .split(|c: char| c.is_whitespace() || c == '(')
+        .filter(|token| !token.is_empty())
+        .take_while(|token| *token != "ON")
+        .collect();
+
+    // BEFORE is SQLite's default when no timing is given
+    let timing = if header.contains(&"INSTEAD") {
+        TriggerTiming::InsteadOf
+    } else if header.contains(&"AFTER") {
+        TriggerTiming::After
+    } else {
+        TriggerTiming::Before
+    };
+
+    let events = header
+        .iter()
+        .filter_map(|token| match *token {
+            "INSERT" => Some(TriggerEvent::Insert),
+            "UPDATE" => Some(TriggerEvent::Update),
+            "DELETE" => Some(TriggerEvent::Delete),
+            _ => None,
+        })
+        .collect();
+
+    (timing, events)
+}
+
+/// The WHEN clause of a `CREATE TRIGGER` statement, i.e. the text between
+/// the top-level WHEN and BEGIN keywords
+fn parse_trigger_condition(sql: &str) -> Option<String> {
+    let bytes = sql.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut depth = 0usize;
+    let mut start = None;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        match bytes[i] {
+            quote @ (b'\'' | b'"' | b'`' | b'[') => {
+                let close = if quote == b'[' { b']' } else { quote };
+                i += 1;
+                while i < bytes.len() && bytes[i] != close {
+                    i += 1;
+                }
+            }
+            b'-' if bytes.get(i + 1) == Some(&b'-') => {
+                while i < bytes.len() && bytes[i] != b'\n' {
+                    i += 1;
+                }
+            }
+            b'/' if bytes.get(i + 1) == Some(&b'*') => {
+                i += 2;
+                while i + 1 < bytes.len() && !(bytes[i] == b'*' && bytes[i + 1] == b'/') {
+                    i += 1;
+                }
+                i += 1;
+            }
+            b'(' => depth += 1,
+            b')' => depth = depth.saturating_sub(1),
+            b if is_word(b) => {
+                let end = bytes[i..]
+                    .iter()
+                    .position(|b| !is_word(*b))
+                    .map_or(bytes.len(), |len| i + len);
+                let word = &sql[i..end];
+                if depth == 0 && (i == 0 || !is_word(bytes[i - 1])) {
+                    if start.is_none() && word.eq_ignore_ascii_case("WHEN") {
+                        start = Some(end);
+                    } else if word.eq_ignore_ascii_case("BEGIN") {
+                        return start
+                            .map(|start| sql[start..i].trim().to_string())
+                            .filter(|condition| !condition.is_empty());
+                    }
+                }
+                i = end;
+                continue;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+    None
+}
+
+/// Extract the predicate of a partial index from its `CREATE INDEX` statement
+fn extract_where_clause(sql: &str) -> Option<String> {
+    let upper = sql.to_ascii_uppercase();
+    let bytes = upper.as_bytes();
+    let is_boundary = |b: u8| b.is_ascii_whitespace() || b == b'(' || b == b')';
+
+    upper
+        .match_indices("WHERE")
+        .filter(|(pos, keyword)| {
+            let end = pos + keyword.len();
+            *pos > 0 && is_boundary(bytes[pos - 1]) && end < bytes.len() && is_boundary(bytes[end])
+        })
+        .last()
+        .map(|(pos, keyword)| sql[pos + keyword.len()..].trim().to_string())
+}
+
+/// CHECK constraints of a `CREATE TABLE` statement, as the constraint's name
+/// if it has one and its expression
+fn extract_check_constraints(sql: &str) -> Vec<(Option<String>, String)> {
+    let upper = sql.to_ascii_uppercase();
+    let bytes = upper.as_bytes();
+    let is_word = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+    let mut checks = Vec::new();
+
+    for (pos, keyword) in upper.match_indices("CHECK") {
+        let end = pos + keyword.len();
+        if (pos > 0 && is_word(bytes[pos - 1])) || (end < bytes.len() && is_word(bytes[end])) {
+            continue;
+        }
+        let rest = &sql[end..];
+        let Some(open) = rest.find('(') else {
+            continue;
+        };
+        if !rest[..open].trim().is_empty() {
+            continue;
+        }
+
+        // The expression ends at the matching parenthesis outside quotes
+        let mut depth = 0;
+        let mut quote = None;
+        let mut close = None;
+        for (i, c) in rest[open..].char_indices() {
+            match (quote, c) {
+                (Some(q), c) if c == q => quote = None,
+                (Some(_), _) => {}
+                (None, '\'' | '"' | '`') => quote = Some(c),
+                (None, '(') => depth += 1,
+                (None, ')') => {
+                    depth -= 1;
+                    if depth == 0 {
+                        close = Some(open + i);
+                        break;
+                    }
+                }
+                _ => {}
+            }
+        }
+        let Some(close) = close else {
+            continue;
+        };
+
+        let name = constraint_name_before(&sql[..pos]);
+        checks.push((name, rest[open + 1..close].trim().to_string()));
+    }
+    checks
+}
+
+/// Name given by a `CONSTRAINT name` clause the text ends with
+fn constraint_name_before(text: &str) -> Option<String> {
+    let text = text.trim_end();
+    let (name, before) = match text.chars().last()? {
+        quote @ ('"' | '`' | ']') => {
+            let opening = if quote == ']' { '[' } else { quote };
+            let start = text[..text.len() - 1].rfind(opening)?;
+            (&text[start + 1..text.len() - 1], &text[..start])
+        }
+        _ => {
+            let start = text.rfind(char::is_whitespace)? + 1;
+            (&text[start..], &text[..start])
+        }
+    };
+
+    let before = before.trim_end();
+    let keyword_start = before.len().checked_sub("CONSTRAINT".len())?;
+    let is_keyword = before
+        .get(keyword_start..)
+        .is_some_and(|keyword| keyword.eq_ignore_ascii_case("CONSTRAINT"))
+        && !before[..keyword_start].ends_with(|c: char| c.is_alphanumeric() || c == '_');
+    is_keyword.then(|| name.to_string())
+}
+
+/// Parse index columns from comma-separated string
+fn parse_index_columns(columns_str: &str) -> Vec<IndexColumn> {
+    if columns_str.is_empty() {
+        return Vec::new();
+    }
+
+    columns_str
+        .split(',')
+        .enumerate()
+        .map(|(i, column)| IndexColumn {
+            name: column.trim().to_string(),
+            position: i as i32 + 1,
+            direction: Some(SortDirection::Ascending),
+            nulls_order: None,
+        })
+        .collect()
+}
@@ -1,4 +1,6 @@
-use crate::database::{ConnectionParams, DatabaseError, QueryResult};
+use crate::database::{
+    ConnectionParams, DatabaseError, DmlStatement, QueryCursor, QueryResult, QuerySession,
+};
 use async_trait::async_trait;
 
 /// Schema information
@@ -170,6 +172,8 @@ pub enum IndexType {
     Gin,
     Spgist,
     Brin,
+    FullText,
+    Spatial,
 }
 
 /// Index column information
@@ -195,6 +199,185 @@ pub enum NullsOrder {
     Last,
 }
 
+/// Foreign key from the columns of one table to those of another
+#[derive(Debug, Clone)]
+pub struct ForeignKey {
+    pub name: String,
+    pub schema: String,
+    pub table_name: String,
+    pub columns: Vec<String>,
+    pub referenced_schema: String,
+    pub referenced_table: String,
+    /// Referenced columns, in the order of `columns`
+    pub referenced_columns: Vec<String>,
+    pub on_update: ForeignKeyAction,
+    pub on_delete: ForeignKeyAction,
+    pub comment: Option<String>,
+}
+
+impl ForeignKey {
+    /// Whether the key belongs to the table, as opposed to referencing it
+    pub fn is_outgoing(&self, schema: &str, table: &str) -> bool {
+        self.schema == schema && self.table_name == table
+    }
+
+    /// Whether the key references the table
+    pub fn is_incoming(&self, schema: &str, table: &str) -> bool {
+        self.referenced_schema == schema && self.referenced_table == table
+    }
+}
+
+/// What a foreign key does to referencing rows when the referenced row changes
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ForeignKeyAction {
+    NoAction,
+    Restrict,
+    Cascade,
+    SetNull,
+    SetDefault,
+}
+
+impl ForeignKeyAction {
+    /// Parse a referential action as `information_schema` spells it
+    pub fn from_rule(rule: &str) -> Self {
+        match rule.to_uppercase().as_str() {
+            "RESTRICT" => ForeignKeyAction::Restrict,
+            "CASCADE" => ForeignKeyAction::Cascade,
+            "SET NULL" => ForeignKeyAction::SetNull,
+            "SET DEFAULT" => ForeignKeyAction::SetDefault,
+            _ => ForeignKeyAction::NoAction,
+        }
+    }
+
+    pub fn to_sql(self) -> &'static str {
+        match self {
+            ForeignKeyAction::NoAction => "NO ACTION",
+            ForeignKeyAction::Restrict => "RESTRICT",
+            ForeignKeyAction::Cascade => "CASCADE",
+            ForeignKeyAction::SetNull => "SET NULL",
+            ForeignKeyAction::SetDefault => "SET DEFAULT",
+        }
+    }
+}
+
+/// Primary key, unique, check or exclusion constraint of a table
+///
+/// Foreign keys are described by `ForeignKey` instead.
+#[derive(Debug, Clone)]
+pub struct Constraint {
+    pub name: String,
+    pub schema: String,
+    pub table_name: String,
+    pub constraint_type: ConstraintType,
+    /// Constrained columns; empty for checks on expressions the server
+    /// does not attribute to columns
+    pub columns: Vec<String>,
+    /// The constraint as written after its name, e.g. `CHECK (price > 0)`
+    pub definition: String,
+    pub comment: Option<String>,
+}
+
+/// Constraint type enumeration
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConstraintType {
+    PrimaryKey,
+    Unique,
+    Check,
+    Exclusion,
+}
+
+/// An object linked to another by a dependency the server records
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dependency {
+    pub kind: DependencyKind,
+    pub schema: String,
+    /// Object name; routines carry their argument types, e.g.
+    /// `order_total(integer)`, and columns and defaults their column name
+    pub name: String,
+    /// Table of a column, default, index, constraint or trigger
+    pub table: Option<String>,
+    /// Columns of the depended-on object the dependency goes through, e.g.
+    /// those a view reads; empty when it involves the whole object
+    pub columns: Vec<String>,
+    /// Links between the two objects; 1 for a direct dependency
+    pub depth: u32,
+    /// Whether the dependent goes away with the object it depends on, like
+    /// the indexes of a table, rather than preventing a plain DROP
+    pub automatic: bool,
+}
+
+impl Dependency {
+    /// Qualified name for display, e.g. `shop.orders.total` for a column
+    pub fn qualified_name(&self) -> String {
+        match &self.table {
+            Some(table) => match self.kind {
+                DependencyKind::Column | DependencyKind::ColumnDefault => {
+                    format!("{}.{}.{}", self.schema, table, self.name)
+                }
+                _ => format!("{} on {}.{}", self.name, self.schema, table),
+            },
+            None if self.schema.is_empty() => self.name.clone(),
+            None => format!("{}.{}", self.schema, self.name),
+        }
+    }
+}
+
+/// Kind of object in a dependency
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencyKind {
+    Table,
+    View,
+    MaterializedView,
+    Sequence,
+    Index,
+    Function,
+    Procedure,
+    Trigger,
+    Constraint,
+    Column,
+    /// Default expression of a column
+    ColumnDefault,
+    /// Anything else, as the server describes it, e.g. `type` or `policy`
+    Other(String),
+}
+
+impl DependencyKind {
+    /// Parse a kind as the dependency queries spell it
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "table" => DependencyKind::Table,
+            "view" => DependencyKind::View,
+            "materialized view" => DependencyKind::MaterializedView,
+            "sequence" => DependencyKind::Sequence,
+            "index" => DependencyKind::Index,
+            "function" => DependencyKind::Function,
+            "procedure" => DependencyKind::Procedure,
+            "trigger" => DependencyKind::Trigger,
+            "constraint" => DependencyKind::Constraint,
+            "column" => DependencyKind::Column,
+            "default" => DependencyKind::ColumnDefault,
+            other => DependencyKind::Other(other.to_string()),
+        }
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            DependencyKind::Table => "table",
+            DependencyKind::View => "view",
+            DependencyKind::MaterializedView => "materialized view",
+            DependencyKind::Sequence => "sequence",
+            DependencyKind::Index => "index",
+            DependencyKind::Function => "function",
+            DependencyKind::Procedure => "procedure",
+            DependencyKind::Trigger => "trigger",
+            DependencyKind::Constraint => "constraint",
+            DependencyKind::Column => "column",
+            DependencyKind::ColumnDefault => "default",
+            DependencyKind::Other(name) => name,
+        }
+    }
+}
+
 /// Object counts for a specific schema
 #[derive(Debug, Clone, Default)]
 pub struct ObjectCounts {
@@ -225,7 +408,7 @@ pub struct DatabaseObjectCounts {
 }
 
 /// Database object category for tree organization
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum ObjectCategory {
     Tables,
     Views,
@@ -264,9 +447,35 @@ pub trait QueryExecutor: Send + Sync {
     /// Execute a SQL query and return results
     async fn execute_query(&self, sql: &str) -> Result<QueryResult, DatabaseError>;
 
+    /// Execute a SQL query and return a cursor that fetches its rows on demand
+    ///
+    /// Errors raised by the statement itself surface on the first fetch.
+    async fn open_cursor(&self, sql: &str) -> Result<QueryCursor, DatabaseError>;
+
+    /// Reserve one server session for running several statements in order
+    async fn open_session(&self) -> Result<QuerySession, DatabaseError>;
+
     /// Execute a query that doesn't return data (INSERT, UPDATE, DELETE, etc.)
     async fn execute_non_query(&self, sql: &str) -> Result<u64, DatabaseError>;
 
+    /// Run generated data changes in one transaction
+    ///
+    /// Each statement must change exactly one row; otherwise nothing is
+    /// committed.
+    async fn execute_dml(&self, statements: &[DmlStatement]) -> Result<u64, DatabaseError>;
+
+    /// Cancel the statement currently running in `execute_query`
+    ///
+    /// The interrupted call fails with `DatabaseError::Cancelled`. Does nothing
+    /// when no statement is running.
+    async fn cancel_query(&self) -> Result<(), DatabaseError>;
+
+    /// Statement timeout in seconds for sessions opened from now on; 0 keeps
+    /// the server's default
+    ///
+    /// Drivers without a server-side limit leave it to the client.
+    fn set_query_timeout(&self, seconds: u32);
+
     /// Get list of schemas in the database
     async fn get_schemas(&self) -> Result<Vec<Schema>, DatabaseError>;
 
@@ -302,6 +511,62 @@ pub trait QueryExecutor: Send + Sync {
     /// Get list of indexes in a specific schema
     async fn get_indexes(&self, schema: &str) -> Result<Vec<Index>, DatabaseError>;
 
+    /// Get the foreign keys of a table and those referencing it
+    async fn get_foreign_keys(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<ForeignKey>, DatabaseError>;
+
+    /// Get the primary key, unique, check and exclusion constraints of a table
+    async fn get_constraints(
+        &self,
+        schema: &str,
+        table: &str,
+    ) -> Result<Vec<Constraint>, DatabaseError>;
+
+    /// Get the statements that recreate an object, with its comments, owner
+    /// and privileges where the server keeps them
+    async fn get_object_ddl(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError>;
+
+    /// Get the statements that recreate the trigger `name` of one table
+    ///
+    /// PostgreSQL allows a trigger name once per table, so the name alone
+    /// may stand for several triggers there.
+    async fn get_trigger_ddl(
+        &self,
+        schema: &str,
+        table: &str,
+        name: &str,
+    ) -> Result<String, DatabaseError>;
+
+    /// Get the objects an object depends on, directly or through others
+    ///
+    /// The `columns` of a dependency are its own columns in use. Drivers
+    /// without dependency tracking return `UnsupportedOperation`.
+    async fn get_dependencies(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError>;
+
+    /// Get the objects depending on an object, directly or through others
+    ///
+    /// The `columns` of a dependent are the object's columns it goes
+    /// through, so the dependents of a single column are those listing it.
+    async fn get_dependents(
+        &self,
+        category: &ObjectCategory,
+        schema: &str,
+        name: &str,
+    ) -> Result<Vec<Dependency>, DatabaseError>;
+
     /// Get all schemas including system schemas
     async fn get_all_schemas(&self) -> Result<Vec<Schema>, DatabaseError>;
 
@@ -89,6 +89,7 @@ impl ConnectionDialog {
             ui.add_space(8.0);
 
             // Database type
+            let previous_type = self.params.database_type.clone();
             ui.horizontal(|ui| {
                 ui.label("Database Type:");
                 ComboBox::from_id_source("db_type")
@@ -112,34 +113,51 @@ impl ConnectionDialog {
                     });
             });
 
+            // Switch to the default port of the newly selected database type
+            if self.params.database_type != previous_type {
+                self.params.port = self.params.database_type.default_port();
+            }
+
             ui.add_space(8.0);
 
-            // Basic connection parameters
-            ui.horizontal(|ui| {
-                ui.label("Host:");
-                ui.add(TextEdit::singleline(&mut self.params.host).desired_width(150.0));
-                ui.label("Port:");
-                ui.add(egui::DragValue::new(&mut self.params.port).range(1..=65535));
-            });
+            if self.params.database_type == DatabaseType::SQLite {
+                // File-based database: only the path (or :memory:) is needed
+                ui.horizontal(|ui| {
+                    ui.label("Database File:");
+                    ui.add(
+                        TextEdit::singleline(&mut self.params.database)
+                            .hint_text("/path/to/database.db or :memory:")
+                            .desired_width(250.0),
+                    );
+                });
+            } else {
+                // Basic connection parameters
+                ui.horizontal(|ui| {
+                    ui.label("Host:");
+                    ui.add(TextEdit::singleline(&mut self.params.host).desired_width(150.0));
+                    ui.label("Port:");
+                    ui.add(egui::DragValue::new(&mut self.params.port).range(1..=65535));
+                });
 
-            ui.horizontal(|ui| {
-                ui.label("Database:");
-                ui.add(TextEdit::singleline(&mut self.params.database).desired_width(200.0));
-            });
+                ui.horizontal(|ui| {
+                    ui.label("Database:");
+                    ui.add(TextEdit::singleline(&mut self.params.database).desired_width(200.0));
+                });
 
-            ui.horizontal(|ui| {
-                ui.label("Username:");
-                ui.add(TextEdit::singleline(&mut self.params.username).desired_width(200.0));
-            });
+                ui.horizontal(|ui| {
+                    ui.label("Username:");
+                    ui.add(TextEdit::singleline(&mut self.params.username).desired_width(200.0));
+                });
 
-            ui.horizontal(|ui| {
-                ui.label("Password:");
-                ui.add(
-                    TextEdit::singleline(&mut self.params.password)
-                        .password(true)
-                        .desired_width(200.0),
-                );
-            });
+                ui.horizontal(|ui| {
+                    ui.label("Password:");
+                    ui.add(
+                        TextEdit::singleline(&mut self.params.password)
+                            .password(true)
+                            .desired_width(200.0),
+                    );
+                });
+            }
 
             ui.add_space(8.0);
 
@@ -159,40 +177,46 @@ impl ConnectionDialog {
                 ui.separator();
                 ui.heading("Advanced Settings");
 
-                // SSL Mode
-                ui.horizontal(|ui| {
-                    ui.label("SSL Mode:");
-                    ComboBox::from_id_source("ssl_mode")
-                        .selected_text(format!("{:?}", self.params.ssl_mode))
-                        .show_ui(ui, |ui| {
-                            ui.selectable_value(
-                                &mut self.params.ssl_mode,
-                                SslMode::Disable,
-                                "Disable",
-                            );
-                            ui.selectable_value(&mut self.params.ssl_mode, SslMode::Allow, "Allow");
-                            ui.selectable_value(
-                                &mut self.params.ssl_mode,
-                                SslMode::Prefer,
-                                "Prefer",
-                            );
-                            ui.selectable_value(
-                                &mut self.params.ssl_mode,
-                                SslMode::Require,
-                                "Require",
-                            );
-                            ui.selectable_value(
-                                &mut self.params.ssl_mode,
-                                SslMode::VerifyCa,
-                                "Verify CA",
-                            );
-                            ui.selectable_value(
-                                &mut self.params.ssl_mode,
-                                SslMode::VerifyFull,
-                                "Verify Full",
-                            );
-                        });
-                });
+                // SSL Mode (not applicable to file-based databases)
+                if self.params.database_type != DatabaseType::SQLite {
+                    ui.horizontal(|ui| {
+                        ui.label("SSL Mode:");
+                        ComboBox::from_id_source("ssl_mode")
+                            .selected_text(format!("{:?}", self.params.ssl_mode))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::Disable,
+                                    "Disable",
+                                );
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::Allow,
+                                    "Allow",
+                                );
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::Prefer,
+                                    "Prefer",
+                                );
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::Require,
+                                    "Require",
+                                );
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::VerifyCa,
+                                    "Verify CA",
+                                );
+                                ui.selectable_value(
+                                    &mut self.params.ssl_mode,
+                                    SslMode::VerifyFull,
+                                    "Verify Full",
+                                );
+                            });
+                    });
+                }
 
                 // Connection timeout
                 ui.horizontal(|ui| {
@@ -201,6 +225,26 @@ impl ConnectionDialog {
                     ui.add(egui::DragValue::new(&mut timeout).range(1..=300));
                     self.params.connection_timeout = Some(timeout);
                 });
+
+                // Query timeout override
+                ui.horizontal(|ui| {
+                    let mut overridden = self.params.query_timeout.is_some();
+                    if ui
+                        .checkbox(&mut overridden, "Query Timeout (seconds):")
+                        .on_hover_text("Overrides the default query timeout; 0 disables the limit")
+                        .changed()
+                    {
+                        self.params.query_timeout = overridden.then_some(30);
+                    }
+                    match &mut self.params.query_timeout {
+                        Some(timeout) => {
+                            ui.add(egui::DragValue::new(timeout).range(0..=86400));
+                        }
+                        None => {
+                            ui.label("Default");
+                        }
+                    }
+                });
             }
 
             ui.add_space(12.0);
@@ -324,6 +368,7 @@ impl ConnectionDialog {
                 || original.password != self.params.password
                 || original.ssl_mode != self.params.ssl_mode
                 || original.connection_timeout != self.params.connection_timeout
+                || original.query_timeout != self.params.query_timeout
         } else {
             true // New connection always has "changes"
         }
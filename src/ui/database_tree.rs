@@ -1,6 +1,8 @@
+use crate::database::dml::quote_identifier;
 use crate::database::{
-    Column, ConnectionParams, Function, Index, ObjectCategory, ObjectCounts, Schema, Sequence,
-    Table, Trigger, View,
+    Column, ConnectionParams, Constraint, ConstraintType, DatabaseType, DriverCapabilities,
+    ForeignKey, Function, Index, ObjectCategory, ObjectCounts, Schema, Sequence, Table, Trigger,
+    View,
 };
 use egui::{CollapsingHeader, ScrollArea, Ui};
 use std::collections::HashMap;
@@ -10,9 +12,13 @@ use std::collections::HashMap;
 pub struct ConnectionNode {
     pub connection_id: String,
     pub connection_name: String,
+    pub capabilities: DriverCapabilities,
     pub schemas: Vec<Schema>,
     pub tables: HashMap<String, Vec<Table>>,
     pub columns: HashMap<String, Vec<Column>>,
+    /// Foreign keys of and referencing each table, by "schema.table"
+    pub foreign_keys: HashMap<String, Vec<ForeignKey>>,
+    pub constraints: HashMap<String, Vec<Constraint>>,
     pub views: HashMap<String, Vec<View>>,
     pub functions: HashMap<String, Vec<Function>>,
     pub triggers: HashMap<String, Vec<Trigger>>,
@@ -23,13 +29,20 @@ pub struct ConnectionNode {
 }
 
 impl ConnectionNode {
-    pub fn new(connection_id: String, connection_name: String) -> Self {
+    pub fn new(
+        connection_id: String,
+        connection_name: String,
+        capabilities: DriverCapabilities,
+    ) -> Self {
         Self {
             connection_id,
             connection_name,
+            capabilities,
             schemas: Vec::new(),
             tables: HashMap::new(),
             columns: HashMap::new(),
+            foreign_keys: HashMap::new(),
+            constraints: HashMap::new(),
             views: HashMap::new(),
             functions: HashMap::new(),
             triggers: HashMap::new(),
@@ -61,6 +74,9 @@ pub struct DatabaseTree {
     schemas_needing_objects: Vec<(String, String, ObjectCategory)>, // (connection_id, schema_name, category)
     tables_needing_columns: Vec<(String, String, String)>, // (connection_id, schema_name, table_name)
     pending_action: Option<(ConnectionAction, String)>,    // (action, connection_id)
+    ddl_requested: Option<(String, ObjectCategory, String, String)>, // (connection_id, category, schema, name)
+    diagram_requested: Option<(String, String)>,                     // (connection_id, schema)
+    dependencies_requested: Option<(String, ObjectCategory, String, String)>, // (connection_id, category, schema, name)
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -361,68 +377,57 @@ impl DatabaseTree {
             .cloned()
             .unwrap_or_default();
 
+        let capabilities = self
+            .connections
+            .get(connection_id)
+            .map(|conn| conn.capabilities.clone())
+            .unwrap_or_default();
+
         let total_objects = counts.tables
             + counts.views
-            + counts.materialized_views
+            + materialized_view_count(&capabilities, &counts)
             + counts.functions
             + counts.procedures
             + counts.triggers
             + counts.sequences
             + counts.indexes;
 
+        // Drivers without schemas list whole databases at this level
+        let schema_icon = if capabilities.supports_schemas {
+            "📁"
+        } else {
+            "🗄"
+        };
+
         let schema_label = if total_objects > 0 {
-            format!("📁 {} ({})", schema.name, total_objects)
+            format!("{} {} ({})", schema_icon, schema.name, total_objects)
         } else {
-            format!("📁 {}", schema.name)
+            format!("{} {}", schema_icon, schema.name)
         };
 
         let header_response = CollapsingHeader::new(schema_label)
             .id_salt(format!("schema_{}_{}", connection_id, schema.name))
             .default_open(schema_expanded)
             .show(ui, |ui| {
-                // Show object categories
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
+                // Show the object categories the driver supports
+                for category in [
                     ObjectCategory::Tables,
-                    &counts,
-                );
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
                     ObjectCategory::Views,
-                    &counts,
-                );
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
                     ObjectCategory::Functions,
-                    &counts,
-                );
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
                     ObjectCategory::Triggers,
-                    &counts,
-                );
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
                     ObjectCategory::Sequences,
-                    &counts,
-                );
-                self.render_object_category(
-                    ui,
-                    connection_id,
-                    &schema.name,
                     ObjectCategory::Indexes,
-                    &counts,
-                );
+                ] {
+                    if capabilities.supports_category(&category) {
+                        self.render_object_category(
+                            ui,
+                            connection_id,
+                            &schema.name,
+                            category,
+                            &counts,
+                        );
+                    }
+                }
             });
 
         // Track expansion state
@@ -445,8 +450,17 @@ impl DatabaseTree {
                 connection_id: connection_id.to_string(),
                 schema: schema.name.clone(),
             });
-            // TODO: Show schema context menu
         }
+        header_response.header_response.context_menu(|ui| {
+            if ui.button("📋 Copy Name").clicked() {
+                ui.ctx().copy_text(schema.name.clone());
+                ui.close_kind(egui::UiKind::Menu);
+            }
+            if ui.button("🗺 ER Diagram").clicked() {
+                self.diagram_requested = Some((connection_id.to_string(), schema.name.clone()));
+                ui.close_kind(egui::UiKind::Menu);
+            }
+        });
     }
 
     fn render_object_category(
@@ -459,7 +473,18 @@ impl DatabaseTree {
     ) {
         let (icon, label, count) = match category {
             ObjectCategory::Tables => ("📋", "Tables", counts.tables),
-            ObjectCategory::Views => ("👁", "Views", counts.views + counts.materialized_views),
+            ObjectCategory::Views => {
+                let capabilities = self
+                    .connections
+                    .get(connection_id)
+                    .map(|conn| conn.capabilities.clone())
+                    .unwrap_or_default();
+                (
+                    "👁",
+                    "Views",
+                    counts.views + materialized_view_count(&capabilities, counts),
+                )
+            }
             ObjectCategory::Functions => ("⚙️", "Functions", counts.functions + counts.procedures),
             ObjectCategory::Triggers => ("⚡", "Triggers", counts.triggers),
             ObjectCategory::Sequences => ("🔢", "Sequences", counts.sequences),
@@ -550,8 +575,11 @@ impl DatabaseTree {
             match category {
                 ObjectCategory::Tables => {
                     if let Some(tables) = connection.tables.get(schema_name).cloned() {
+                        let materialized = connection.capabilities.supports_materialized_views;
                         for table in &tables {
-                            if self.matches_search(&table.name) {
+                            if (materialized || table.table_type != "MATERIALIZED VIEW")
+                                && self.matches_search(&table.name)
+                            {
                                 self.render_table_node(ui, connection_id, schema_name, table);
                             }
                         }
@@ -561,8 +589,11 @@ impl DatabaseTree {
                 }
                 ObjectCategory::Views => {
                     if let Some(views) = connection.views.get(schema_name).cloned() {
+                        let capabilities = connection.capabilities.clone();
                         for view in &views {
-                            if self.matches_search(&view.name) {
+                            if capabilities.supports_view_type(&view.view_type)
+                                && self.matches_search(&view.name)
+                            {
                                 self.render_view_node(ui, connection_id, schema_name, view);
                             }
                         }
@@ -665,6 +696,31 @@ impl DatabaseTree {
                         ui.label("Loading columns...");
                     }
                 }
+
+                // Keys and constraints load along with the columns
+                let column_key = format!("{}.{}", schema_name, table.name);
+                let keys = self.connections.get(connection_id).and_then(|connection| {
+                    Some((
+                        connection.foreign_keys.get(&column_key).cloned()?,
+                        connection.constraints.get(&column_key).cloned()?,
+                    ))
+                });
+                if let Some((foreign_keys, constraints)) = keys {
+                    self.render_keys_node(
+                        ui,
+                        connection_id,
+                        schema_name,
+                        &table.name,
+                        &foreign_keys,
+                    );
+                    self.render_constraints_node(
+                        ui,
+                        connection_id,
+                        schema_name,
+                        &table.name,
+                        &constraints,
+                    );
+                }
             });
 
         // Track expansion state
@@ -692,8 +748,32 @@ impl DatabaseTree {
                 schema: schema_name.to_string(),
                 table: table.name.clone(),
             });
-            // TODO: Show table context menu
         }
+        let supports_dependencies = self.supports_dependencies(connection_id);
+        header_response.header_response.context_menu(|ui| {
+            if ui.button("📋 Copy Name").clicked() {
+                ui.ctx().copy_text(table.name.clone());
+                ui.close_kind(egui::UiKind::Menu);
+            }
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Tables,
+                    schema_name.to_string(),
+                    table.name.clone(),
+                ));
+                ui.close_kind(egui::UiKind::Menu);
+            }
+            if supports_dependencies && ui.button("🕸 Dependencies").clicked() {
+                self.dependencies_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Tables,
+                    schema_name.to_string(),
+                    table.name.clone(),
+                ));
+                ui.close_kind(egui::UiKind::Menu);
+            }
+        });
 
         // Handle double-click to show table data
         if header_response.header_response.double_clicked() {
@@ -758,6 +838,130 @@ impl DatabaseTree {
         }
     }
 
+    /// Foreign keys of the table and those referencing it; clicking one
+    /// selects the table at its other end
+    fn render_keys_node(
+        &mut self,
+        ui: &mut Ui,
+        connection_id: &str,
+        schema_name: &str,
+        table_name: &str,
+        foreign_keys: &[ForeignKey],
+    ) {
+        CollapsingHeader::new(format!("🔗 Keys ({})", foreign_keys.len()))
+            .id_salt(format!(
+                "keys_{}_{}_{}",
+                connection_id, schema_name, table_name
+            ))
+            .show(ui, |ui| {
+                if foreign_keys.is_empty() {
+                    ui.label("No foreign keys");
+                }
+                for key in foreign_keys {
+                    // A key of a table referencing itself is listed once, as outgoing
+                    let outgoing = key.is_outgoing(schema_name, table_name);
+                    let (text, other_schema, other_table) = if outgoing {
+                        (
+                            format!(
+                                "➡ {} ({}) → {} ({})",
+                                key.name,
+                                key.columns.join(", "),
+                                key.referenced_table,
+                                key.referenced_columns.join(", ")
+                            ),
+                            &key.referenced_schema,
+                            &key.referenced_table,
+                        )
+                    } else {
+                        (
+                            format!(
+                                "⬅ {} ({}) ← {} ({})",
+                                key.name,
+                                key.referenced_columns.join(", "),
+                                key.table_name,
+                                key.columns.join(", ")
+                            ),
+                            &key.schema,
+                            &key.table_name,
+                        )
+                    };
+
+                    let mut hover_text = format!(
+                        "ON UPDATE {}\nON DELETE {}",
+                        key.on_update.to_sql(),
+                        key.on_delete.to_sql()
+                    );
+                    if let Some(comment) = &key.comment {
+                        hover_text.push_str(&format!("\nComment: {}", comment));
+                    }
+                    let response = ui.selectable_label(false, text).on_hover_text(hover_text);
+                    if response.clicked() {
+                        self.selected_item = Some(TreeItem::Table {
+                            connection_id: connection_id.to_string(),
+                            schema: other_schema.clone(),
+                            table: other_table.clone(),
+                        });
+                    }
+                    response.context_menu(|ui| {
+                        if ui.button("📋 Copy Name").clicked() {
+                            ui.ctx().copy_text(key.name.clone());
+                            ui.close_kind(egui::UiKind::Menu);
+                        }
+                    });
+                }
+            });
+    }
+
+    fn render_constraints_node(
+        &mut self,
+        ui: &mut Ui,
+        connection_id: &str,
+        schema_name: &str,
+        table_name: &str,
+        constraints: &[Constraint],
+    ) {
+        CollapsingHeader::new(format!("✔ Constraints ({})", constraints.len()))
+            .id_salt(format!(
+                "constraints_{}_{}_{}",
+                connection_id, schema_name, table_name
+            ))
+            .show(ui, |ui| {
+                if constraints.is_empty() {
+                    ui.label("No constraints");
+                }
+                for constraint in constraints {
+                    let icon = match constraint.constraint_type {
+                        ConstraintType::PrimaryKey => "🔑",
+                        ConstraintType::Unique => "🔒",
+                        ConstraintType::Check => "✔",
+                        ConstraintType::Exclusion => "⛔",
+                    };
+
+                    let mut hover_text = constraint.definition.clone();
+                    if !constraint.columns.is_empty() {
+                        hover_text
+                            .push_str(&format!("\nColumns: {}", constraint.columns.join(", ")));
+                    }
+                    if let Some(comment) = &constraint.comment {
+                        hover_text.push_str(&format!("\nComment: {}", comment));
+                    }
+                    let response = ui
+                        .selectable_label(false, format!("{} {}", icon, constraint.name))
+                        .on_hover_text(hover_text);
+                    response.context_menu(|ui| {
+                        if ui.button("📋 Copy Name").clicked() {
+                            ui.ctx().copy_text(constraint.name.clone());
+                            ui.close_kind(egui::UiKind::Menu);
+                        }
+                        if ui.button("📋 Copy Definition").clicked() {
+                            ui.ctx().copy_text(constraint.definition.clone());
+                            ui.close_kind(egui::UiKind::Menu);
+                        }
+                    });
+                }
+            });
+    }
+
     fn render_view_node(
         &mut self,
         ui: &mut Ui,
@@ -799,14 +1003,29 @@ impl DatabaseTree {
         }
         let response = response.on_hover_text(hover_text);
 
+        let supports_dependencies = self.supports_dependencies(connection_id);
         // Handle right-click context menu
         response.context_menu(|ui| {
             if ui.button("📋 Copy Name").clicked() {
                 ui.output_mut(|o| o.copied_text = view.name.clone());
                 ui.close_kind(egui::UiKind::Menu);
             }
-            if ui.button("👁 View Definition").clicked() {
-                // TODO: Show view definition
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Views,
+                    schema_name.to_string(),
+                    view.name.clone(),
+                ));
+                ui.close_kind(egui::UiKind::Menu);
+            }
+            if supports_dependencies && ui.button("🕸 Dependencies").clicked() {
+                self.dependencies_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Views,
+                    schema_name.to_string(),
+                    view.name.clone(),
+                ));
                 ui.close_kind(egui::UiKind::Menu);
             }
             if ui.button("📊 Properties").clicked() {
@@ -866,14 +1085,29 @@ impl DatabaseTree {
         }
         let response = response.on_hover_text(hover_text);
 
+        let supports_dependencies = self.supports_dependencies(connection_id);
         // Handle right-click context menu
         response.context_menu(|ui| {
             if ui.button("📋 Copy Name").clicked() {
                 ui.output_mut(|o| o.copied_text = function.name.clone());
                 ui.close_kind(egui::UiKind::Menu);
             }
-            if ui.button("⚙️ View Definition").clicked() {
-                // TODO: Show function definition
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Functions,
+                    schema_name.to_string(),
+                    function.name.clone(),
+                ));
+                ui.close_kind(egui::UiKind::Menu);
+            }
+            if supports_dependencies && ui.button("🕸 Dependencies").clicked() {
+                self.dependencies_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Functions,
+                    schema_name.to_string(),
+                    function.name.clone(),
+                ));
                 ui.close_kind(egui::UiKind::Menu);
             }
             if ui.button("📊 Properties").clicked() {
@@ -937,8 +1171,13 @@ impl DatabaseTree {
                 ui.output_mut(|o| o.copied_text = trigger.name.clone());
                 ui.close_kind(egui::UiKind::Menu);
             }
-            if ui.button("⚡ View Definition").clicked() {
-                // TODO: Show trigger definition
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Triggers,
+                    schema_name.to_string(),
+                    trigger.name.clone(),
+                ));
                 ui.close_kind(egui::UiKind::Menu);
             }
             if ui.button("📊 Properties").clicked() {
@@ -1000,8 +1239,13 @@ impl DatabaseTree {
                 ui.output_mut(|o| o.copied_text = sequence.name.clone());
                 ui.close_kind(egui::UiKind::Menu);
             }
-            if ui.button("🔢 View Definition").clicked() {
-                // TODO: Show sequence definition
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Sequences,
+                    schema_name.to_string(),
+                    sequence.name.clone(),
+                ));
                 ui.close_kind(egui::UiKind::Menu);
             }
             if ui.button("📊 Properties").clicked() {
@@ -1072,8 +1316,13 @@ impl DatabaseTree {
                 ui.output_mut(|o| o.copied_text = index.name.clone());
                 ui.close_kind(egui::UiKind::Menu);
             }
-            if ui.button("🗂️ View Definition").clicked() {
-                // TODO: Show index definition
+            if ui.button("📜 Generate DDL").clicked() {
+                self.ddl_requested = Some((
+                    connection_id.to_string(),
+                    ObjectCategory::Indexes,
+                    schema_name.to_string(),
+                    index.name.clone(),
+                ));
                 ui.close_kind(egui::UiKind::Menu);
             }
             if ui.button("📊 Properties").clicked() {
@@ -1120,7 +1369,10 @@ impl DatabaseTree {
                 }
                 ObjectCategory::Views => {
                     if let Some(views) = connection.views.get(schema_name) {
-                        return views.iter().any(|view| self.matches_search(&view.name));
+                        return views.iter().any(|view| {
+                            connection.capabilities.supports_view_type(&view.view_type)
+                                && self.matches_search(&view.name)
+                        });
                     }
                 }
                 ObjectCategory::Functions => {
@@ -1158,8 +1410,13 @@ impl DatabaseTree {
         false
     }
 
-    pub fn add_connection(&mut self, connection_id: String, connection_name: String) {
-        let connection = ConnectionNode::new(connection_id.clone(), connection_name);
+    pub fn add_connection(
+        &mut self,
+        connection_id: String,
+        connection_name: String,
+        capabilities: DriverCapabilities,
+    ) {
+        let connection = ConnectionNode::new(connection_id.clone(), connection_name, capabilities);
         self.connections.insert(connection_id, connection);
     }
 
@@ -1205,6 +1462,21 @@ impl DatabaseTree {
         }
     }
 
+    pub fn set_keys(
+        &mut self,
+        connection_id: &str,
+        schema: String,
+        table: String,
+        foreign_keys: Vec<ForeignKey>,
+        constraints: Vec<Constraint>,
+    ) {
+        if let Some(connection) = self.connections.get_mut(connection_id) {
+            let key = format!("{}.{}", schema, table);
+            connection.foreign_keys.insert(key.clone(), foreign_keys);
+            connection.constraints.insert(key, constraints);
+        }
+    }
+
     pub fn set_views(&mut self, connection_id: &str, schema: String, views: Vec<View>) {
         if let Some(connection) = self.connections.get_mut(connection_id) {
             connection.views.insert(schema, views);
@@ -1291,12 +1563,14 @@ impl DatabaseTree {
         self.expanded_tables.clear();
     }
 
-    /// Generate SQL for selected item
-    pub fn get_sql_for_selected(&self) -> Option<String> {
+    /// Generate SQL for selected item, quoting names as `database_type` does
+    pub fn get_sql_for_selected(&self, database_type: &DatabaseType) -> Option<String> {
+        let quote = |name: &str| quote_identifier(database_type, name);
         match &self.selected_item {
             Some(TreeItem::Table { schema, table, .. }) => Some(format!(
-                "SELECT * FROM \"{}\".\"{}\" LIMIT 100;",
-                schema, table
+                "SELECT * FROM {}.{} LIMIT 100;",
+                quote(schema),
+                quote(table)
             )),
             Some(TreeItem::Column {
                 schema,
@@ -1304,8 +1578,10 @@ impl DatabaseTree {
                 column,
                 ..
             }) => Some(format!(
-                "SELECT \"{}\" FROM \"{}\".\"{}\" LIMIT 100;",
-                column, schema, table
+                "SELECT {} FROM {}.{} LIMIT 100;",
+                quote(column),
+                quote(schema),
+                quote(table)
             )),
             _ => None,
         }
@@ -1339,6 +1615,51 @@ impl DatabaseTree {
         self.pending_action.take()
     }
 
+    /// The object whose DDL the user asked for, as (connection_id, category,
+    /// schema, name)
+    pub fn take_ddl_request(&mut self) -> Option<(String, ObjectCategory, String, String)> {
+        self.ddl_requested.take()
+    }
+
+    /// The schema whose ER diagram the user asked for, as (connection_id, schema)
+    pub fn take_diagram_request(&mut self) -> Option<(String, String)> {
+        self.diagram_requested.take()
+    }
+
+    /// The object whose dependencies the user asked for, as (connection_id,
+    /// category, schema, name)
+    pub fn take_dependencies_request(
+        &mut self,
+    ) -> Option<(String, ObjectCategory, String, String)> {
+        self.dependencies_requested.take()
+    }
+
+    /// Whether the connection's driver can list dependencies
+    fn supports_dependencies(&self, connection_id: &str) -> bool {
+        self.connections
+            .get(connection_id)
+            .is_some_and(|conn| conn.capabilities.supports_dependencies)
+    }
+
+    /// Connected databases with the schemas loaded for them, as
+    /// (connection_id, connection_name, schemas), by name
+    pub fn connected_schemas(&self) -> Vec<(String, String, Vec<String>)> {
+        let mut connections: Vec<(String, String, Vec<String>)> = self
+            .connections
+            .values()
+            .filter(|node| node.is_connected)
+            .map(|node| {
+                (
+                    node.connection_id.clone(),
+                    node.connection_name.clone(),
+                    node.schemas.iter().map(|s| s.name.clone()).collect(),
+                )
+            })
+            .collect();
+        connections.sort_by(|a, b| a.1.cmp(&b.1));
+        connections
+    }
+
     /// Update saved connection status
     pub fn update_saved_connection_status(&mut self, connection_id: &str, is_connected: bool) {
         // Update the connection status in the tree
@@ -1389,6 +1710,18 @@ impl Default for DatabaseTree {
             schemas_needing_objects: Vec::new(),
             tables_needing_columns: Vec::new(),
             pending_action: None,
+            ddl_requested: None,
+            diagram_requested: None,
+            dependencies_requested: None,
         }
     }
 }
+
+/// Materialized views counted in a schema, unless the driver has none to show
+fn materialized_view_count(capabilities: &DriverCapabilities, counts: &ObjectCounts) -> usize {
+    if capabilities.supports_materialized_views {
+        counts.materialized_views
+    } else {
+        0
+    }
+}
@@ -0,0 +1,92 @@
+use egui::{ScrollArea, TextEdit};
+
+/// Window showing the statements that recreate a database object
+#[derive(Default)]
+pub struct DdlDialog {
+    pub open: bool,
+    /// Object the statements are for, e.g. `public.orders`
+    title: String,
+    /// The statements or the error; `None` while they are generated
+    ddl: Option<Result<String, String>>,
+    /// Statements the user wants in the query editor
+    editor_request: Option<String>,
+}
+
+impl DdlDialog {
+    /// Open the window for an object whose statements are on their way
+    pub fn show_loading(&mut self, title: String) {
+        self.open = true;
+        self.title = title;
+        self.ddl = None;
+    }
+
+    /// Show the generated statements, unless the window has since moved on
+    /// to another object
+    pub fn set_ddl(&mut self, title: &str, ddl: Result<String, String>) {
+        if self.title == title {
+            self.ddl = Some(ddl);
+        }
+    }
+
+    /// Statements to put in the query editor, if the user asked for it
+    pub fn take_editor_request(&mut self) -> Option<String> {
+        self.editor_request.take()
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new(format!("DDL: {}", self.title))
+            .id(egui::Id::new("ddl_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([640.0, 420.0])
+            .show(ctx, |ui| {
+                match &self.ddl {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Generating DDL...");
+                        });
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    Some(Ok(ddl)) => {
+                        ScrollArea::both()
+                            .id_salt("ddl_dialog_scroll")
+                            .max_height(ui.available_height() - 36.0)
+                            .show(ui, |ui| {
+                                // Read-only, but selectable for partial copies
+                                let mut text = ddl.as_str();
+                                ui.add(
+                                    TextEdit::multiline(&mut text)
+                                        .code_editor()
+                                        .desired_width(f32::INFINITY),
+                                );
+                            });
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if let Some(Ok(ddl)) = &self.ddl {
+                        if ui.button("📋 Copy").clicked() {
+                            ui.ctx().copy_text(ddl.clone());
+                        }
+                        if ui.button("📝 Open in Editor").clicked() {
+                            self.editor_request = Some(ddl.clone());
+                            self.open = false;
+                        }
+                    }
+                    if ui.button("Close").clicked() {
+                        self.open = false;
+                    }
+                });
+            });
+        self.open &= open;
+    }
+}
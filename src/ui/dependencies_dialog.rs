@@ -0,0 +1,191 @@
+use crate::database::{Dependency, DependencyKind};
+use egui::{Grid, RichText, ScrollArea, Ui};
+
+/// What an object depends on, and the objects depending on it
+pub type DependencyLists = (Vec<Dependency>, Vec<Dependency>);
+
+/// Which list of the window is shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+enum DependencyTab {
+    #[default]
+    Dependents,
+    DependsOn,
+}
+
+/// Window listing what an object depends on and what depends on it
+#[derive(Default)]
+pub struct DependenciesDialog {
+    pub open: bool,
+    /// Object the lists are for, e.g. `table public.orders`
+    title: String,
+    /// What the object depends on and its dependents, or the error; `None`
+    /// while they load
+    dependencies: Option<Result<DependencyLists, String>>,
+    tab: DependencyTab,
+}
+
+impl DependenciesDialog {
+    /// Open the window for an object whose dependencies are on their way
+    pub fn show_loading(&mut self, title: String) {
+        self.open = true;
+        self.title = title;
+        self.dependencies = None;
+    }
+
+    /// Show the dependencies and dependents, unless the window has since
+    /// moved on to another object
+    pub fn set_dependencies(&mut self, title: &str, dependencies: Result<DependencyLists, String>) {
+        if self.title == title {
+            self.dependencies = Some(dependencies);
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new(format!("Dependencies: {}", self.title))
+            .id(egui::Id::new("dependencies_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([640.0, 420.0])
+            .show(ctx, |ui| {
+                match &self.dependencies {
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.spinner();
+                            ui.label("Loading dependencies...");
+                        });
+                    }
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    Some(Ok((depends_on, dependents))) => {
+                        ui.horizontal(|ui| {
+                            ui.selectable_value(
+                                &mut self.tab,
+                                DependencyTab::Dependents,
+                                format!("➡ Dependents ({})", dependents.len()),
+                            );
+                            ui.selectable_value(
+                                &mut self.tab,
+                                DependencyTab::DependsOn,
+                                format!("⬅ Depends On ({})", depends_on.len()),
+                            );
+                        });
+                        ui.separator();
+                        let (list, dependents_shown) = match self.tab {
+                            DependencyTab::Dependents => (dependents, true),
+                            DependencyTab::DependsOn => (depends_on, false),
+                        };
+                        ScrollArea::both()
+                            .id_salt("dependencies_dialog_scroll")
+                            .max_height(ui.available_height() - 36.0)
+                            .show(ui, |ui| render_list(ui, list, dependents_shown));
+                    }
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if let Some(Ok((depends_on, dependents))) = &self.dependencies {
+                        if ui.button("📋 Copy").clicked() {
+                            let list = match self.tab {
+                                DependencyTab::Dependents => dependents,
+                                DependencyTab::DependsOn => depends_on,
+                            };
+                            ui.ctx().copy_text(list_text(list));
+                        }
+                    }
+                    if ui.button("Close").clicked() {
+                        self.open = false;
+                    }
+                });
+            });
+        self.open &= open;
+    }
+}
+
+/// One row per object; for dependents, whether they would stop a DROP
+fn render_list(ui: &mut Ui, list: &[Dependency], dependents: bool) {
+    if list.is_empty() {
+        ui.label(if dependents {
+            "Nothing depends on this object"
+        } else {
+            "This object depends on nothing else"
+        });
+        return;
+    }
+
+    Grid::new("dependencies_dialog_grid")
+        .num_columns(5)
+        .striped(true)
+        .spacing([12.0, 4.0])
+        .show(ui, |ui| {
+            ui.strong("Kind");
+            ui.strong("Object");
+            ui.strong("Columns");
+            ui.strong("Depth");
+            ui.strong(if dependents { "On DROP" } else { "" });
+            ui.end_row();
+            for dependency in list {
+                ui.label(format!(
+                    "{} {}",
+                    kind_icon(&dependency.kind),
+                    dependency.kind.label()
+                ));
+                ui.label(dependency.qualified_name());
+                ui.label(dependency.columns.join(", "));
+                if dependency.depth == 1 {
+                    ui.label("direct");
+                } else {
+                    ui.label(RichText::new(format!("via {}", dependency.depth - 1)).weak())
+                        .on_hover_text(format!("Through {} other object(s)", dependency.depth - 1));
+                }
+                if !dependents {
+                    ui.label("");
+                } else if dependency.automatic {
+                    ui.label(RichText::new("dropped along").weak());
+                } else {
+                    ui.colored_label(ui.visuals().warn_fg_color, "⚠ blocks DROP")
+                        .on_hover_text("A plain DROP fails while this exists; CASCADE drops it");
+                }
+                ui.end_row();
+            }
+        });
+}
+
+/// The list as plain text, one object per line
+fn list_text(list: &[Dependency]) -> String {
+    list.iter()
+        .map(|dependency| {
+            let mut line = format!(
+                "{} {}",
+                dependency.kind.label(),
+                dependency.qualified_name()
+            );
+            if !dependency.columns.is_empty() {
+                line.push_str(&format!(" ({})", dependency.columns.join(", ")));
+            }
+            line
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Icons as the database tree shows the objects
+fn kind_icon(kind: &DependencyKind) -> &'static str {
+    match kind {
+        DependencyKind::Table => "📋",
+        DependencyKind::View => "👁",
+        DependencyKind::MaterializedView => "📊",
+        DependencyKind::Sequence => "🔢",
+        DependencyKind::Index => "🗂️",
+        DependencyKind::Function | DependencyKind::Procedure => "⚙",
+        DependencyKind::Trigger => "⚡",
+        DependencyKind::Constraint => "🔒",
+        DependencyKind::Column | DependencyKind::ColumnDefault => "📄",
+        DependencyKind::Other(_) => "•",
+    }
+}
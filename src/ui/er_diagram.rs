@@ -0,0 +1,1282 @@
+use crate::database::er_model::{ErModel, ErTable, Relationship};
+use egui::epaint::text::Fonts;
+use egui::epaint::{
+    ClippedPrimitive, ClippedShape, ColorImage, CornerRadius, CubicBezierShape, Primitive,
+    TextureId,
+};
+use egui::{
+    pos2, vec2, Color32, CursorIcon, FontId, Pos2, Rect, RichText, Sense, Shape, Stroke,
+    StrokeKind, Ui, Vec2,
+};
+use std::collections::HashMap;
+use std::fmt::Write;
+
+const FONT_SIZE: f32 = 13.0;
+const HEADER_HEIGHT: f32 = 24.0;
+const ROW_HEIGHT: f32 = 18.0;
+const PADDING: f32 = 8.0;
+/// Space between the name and type columns of a table box
+const COLUMN_GAP: f32 = 16.0;
+const CORNER_RADIUS: f32 = 4.0;
+/// Straight part of an edge next to a box, which carries its end markers
+const STUB: f32 = 20.0;
+/// Space between boxes placed by the automatic layouts
+const HORIZONTAL_GAP: f32 = 80.0;
+const VERTICAL_GAP: f32 = 30.0;
+/// Empty border around exported images
+const MARGIN: f32 = 20.0;
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 3.0;
+/// Longest side of an exported PNG, in pixels
+const MAX_IMAGE_SIDE: f32 = 8192.0;
+
+const CANVAS_FILL: Color32 = Color32::from_gray(248);
+const BOX_FILL: Color32 = Color32::WHITE;
+const HEADER_FILL: Color32 = Color32::from_rgb(214, 230, 245);
+const BORDER_COLOR: Color32 = Color32::from_gray(140);
+const TEXT_COLOR: Color32 = Color32::from_gray(30);
+const TYPE_COLOR: Color32 = Color32::from_gray(110);
+const EDGE_COLOR: Color32 = Color32::from_rgb(70, 90, 130);
+
+/// How the automatic layout places the tables
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramLayout {
+    /// Columns by foreign key depth, referenced tables on the left
+    Layered,
+    /// Related tables pulled together, all tables pushed apart
+    ForceDirected,
+}
+
+/// Files and text the diagram exports to
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DiagramFormat {
+    Svg,
+    Png,
+    Mermaid,
+    PlantUml,
+    Dot,
+}
+
+impl DiagramFormat {
+    pub const ALL: [DiagramFormat; 5] = [
+        DiagramFormat::Svg,
+        DiagramFormat::Png,
+        DiagramFormat::Mermaid,
+        DiagramFormat::PlantUml,
+        DiagramFormat::Dot,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            DiagramFormat::Svg => "SVG Image",
+            DiagramFormat::Png => "PNG Image",
+            DiagramFormat::Mermaid => "Mermaid",
+            DiagramFormat::PlantUml => "PlantUML",
+            DiagramFormat::Dot => "Graphviz DOT",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DiagramFormat::Svg => "svg",
+            DiagramFormat::Png => "png",
+            DiagramFormat::Mermaid => "mmd",
+            DiagramFormat::PlantUml => "puml",
+            DiagramFormat::Dot => "dot",
+        }
+    }
+
+    /// Whether the export is text that can go to the clipboard
+    pub fn is_text(&self) -> bool {
+        !matches!(self, DiagramFormat::Png)
+    }
+}
+
+/// Size of a table box and where its text columns start, in diagram points
+#[derive(Debug, Clone, Copy)]
+struct BoxMetrics {
+    size: Vec2,
+    name_x: f32,
+    type_x: f32,
+}
+
+/// What the pointer is dragging on the canvas
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Drag {
+    Table(usize),
+    Canvas,
+}
+
+/// Edge pieces in diagram points, shared by the painter and the SVG writer
+#[derive(Debug, Clone, Copy)]
+enum Mark {
+    Line([Pos2; 2]),
+    Curve([Pos2; 4]),
+    /// Hollow circle, by center and radius
+    Circle(Pos2, f32),
+}
+
+/// ER diagram of one schema, shown in its own tab
+///
+/// Table positions are diagram points at 100% zoom; the canvas maps them to
+/// the screen through the zoom and pan. Positions the user arranged by
+/// dragging are handed out through `take_layout_change` to be saved.
+pub struct ErDiagramView {
+    pub connection_id: String,
+    pub schema: String,
+    /// The model, or why it failed to load; `None` while loading
+    model: Option<Result<ErModel, String>>,
+    /// Top-left corner of each table box, in the order of the model's tables
+    positions: Vec<Pos2>,
+    metrics: Vec<BoxMetrics>,
+    /// Positions by table name, applied when the model is measured
+    saved_layout: HashMap<String, (f32, f32)>,
+    zoom: f32,
+    pan: Vec2,
+    dragging: Option<Drag>,
+    /// Canvas of the last frame, which toolbar zooms center on
+    canvas: Rect,
+    fit_requested: bool,
+    layout_changed: bool,
+    reload_requested: bool,
+    status: Option<String>,
+}
+
+impl ErDiagramView {
+    /// A diagram waiting for its model, placing tables at saved positions
+    pub fn new(
+        connection_id: impl Into<String>,
+        schema: impl Into<String>,
+        saved_layout: HashMap<String, (f32, f32)>,
+    ) -> Self {
+        Self {
+            connection_id: connection_id.into(),
+            schema: schema.into(),
+            model: None,
+            positions: Vec::new(),
+            metrics: Vec::new(),
+            saved_layout,
+            zoom: 1.0,
+            pan: Vec2::ZERO,
+            dragging: None,
+            canvas: Rect::ZERO,
+            fit_requested: false,
+            layout_changed: false,
+            reload_requested: false,
+            status: None,
+        }
+    }
+
+    pub fn title(&self) -> String {
+        format!("ER Diagram: {}", self.schema)
+    }
+
+    /// Show a loaded model, keeping the tables where they are now
+    pub fn set_model(&mut self, model: Result<ErModel, String>) {
+        if !self.metrics.is_empty() {
+            self.saved_layout = self.layout();
+        }
+        self.model = Some(model);
+        self.positions.clear();
+        self.metrics.clear();
+    }
+
+    pub fn model(&self) -> Option<&ErModel> {
+        self.model.as_ref().and_then(|model| model.as_ref().ok())
+    }
+
+    /// Whether the user asked to read the schema again
+    pub fn take_reload_request(&mut self) -> bool {
+        std::mem::take(&mut self.reload_requested)
+    }
+
+    /// Table positions to save, after the user moved or rearranged them
+    pub fn take_layout_change(&mut self) -> Option<HashMap<String, (f32, f32)>> {
+        std::mem::take(&mut self.layout_changed).then(|| self.layout())
+    }
+
+    /// Current position of each table, by name
+    pub fn layout(&self) -> HashMap<String, (f32, f32)> {
+        let Some(model) = self.model() else {
+            return HashMap::new();
+        };
+        model
+            .tables
+            .iter()
+            .zip(&self.positions)
+            .map(|(table, position)| (table.name.clone(), (position.x, position.y)))
+            .collect()
+    }
+
+    /// Box of each table, in diagram points
+    pub fn table_rects(&self) -> Vec<Rect> {
+        (0..self.positions.len())
+            .map(|index| self.table_rect(index))
+            .collect()
+    }
+
+    /// Measure the table boxes of a new model and place them: at their saved
+    /// positions, or by the layered layout when any table has none
+    pub fn arrange(&mut self, ctx: &egui::Context) {
+        let Some(Ok(model)) = &self.model else {
+            return;
+        };
+        if self.metrics.len() == model.tables.len() {
+            return;
+        }
+        self.metrics = ctx.fonts(|fonts| {
+            model
+                .tables
+                .iter()
+                .map(|table| measure(fonts, table))
+                .collect()
+        });
+
+        let saved: Vec<Option<Pos2>> = model
+            .tables
+            .iter()
+            .map(|table| self.saved_layout.get(&table.name).map(|&(x, y)| pos2(x, y)))
+            .collect();
+        self.positions = if saved.iter().all(Option::is_some) {
+            saved.into_iter().flatten().collect()
+        } else {
+            let sizes = self.sizes();
+            let mut positions = layered_layout(model, &sizes);
+            if saved.iter().any(Option::is_some) {
+                // Tables new to the schema go below the arranged ones
+                let bottom = saved
+                    .iter()
+                    .zip(&sizes)
+                    .filter_map(|(position, size)| position.map(|p| p.y + size.y))
+                    .fold(0.0, f32::max);
+                for (position, saved) in positions.iter_mut().zip(&saved) {
+                    *position = saved.unwrap_or(*position + vec2(0.0, bottom + VERTICAL_GAP));
+                }
+            }
+            positions
+        };
+        self.fit_requested = true;
+    }
+
+    /// Place every table anew
+    pub fn apply_layout(&mut self, layout: DiagramLayout) {
+        let Some(model) = self.model() else {
+            return;
+        };
+        let sizes = self.sizes();
+        self.positions = match layout {
+            DiagramLayout::Layered => layered_layout(model, &sizes),
+            DiagramLayout::ForceDirected => force_layout(model, &sizes, &self.positions),
+        };
+        self.layout_changed = true;
+        self.fit_requested = true;
+    }
+
+    pub fn render(&mut self, ui: &mut Ui) {
+        match &self.model {
+            None => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(format!("Loading tables of {}...", self.schema));
+                });
+                return;
+            }
+            Some(Err(error)) => {
+                ui.colored_label(
+                    ui.visuals().error_fg_color,
+                    format!("Failed to load the diagram: {}", error),
+                );
+                if ui.button("⟳ Reload").clicked() {
+                    self.reload_requested = true;
+                }
+                return;
+            }
+            Some(Ok(_)) => {}
+        }
+
+        self.arrange(ui.ctx());
+        self.render_toolbar(ui);
+        ui.separator();
+        self.render_canvas(ui);
+    }
+
+    fn render_toolbar(&mut self, ui: &mut Ui) {
+        let (tables, relationships) = self
+            .model()
+            .map(|model| (model.tables.len(), model.relationships.len()))
+            .unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.label(format!(
+                "{} tables, {} relationships",
+                tables, relationships
+            ));
+            ui.separator();
+
+            if ui
+                .button("▤ Layered")
+                .on_hover_text("Referenced tables left of the tables referencing them")
+                .clicked()
+            {
+                self.apply_layout(DiagramLayout::Layered);
+            }
+            if ui
+                .button("✳ Force-directed")
+                .on_hover_text("Related tables pulled together")
+                .clicked()
+            {
+                self.apply_layout(DiagramLayout::ForceDirected);
+            }
+            ui.separator();
+
+            let center = self.canvas.center();
+            if ui.button("➖").on_hover_text("Zoom out").clicked() {
+                self.zoom_about(center, 1.0 / 1.25);
+            }
+            ui.label(format!("{:.0}%", self.zoom * 100.0));
+            if ui.button("➕").on_hover_text("Zoom in").clicked() {
+                self.zoom_about(center, 1.25);
+            }
+            if ui
+                .button("Fit")
+                .on_hover_text("Show the whole diagram")
+                .clicked()
+            {
+                self.fit_requested = true;
+            }
+            ui.separator();
+
+            ui.menu_button("💾 Export", |ui| {
+                for format in DiagramFormat::ALL {
+                    if ui.button(format!("{}...", format.label())).clicked() {
+                        self.save(ui.ctx(), format);
+                        ui.close();
+                    }
+                }
+                ui.separator();
+                for format in DiagramFormat::ALL
+                    .into_iter()
+                    .filter(DiagramFormat::is_text)
+                {
+                    if ui
+                        .button(format!("📋 Copy as {}", format.label()))
+                        .clicked()
+                    {
+                        if let Ok(bytes) = self.export(ui.ctx(), format) {
+                            ui.ctx()
+                                .copy_text(String::from_utf8_lossy(&bytes).into_owned());
+                            self.status = Some(format!("Copied the {} diagram", format.label()));
+                        }
+                        ui.close();
+                    }
+                }
+            });
+            if ui
+                .button("⟳ Reload")
+                .on_hover_text("Read the tables again")
+                .clicked()
+            {
+                self.reload_requested = true;
+            }
+
+            if let Some(status) = &self.status {
+                ui.label(RichText::new(status).weak());
+            }
+        });
+    }
+
+    fn render_canvas(&mut self, ui: &mut Ui) {
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let rect = response.rect;
+        self.canvas = rect;
+        painter.rect_filled(rect, 0.0, CANVAS_FILL);
+
+        if self.positions.is_empty() {
+            painter.text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "No tables in this schema",
+                FontId::proportional(14.0),
+                TYPE_COLOR,
+            );
+            return;
+        }
+        if std::mem::take(&mut self.fit_requested) {
+            self.fit(rect);
+        }
+
+        // Ctrl+wheel and pinch zoom around the pointer, the wheel pans
+        if let Some(pointer) = response.hover_pos() {
+            let (zoom_delta, scroll) = ui.input(|i| (i.zoom_delta(), i.smooth_scroll_delta));
+            if zoom_delta != 1.0 {
+                self.zoom_about(pointer, zoom_delta);
+            }
+            self.pan += scroll;
+        }
+
+        if response.drag_started() {
+            let table = response
+                .interact_pointer_pos()
+                .and_then(|pointer| self.table_at(self.to_diagram(pointer)));
+            self.dragging = Some(table.map_or(Drag::Canvas, Drag::Table));
+        }
+        if response.dragged() {
+            let delta = response.drag_delta();
+            match self.dragging {
+                Some(Drag::Table(index)) => self.positions[index] += delta / self.zoom,
+                Some(Drag::Canvas) => self.pan += delta,
+                None => {}
+            }
+        }
+        if response.drag_stopped() {
+            if let Some(Drag::Table(_)) = self.dragging.take() {
+                self.layout_changed = true;
+            }
+        }
+
+        if let Some(Drag::Table(_)) = self.dragging {
+            ui.ctx().set_cursor_icon(CursorIcon::Grabbing);
+        } else if response
+            .hover_pos()
+            .is_some_and(|pointer| self.table_at(self.to_diagram(pointer)).is_some())
+        {
+            ui.ctx().set_cursor_icon(CursorIcon::Grab);
+        }
+
+        let origin = rect.min + self.pan;
+        let shapes = ui.fonts(|fonts| self.shapes(fonts, origin, self.zoom));
+        painter.extend(shapes);
+        painter.text(
+            rect.left_bottom() + vec2(8.0, -6.0),
+            egui::Align2::LEFT_BOTTOM,
+            "Drag tables to move them, drag the background to pan, Ctrl+wheel to zoom",
+            FontId::proportional(11.0),
+            TYPE_COLOR,
+        );
+    }
+
+    fn save(&mut self, ctx: &egui::Context, format: DiagramFormat) {
+        let bytes = match self.export(ctx, format) {
+            Ok(bytes) => bytes,
+            Err(error) => {
+                self.status = Some(error);
+                return;
+            }
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title(format!("Export {}", format.label()))
+            .set_file_name(format!("{}.{}", self.schema, format.extension()))
+            .add_filter(format.label(), &[format.extension()])
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match std::fs::write(&path, bytes) {
+            Ok(()) => format!("Saved to {}", path.display()),
+            Err(e) => format!("Failed to write {}: {}", path.display(), e),
+        });
+    }
+
+    /// The diagram in an export format
+    pub fn export(&self, ctx: &egui::Context, format: DiagramFormat) -> Result<Vec<u8>, String> {
+        let model = self.model().ok_or("The diagram is not loaded")?;
+        Ok(match format {
+            DiagramFormat::Svg => self.to_svg().into_bytes(),
+            DiagramFormat::Png => self.to_png(ctx)?,
+            DiagramFormat::Mermaid => model.to_mermaid().into_bytes(),
+            DiagramFormat::PlantUml => model.to_plantuml().into_bytes(),
+            DiagramFormat::Dot => model.to_dot().into_bytes(),
+        })
+    }
+
+    /// The diagram as drawn, at 100% zoom, as an SVG document
+    pub fn to_svg(&self) -> String {
+        let bounds = self.bounds();
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w:.0}\" height=\"{h:.0}\" \
+             viewBox=\"0 0 {w:.0} {h:.0}\" font-family=\"monospace\" font-size=\"{}\">",
+            FONT_SIZE,
+            w = bounds.width(),
+            h = bounds.height()
+        );
+        let _ = writeln!(
+            out,
+            "<rect width=\"100%\" height=\"100%\" fill=\"{}\"/>",
+            hex(Color32::WHITE)
+        );
+        let _ = writeln!(
+            out,
+            "<g transform=\"translate({:.1} {:.1})\">",
+            -bounds.min.x, -bounds.min.y
+        );
+
+        let Some(model) = self.model().filter(|_| !self.metrics.is_empty()) else {
+            out.push_str("</g>\n</svg>\n");
+            return out;
+        };
+        let edge = format!("stroke=\"{}\" stroke-width=\"1.5\"", hex(EDGE_COLOR));
+        for relationship in &model.relationships {
+            for mark in self.edge_marks(model, relationship) {
+                let _ = match mark {
+                    Mark::Line([a, b]) => writeln!(
+                        out,
+                        "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" {}/>",
+                        a.x, a.y, b.x, b.y, edge
+                    ),
+                    Mark::Curve([a, b, c, d]) => writeln!(
+                        out,
+                        "<path d=\"M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}\" \
+                         fill=\"none\" {}/>",
+                        a.x, a.y, b.x, b.y, c.x, c.y, d.x, d.y, edge
+                    ),
+                    Mark::Circle(center, radius) => writeln!(
+                        out,
+                        "<circle cx=\"{:.1}\" cy=\"{:.1}\" r=\"{:.1}\" fill=\"{}\" {}/>",
+                        center.x,
+                        center.y,
+                        radius,
+                        hex(BOX_FILL),
+                        edge
+                    ),
+                };
+            }
+        }
+
+        for (index, table) in model.tables.iter().enumerate() {
+            let rect = self.table_rect(index);
+            let metrics = &self.metrics[index];
+            let (x, y, w) = (rect.min.x, rect.min.y, rect.width());
+            let r = CORNER_RADIUS;
+            let _ = writeln!(
+                out,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{}\" \
+                 fill=\"{}\"/>",
+                x,
+                y,
+                w,
+                rect.height(),
+                r,
+                hex(BOX_FILL)
+            );
+            // Header with rounded top corners only
+            let _ = writeln!(
+                out,
+                "<path d=\"M {:.1} {:.1} a {r} {r} 0 0 1 {r} -{r} h {:.1} a {r} {r} 0 0 1 {r} {r} \
+                 v {:.1} h -{:.1} z\" fill=\"{}\"/>",
+                x,
+                y + r,
+                w - 2.0 * r,
+                HEADER_HEIGHT - r,
+                w,
+                hex(HEADER_FILL)
+            );
+            let _ = writeln!(
+                out,
+                "<line x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\" stroke=\"{}\"/>",
+                x,
+                y + HEADER_HEIGHT,
+                x + w,
+                y + HEADER_HEIGHT,
+                hex(BORDER_COLOR)
+            );
+            let _ = writeln!(
+                out,
+                "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\" rx=\"{}\" \
+                 fill=\"none\" stroke=\"{}\"/>",
+                x,
+                y,
+                w,
+                rect.height(),
+                r,
+                hex(BORDER_COLOR)
+            );
+            svg_text(
+                &mut out,
+                pos2(x + PADDING, y + HEADER_HEIGHT / 2.0),
+                &table.name,
+                TEXT_COLOR,
+                " font-weight=\"bold\"",
+            );
+            for (row, column) in table.columns.iter().enumerate() {
+                let row_y = y + HEADER_HEIGHT + (row as f32 + 0.5) * ROW_HEIGHT;
+                let markers = markers(column);
+                if !markers.is_empty() {
+                    svg_text(&mut out, pos2(x + PADDING, row_y), markers, TEXT_COLOR, "");
+                }
+                svg_text(
+                    &mut out,
+                    pos2(x + metrics.name_x, row_y),
+                    &column.name,
+                    TEXT_COLOR,
+                    "",
+                );
+                svg_text(
+                    &mut out,
+                    pos2(x + metrics.type_x, row_y),
+                    &column.data_type,
+                    TYPE_COLOR,
+                    "",
+                );
+            }
+        }
+        out.push_str("</g>\n</svg>\n");
+        out
+    }
+
+    /// The diagram as drawn, at 100% zoom and the screen's pixel density, as
+    /// PNG bytes
+    pub fn to_png(&self, ctx: &egui::Context) -> Result<Vec<u8>, String> {
+        let bounds = self.bounds();
+        let pixels_per_point = ctx.pixels_per_point();
+        let scale = pixels_per_point.min(MAX_IMAGE_SIDE / bounds.width().max(bounds.height()));
+        let size = [
+            (bounds.width() * scale).ceil() as usize,
+            (bounds.height() * scale).ceil() as usize,
+        ];
+
+        let origin = Pos2::ZERO - bounds.min.to_vec2();
+        let clip_rect = Rect::from_min_size(Pos2::ZERO, bounds.size());
+        let shapes = ctx.fonts(|fonts| self.shapes(fonts, origin, 1.0));
+        let primitives = ctx.tessellate(
+            shapes
+                .into_iter()
+                .map(|shape| ClippedShape { clip_rect, shape })
+                .collect(),
+            pixels_per_point,
+        );
+        // Read after laying out the text, which adds its glyphs to the atlas
+        let font_atlas = ctx.fonts(|fonts| fonts.image());
+        let image = rasterize(&primitives, &font_atlas, scale, size, Color32::WHITE);
+
+        let bytes = image
+            .pixels
+            .iter()
+            .flat_map(|color| color.to_srgba_unmultiplied())
+            .collect();
+        let buffer = image::RgbaImage::from_raw(size[0] as u32, size[1] as u32, bytes)
+            .ok_or("Failed to build the image")?;
+        let mut png = Vec::new();
+        buffer
+            .write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)
+            .map_err(|e| format!("Failed to encode the image: {}", e))?;
+        Ok(png)
+    }
+
+    /// The diagram's shapes, with diagram points mapped to `origin + p * zoom`
+    fn shapes(&self, fonts: &Fonts, origin: Pos2, zoom: f32) -> Vec<Shape> {
+        let Some(model) = self.model() else {
+            return Vec::new();
+        };
+        if self.metrics.len() != model.tables.len() {
+            return Vec::new();
+        }
+        let to_screen = |p: Pos2| origin + p.to_vec2() * zoom;
+        let font = FontId::monospace(FONT_SIZE * zoom);
+        let mut shapes = Vec::new();
+
+        let edge = Stroke::new(1.5 * zoom, EDGE_COLOR);
+        for relationship in &model.relationships {
+            for mark in self.edge_marks(model, relationship) {
+                match mark {
+                    Mark::Line([a, b]) => {
+                        shapes.push(Shape::line_segment([to_screen(a), to_screen(b)], edge))
+                    }
+                    Mark::Curve(points) => shapes.push(
+                        CubicBezierShape::from_points_stroke(
+                            points.map(to_screen),
+                            false,
+                            Color32::TRANSPARENT,
+                            edge,
+                        )
+                        .into(),
+                    ),
+                    Mark::Circle(center, radius) => {
+                        shapes.push(Shape::circle_filled(
+                            to_screen(center),
+                            radius * zoom,
+                            BOX_FILL,
+                        ));
+                        shapes.push(Shape::circle_stroke(to_screen(center), radius * zoom, edge));
+                    }
+                }
+            }
+        }
+
+        let radius = (CORNER_RADIUS * zoom).round() as u8;
+        for index in self.draw_order() {
+            let table = &model.tables[index];
+            let metrics = &self.metrics[index];
+            let diagram_rect = self.table_rect(index);
+            let rect = Rect::from_min_max(to_screen(diagram_rect.min), to_screen(diagram_rect.max));
+            let header = Rect::from_min_size(rect.min, vec2(rect.width(), HEADER_HEIGHT * zoom));
+
+            shapes.push(Shape::rect_filled(rect, radius, BOX_FILL));
+            shapes.push(Shape::rect_filled(
+                header,
+                CornerRadius {
+                    nw: radius,
+                    ne: radius,
+                    sw: 0,
+                    se: 0,
+                },
+                HEADER_FILL,
+            ));
+            shapes.push(Shape::line_segment(
+                [header.left_bottom(), header.right_bottom()],
+                Stroke::new(zoom, BORDER_COLOR),
+            ));
+            shapes.push(Shape::rect_stroke(
+                rect,
+                radius,
+                Stroke::new(zoom, BORDER_COLOR),
+                StrokeKind::Inside,
+            ));
+
+            let mut text = |x: f32, center_y: f32, text: &str, color: Color32| {
+                let galley = fonts.layout_no_wrap(text.to_string(), font.clone(), color);
+                let position = pos2(
+                    rect.min.x + x * zoom,
+                    rect.min.y + center_y * zoom - galley.size().y / 2.0,
+                );
+                shapes.push(Shape::galley(position, galley, color));
+            };
+            text(PADDING, HEADER_HEIGHT / 2.0, &table.name, TEXT_COLOR);
+            for (row, column) in table.columns.iter().enumerate() {
+                let center_y = HEADER_HEIGHT + (row as f32 + 0.5) * ROW_HEIGHT;
+                text(PADDING, center_y, markers(column), TEXT_COLOR);
+                text(metrics.name_x, center_y, &column.name, TEXT_COLOR);
+                text(metrics.type_x, center_y, &column.data_type, TYPE_COLOR);
+            }
+        }
+        shapes
+    }
+
+    /// Lines of a relationship: a crow's foot at the referencing column, a
+    /// bar at the referenced one, and a circle there when it is optional
+    fn edge_marks(&self, model: &ErModel, relationship: &Relationship) -> Vec<Mark> {
+        let (Some(from), Some(to)) = (
+            model.table_index(&relationship.from_table),
+            model.table_index(&relationship.to_table),
+        ) else {
+            return Vec::new();
+        };
+        let from_rect = self.table_rect(from);
+        let to_rect = self.table_rect(to);
+
+        // Leave and enter on the facing sides; loop on the right when the
+        // boxes overlap horizontally
+        let (from_dir, to_dir) = if from != to && from_rect.max.x + 2.0 * STUB <= to_rect.min.x {
+            (1.0, -1.0)
+        } else if from != to && to_rect.max.x + 2.0 * STUB <= from_rect.min.x {
+            (-1.0, 1.0)
+        } else {
+            (1.0, 1.0)
+        };
+        let side = |rect: Rect, dir: f32| if dir > 0.0 { rect.max.x } else { rect.min.x };
+        let from_anchor = pos2(
+            side(from_rect, from_dir),
+            self.row_center(model, from, &relationship.from_columns),
+        );
+        let to_anchor = pos2(
+            side(to_rect, to_dir),
+            self.row_center(model, to, &relationship.to_columns),
+        );
+        let from_end = from_anchor + vec2(from_dir * STUB, 0.0);
+        let to_end = to_anchor + vec2(to_dir * STUB, 0.0);
+        let bend = ((to_end.x - from_end.x).abs() / 2.0).max(40.0);
+
+        let mut marks = vec![
+            Mark::Line([from_anchor, from_end]),
+            Mark::Curve([
+                from_end,
+                from_end + vec2(from_dir * bend, 0.0),
+                to_end + vec2(to_dir * bend, 0.0),
+                to_end,
+            ]),
+            Mark::Line([to_anchor, to_end]),
+            Mark::Line([
+                from_anchor + vec2(from_dir * 12.0, 0.0),
+                from_anchor + vec2(0.0, -6.0),
+            ]),
+            Mark::Line([
+                from_anchor + vec2(from_dir * 12.0, 0.0),
+                from_anchor + vec2(0.0, 6.0),
+            ]),
+            Mark::Line([
+                to_anchor + vec2(to_dir * 6.0, -6.0),
+                to_anchor + vec2(to_dir * 6.0, 6.0),
+            ]),
+        ];
+        if relationship.optional {
+            marks.push(Mark::Circle(to_anchor + vec2(to_dir * 14.0, 0.0), 4.0));
+        }
+        marks
+    }
+
+    /// Height of the first of `columns` in a table box, or of its header
+    fn row_center(&self, model: &ErModel, index: usize, columns: &[String]) -> f32 {
+        let top = self.positions[index].y;
+        columns
+            .first()
+            .and_then(|name| {
+                model.tables[index]
+                    .columns
+                    .iter()
+                    .position(|column| &column.name == name)
+            })
+            .map_or(top + HEADER_HEIGHT / 2.0, |row| {
+                top + HEADER_HEIGHT + (row as f32 + 0.5) * ROW_HEIGHT
+            })
+    }
+
+    fn table_rect(&self, index: usize) -> Rect {
+        Rect::from_min_size(self.positions[index], self.metrics[index].size)
+    }
+
+    fn sizes(&self) -> Vec<Vec2> {
+        self.metrics.iter().map(|metrics| metrics.size).collect()
+    }
+
+    /// Tables bottom to top, the one being dragged last
+    fn draw_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.positions.len()).collect();
+        if let Some(Drag::Table(index)) = self.dragging {
+            order.retain(|&i| i != index);
+            order.push(index);
+        }
+        order
+    }
+
+    /// The topmost table under a diagram point
+    fn table_at(&self, point: Pos2) -> Option<usize> {
+        self.draw_order()
+            .into_iter()
+            .rev()
+            .find(|&index| self.table_rect(index).contains(point))
+    }
+
+    /// Area of the boxes and edges with a margin around it, in diagram points
+    fn bounds(&self) -> Rect {
+        let mut bounds = Rect::NOTHING;
+        for rect in self.table_rects() {
+            bounds = bounds.union(rect);
+        }
+        if let Some(model) = self.model().filter(|_| !self.metrics.is_empty()) {
+            for relationship in &model.relationships {
+                for mark in self.edge_marks(model, relationship) {
+                    match mark {
+                        Mark::Line(points) => points.iter().for_each(|p| bounds.extend_with(*p)),
+                        Mark::Curve(points) => points.iter().for_each(|p| bounds.extend_with(*p)),
+                        Mark::Circle(center, radius) => {
+                            bounds = bounds
+                                .union(Rect::from_center_size(center, Vec2::splat(radius * 2.0)))
+                        }
+                    }
+                }
+            }
+        }
+        if !bounds.is_positive() {
+            bounds = Rect::from_min_size(Pos2::ZERO, Vec2::ZERO);
+        }
+        bounds.expand(MARGIN)
+    }
+
+    fn to_diagram(&self, screen: Pos2) -> Pos2 {
+        ((screen - self.canvas.min - self.pan) / self.zoom).to_pos2()
+    }
+
+    /// Change the zoom, keeping the diagram point under `screen` in place
+    fn zoom_about(&mut self, screen: Pos2, factor: f32) {
+        let anchor = self.to_diagram(screen);
+        self.zoom = (self.zoom * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+        self.pan = screen - self.canvas.min - anchor.to_vec2() * self.zoom;
+    }
+
+    /// Zoom and pan to show the whole diagram, at most at 100%
+    fn fit(&mut self, canvas: Rect) {
+        let bounds = self.bounds();
+        self.zoom = (canvas.width() / bounds.width())
+            .min(canvas.height() / bounds.height())
+            .clamp(MIN_ZOOM, 1.0);
+        self.pan =
+            (canvas.size() - bounds.size() * self.zoom) / 2.0 - bounds.min.to_vec2() * self.zoom;
+    }
+}
+
+/// "🔑" for primary key columns, "🔗" for foreign key ones
+fn markers(column: &crate::database::er_model::ErColumn) -> &'static str {
+    match (column.is_primary_key, column.is_foreign_key) {
+        (true, true) => "🔑🔗",
+        (true, false) => "🔑",
+        (false, true) => "🔗",
+        (false, false) => "",
+    }
+}
+
+fn measure(fonts: &Fonts, table: &ErTable) -> BoxMetrics {
+    let font = FontId::monospace(FONT_SIZE);
+    let width = |text: &str| {
+        fonts
+            .layout_no_wrap(text.to_string(), font.clone(), TEXT_COLOR)
+            .size()
+            .x
+    };
+    let widest = |texts: &mut dyn Iterator<Item = &str>| texts.map(width).fold(0.0, f32::max);
+
+    let marker_width = widest(&mut table.columns.iter().map(markers));
+    let name_x = PADDING
+        + if marker_width > 0.0 {
+            marker_width + 4.0
+        } else {
+            0.0
+        };
+    let type_x =
+        name_x + widest(&mut table.columns.iter().map(|column| column.name.as_str())) + COLUMN_GAP;
+    let type_width = widest(&mut table.columns.iter().map(|column| column.data_type.as_str()));
+    let box_width = (type_x + type_width).max(PADDING + width(&table.name)) + PADDING;
+    let height = HEADER_HEIGHT + table.columns.len() as f32 * ROW_HEIGHT + PADDING / 2.0;
+    BoxMetrics {
+        size: vec2(box_width.ceil(), height),
+        name_x,
+        type_x,
+    }
+}
+
+/// Relationships as (referencing, referenced) table indexes, without
+/// self-references
+fn edges(model: &ErModel) -> Vec<(usize, usize)> {
+    model
+        .relationships
+        .iter()
+        .filter_map(|relationship| {
+            let from = model.table_index(&relationship.from_table)?;
+            let to = model.table_index(&relationship.to_table)?;
+            (from != to).then_some((from, to))
+        })
+        .collect()
+}
+
+/// Place tables in columns by how deep their foreign keys reach: tables
+/// referencing nothing on the left, each other table right of the tables it
+/// references. Tables without relationships fill columns on the far right.
+pub fn layered_layout(model: &ErModel, sizes: &[Vec2]) -> Vec<Pos2> {
+    let count = model.tables.len();
+    let edges = edges(model);
+
+    // Longest path to a table referencing nothing; capped so cycles end
+    let mut rank = vec![0; count];
+    for _ in 0..count {
+        let mut changed = false;
+        for &(from, to) in &edges {
+            if rank[from] < rank[to] + 1 && rank[to] + 1 < count {
+                rank[from] = rank[to] + 1;
+                changed = true;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    let mut connected = vec![false; count];
+    for &(from, to) in &edges {
+        connected[from] = true;
+        connected[to] = true;
+    }
+
+    let mut positions = vec![Pos2::ZERO; count];
+    let mut centers: Vec<Option<f32>> = vec![None; count];
+    let mut x = 0.0;
+    let mut tallest: f32 = 0.0;
+    let layers = (0..count)
+        .filter(|&i| connected[i])
+        .map(|i| rank[i] + 1)
+        .max()
+        .unwrap_or(0);
+    for layer in 0..layers {
+        // Next to the tables they reference, to keep edges short
+        let mut members: Vec<(usize, f32)> = (0..count)
+            .filter(|&i| connected[i] && rank[i] == layer)
+            .map(|i| {
+                let referenced: Vec<f32> = edges
+                    .iter()
+                    .filter(|(from, _)| *from == i)
+                    .filter_map(|(_, to)| centers[*to])
+                    .collect();
+                let key = if referenced.is_empty() {
+                    f32::MAX
+                } else {
+                    referenced.iter().sum::<f32>() / referenced.len() as f32
+                };
+                (i, key)
+            })
+            .collect();
+        members.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        let mut y = 0.0;
+        let mut width: f32 = 0.0;
+        for (i, _) in members {
+            positions[i] = pos2(x, y);
+            centers[i] = Some(y + sizes[i].y / 2.0);
+            y += sizes[i].y + VERTICAL_GAP;
+            width = width.max(sizes[i].x);
+        }
+        tallest = tallest.max(y);
+        x += width + HORIZONTAL_GAP;
+    }
+
+    let column_height = tallest.max(600.0);
+    let mut y = 0.0;
+    let mut width: f32 = 0.0;
+    for i in (0..count).filter(|&i| !connected[i]) {
+        if y > 0.0 && y + sizes[i].y > column_height {
+            x += width + HORIZONTAL_GAP;
+            y = 0.0;
+            width = 0.0;
+        }
+        positions[i] = pos2(x, y);
+        y += sizes[i].y + VERTICAL_GAP;
+        width = width.max(sizes[i].x);
+    }
+    positions
+}
+
+/// Spring layout starting from `start`: every pair of tables repels, each
+/// relationship pulls its tables together, and a weak pull toward the middle
+/// keeps unrelated tables near. Overlaps left at the end are pushed apart.
+pub fn force_layout(model: &ErModel, sizes: &[Vec2], start: &[Pos2]) -> Vec<Pos2> {
+    const ITERATIONS: usize = 300;
+    let count = model.tables.len();
+    if count == 0 {
+        return Vec::new();
+    }
+    let edges = edges(model);
+    let start = if start.len() == count {
+        start.to_vec()
+    } else {
+        layered_layout(model, sizes)
+    };
+
+    let mut centers: Vec<Vec2> = (0..count)
+        .map(|i| start[i].to_vec2() + sizes[i] / 2.0)
+        .collect();
+    let ideal = 80.0 + sizes.iter().map(|size| size.length()).sum::<f32>() / count as f32;
+    for step in 0..ITERATIONS {
+        let mut forces = vec![Vec2::ZERO; count];
+        for i in 0..count {
+            for j in i + 1..count {
+                let mut delta = centers[i] - centers[j];
+                if delta.length() < 0.01 {
+                    // Deterministic nudge for tables on the same spot
+                    delta = Vec2::angled(i as f32 * 2.4 + j as f32);
+                }
+                let distance = delta.length().max(1.0);
+                let force = delta / distance * (ideal * ideal / distance);
+                forces[i] += force;
+                forces[j] -= force;
+            }
+        }
+        for &(from, to) in &edges {
+            let delta = centers[to] - centers[from];
+            let distance = delta.length();
+            if distance < 0.01 {
+                continue;
+            }
+            let force = delta / distance * (distance * distance / ideal);
+            forces[from] += force;
+            forces[to] -= force;
+        }
+        let middle = centers.iter().fold(Vec2::ZERO, |sum, c| sum + *c) / count as f32;
+        let temperature = ideal * (1.0 - step as f32 / ITERATIONS as f32) + 1.0;
+        for i in 0..count {
+            let force = forces[i] + (middle - centers[i]) * 0.05;
+            let length = force.length();
+            if length > 0.0 {
+                centers[i] += force / length * length.min(temperature);
+            }
+        }
+    }
+
+    let mut positions: Vec<Pos2> = (0..count)
+        .map(|i| (centers[i] - sizes[i] / 2.0).to_pos2())
+        .collect();
+    remove_overlaps(&mut positions, sizes);
+    let min = positions
+        .iter()
+        .fold(pos2(f32::MAX, f32::MAX), |min, p| min.min(*p));
+    positions.iter().map(|p| *p - min.to_vec2()).collect()
+}
+
+/// Push overlapping boxes apart along the axis needing the shorter move
+fn remove_overlaps(positions: &mut [Pos2], sizes: &[Vec2]) {
+    for _ in 0..1000 {
+        let mut moved = false;
+        for i in 0..positions.len() {
+            for j in i + 1..positions.len() {
+                let a = Rect::from_min_size(positions[i], sizes[i]).expand(VERTICAL_GAP / 2.0);
+                let b = Rect::from_min_size(positions[j], sizes[j]).expand(VERTICAL_GAP / 2.0);
+                let overlap = a.intersect(b);
+                if !overlap.is_positive() {
+                    continue;
+                }
+                moved = true;
+                let sign = |difference: f32| if difference > 0.0 { 1.0 } else { -1.0 };
+                let push = if overlap.width() < overlap.height() {
+                    vec2(
+                        sign(a.center().x - b.center().x) * (overlap.width() / 2.0 + 0.5),
+                        0.0,
+                    )
+                } else {
+                    vec2(
+                        0.0,
+                        sign(a.center().y - b.center().y) * (overlap.height() / 2.0 + 0.5),
+                    )
+                };
+                positions[i] += push;
+                positions[j] -= push;
+            }
+        }
+        if !moved {
+            break;
+        }
+    }
+}
+
+/// Fill tessellated meshes into an image over a background color
+///
+/// A small software renderer for image exports: triangles take their
+/// interpolated vertex colors, multiplied by the font atlas for text, and
+/// are blended with premultiplied alpha like egui's own painters. `scale`
+/// is pixels per point of the image.
+pub fn rasterize(
+    primitives: &[ClippedPrimitive],
+    font_atlas: &ColorImage,
+    scale: f32,
+    size: [usize; 2],
+    background: Color32,
+) -> ColorImage {
+    let [width, height] = size;
+    let mut pixels = vec![to_unit(background); width * height];
+
+    for primitive in primitives {
+        let Primitive::Mesh(mesh) = &primitive.primitive else {
+            continue;
+        };
+        let textured = mesh.texture_id == TextureId::default();
+        let clip = Rect::from_min_max(
+            primitive.clip_rect.min * scale,
+            primitive.clip_rect.max * scale,
+        )
+        .intersect(Rect::from_min_size(
+            Pos2::ZERO,
+            vec2(width as f32, height as f32),
+        ));
+        if !clip.is_positive() {
+            continue;
+        }
+
+        for triangle in mesh.indices.chunks_exact(3) {
+            let vertices = [0, 1, 2].map(|k| &mesh.vertices[triangle[k] as usize]);
+            let points = vertices.map(|vertex| vertex.pos * scale);
+            let area = edge_function(points[0], points[1], points[2]);
+            if area.abs() < 1e-6 {
+                continue;
+            }
+            let bounds = Rect::from_points(&points).intersect(clip);
+            if !bounds.is_positive() {
+                continue;
+            }
+
+            for y in bounds.min.y.floor() as usize..bounds.max.y.ceil() as usize {
+                for x in bounds.min.x.floor() as usize..bounds.max.x.ceil() as usize {
+                    let center = pos2(x as f32 + 0.5, y as f32 + 0.5);
+                    let weights = [
+                        edge_function(points[1], points[2], center) / area,
+                        edge_function(points[2], points[0], center) / area,
+                        edge_function(points[0], points[1], center) / area,
+                    ];
+                    if weights.iter().any(|weight| *weight < 0.0) {
+                        continue;
+                    }
+
+                    let mut color = [0.0; 4];
+                    let mut uv = Vec2::ZERO;
+                    for (vertex, weight) in vertices.iter().zip(weights) {
+                        let vertex_color = to_unit(vertex.color);
+                        for k in 0..4 {
+                            color[k] += vertex_color[k] * weight;
+                        }
+                        uv += vertex.uv.to_vec2() * weight;
+                    }
+                    if textured {
+                        let texel = to_unit(sample(font_atlas, uv));
+                        for k in 0..4 {
+                            color[k] *= texel[k];
+                        }
+                    }
+
+                    let pixel = &mut pixels[y * width + x];
+                    for k in 0..4 {
+                        pixel[k] = color[k] + pixel[k] * (1.0 - color[3]);
+                    }
+                }
+            }
+        }
+    }
+
+    ColorImage::new(
+        size,
+        pixels
+            .into_iter()
+            .map(|[r, g, b, a]| {
+                let byte = |value: f32| (value.clamp(0.0, 1.0) * 255.0).round() as u8;
+                Color32::from_rgba_premultiplied(byte(r), byte(g), byte(b), byte(a))
+            })
+            .collect(),
+    )
+}
+
+/// Twice the signed area of the triangle (a, b, c)
+fn edge_function(a: Pos2, b: Pos2, c: Pos2) -> f32 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Nearest texel at normalized texture coordinates
+fn sample(image: &ColorImage, uv: Vec2) -> Color32 {
+    let [width, height] = image.size;
+    let x = ((uv.x * width as f32) as usize).min(width.saturating_sub(1));
+    let y = ((uv.y * height as f32) as usize).min(height.saturating_sub(1));
+    image
+        .pixels
+        .get(y * width + x)
+        .copied()
+        .unwrap_or(Color32::WHITE)
+}
+
+/// Premultiplied color components between 0 and 1
+fn to_unit(color: Color32) -> [f32; 4] {
+    color.to_array().map(|component| component as f32 / 255.0)
+}
+
+fn svg_text(out: &mut String, at: Pos2, text: &str, color: Color32, attributes: &str) {
+    let _ = writeln!(
+        out,
+        "<text x=\"{:.1}\" y=\"{:.1}\" dominant-baseline=\"central\" fill=\"{}\"{}>{}</text>",
+        at.x,
+        at.y,
+        hex(color),
+        attributes,
+        xml_escape(text)
+    );
+}
+
+fn hex(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
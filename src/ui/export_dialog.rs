@@ -0,0 +1,197 @@
+use crate::database::export::export;
+use crate::database::{
+    DatabaseType, ExportFormat, ExportOptions, ExportSource, QuoteStyle, TextEncoding,
+};
+use egui::{ComboBox, TextEdit, Ui};
+
+/// Window for choosing an export format and its options, then the file to
+/// write the rows to
+#[derive(Default)]
+pub struct ExportDialog {
+    pub open: bool,
+    options: ExportOptions,
+    /// Outcome of the last export: the file written, or the error
+    status: Option<Result<String, String>>,
+}
+
+impl ExportDialog {
+    /// Open the dialog, with INSERT statements aimed at `table` in the
+    /// given dialect
+    pub fn show(&mut self, table: Option<String>, database_type: Option<DatabaseType>) {
+        self.open = true;
+        self.status = None;
+        if let Some(table) = table {
+            self.options.table_name = table;
+        }
+        if let Some(database_type) = database_type {
+            self.options.database_type = database_type;
+        }
+    }
+
+    pub fn render(&mut self, ctx: &egui::Context, source: &ExportSource) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        let mut save = false;
+        egui::Window::new("Export Results")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "{} rows and {} columns, as shown in the grid",
+                    source.rows.len(),
+                    source.columns.len()
+                ));
+                ui.separator();
+
+                egui::Grid::new("export_options")
+                    .num_columns(2)
+                    .spacing([12.0, 6.0])
+                    .show(ui, |ui| self.render_options(ui));
+
+                if let Some(status) = &self.status {
+                    ui.separator();
+                    match status {
+                        Ok(path) => ui.label(format!("✅ Exported to {}", path)),
+                        Err(error) => ui.colored_label(ui.visuals().error_fg_color, error),
+                    };
+                }
+
+                ui.separator();
+                ui.horizontal(|ui| {
+                    save = ui.button("Export...").clicked();
+                    if ui.button("Close").clicked() {
+                        self.open = false;
+                    }
+                });
+            });
+        self.open &= open;
+
+        if save {
+            self.save(source);
+        }
+    }
+
+    fn render_options(&mut self, ui: &mut Ui) {
+        let options = &mut self.options;
+
+        ui.label("Format:");
+        ComboBox::from_id_salt("export_format")
+            .selected_text(options.format.name())
+            .show_ui(ui, |ui| {
+                for format in ExportFormat::ALL {
+                    ui.selectable_value(&mut options.format, format, format.name());
+                }
+            });
+        ui.end_row();
+
+        match options.format {
+            ExportFormat::Csv | ExportFormat::Tsv => {
+                if options.format == ExportFormat::Csv {
+                    ui.label("Delimiter:");
+                    let mut delimiter = options.delimiter.to_string();
+                    if ui
+                        .add(TextEdit::singleline(&mut delimiter).desired_width(30.0))
+                        .changed()
+                    {
+                        if let Some(c) = delimiter.chars().last() {
+                            options.delimiter = c;
+                        }
+                    }
+                    ui.end_row();
+                }
+
+                ui.label("Quoting:");
+                ComboBox::from_id_salt("export_quoting")
+                    .selected_text(options.quoting.name())
+                    .show_ui(ui, |ui| {
+                        for quoting in QuoteStyle::ALL {
+                            ui.selectable_value(&mut options.quoting, quoting, quoting.name());
+                        }
+                    });
+                ui.end_row();
+
+                ui.label("Header:");
+                ui.checkbox(
+                    &mut options.include_header,
+                    "Column names in the first line",
+                );
+                ui.end_row();
+            }
+            ExportFormat::Html => {
+                ui.label("Header:");
+                ui.checkbox(&mut options.include_header, "Column names in the first row");
+                ui.end_row();
+            }
+            ExportFormat::SqlInsert => {
+                ui.label("Table:");
+                ui.add(TextEdit::singleline(&mut options.table_name).desired_width(200.0));
+                ui.end_row();
+
+                ui.label("Dialect:");
+                ComboBox::from_id_salt("export_dialect")
+                    .selected_text(format!("{:?}", options.database_type))
+                    .show_ui(ui, |ui| {
+                        for (database_type, name) in [
+                            (DatabaseType::PostgreSQL, "PostgreSQL"),
+                            (DatabaseType::MySQL, "MySQL"),
+                            (DatabaseType::SQLite, "SQLite"),
+                        ] {
+                            ui.selectable_value(&mut options.database_type, database_type, name);
+                        }
+                    });
+                ui.end_row();
+            }
+            ExportFormat::Json | ExportFormat::Ndjson | ExportFormat::Markdown => {}
+        }
+
+        if options.format.uses_null_text() {
+            ui.label("NULL as:");
+            ui.add(
+                TextEdit::singleline(&mut options.null_text)
+                    .hint_text("(empty)")
+                    .desired_width(100.0),
+            );
+            ui.end_row();
+        }
+
+        ui.label("Encoding:");
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("export_encoding")
+                .selected_text(options.encoding.name())
+                .show_ui(ui, |ui| {
+                    for encoding in TextEncoding::ALL {
+                        ui.selectable_value(&mut options.encoding, encoding, encoding.name());
+                    }
+                });
+            ui.checkbox(&mut options.bom, "Byte order mark");
+        });
+        ui.end_row();
+    }
+
+    /// Ask for a file and write the export to it
+    fn save(&mut self, source: &ExportSource) {
+        let format = self.options.format;
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Export Results")
+            .set_file_name(format!("result.{}", format.extension()))
+            .add_filter(format.name(), &[format.extension()])
+            .save_file()
+        else {
+            return;
+        };
+
+        let bytes = export(source, &self.options);
+        self.status = Some(match std::fs::write(&path, bytes) {
+            Ok(()) => {
+                log::info!("Exported {} rows to {}", source.rows.len(), path.display());
+                Ok(path.display().to_string())
+            }
+            Err(e) => Err(format!("Failed to write {}: {}", path.display(), e)),
+        });
+    }
+}
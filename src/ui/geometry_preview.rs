@@ -0,0 +1,292 @@
+use crate::database::{BoundingBox, Coordinate, Geometry};
+use egui::{Color32, Painter, Pos2, Sense, Shape, Stroke, Ui, Vec2};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Pixels kept free around the fitted shapes
+const MARGIN: f32 = 12.0;
+/// How close the pointer must be to a point or line to hover it
+const HIT_DISTANCE: f32 = 5.0;
+
+/// A geometry to draw, with the result row it belongs to
+pub struct PreviewShape {
+    pub row: usize,
+    pub geometry: Arc<Geometry>,
+    pub bbox: BoundingBox,
+}
+
+/// Side panel drawing the geometries of the selected row, or of every row
+/// on the page, on a plain canvas
+pub struct GeometryPreview {
+    pub open: bool,
+    /// Draw the geometries of every row on the page, not just the selected one
+    pub all_rows: bool,
+    /// Row of the shape under the pointer
+    pub hovered_row: Option<usize>,
+    /// Pan in points and zoom factor applied on top of the fitted view
+    pan: Vec2,
+    zoom: f32,
+}
+
+impl Default for GeometryPreview {
+    fn default() -> Self {
+        Self {
+            open: false,
+            all_rows: false,
+            hovered_row: None,
+            pan: Vec2::ZERO,
+            zoom: 1.0,
+        }
+    }
+}
+
+impl GeometryPreview {
+    /// Go back to the view that fits all shapes
+    pub fn reset_view(&mut self) {
+        self.pan = Vec2::ZERO;
+        self.zoom = 1.0;
+    }
+
+    /// Draw the shapes; returns the row of a shape that was clicked
+    ///
+    /// Shapes of `highlighted_row` are drawn in the highlight color.
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        shapes: &[PreviewShape],
+        highlighted_row: Option<usize>,
+    ) -> Option<usize> {
+        ui.horizontal(|ui| {
+            ui.strong("Geometry");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.open = false;
+                }
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.all_rows, "All rows on page");
+            if ui.button("Fit").clicked() {
+                self.reset_view();
+            }
+            if ui
+                .add_enabled(!shapes.is_empty(), egui::Button::new("Copy GeoJSON"))
+                .clicked()
+            {
+                ui.ctx().copy_text(feature_collection(shapes));
+            }
+        });
+        ui.separator();
+
+        self.hovered_row = None;
+        let Some(bbox) = shapes.iter().map(|shape| shape.bbox).reduce(union) else {
+            ui.label(if self.all_rows {
+                "No geometries on this page"
+            } else {
+                "Select a row with a geometry to preview it"
+            });
+            return None;
+        };
+
+        let (response, painter) = ui.allocate_painter(ui.available_size(), Sense::click_and_drag());
+        let rect = response.rect;
+        painter.rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+
+        if response.dragged() {
+            self.pan += response.drag_delta();
+        }
+        if response.double_clicked() {
+            self.reset_view();
+        }
+        if let Some(pointer) = response.hover_pos() {
+            let scroll = ui.input(|input| input.smooth_scroll_delta.y);
+            if scroll != 0.0 {
+                // Zoom around the pointer, keeping the spot under it in place
+                let factor = (scroll * 0.002).exp();
+                let offset = pointer - rect.center();
+                self.pan = offset - (offset - self.pan) * factor;
+                self.zoom *= factor;
+            }
+        }
+
+        let view = View::fit(bbox, rect.shrink(MARGIN), self.pan, self.zoom);
+        let painter = painter.with_clip_rect(rect);
+
+        if let Some(pointer) = response.hover_pos() {
+            self.hovered_row = shapes
+                .iter()
+                .rev()
+                .find(|shape| hits(&shape.geometry, &view, pointer))
+                .map(|shape| shape.row);
+        }
+
+        let visuals = ui.visuals();
+        let normal = visuals.hyperlink_color;
+        let highlight = visuals.warn_fg_color;
+        let is_highlighted =
+            |row: usize| Some(row) == self.hovered_row || Some(row) == highlighted_row;
+
+        // Highlighted shapes go last so they are drawn on top
+        let (highlighted, others): (Vec<_>, Vec<_>) =
+            shapes.iter().partition(|shape| is_highlighted(shape.row));
+        for shape in others {
+            draw(&painter, &shape.geometry, &view, normal);
+        }
+        for shape in highlighted {
+            draw(&painter, &shape.geometry, &view, highlight);
+        }
+
+        if let Some(row) = self.hovered_row {
+            response
+                .clone()
+                .on_hover_text_at_pointer(format!("Row {}", row + 1));
+        }
+        if response.clicked() {
+            return self.hovered_row;
+        }
+        None
+    }
+}
+
+/// Mapping from geometry coordinates to screen positions
+struct View {
+    center_x: f64,
+    center_y: f64,
+    scale: f64,
+    origin: Pos2,
+}
+
+impl View {
+    /// Fit the bounding box into the rectangle, then apply pan and zoom
+    fn fit(bbox: BoundingBox, rect: egui::Rect, pan: Vec2, zoom: f32) -> Self {
+        let width = bbox.max_x - bbox.min_x;
+        let height = bbox.max_y - bbox.min_y;
+        let scale_x = rect.width() as f64 / width;
+        let scale_y = rect.height() as f64 / height;
+        // A single point, or shapes along one axis, leave a dimension zero wide
+        let scale = match (width > 0.0, height > 0.0) {
+            (true, true) => scale_x.min(scale_y),
+            (true, false) => scale_x,
+            (false, true) => scale_y,
+            (false, false) => 1.0,
+        };
+
+        Self {
+            center_x: (bbox.min_x + bbox.max_x) / 2.0,
+            center_y: (bbox.min_y + bbox.max_y) / 2.0,
+            scale: scale * zoom as f64,
+            origin: rect.center() + pan,
+        }
+    }
+
+    fn to_screen(&self, coordinate: &Coordinate) -> Pos2 {
+        // Screen y grows downwards
+        Pos2::new(
+            self.origin.x + ((coordinate.x - self.center_x) * self.scale) as f32,
+            self.origin.y - ((coordinate.y - self.center_y) * self.scale) as f32,
+        )
+    }
+
+    fn points(&self, coordinates: &[Coordinate]) -> Vec<Pos2> {
+        coordinates.iter().map(|c| self.to_screen(c)).collect()
+    }
+}
+
+fn union(a: BoundingBox, b: BoundingBox) -> BoundingBox {
+    BoundingBox {
+        min_x: a.min_x.min(b.min_x),
+        min_y: a.min_y.min(b.min_y),
+        max_x: a.max_x.max(b.max_x),
+        max_y: a.max_y.max(b.max_y),
+    }
+}
+
+fn draw(painter: &Painter, geometry: &Geometry, view: &View, color: Color32) {
+    let stroke = Stroke::new(1.5, color);
+    match geometry {
+        Geometry::Point(coordinate) => {
+            if let Some(coordinate) = coordinate {
+                painter.circle_filled(view.to_screen(coordinate), 3.5, color);
+            }
+        }
+        Geometry::LineString(points) => {
+            painter.line(view.points(points), stroke);
+        }
+        Geometry::Polygon(rings) => {
+            for ring in rings {
+                painter.add(Shape::closed_line(view.points(ring), stroke));
+            }
+        }
+        Geometry::MultiPoint(parts)
+        | Geometry::MultiLineString(parts)
+        | Geometry::MultiPolygon(parts)
+        | Geometry::Collection(parts) => {
+            for part in parts {
+                draw(painter, part, view, color);
+            }
+        }
+    }
+}
+
+/// Whether the pointer is on a point or line, or inside a polygon
+fn hits(geometry: &Geometry, view: &View, pointer: Pos2) -> bool {
+    match geometry {
+        Geometry::Point(coordinate) => coordinate
+            .as_ref()
+            .is_some_and(|c| view.to_screen(c).distance(pointer) <= HIT_DISTANCE),
+        Geometry::LineString(points) => near_line(&view.points(points), pointer),
+        Geometry::Polygon(rings) => {
+            let rings: Vec<Vec<Pos2>> = rings.iter().map(|ring| view.points(ring)).collect();
+            // Even-odd rule, so points in holes fall outside
+            let inside = rings.iter().filter(|ring| encloses(ring, pointer)).count() % 2 == 1;
+            inside || rings.iter().any(|ring| near_line(ring, pointer))
+        }
+        Geometry::MultiPoint(parts)
+        | Geometry::MultiLineString(parts)
+        | Geometry::MultiPolygon(parts)
+        | Geometry::Collection(parts) => parts.iter().any(|part| hits(part, view, pointer)),
+    }
+}
+
+fn near_line(points: &[Pos2], pointer: Pos2) -> bool {
+    points.windows(2).any(|segment| {
+        let (a, b) = (segment[0], segment[1]);
+        let along = b - a;
+        let t = if along.length_sq() > 0.0 {
+            ((pointer - a).dot(along) / along.length_sq()).clamp(0.0, 1.0)
+        } else {
+            0.0
+        };
+        (a + along * t).distance(pointer) <= HIT_DISTANCE
+    })
+}
+
+/// Ray casting test for a closed ring
+fn encloses(ring: &[Pos2], pointer: Pos2) -> bool {
+    let mut inside = false;
+    for (i, a) in ring.iter().enumerate() {
+        let b = ring[(i + 1) % ring.len()];
+        if (a.y > pointer.y) != (b.y > pointer.y)
+            && pointer.x < a.x + (pointer.y - a.y) / (b.y - a.y) * (b.x - a.x)
+        {
+            inside = !inside;
+        }
+    }
+    inside
+}
+
+/// GeoJSON FeatureCollection of the shapes, numbering rows from 1
+fn feature_collection(shapes: &[PreviewShape]) -> String {
+    let features: Vec<serde_json::Value> = shapes
+        .iter()
+        .map(|shape| {
+            json!({
+                "type": "Feature",
+                "geometry": shape.geometry.to_geojson(),
+                "properties": { "row": shape.row + 1 },
+            })
+        })
+        .collect();
+    let collection = json!({ "type": "FeatureCollection", "features": features });
+    serde_json::to_string_pretty(&collection).unwrap_or_default()
+}
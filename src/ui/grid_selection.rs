@@ -0,0 +1,80 @@
+use std::ops::RangeInclusive;
+
+/// A rectangle of cells selected in the result grid
+///
+/// Rows are positions in the grid's display order, so a selection made on
+/// sorted or filtered rows covers the rows as they are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GridSelection {
+    /// Cell the selection started from, as (row position, column)
+    anchor: (usize, usize),
+    /// Cell the selection extends to
+    focus: (usize, usize),
+}
+
+impl GridSelection {
+    pub fn cell(row: usize, column: usize) -> Self {
+        Self {
+            anchor: (row, column),
+            focus: (row, column),
+        }
+    }
+
+    /// Every column of a row
+    pub fn row(row: usize, column_count: usize) -> Self {
+        Self {
+            anchor: (row, 0),
+            focus: (row, column_count.saturating_sub(1)),
+        }
+    }
+
+    /// Every row of a column
+    pub fn column(column: usize, row_count: usize) -> Self {
+        Self {
+            anchor: (0, column),
+            focus: (row_count.saturating_sub(1), column),
+        }
+    }
+
+    /// Every cell
+    pub fn all(row_count: usize, column_count: usize) -> Self {
+        Self {
+            anchor: (0, 0),
+            focus: (row_count.saturating_sub(1), column_count.saturating_sub(1)),
+        }
+    }
+
+    /// Move the far corner to a cell, keeping the one the selection started from
+    pub fn extend_to(&mut self, row: usize, column: usize) {
+        self.focus = (row, column);
+    }
+
+    /// Extend a selection of whole rows to another row
+    pub fn extend_rows_to(&mut self, row: usize, column_count: usize) {
+        self.anchor.1 = 0;
+        self.focus = (row, column_count.saturating_sub(1));
+    }
+
+    pub fn rows(&self) -> RangeInclusive<usize> {
+        self.anchor.0.min(self.focus.0)..=self.anchor.0.max(self.focus.0)
+    }
+
+    pub fn columns(&self) -> RangeInclusive<usize> {
+        self.anchor.1.min(self.focus.1)..=self.anchor.1.max(self.focus.1)
+    }
+
+    pub fn contains(&self, row: usize, column: usize) -> bool {
+        self.rows().contains(&row) && self.columns().contains(&column)
+    }
+
+    /// Whether the selection spans every column of a row
+    pub fn contains_row(&self, row: usize, column_count: usize) -> bool {
+        self.rows().contains(&row)
+            && *self.columns().start() == 0
+            && *self.columns().end() + 1 >= column_count
+    }
+
+    pub fn cell_count(&self) -> usize {
+        self.rows().count() * self.columns().count()
+    }
+}
@@ -2,16 +2,37 @@ pub mod components;
 pub mod confirmation_dialog;
 pub mod connection_dialog;
 pub mod database_tree;
+pub mod ddl_dialog;
+pub mod dependencies_dialog;
+pub mod er_diagram;
+pub mod export_dialog;
 pub mod fonts;
+pub mod geometry_preview;
+pub mod grid_selection;
+pub mod pending_changes;
 pub mod query_editor;
 pub mod result_table;
+pub mod schema_compare;
+pub mod script_results;
+pub mod task_panel;
 pub mod theme;
+pub mod value_inspector;
 
 // Re-export main UI components
 pub use confirmation_dialog::ConfirmationDialog;
 pub use connection_dialog::{ConnectionDialog, DialogAction};
 pub use database_tree::{ConnectionAction, DatabaseTree, TreeItem};
+pub use ddl_dialog::DdlDialog;
+pub use dependencies_dialog::DependenciesDialog;
+pub use er_diagram::ErDiagramView;
+pub use export_dialog::ExportDialog;
 pub use fonts::{setup_chinese_fonts, test_chinese_rendering, FontError};
+pub use geometry_preview::GeometryPreview;
+pub use grid_selection::GridSelection;
 pub use query_editor::QueryEditor;
-pub use result_table::ResultTable;
+pub use result_table::{ResultTable, RowFetch};
+pub use schema_compare::{CompareRequest, SchemaCompareDialog};
+pub use script_results::ScriptResults;
+pub use task_panel::TaskPanel;
 pub use theme::setup_light_theme;
+pub use value_inspector::ValueInspector;
@@ -0,0 +1,165 @@
+use crate::database::{DmlStatement, EditTarget, QueryResult, RowChange};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// A row of the grid: one of the result's rows or a row added for insertion
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditRow {
+    Existing(usize),
+    New(usize),
+}
+
+/// Changes made in the result grid that have not been saved yet
+///
+/// Cell values are kept as the text the user typed, or `None` for NULL, and
+/// converted to the column's type by the server when saved.
+#[derive(Debug, Default)]
+pub struct PendingChanges {
+    /// New cell values of existing rows by row and column
+    pub edits: BTreeMap<(usize, usize), Option<String>>,
+    /// Existing rows to delete
+    pub deleted: BTreeSet<usize>,
+    /// Rows to insert; columns left out take their default
+    pub inserted: Vec<BTreeMap<usize, Option<String>>>,
+}
+
+impl PendingChanges {
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty() && self.deleted.is_empty() && self.inserted.is_empty()
+    }
+
+    /// Number of rows that will be written
+    pub fn row_count(&self) -> usize {
+        let edited_rows: BTreeSet<usize> = self
+            .edits
+            .keys()
+            .map(|(row, _)| *row)
+            .filter(|row| !self.deleted.contains(row))
+            .collect();
+        edited_rows.len() + self.deleted.len() + self.inserted.len()
+    }
+
+    pub fn clear(&mut self) {
+        self.edits.clear();
+        self.deleted.clear();
+        self.inserted.clear();
+    }
+
+    /// The pending value of a cell; `None` when the cell is unchanged, or a
+    /// new row's cell is left to its default
+    pub fn value(&self, row: EditRow, column: usize) -> Option<&Option<String>> {
+        match row {
+            EditRow::Existing(row) => self.edits.get(&(row, column)),
+            EditRow::New(index) => self.inserted.get(index)?.get(&column),
+        }
+    }
+
+    pub fn set_value(&mut self, row: EditRow, column: usize, value: Option<String>) {
+        match row {
+            EditRow::Existing(row) => {
+                self.edits.insert((row, column), value);
+            }
+            EditRow::New(index) => {
+                if let Some(values) = self.inserted.get_mut(index) {
+                    values.insert(column, value);
+                }
+            }
+        }
+    }
+
+    /// Undo the change to one cell
+    pub fn revert_value(&mut self, row: EditRow, column: usize) {
+        match row {
+            EditRow::Existing(row) => {
+                self.edits.remove(&(row, column));
+            }
+            EditRow::New(index) => {
+                if let Some(values) = self.inserted.get_mut(index) {
+                    values.remove(&column);
+                }
+            }
+        }
+    }
+
+    /// Add an empty row for insertion and return it
+    pub fn insert_row(&mut self) -> EditRow {
+        self.inserted.push(BTreeMap::new());
+        EditRow::New(self.inserted.len() - 1)
+    }
+
+    /// Mark a row for deletion; rows added for insertion are dropped instead
+    pub fn delete_row(&mut self, row: EditRow) {
+        match row {
+            EditRow::Existing(row) => {
+                self.deleted.insert(row);
+            }
+            EditRow::New(index) => {
+                if index < self.inserted.len() {
+                    self.inserted.remove(index);
+                }
+            }
+        }
+    }
+
+    pub fn undelete_row(&mut self, row: usize) {
+        self.deleted.remove(&row);
+    }
+
+    /// Follow the result's rows to their new positions after sorting;
+    /// `order[new_position]` is the old position of each row
+    pub fn reorder_rows(&mut self, order: &[usize]) {
+        let mut new_positions = vec![0; order.len()];
+        for (new, old) in order.iter().enumerate() {
+            new_positions[*old] = new;
+        }
+        let moved = |row: usize| new_positions.get(row).copied().unwrap_or(row);
+
+        self.edits = std::mem::take(&mut self.edits)
+            .into_iter()
+            .map(|((row, column), value)| ((moved(row), column), value))
+            .collect();
+        self.deleted = std::mem::take(&mut self.deleted)
+            .into_iter()
+            .map(moved)
+            .collect();
+    }
+
+    /// Statements writing the changes: updates, then deletes, then inserts
+    pub fn statements(&self, target: &EditTarget, result: &QueryResult) -> Vec<DmlStatement> {
+        let mut changes = Vec::new();
+
+        let mut updates: BTreeMap<usize, Vec<(usize, Option<String>)>> = BTreeMap::new();
+        for ((row, column), value) in &self.edits {
+            if !self.deleted.contains(row) {
+                updates
+                    .entry(*row)
+                    .or_default()
+                    .push((*column, value.clone()));
+            }
+        }
+        for (row, values) in updates {
+            if let Some(key) = target.row_key(result, row) {
+                changes.push(RowChange::Update { key, values });
+            }
+        }
+
+        for row in &self.deleted {
+            if let Some(key) = target.row_key(result, *row) {
+                changes.push(RowChange::Delete { key });
+            }
+        }
+
+        for values in &self.inserted {
+            changes.push(RowChange::Insert {
+                values: values
+                    .iter()
+                    .map(|(column, value)| (*column, value.clone()))
+                    .collect(),
+            });
+        }
+
+        changes
+            .iter()
+            .map(|change| target.statement(change))
+            .collect()
+    }
+}
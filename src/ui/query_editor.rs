@@ -1,14 +1,36 @@
+use crate::database::{statement_at, DriverCapabilities, ExplainFormat, ScriptStatement};
 use crate::ui::theme::get_sql_syntax_colors;
+use egui::text::{CCursorRange, LayoutJob, TextFormat};
 use egui::{Color32, ScrollArea, TextEdit, Ui};
+use std::ops::Range;
+use std::time::{Duration, Instant};
+
+/// How long an executed range stays highlighted
+const HIGHLIGHT_DURATION: Duration = Duration::from_millis(800);
 
 /// SQL Query Editor component
 #[derive(Default)]
 pub struct QueryEditor {
     sql_text: String,
     is_executing: bool,
+    is_cancelling: bool,
     last_execution_time: Option<std::time::Duration>,
     cursor_position: Option<egui::text::CCursor>,
+    /// Selected text as character positions, when anything is selected
+    selection: Option<CCursorRange>,
+    /// Byte range of the text that was last executed, and when
+    highlight: Option<(Range<usize>, Instant)>,
     execute_requested: bool,
+    execute_statement_requested: bool,
+    run_script_requested: bool,
+    cancel_requested: bool,
+    explain_requested: Option<ExplainFormat>,
+    /// The running statement's driver can cancel it
+    can_cancel: bool,
+    /// EXPLAIN formats of the active connection's driver
+    explain_formats: Vec<ExplainFormat>,
+    /// Keep running a script after a statement fails
+    continue_on_error: bool,
 }
 
 impl QueryEditor {
@@ -18,9 +40,19 @@ impl QueryEditor {
                 "-- Enter your SQL query here\nSELECT * FROM information_schema.tables LIMIT 10;"
                     .to_string(),
             is_executing: false,
+            is_cancelling: false,
             last_execution_time: None,
             cursor_position: None,
+            selection: None,
+            highlight: None,
             execute_requested: false,
+            execute_statement_requested: false,
+            run_script_requested: false,
+            cancel_requested: false,
+            explain_requested: None,
+            can_cancel: false,
+            explain_formats: Vec::new(),
+            continue_on_error: false,
         }
     }
 
@@ -28,21 +60,70 @@ impl QueryEditor {
         ui.vertical(|ui| {
             // Toolbar
             ui.horizontal(|ui| {
-                let execute_clicked = ui.button("Execute (F5)").clicked();
+                let execute_clicked = ui
+                    .button("Execute (F5)")
+                    .on_hover_text("Run the selection, or the whole editor")
+                    .clicked();
+
+                if ui
+                    .button("Run Statement (Ctrl+Enter)")
+                    .on_hover_text("Run the statement under the cursor")
+                    .clicked()
+                {
+                    self.execute_statement_requested = true;
+                }
+
+                if ui
+                    .button("Run Script (Alt+X)")
+                    .on_hover_text("Run each statement separately, one result tab per statement")
+                    .clicked()
+                {
+                    self.run_script_requested = true;
+                }
+
+                if !self.explain_formats.is_empty() {
+                    ui.menu_button("Explain", |ui| {
+                        for format in &self.explain_formats {
+                            if ui.button(format.label()).clicked() {
+                                self.explain_requested = Some(*format);
+                                ui.close_kind(egui::UiKind::Menu);
+                            }
+                        }
+                    })
+                    .response
+                    .on_hover_text(
+                        "Show the plan of the selection or the statement under the cursor",
+                    );
+                }
+
+                if self.is_executing
+                    && self.can_cancel
+                    && ui
+                        .add_enabled(!self.is_cancelling, egui::Button::new("Cancel (Esc)"))
+                        .clicked()
+                {
+                    self.cancel_requested = true;
+                }
 
                 if ui.button("Clear").clicked() {
-                    self.sql_text.clear();
+                    self.clear();
                 }
 
                 if ui.button("Format").clicked() {
                     // TODO: Implement SQL formatting
                 }
 
+                ui.checkbox(&mut self.continue_on_error, "Continue on error");
+
                 ui.separator();
 
                 if self.is_executing {
                     ui.spinner();
-                    ui.label("Executing...");
+                    if self.is_cancelling {
+                        ui.label("Cancelling...");
+                    } else {
+                        ui.label("Executing...");
+                    }
                 } else if let Some(duration) = self.last_execution_time {
                     ui.label(format!("Last execution: {:.2}ms", duration.as_millis()));
                 }
@@ -61,18 +142,65 @@ impl QueryEditor {
 
             ui.separator();
 
+            // Let the highlight of the last executed text fade out
+            let highlight = match &self.highlight {
+                Some((range, at)) if at.elapsed() < HIGHLIGHT_DURATION => {
+                    ui.ctx()
+                        .request_repaint_after(HIGHLIGHT_DURATION - at.elapsed());
+                    Some(range.clone())
+                }
+                _ => None,
+            };
+            let highlight_color = ui.visuals().selection.bg_fill.linear_multiply(0.5);
+            let mut layouter = |ui: &Ui, text: &dyn egui::TextBuffer, wrap_width: f32| {
+                let text = text.as_str();
+                let font_id = egui::TextStyle::Monospace.resolve(ui.style());
+                let color = ui
+                    .visuals()
+                    .override_text_color
+                    .unwrap_or_else(|| ui.visuals().widgets.inactive.text_color());
+                let plain = TextFormat::simple(font_id, color);
+
+                let mut job = LayoutJob::default();
+                match highlight
+                    .clone()
+                    .filter(|range| text.get(range.clone()).is_some())
+                {
+                    Some(range) => {
+                        job.append(&text[..range.start], 0.0, plain.clone());
+                        job.append(
+                            &text[range.clone()],
+                            0.0,
+                            TextFormat {
+                                background: highlight_color,
+                                ..plain.clone()
+                            },
+                        );
+                        job.append(&text[range.end..], 0.0, plain);
+                    }
+                    None => job.append(text, 0.0, plain),
+                }
+                job.wrap.max_width = wrap_width;
+                ui.fonts(|fonts| fonts.layout_job(job))
+            };
+
             // SQL Editor
             ScrollArea::vertical()
                 .auto_shrink([false, false])
                 .id_source("query_editor_scroll")
                 .show(ui, |ui| {
-                    let response = ui.add(
-                        TextEdit::multiline(&mut self.sql_text)
-                            .font(egui::TextStyle::Monospace)
-                            .code_editor()
-                            .desired_width(f32::INFINITY)
-                            .desired_rows(15),
-                    );
+                    let output = TextEdit::multiline(&mut self.sql_text)
+                        .font(egui::TextStyle::Monospace)
+                        .code_editor()
+                        .desired_width(f32::INFINITY)
+                        .desired_rows(15)
+                        .layouter(&mut layouter)
+                        .show(ui);
+                    let response = output.response;
+
+                    if response.changed() {
+                        self.highlight = None;
+                    }
 
                     // Handle keyboard shortcuts
                     if response.has_focus() {
@@ -94,29 +222,34 @@ impl QueryEditor {
                         });
                     }
 
-                    // Store cursor position for potential syntax highlighting
-                    // Note: cursor_range is not available in egui 0.28
-                    // if let Some(cursor) = response.cursor_range {
-                    //     self.cursor_position = Some(cursor.primary);
-                    // }
+                    // The cursor range is only reported while the editor has focus,
+                    // so the last one is kept for the toolbar buttons
+                    if let Some(cursor_range) = output.cursor_range {
+                        self.cursor_position = Some(cursor_range.primary);
+                        self.selection = (cursor_range.primary != cursor_range.secondary)
+                            .then_some(cursor_range);
+                    }
                 });
 
             // Status bar
             ui.separator();
             ui.horizontal(|ui| {
-                ui.label("Ready");
+                if self.is_cancelling {
+                    ui.label("Cancelling");
+                } else if self.is_executing {
+                    ui.label("Executing");
+                } else {
+                    ui.label("Ready");
+                }
 
                 ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                     if let Some(pos) = self.cursor_position {
-                        let line = self.sql_text[..pos.index.min(self.sql_text.len())]
-                            .chars()
-                            .filter(|&c| c == '\n')
-                            .count()
-                            + 1;
-                        let col = self.sql_text[..pos.index.min(self.sql_text.len())]
-                            .lines()
-                            .last()
-                            .map(|l| l.len())
+                        let before = &self.sql_text[..byte_offset(&self.sql_text, pos.index)];
+                        let line = before.chars().filter(|&c| c == '\n').count() + 1;
+                        let col = before
+                            .rsplit('\n')
+                            .next()
+                            .map(|l| l.chars().count())
                             .unwrap_or(0)
                             + 1;
                         ui.label(format!("Line {}, Column {}", line, col));
@@ -132,10 +265,15 @@ impl QueryEditor {
 
     pub fn set_sql(&mut self, sql: String) {
         self.sql_text = sql;
+        self.selection = None;
+        self.highlight = None;
     }
 
     pub fn clear(&mut self) {
         self.sql_text.clear();
+        self.cursor_position = None;
+        self.selection = None;
+        self.highlight = None;
     }
 
     pub fn is_executing(&self) -> bool {
@@ -144,11 +282,17 @@ impl QueryEditor {
 
     pub fn set_executing(&mut self, executing: bool) {
         self.is_executing = executing;
+        self.is_cancelling = false;
+    }
+
+    pub fn set_cancelling(&mut self, cancelling: bool) {
+        self.is_cancelling = cancelling;
     }
 
     pub fn set_execution_time(&mut self, duration: std::time::Duration) {
         self.last_execution_time = Some(duration);
         self.is_executing = false;
+        self.is_cancelling = false;
     }
 
     pub fn is_execute_requested(&self) -> bool {
@@ -159,9 +303,75 @@ impl QueryEditor {
         self.execute_requested = false;
     }
 
+    pub fn is_execute_statement_requested(&self) -> bool {
+        self.execute_statement_requested
+    }
+
+    pub fn clear_execute_statement_request(&mut self) {
+        self.execute_statement_requested = false;
+    }
+
+    pub fn is_run_script_requested(&self) -> bool {
+        self.run_script_requested
+    }
+
+    pub fn clear_run_script_request(&mut self) {
+        self.run_script_requested = false;
+    }
+
+    pub fn continue_on_error(&self) -> bool {
+        self.continue_on_error
+    }
+
+    pub fn is_cancel_requested(&self) -> bool {
+        self.cancel_requested
+    }
+
+    pub fn clear_cancel_request(&mut self) {
+        self.cancel_requested = false;
+    }
+
+    /// EXPLAIN format picked from the toolbar, if any
+    pub fn take_explain_request(&mut self) -> Option<ExplainFormat> {
+        self.explain_requested.take()
+    }
+
+    /// Offer only what the drivers involved support: cancelling the running
+    /// statement and the active connection's EXPLAIN formats
+    pub fn set_capabilities(
+        &mut self,
+        running: Option<&DriverCapabilities>,
+        active: Option<&DriverCapabilities>,
+    ) {
+        self.can_cancel = running.is_some_and(|capabilities| capabilities.supports_cancellation);
+        self.explain_formats = active
+            .map(|capabilities| capabilities.explain_formats.clone())
+            .unwrap_or_default();
+    }
+
     pub fn get_selected_text(&self) -> Option<String> {
-        // TODO: Implement getting selected text
-        None
+        self.selection_range()
+            .map(|range| self.sql_text[range].to_string())
+    }
+
+    /// Byte range of the selected text, when anything is selected
+    pub fn selection_range(&self) -> Option<Range<usize>> {
+        let selection = self.selection?;
+        let [start, end] = [selection.primary.index, selection.secondary.index]
+            .map(|index| byte_offset(&self.sql_text, index));
+        let range = start.min(end)..start.max(end);
+        (!range.is_empty()).then_some(range)
+    }
+
+    /// Statement containing the text cursor
+    pub fn statement_at_cursor(&self) -> Option<ScriptStatement> {
+        let cursor = byte_offset(&self.sql_text, self.cursor_position?.index);
+        statement_at(&self.sql_text, cursor)
+    }
+
+    /// Briefly highlight a byte range of the text, e.g. the one being executed
+    pub fn highlight_range(&mut self, range: Range<usize>) {
+        self.highlight = Some((range, Instant::now()));
     }
 
     pub fn insert_text(&mut self, text: &str) {
@@ -263,3 +473,10 @@ impl QueryEditor {
         ]
     }
 }
+
+/// Byte offset of a character position, clamped to the end of the text
+fn byte_offset(text: &str, char_index: usize) -> usize {
+    text.char_indices()
+        .nth(char_index)
+        .map_or(text.len(), |(offset, _)| offset)
+}
@@ -1,16 +1,83 @@
-use crate::database::{QueryResult, QueryValue};
-use crate::ui::theme::get_table_colors;
-use egui::{ScrollArea, Sense, Ui};
+use crate::database::result_filter::{
+    filter_rows, lookup_query, matches_search, rewrite_query, sort_order,
+};
+use crate::database::{
+    Column, ColumnFilter, CopyFormat, DatabaseType, DmlStatement, EditTarget, ExportOptions,
+    ExportSource, FilterCondition, ForeignKey, NullsOrder, QueryColumn, QueryResult, QueryRow,
+    QueryValue, SortDirection, SortKey,
+};
+use crate::ui::geometry_preview::PreviewShape;
+use crate::ui::pending_changes::{EditRow, PendingChanges};
+use crate::ui::theme::{get_table_colors, TableColors};
+use crate::ui::{ExportDialog, GeometryPreview, GridSelection, ValueInspector};
+use egui::{RichText, ScrollArea, Sense, Ui};
+
+/// Rows the user asked to fetch beyond those already shown
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RowFetch {
+    /// One more page of rows
+    NextPage,
+    /// Every remaining row, up to the display limit
+    All,
+}
+
+/// Text of the cell being edited
+struct CellEditor {
+    row: EditRow,
+    column: usize,
+    text: String,
+    /// Focus the text field when it is first shown
+    focus: bool,
+}
 
 /// Result table for displaying query results
 pub struct ResultTable {
     result: Option<QueryResult>,
     selected_row: Option<usize>,
     selected_column: Option<usize>,
+    /// Cells selected for copying, of which the selected cell is one
+    selection: Option<GridSelection>,
+    /// Whether copied cells start with their column names
+    copy_header: bool,
+    /// Outcome of the last copy
+    copy_message: Option<String>,
     show_row_numbers: bool,
     max_cell_width: f32,
     page_size: usize,
     current_page: usize,
+    has_more_rows: bool,
+    row_limit_reached: bool,
+    is_fetching: bool,
+    fetch_requested: Option<RowFetch>,
+    inspector: ValueInspector,
+    preview: GeometryPreview,
+    export: ExportDialog,
+    /// Server the result came from, whose dialect exported SQL uses
+    database_type: Option<DatabaseType>,
+    /// Row under the pointer in the grid, highlighted in the geometry preview
+    hovered_row: Option<usize>,
+    /// Columns the rows are sorted by, most significant first
+    sort: Vec<SortKey>,
+    /// Text typed into each column's filter box
+    filters: Vec<String>,
+    /// Text to highlight in the cells
+    search: String,
+    /// Positions of the rows passing the filters, in display order
+    visible_rows: Vec<usize>,
+    /// Whether the user asked for the filters and sort to run on the server
+    server_filter_requested: bool,
+    /// Table the rows can be edited in, once its primary key is known
+    edit_target: Option<EditTarget>,
+    changes: PendingChanges,
+    editing: Option<CellEditor>,
+    /// Statements shown for confirmation before saving
+    save_preview: Option<Vec<DmlStatement>>,
+    save_requested: Option<Vec<DmlStatement>>,
+    is_saving: bool,
+    /// Foreign keys of the result's source table and those referencing it
+    foreign_keys: Vec<ForeignKey>,
+    /// Query for the rows a foreign key leads to, which the user asked to open
+    navigate_requested: Option<String>,
 }
 
 impl ResultTable {
@@ -19,10 +86,35 @@ impl ResultTable {
             result: None,
             selected_row: None,
             selected_column: None,
+            selection: None,
+            copy_header: true,
+            copy_message: None,
             show_row_numbers: true,
             max_cell_width: 200.0,
             page_size: 100,
             current_page: 0,
+            has_more_rows: false,
+            row_limit_reached: false,
+            is_fetching: false,
+            fetch_requested: None,
+            inspector: ValueInspector::default(),
+            preview: GeometryPreview::default(),
+            export: ExportDialog::default(),
+            database_type: None,
+            hovered_row: None,
+            sort: Vec::new(),
+            filters: Vec::new(),
+            search: String::new(),
+            visible_rows: Vec::new(),
+            server_filter_requested: false,
+            edit_target: None,
+            changes: PendingChanges::default(),
+            editing: None,
+            save_preview: None,
+            save_requested: None,
+            is_saving: false,
+            foreign_keys: Vec::new(),
+            navigate_requested: None,
         }
     }
 
@@ -34,7 +126,59 @@ impl ResultTable {
 
             // Table content
             if let Some(result) = self.result.clone() {
+                if self.inspector.open {
+                    let cell = self.selected_row.zip(self.selected_column);
+                    let column = self.selected_column.and_then(|col| result.columns.get(col));
+                    let value = cell.and_then(|(row, col)| result.get_value(row, col));
+                    let editable =
+                        self.selected_row
+                            .zip(self.selected_column)
+                            .filter(|(row, col)| {
+                                !self.is_saving
+                                    && !self.changes.deleted.contains(row)
+                                    && self
+                                        .edit_target
+                                        .as_ref()
+                                        .is_some_and(|target| target.is_editable(*col))
+                            });
+                    let loaded = egui::SidePanel::right("value_inspector")
+                        .resizable(true)
+                        .default_width(280.0)
+                        .show_inside(ui, |ui| {
+                            self.inspector
+                                .render(ui, cell, column, value, editable.is_some())
+                        })
+                        .inner;
+                    // A file loaded into a binary cell becomes a pending edit
+                    if let (Some(bytes), Some((row, col))) = (loaded, editable) {
+                        self.inspector.invalidate();
+                        self.changes.set_value(
+                            EditRow::Existing(row),
+                            col,
+                            QueryValue::Bytes(bytes).to_sql_text(),
+                        );
+                    }
+                }
+                if self.preview.open {
+                    let shapes = self.preview_shapes(&result);
+                    let highlighted_row = self.hovered_row.or(self.selected_row);
+                    let clicked = egui::SidePanel::right("geometry_preview")
+                        .resizable(true)
+                        .default_width(320.0)
+                        .show_inside(ui, |ui| self.preview.render(ui, &shapes, highlighted_row))
+                        .inner;
+                    if let Some(row) = clicked {
+                        self.selected_row = Some(row);
+                    }
+                }
                 self.render_table(ui, &result);
+                self.handle_shortcuts(ui);
+                self.render_save_preview(ui.ctx());
+                if self.export.open {
+                    let columns = (0..result.column_count()).collect();
+                    let source = ExportSource::new(&result, columns, self.visible_rows.clone());
+                    self.export.render(ui.ctx(), &source);
+                }
             } else {
                 ui.centered_and_justified(|ui| {
                     ui.label("No query results to display");
@@ -46,7 +190,17 @@ impl ResultTable {
     fn render_toolbar(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
             if let Some(result) = &self.result {
-                ui.label(format!("Rows: {}", result.row_count()));
+                let more = if self.has_more_rows { "+" } else { "" };
+                if self.visible_rows.len() == result.row_count() {
+                    ui.label(format!("Rows: {}{}", result.row_count(), more));
+                } else {
+                    ui.label(format!(
+                        "Rows: {} of {}{}",
+                        self.visible_rows.len(),
+                        result.row_count(),
+                        more
+                    ));
+                }
                 ui.separator();
                 ui.label(format!("Columns: {}", result.column_count()));
 
@@ -59,26 +213,85 @@ impl ResultTable {
                     ui.separator();
                     ui.label(format!("Affected: {}", rows_affected));
                 }
+
+                if self.is_fetching {
+                    ui.separator();
+                    ui.spinner();
+                    ui.label("Fetching rows...");
+                } else if self.has_more_rows {
+                    ui.separator();
+                    if ui
+                        .button(format!("Fetch next {}", self.page_size))
+                        .clicked()
+                    {
+                        self.fetch_requested = Some(RowFetch::NextPage);
+                    }
+                    if ui.button("Fetch all").clicked() {
+                        self.fetch_requested = Some(RowFetch::All);
+                    }
+                } else if self.row_limit_reached {
+                    ui.separator();
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Showing the first {} rows (display limit)",
+                            result.row_count()
+                        ))
+                        .color(ui.visuals().warn_fg_color),
+                    );
+                }
             }
 
+            self.render_filter_controls(ui);
+            self.render_edit_controls(ui);
+
             ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                 ui.checkbox(&mut self.show_row_numbers, "Row Numbers");
+                ui.checkbox(&mut self.inspector.open, "Value Inspector");
+                if self.result.as_ref().is_some_and(has_geometry) {
+                    ui.checkbox(&mut self.preview.open, "Geometry Preview");
+                }
 
-                if ui.button("Export").clicked() {
-                    // TODO: Implement export functionality
+                if self.result.is_some() && ui.button("Export").clicked() {
+                    let table = self
+                        .result
+                        .as_ref()
+                        .and_then(EditTarget::source_table)
+                        .map(|(schema, table)| format!("{}.{}", schema, table));
+                    self.export.show(table, self.database_type.clone());
                 }
 
-                if ui.button("Copy").clicked() {
-                    // TODO: Implement copy functionality
+                ui.menu_button("Copy as", |ui| {
+                    ui.checkbox(&mut self.copy_header, "Include column names");
+                    ui.separator();
+                    for format in CopyFormat::ALL {
+                        if ui
+                            .add_enabled(self.selection.is_some(), egui::Button::new(format.name()))
+                            .clicked()
+                        {
+                            self.copy_selection(format);
+                            ui.close();
+                        }
+                    }
+                });
+                if ui
+                    .add_enabled(self.selection.is_some(), egui::Button::new("Copy"))
+                    .on_hover_text("Copy the selected cells as TSV (Ctrl+C)")
+                    .clicked()
+                {
+                    self.copy_selection(CopyFormat::Tsv);
+                }
+                if let Some(message) = &self.copy_message {
+                    ui.label(RichText::new(message).weak());
                 }
             });
         });
 
         // Pagination controls
-        if let Some(result) = &self.result {
-            if self.page_size > 0 && result.row_count() > self.page_size {
+        if self.result.is_some() {
+            let row_count = self.visible_rows.len();
+            if self.page_size > 0 && row_count > self.page_size {
                 ui.horizontal(|ui| {
-                    let total_pages = (result.row_count() + self.page_size - 1) / self.page_size;
+                    let total_pages = row_count.div_ceil(self.page_size);
 
                     if ui.button("◀◀").clicked() {
                         self.current_page = 0;
@@ -106,8 +319,137 @@ impl ResultTable {
         }
     }
 
+    /// Quick search and the controls for the column filters and sort
+    fn render_filter_controls(&mut self, ui: &mut Ui) {
+        if self.result.is_none() {
+            return;
+        }
+
+        ui.separator();
+        ui.add(
+            egui::TextEdit::singleline(&mut self.search)
+                .hint_text("Search")
+                .desired_width(140.0),
+        )
+        .on_hover_text("Highlight cells containing this text");
+
+        let filtered = self
+            .filters
+            .iter()
+            .any(|text| FilterCondition::parse(text).is_some());
+        if !filtered && self.sort.is_empty() {
+            return;
+        }
+        if filtered && ui.button("Clear Filters").clicked() {
+            self.filters.iter_mut().for_each(String::clear);
+            self.selection = None;
+            self.apply_filters();
+        }
+        if ui
+            .button("Run on Server")
+            .on_hover_text("Filter and sort by running the query again with WHERE and ORDER BY")
+            .clicked()
+        {
+            self.server_filter_requested = true;
+        }
+    }
+
+    /// Buttons for adding, deleting and saving rows of an editable result
+    fn render_edit_controls(&mut self, ui: &mut Ui) {
+        let (Some(result), Some(target)) = (&self.result, &self.edit_target) else {
+            return;
+        };
+
+        ui.separator();
+        ui.label(format!("Editing {}.{}", target.schema, target.table));
+
+        ui.add_enabled_ui(!self.is_saving, |ui| {
+            if ui.button("Add Row").clicked() {
+                self.changes.insert_row();
+            }
+            if ui
+                .add_enabled(self.selected_row.is_some(), egui::Button::new("Delete Row"))
+                .clicked()
+            {
+                if let Some(row) = self.selected_row {
+                    self.changes.delete_row(EditRow::Existing(row));
+                }
+            }
+
+            let has_changes = !self.changes.is_empty();
+            let save_label = if has_changes {
+                format!("Save ({})", self.changes.row_count())
+            } else {
+                "Save".to_string()
+            };
+            // Sorting after fetching moves rows, so wait for the fetch
+            if ui
+                .add_enabled(
+                    has_changes && !self.is_fetching,
+                    egui::Button::new(save_label),
+                )
+                .clicked()
+            {
+                self.editing = None;
+                self.save_preview = Some(self.changes.statements(target, result));
+            }
+            if ui
+                .add_enabled(has_changes, egui::Button::new("Revert"))
+                .clicked()
+            {
+                self.changes.clear();
+                self.editing = None;
+            }
+        });
+
+        if self.is_saving {
+            ui.spinner();
+            ui.label("Saving changes...");
+        }
+    }
+
+    /// Window listing the statements a save will run, with a button to run them
+    fn render_save_preview(&mut self, ctx: &egui::Context) {
+        let Some(statements) = &self.save_preview else {
+            return;
+        };
+
+        let mut execute = false;
+        let mut cancel = false;
+        egui::Window::new("Save Changes")
+            .collapsible(false)
+            .resizable(true)
+            .default_width(560.0)
+            .anchor(egui::Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "These {} statements run in one transaction. If any of them does not \
+                     change exactly one row, none of the changes are kept.",
+                    statements.len()
+                ));
+                ui.separator();
+                ScrollArea::vertical().max_height(320.0).show(ui, |ui| {
+                    for statement in statements {
+                        ui.monospace(format!("{};", statement.preview));
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    execute = ui.button("Execute").clicked();
+                    cancel = ui.button("Cancel").clicked();
+                });
+            });
+
+        if execute {
+            self.save_requested = self.save_preview.take();
+            self.is_saving = true;
+        } else if cancel {
+            self.save_preview = None;
+        }
+    }
+
     fn render_table(&mut self, ui: &mut Ui, result: &QueryResult) {
-        let _colors = get_table_colors();
+        let colors = get_table_colors();
 
         ScrollArea::both()
             .auto_shrink([false, false])
@@ -123,7 +465,7 @@ impl ResultTable {
                         egui_extras::Column::auto().resizable(true).clip(true),
                         if self.show_row_numbers { 1 } else { 0 } + result.column_count(),
                     )
-                    .header(25.0, |mut header| {
+                    .header(46.0, |mut header| {
                         // Row number header
                         if self.show_row_numbers {
                             header.col(|ui| {
@@ -131,17 +473,12 @@ impl ResultTable {
                             });
                         }
 
-                        // Column headers
+                        // Column headers, each with its filter box
                         for (i, column) in result.columns.iter().enumerate() {
                             header.col(|ui| {
-                                let response = ui.button(&column.name);
-                                if response.clicked() {
-                                    self.selected_column = Some(i);
-                                    // TODO: Implement column sorting
-                                }
-
-                                // Show data type on hover
-                                response.on_hover_text(&column.data_type);
+                                ui.vertical(|ui| {
+                                    self.render_column_header(ui, i, column);
+                                });
                             });
                         }
                     })
@@ -153,20 +490,42 @@ impl ResultTable {
                             self.page_size
                         };
                         let start_row = self.current_page * page_size;
-                        let end_row = (start_row + page_size).min(result.row_count());
+                        let end_row = (start_row + page_size).min(self.visible_rows.len());
+
+                        let preview_hovered_row =
+                            self.preview.hovered_row.filter(|_| self.preview.open);
+                        self.hovered_row = None;
 
-                        for row_idx in start_row..end_row {
-                            let actual_row_idx = row_idx;
+                        for position in start_row..end_row {
+                            let actual_row_idx = self.visible_rows[position];
                             body.row(20.0, |mut row| {
+                                row.set_hovered(preview_hovered_row == Some(actual_row_idx));
+
                                 // Row number
                                 if self.show_row_numbers {
                                     row.col(|ui| {
+                                        let column_count = result.column_count();
+                                        let whole_row = self.selection.is_some_and(|selection| {
+                                            selection.contains_row(position, column_count)
+                                        });
                                         let response = ui.selectable_label(
-                                            self.selected_row == Some(actual_row_idx),
+                                            whole_row || self.selected_row == Some(actual_row_idx),
                                             format!("{}", actual_row_idx + 1),
                                         );
                                         if response.clicked() {
                                             self.selected_row = Some(actual_row_idx);
+                                            let extend = ui.input(|input| input.modifiers.shift);
+                                            match &mut self.selection {
+                                                Some(selection) if extend => {
+                                                    selection.extend_rows_to(position, column_count)
+                                                }
+                                                _ => {
+                                                    self.selection = Some(GridSelection::row(
+                                                        position,
+                                                        column_count,
+                                                    ))
+                                                }
+                                            }
                                         }
                                     });
                                 }
@@ -175,59 +534,767 @@ impl ResultTable {
                                 if let Some(query_row) = result.rows.get(actual_row_idx) {
                                     for (col_idx, value) in query_row.values.iter().enumerate() {
                                         row.col(|ui| {
-                                            let is_selected = self.selected_row
-                                                == Some(actual_row_idx)
-                                                && self.selected_column == Some(col_idx);
+                                            self.render_cell(
+                                                ui,
+                                                EditRow::Existing(actual_row_idx),
+                                                Some(position),
+                                                col_idx,
+                                                Some(value),
+                                                &colors,
+                                            );
+                                        });
+                                    }
+                                }
+
+                                if row.response().hovered() {
+                                    self.hovered_row = Some(actual_row_idx);
+                                }
+                            });
+                        }
+
+                        // Rows added for insertion follow the page
+                        for index in 0..self.changes.inserted.len() {
+                            body.row(20.0, |mut row| {
+                                if self.show_row_numbers {
+                                    row.col(|ui| {
+                                        ui.label("+");
+                                    });
+                                }
+                                for col_idx in 0..result.column_count() {
+                                    row.col(|ui| {
+                                        self.render_cell(
+                                            ui,
+                                            EditRow::New(index),
+                                            None,
+                                            col_idx,
+                                            None,
+                                            &colors,
+                                        );
+                                    });
+                                }
+                            });
+                        }
+                    });
+            });
+    }
 
-                                            let text = self.format_cell_value(value);
+    /// Draw one data cell, showing its pending value when it has one
+    ///
+    /// `original` is the value read from the server; rows added for
+    /// insertion have none, nor a `position` in the grid's display order.
+    fn render_cell(
+        &mut self,
+        ui: &mut Ui,
+        row: EditRow,
+        position: Option<usize>,
+        col_idx: usize,
+        original: Option<&QueryValue>,
+        colors: &TableColors,
+    ) {
+        if self
+            .editing
+            .as_ref()
+            .is_some_and(|editor| editor.row == row && editor.column == col_idx)
+        {
+            self.render_cell_editor(ui, original);
+            return;
+        }
 
-                                            // Disable text wrapping for table cells to prevent multi-line display
-                                            ui.style_mut().wrap_mode = None;
+        let pending = self.changes.value(row, col_idx).cloned();
+        let deleted = matches!(row, EditRow::Existing(r) if self.changes.deleted.contains(&r));
+        let editable = !self.is_saving
+            && !deleted
+            && self
+                .edit_target
+                .as_ref()
+                .is_some_and(|target| target.is_editable(col_idx));
 
-                                            // Use a horizontal layout to ensure single-line display
-                                            ui.horizontal(|ui| {
-                                                ui.set_max_height(20.0); // Limit cell height to single line
-                                                let response =
-                                                    ui.selectable_label(is_selected, text);
+        if pending.is_some() {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, colors.edited_bg);
+        } else if matches!(row, EditRow::New(_)) {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, colors.inserted_bg);
+        } else if !self.search.is_empty()
+            && original.is_some_and(|value| matches_search(value, &self.search))
+        {
+            ui.painter()
+                .rect_filled(ui.max_rect(), 0.0, colors.search_match_bg);
+        }
 
-                                                if response.clicked() {
-                                                    self.selected_row = Some(actual_row_idx);
-                                                    self.selected_column = Some(col_idx);
-                                                }
+        let full_text = match (&pending, original) {
+            (Some(Some(text)), _) => text.clone(),
+            (Some(None), _) => "NULL".to_string(),
+            (None, Some(value)) => value.to_display_string(),
+            (None, None) => "DEFAULT".to_string(),
+        };
+        let mut text = RichText::new(self.format_cell_value(&full_text));
+        if pending.is_none() && original.is_none() {
+            text = text.weak();
+        }
+        if deleted {
+            text = text.strikethrough().color(colors.deleted_text);
+        }
 
-                                                // Show full value on hover for long text
-                                                let full_text = value.to_display_string();
-                                                if full_text.chars().count() > 50 {
-                                                    response.on_hover_text(&full_text);
-                                                }
-                                            });
-                                        });
-                                    }
-                                }
-                            });
+        let is_selected = match row {
+            EditRow::Existing(r) => {
+                (self.selected_row == Some(r) && self.selected_column == Some(col_idx))
+                    || position
+                        .zip(self.selection)
+                        .is_some_and(|(position, selection)| selection.contains(position, col_idx))
+            }
+            EditRow::New(_) => false,
+        };
+
+        // Disable text wrapping for table cells to prevent multi-line display
+        ui.style_mut().wrap_mode = None;
+
+        // Use a horizontal layout to ensure single-line display
+        ui.horizontal(|ui| {
+            ui.set_max_height(20.0); // Limit cell height to single line
+            let response = ui.selectable_label(is_selected, text);
+
+            if response.clicked() {
+                if let (EditRow::Existing(r), Some(position)) = (row, position) {
+                    self.selected_row = Some(r);
+                    self.selected_column = Some(col_idx);
+                    let extend = ui.input(|input| input.modifiers.shift);
+                    match &mut self.selection {
+                        Some(selection) if extend => selection.extend_to(position, col_idx),
+                        _ => self.selection = Some(GridSelection::cell(position, col_idx)),
+                    }
+                }
+            }
+            if response.double_clicked() && editable {
+                let text = match &pending {
+                    Some(value) => value.clone().unwrap_or_default(),
+                    None => original
+                        .and_then(|value| value.to_sql_text())
+                        .unwrap_or_default(),
+                };
+                self.editing = Some(CellEditor {
+                    row,
+                    column: col_idx,
+                    text,
+                    focus: true,
+                });
+            }
+
+            // Show full value on hover for long text
+            let response = if full_text.chars().count() > 50 {
+                response.on_hover_text(&full_text)
+            } else {
+                response
+            };
+
+            response.context_menu(|ui| {
+                if self
+                    .selection
+                    .is_some_and(|selection| selection.cell_count() > 1)
+                {
+                    ui.menu_button("Copy Selection as", |ui| {
+                        for format in CopyFormat::ALL {
+                            if ui.button(format.name()).clicked() {
+                                self.copy_selection(format);
+                                ui.close();
+                            }
                         }
                     });
+                }
+                if let Some(value) = original {
+                    if ui.button("Copy Value").clicked() {
+                        ui.ctx().copy_text(full_text.clone());
+                        ui.close();
+                    }
+                    if ui.button("Copy as SQL Literal").clicked() {
+                        ui.ctx().copy_text(value.to_sql_literal());
+                        ui.close();
+                    }
+                    if let QueryValue::Geometry(geometry) = value {
+                        if let Some(geojson) = geometry.to_geojson() {
+                            if ui.button("Copy as GeoJSON").clicked() {
+                                ui.ctx().copy_text(geojson.to_string());
+                                ui.close();
+                            }
+                        }
+                    }
+                }
+
+                if let EditRow::Existing(r) = row {
+                    let lookups = self.foreign_key_lookups(r, col_idx);
+                    if !lookups.is_empty() {
+                        ui.separator();
+                    }
+                    for (label, sql) in lookups {
+                        if ui.button(label).on_hover_text(&sql).clicked() {
+                            self.navigate_requested = Some(sql);
+                            ui.close();
+                        }
+                    }
+                }
+
+                if self.edit_target.is_none() || self.is_saving {
+                    return;
+                }
+                ui.separator();
+                if editable && ui.button("Set NULL").clicked() {
+                    self.changes.set_value(row, col_idx, None);
+                    ui.close();
+                }
+                if pending.is_some() && ui.button("Revert Value").clicked() {
+                    self.changes.revert_value(row, col_idx);
+                    ui.close();
+                }
+                match row {
+                    EditRow::Existing(r) if deleted => {
+                        if ui.button("Restore Row").clicked() {
+                            self.changes.undelete_row(r);
+                            ui.close();
+                        }
+                    }
+                    _ => {
+                        if ui.button("Delete Row").clicked() {
+                            self.changes.delete_row(row);
+                            // Removing a new row moves the ones after it
+                            self.editing = None;
+                            ui.close();
+                        }
+                    }
+                }
             });
+        });
     }
 
-    fn format_cell_value(&self, value: &QueryValue) -> String {
-        let display = value.to_display_string();
+    /// Text field for the cell being edited; Enter or clicking elsewhere
+    /// keeps the text, Escape drops it
+    fn render_cell_editor(&mut self, ui: &mut Ui, original: Option<&QueryValue>) {
+        let Some(editor) = &mut self.editing else {
+            return;
+        };
 
+        let response = ui
+            .add(egui::TextEdit::singleline(&mut editor.text).desired_width(ui.available_width()));
+        if editor.focus {
+            response.request_focus();
+            editor.focus = false;
+        }
+        if !response.lost_focus() {
+            return;
+        }
+
+        let cancelled = ui.input(|input| input.key_pressed(egui::Key::Escape));
+        let Some(editor) = self.editing.take() else {
+            return;
+        };
+        if cancelled {
+            return;
+        }
+        // Typing the value back in is not a change
+        let unchanged =
+            original.is_some_and(|value| value.to_sql_text().as_deref() == Some(&editor.text));
+        if unchanged {
+            self.changes.revert_value(editor.row, editor.column);
+        } else {
+            self.changes
+                .set_value(editor.row, editor.column, Some(editor.text));
+        }
+    }
+
+    /// Column name button with its sort marker and menu, and the filter box
+    fn render_column_header(&mut self, ui: &mut Ui, column: usize, info: &QueryColumn) {
+        let position = self.sort.iter().position(|key| key.column == column);
+        let mut label = info.name.clone();
+        if let Some(position) = position {
+            label.push_str(match self.sort[position].direction {
+                SortDirection::Ascending => " ▲",
+                SortDirection::Descending => " ▼",
+            });
+            if self.sort.len() > 1 {
+                label.push_str(&(position + 1).to_string());
+            }
+        }
+
+        let response = ui.button(label);
+        if response.clicked() {
+            self.selected_column = Some(column);
+            let modifiers = ui.input(|input| input.modifiers);
+            if modifiers.command {
+                self.selection = Some(GridSelection::column(column, self.visible_rows.len()));
+            } else {
+                self.toggle_sort(column, modifiers.shift);
+            }
+        }
+
+        // Show type and source column on hover
+        let mut hover_text = info.display_type().to_string();
+        if !info.nullable {
+            hover_text.push_str(" NOT NULL");
+        }
+        if let Some(source) = info.source() {
+            hover_text.push_str(&format!("\nFrom: {}", source));
+        }
+        hover_text.push_str("\nShift+click to sort by several columns");
+        hover_text.push_str("\nCtrl+click to select the column");
+        let response = response.on_hover_text(hover_text);
+
+        let current = position.map(|position| self.sort[position].clone());
+        let mut chosen: Option<(SortDirection, NullsOrder)> = None;
+        response.context_menu(|ui| {
+            if ui.button("Select Column").clicked() {
+                self.selected_column = Some(column);
+                self.selection = Some(GridSelection::column(column, self.visible_rows.len()));
+                ui.close();
+            }
+            ui.separator();
+            if ui.button("Sort Ascending").clicked() {
+                chosen = Some((SortDirection::Ascending, NullsOrder::Last));
+            }
+            if ui.button("Sort Descending").clicked() {
+                chosen = Some((SortDirection::Descending, NullsOrder::First));
+            }
+            if let Some(key) = &current {
+                ui.separator();
+                if ui.button("NULLs First").clicked() {
+                    chosen = Some((key.direction.clone(), NullsOrder::First));
+                }
+                if ui.button("NULLs Last").clicked() {
+                    chosen = Some((key.direction.clone(), NullsOrder::Last));
+                }
+                ui.separator();
+                // The rows keep their order; there is none to go back to
+                if ui.button("Remove from Sort").clicked() {
+                    self.sort.retain(|key| key.column != column);
+                    ui.close();
+                }
+            }
+            if !self.sort.is_empty() && ui.button("Clear Sort").clicked() {
+                self.sort.clear();
+                ui.close();
+            }
+            if chosen.is_some() {
+                ui.close();
+            }
+        });
+        if let Some((direction, nulls)) = chosen {
+            let key = SortKey {
+                column,
+                direction,
+                nulls,
+            };
+            match position {
+                Some(position) => self.sort[position] = key,
+                None => self.sort.push(key),
+            }
+            self.sort_rows();
+        }
+
+        let Some(filter) = self.filters.get_mut(column) else {
+            return;
+        };
+        let response = ui
+            .add(
+                egui::TextEdit::singleline(filter)
+                    .hint_text("Filter")
+                    .desired_width(ui.available_width().max(60.0)),
+            )
+            .on_hover_text("Text to search for, or =x, >=x, <=x, a..b, NULL or NOT NULL");
+        if response.changed() {
+            self.selection = None;
+            self.apply_filters();
+        }
+    }
+
+    /// Sort by a column alone, or with `add` after the columns sorted by
+    /// already; a column sorted by already changes direction
+    fn toggle_sort(&mut self, column: usize, add: bool) {
+        let only_key = self.sort.len() == 1;
+        match self.sort.iter_mut().find(|key| key.column == column) {
+            Some(key) if add || only_key => key.reverse(),
+            Some(_) => self.sort = vec![SortKey::ascending(column)],
+            None if add => self.sort.push(SortKey::ascending(column)),
+            None => self.sort = vec![SortKey::ascending(column)],
+        }
+        self.selected_row = None;
+        self.sort_rows();
+    }
+
+    fn sort_rows(&mut self) {
+        let Some(result) = &mut self.result else {
+            return;
+        };
+        if self.sort.is_empty() {
+            return;
+        }
+
+        let order = sort_order(result, &self.sort);
+        let mut rows: Vec<Option<QueryRow>> = std::mem::take(&mut result.rows)
+            .into_iter()
+            .map(Some)
+            .collect();
+        result.rows = order.iter().filter_map(|old| rows[*old].take()).collect();
+        // Row positions now hold other rows
+        self.inspector.invalidate();
+
+        // Pending changes are kept by row position, so they move along
+        self.changes.reorder_rows(&order);
+        self.editing = None;
+        self.selection = None;
+        self.apply_filters();
+    }
+
+    /// Work out which rows pass the column filters
+    fn apply_filters(&mut self) {
+        let Some(result) = &self.result else {
+            self.visible_rows.clear();
+            return;
+        };
+        self.visible_rows = filter_rows(result, &self.column_filters());
+
+        let last_page = self.visible_rows.len().saturating_sub(1) / self.page_size.max(1);
+        self.current_page = self.current_page.min(last_page);
+    }
+
+    fn column_filters(&self) -> Vec<ColumnFilter> {
+        self.filters
+            .iter()
+            .enumerate()
+            .filter_map(|(column, text)| {
+                Some(ColumnFilter {
+                    column,
+                    condition: FilterCondition::parse(text)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Ctrl+A selects every cell and Ctrl+C copies the selection as TSV,
+    /// unless a text field has the keyboard
+    fn handle_shortcuts(&mut self, ui: &mut Ui) {
+        if ui.memory(|memory| memory.focused().is_some()) {
+            return;
+        }
+        let (select_all, copy) = ui.input(|input| {
+            (
+                input.modifiers.command && input.key_pressed(egui::Key::A),
+                // Ctrl+Shift+C opens the connection dialog
+                !input.modifiers.shift
+                    && input
+                        .events
+                        .iter()
+                        .any(|event| matches!(event, egui::Event::Copy)),
+            )
+        });
+
+        if select_all {
+            if let Some(result) = &self.result {
+                self.selection = Some(GridSelection::all(
+                    self.visible_rows.len(),
+                    result.column_count(),
+                ));
+            }
+        }
+        if copy && self.selection.is_some() {
+            self.copy_selection(CopyFormat::Tsv);
+        }
+    }
+
+    /// The selected cells, with their rows in display order
+    fn selection_source(&self) -> Option<ExportSource<'_>> {
+        let result = self.result.as_ref()?;
+        let selection = self.selection?;
+        let rows = selection
+            .rows()
+            .filter_map(|position| self.visible_rows.get(position).copied())
+            .collect();
+        let columns = selection
+            .columns()
+            .filter(|column| *column < result.column_count())
+            .collect();
+        Some(ExportSource::new(result, columns, rows))
+    }
+
+    /// Copy the selected cells to the clipboard
+    fn copy_selection(&mut self, format: CopyFormat) {
+        let Some(source) = self.selection_source() else {
+            return;
+        };
+        let options = ExportOptions {
+            include_header: self.copy_header,
+            table_name: EditTarget::source_table(source.result)
+                .map(|(schema, table)| format!("{}.{}", schema, table))
+                .unwrap_or_else(|| "table_name".to_string()),
+            database_type: self
+                .database_type
+                .clone()
+                .unwrap_or(DatabaseType::PostgreSQL),
+            ..ExportOptions::default()
+        };
+        let text = format.formatter().format(&source, &options);
+        let cells = source.rows.len() * source.columns.len();
+
+        let copied = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(text));
+        self.copy_message = Some(match copied {
+            Ok(()) => format!("Copied {} cells as {}", cells, format.name()),
+            Err(err) => {
+                log::error!("Failed to copy to clipboard: {}", err);
+                format!("Failed to copy: {}", err)
+            }
+        });
+    }
+
+    /// Take whether the user asked to filter and sort on the server since
+    /// the last call
+    pub fn take_server_filter_request(&mut self) -> bool {
+        std::mem::take(&mut self.server_filter_requested)
+    }
+
+    /// The displayed result's query with the filters and sort added for the
+    /// server to apply
+    pub fn server_query(&self, database_type: &DatabaseType) -> Option<String> {
+        let result = self.result.as_ref()?;
+        rewrite_query(database_type, result, &self.column_filters(), &self.sort)
+    }
+
+    /// Geometries to preview: those of the selected row, or of the whole page
+    fn preview_shapes(&self, result: &QueryResult) -> Vec<PreviewShape> {
+        let rows: Vec<usize> = if self.preview.all_rows {
+            let page_size = self.page_size.max(1);
+            let start = (self.current_page * page_size).min(self.visible_rows.len());
+            let end = (start + page_size).min(self.visible_rows.len());
+            self.visible_rows[start..end].to_vec()
+        } else {
+            self.selected_row.into_iter().collect()
+        };
+
+        let mut shapes = Vec::new();
+        for row in rows {
+            let Some(values) = result.rows.get(row).map(|r| &r.values) else {
+                continue;
+            };
+            for value in values {
+                if let QueryValue::Geometry(geometry) = value {
+                    if let (Some(shape), Some(bbox)) = (&geometry.shape, geometry.bbox) {
+                        shapes.push(PreviewShape {
+                            row,
+                            geometry: shape.clone(),
+                            bbox,
+                        });
+                    }
+                }
+            }
+        }
+        shapes
+    }
+
+    fn format_cell_value(&self, display: &str) -> String {
         // Truncate long values safely for UTF-8 multi-byte characters
         if display.chars().count() > 100 {
             // Use character-based truncation instead of byte-based
             let truncated: String = display.chars().take(97).collect();
             format!("{}...", truncated)
         } else {
-            display
+            display.to_string()
         }
     }
 
     pub fn set_result(&mut self, result: QueryResult) {
         self.result = Some(result);
+        self.database_type = None;
         self.selected_row = None;
         self.selected_column = None;
+        self.selection = None;
+        self.copy_message = None;
         self.current_page = 0;
+        self.has_more_rows = false;
+        self.row_limit_reached = false;
+        self.is_fetching = false;
+        self.fetch_requested = None;
+        self.sort.clear();
+        self.filters = vec![String::new(); self.result.as_ref().map_or(0, |r| r.column_count())];
+        self.server_filter_requested = false;
+        self.preview.reset_view();
+        self.inspector.invalidate();
+        self.reset_editing();
+        self.foreign_keys.clear();
+        self.apply_filters();
+    }
+
+    /// Record the foreign keys of the table the result was read from, so
+    /// its cells can lead to the rows they relate to
+    pub fn set_foreign_keys(&mut self, schema: &str, table: &str, foreign_keys: Vec<ForeignKey>) {
+        let Some(result) = &self.result else {
+            return;
+        };
+        if EditTarget::source_table(result) == Some((schema.to_string(), table.to_string())) {
+            self.foreign_keys = foreign_keys;
+        }
+    }
+
+    /// Take the query for the rows a foreign key leads to, if the user asked
+    /// to open them since the last call
+    pub fn take_navigate_request(&mut self) -> Option<String> {
+        self.navigate_requested.take()
+    }
+
+    /// Queries for the rows related to a cell's row through the foreign keys
+    /// its column belongs to, with a label for each
+    ///
+    /// Outgoing keys lead to the referenced row, incoming ones to the rows
+    /// referencing this one. Keys with a NULL or a column missing from the
+    /// result are left out.
+    fn foreign_key_lookups(&self, row: usize, column: usize) -> Vec<(String, String)> {
+        let (Some(result), Some(database_type)) = (&self.result, &self.database_type) else {
+            return Vec::new();
+        };
+        let Some((schema, table)) = EditTarget::source_table(result) else {
+            return Vec::new();
+        };
+        let Some(name) = result
+            .columns
+            .get(column)
+            .filter(|c| c.table_oid.is_some())
+            .and_then(|c| c.source_column.as_deref())
+        else {
+            return Vec::new();
+        };
+
+        // Values of the row's table columns, in the order of `columns`
+        let values_of = |columns: &[String]| -> Option<Vec<&QueryValue>> {
+            columns
+                .iter()
+                .map(|name| {
+                    let index = result.columns.iter().position(|c| {
+                        c.table_oid.is_some() && c.source_column.as_deref() == Some(name)
+                    })?;
+                    result
+                        .get_value(row, index)
+                        .filter(|value| !value.is_null())
+                })
+                .collect()
+        };
+
+        let mut lookups = Vec::new();
+        for key in &self.foreign_keys {
+            if key.is_outgoing(&schema, &table) && key.columns.iter().any(|c| c == name) {
+                if let Some(values) = values_of(&key.columns) {
+                    let conditions: Vec<(&str, &QueryValue)> = key
+                        .referenced_columns
+                        .iter()
+                        .map(String::as_str)
+                        .zip(values)
+                        .collect();
+                    lookups.push((
+                        format!("➡ Open Referenced Row in {}", key.referenced_table),
+                        lookup_query(
+                            database_type,
+                            &key.referenced_schema,
+                            &key.referenced_table,
+                            &conditions,
+                        ),
+                    ));
+                }
+            }
+            if key.is_incoming(&schema, &table) && key.referenced_columns.iter().any(|c| c == name)
+            {
+                if let Some(values) = values_of(&key.referenced_columns) {
+                    let conditions: Vec<(&str, &QueryValue)> =
+                        key.columns.iter().map(String::as_str).zip(values).collect();
+                    lookups.push((
+                        format!(
+                            "⬅ Open Referencing Rows in {} ({})",
+                            key.table_name, key.name
+                        ),
+                        lookup_query(database_type, &key.schema, &key.table_name, &conditions),
+                    ));
+                }
+            }
+        }
+        lookups
+    }
+
+    /// Let the rows be edited in a table, if the result was read from it and
+    /// includes its primary key
+    pub fn enable_editing(&mut self, schema: &str, table: &str, table_columns: &[Column]) {
+        let Some(result) = &self.result else {
+            return;
+        };
+        // The result may have been replaced while the columns were loading
+        if EditTarget::source_table(result) != Some((schema.to_string(), table.to_string())) {
+            return;
+        }
+        self.edit_target = EditTarget::new(result, table_columns);
+    }
+
+    fn reset_editing(&mut self) {
+        self.edit_target = None;
+        self.changes.clear();
+        self.editing = None;
+        self.save_preview = None;
+        self.save_requested = None;
+        self.is_saving = false;
+    }
+
+    /// Take the statements the user confirmed saving since the last call
+    pub fn take_save_request(&mut self) -> Option<Vec<DmlStatement>> {
+        self.save_requested.take()
+    }
+
+    /// Record the end of a save; saved changes are dropped, failed ones are
+    /// kept so they can be corrected and saved again
+    pub fn finish_saving(&mut self, saved: bool) {
+        self.is_saving = false;
+        if saved {
+            self.changes.clear();
+        }
+    }
+
+    /// Add rows fetched after the initial result and show the first of them,
+    /// unless the rows are sorted
+    pub fn append_rows(&mut self, rows: Vec<QueryRow>) {
+        let Some(result) = &mut self.result else {
+            return;
+        };
+        if rows.is_empty() {
+            return;
+        }
+
+        let first_new_row = result.rows.len();
+        result.rows.extend(rows);
+        if !self.sort.is_empty() {
+            // New rows fall into place among the old ones
+            self.sort_rows();
+        } else {
+            self.apply_filters();
+            let position = self
+                .visible_rows
+                .partition_point(|row| *row < first_new_row);
+            self.current_page = position / self.page_size.max(1);
+        }
+    }
+
+    /// Record whether the server has rows left and whether they are being
+    /// held back by the display limit
+    pub fn set_fetch_state(&mut self, has_more_rows: bool, row_limit_reached: bool) {
+        self.has_more_rows = has_more_rows;
+        self.row_limit_reached = row_limit_reached;
+        self.is_fetching = false;
+    }
+
+    pub fn set_fetching(&mut self, fetching: bool) {
+        self.is_fetching = fetching;
+    }
+
+    pub fn is_fetching(&self) -> bool {
+        self.is_fetching
+    }
+
+    /// Take the fetch the user asked for since the last call
+    pub fn take_fetch_request(&mut self) -> Option<RowFetch> {
+        self.fetch_requested.take()
+    }
+
+    pub fn page_size(&self) -> usize {
+        self.page_size
     }
 
     pub fn set_page_size(&mut self, page_size: usize) {
@@ -238,9 +1305,34 @@ impl ResultTable {
 
     pub fn clear(&mut self) {
         self.result = None;
+        self.database_type = None;
+        self.export.open = false;
         self.selected_row = None;
         self.selected_column = None;
+        self.selection = None;
+        self.copy_message = None;
         self.current_page = 0;
+        self.has_more_rows = false;
+        self.row_limit_reached = false;
+        self.is_fetching = false;
+        self.fetch_requested = None;
+        self.sort.clear();
+        self.filters.clear();
+        self.visible_rows.clear();
+        self.server_filter_requested = false;
+        self.reset_editing();
+        self.foreign_keys.clear();
+        self.navigate_requested = None;
+    }
+
+    /// Record the server the displayed result came from
+    pub fn set_database_type(&mut self, database_type: DatabaseType) {
+        self.database_type = Some(database_type);
+    }
+
+    /// SQL of the displayed result
+    pub fn query(&self) -> Option<&str> {
+        self.result.as_ref().map(|result| result.query.as_str())
     }
 
     pub fn get_selected_value(&self) -> Option<&QueryValue> {
@@ -252,39 +1344,6 @@ impl ResultTable {
             None
         }
     }
-
-    pub fn export_to_csv(&self) -> Option<String> {
-        if let Some(result) = &self.result {
-            let mut csv = String::new();
-
-            // Headers
-            let headers: Vec<String> = result
-                .columns
-                .iter()
-                .map(|col| format!("\"{}\"", col.name.replace("\"", "\"\"")))
-                .collect();
-            csv.push_str(&headers.join(","));
-            csv.push('\n');
-
-            // Data rows
-            for row in &result.rows {
-                let values: Vec<String> = row
-                    .values
-                    .iter()
-                    .map(|val| {
-                        let display = val.to_display_string();
-                        format!("\"{}\"", display.replace("\"", "\"\""))
-                    })
-                    .collect();
-                csv.push_str(&values.join(","));
-                csv.push('\n');
-            }
-
-            Some(csv)
-        } else {
-            None
-        }
-    }
 }
 
 impl Default for ResultTable {
@@ -293,10 +1352,43 @@ impl Default for ResultTable {
             result: None,
             selected_row: None,
             selected_column: None,
+            selection: None,
+            copy_header: true,
+            copy_message: None,
             show_row_numbers: true,
             max_cell_width: 200.0,
             page_size: 100, // Ensure page_size is never 0
             current_page: 0,
+            has_more_rows: false,
+            row_limit_reached: false,
+            is_fetching: false,
+            fetch_requested: None,
+            inspector: ValueInspector::default(),
+            preview: GeometryPreview::default(),
+            export: ExportDialog::default(),
+            database_type: None,
+            hovered_row: None,
+            sort: Vec::new(),
+            filters: Vec::new(),
+            search: String::new(),
+            visible_rows: Vec::new(),
+            server_filter_requested: false,
+            edit_target: None,
+            changes: PendingChanges::default(),
+            editing: None,
+            save_preview: None,
+            save_requested: None,
+            is_saving: false,
+            foreign_keys: Vec::new(),
+            navigate_requested: None,
         }
     }
 }
+
+fn has_geometry(result: &QueryResult) -> bool {
+    result.rows.iter().any(|row| {
+        row.values
+            .iter()
+            .any(|value| matches!(value, QueryValue::Geometry(_)))
+    })
+}
@@ -0,0 +1,295 @@
+use crate::database::schema_diff::{ChangeKind, SchemaDiff};
+use crate::database::ObjectCategory;
+use egui::{ComboBox, Grid, RichText, ScrollArea, TextEdit, Ui};
+
+/// Two schemas the user asked to compare; the script changes the target
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompareRequest {
+    pub source_connection: String,
+    pub source_schema: String,
+    pub target_connection: String,
+    pub target_schema: String,
+}
+
+/// Window comparing two schemas side by side, with the migration script
+/// that makes the target match the source
+#[derive(Default)]
+pub struct SchemaCompareDialog {
+    pub open: bool,
+    source: (String, String),
+    target: (String, String),
+    /// Comparison running or shown
+    request: Option<CompareRequest>,
+    /// The differences and script, or the error; `None` while comparing
+    result: Option<Result<(SchemaDiff, String), String>>,
+    compare_request: Option<CompareRequest>,
+    /// Script the user wants in the query editor
+    editor_request: Option<String>,
+}
+
+impl SchemaCompareDialog {
+    /// Schemas the user wants compared, once they press Compare
+    pub fn take_compare_request(&mut self) -> Option<CompareRequest> {
+        self.compare_request.take()
+    }
+
+    /// Script to put in the query editor, if the user asked for it
+    pub fn take_editor_request(&mut self) -> Option<String> {
+        self.editor_request.take()
+    }
+
+    /// Show a comparison, unless the user has since started another one
+    pub fn set_result(
+        &mut self,
+        request: &CompareRequest,
+        result: Result<(SchemaDiff, String), String>,
+    ) {
+        if self.request.as_ref() == Some(request) {
+            self.result = Some(result);
+        }
+    }
+
+    /// Render the window; `connections` are the connected databases as
+    /// (connection_id, connection_name, schemas)
+    pub fn render(&mut self, ctx: &egui::Context, connections: &[(String, String, Vec<String>)]) {
+        if !self.open {
+            return;
+        }
+
+        let mut open = self.open;
+        egui::Window::new("Compare Schemas")
+            .id(egui::Id::new("schema_compare_dialog"))
+            .open(&mut open)
+            .collapsible(false)
+            .default_size([760.0, 560.0])
+            .show(ctx, |ui| {
+                Grid::new("schema_compare_sides")
+                    .num_columns(2)
+                    .spacing([8.0, 6.0])
+                    .show(ui, |ui| {
+                        ui.label("Source:");
+                        schema_picker(ui, "compare_source", &mut self.source, connections);
+                        ui.end_row();
+                        ui.label("Target:");
+                        schema_picker(ui, "compare_target", &mut self.target, connections);
+                        ui.end_row();
+                    });
+                ui.label(
+                    RichText::new("The script changes the target to match the source")
+                        .small()
+                        .weak(),
+                );
+
+                ui.horizontal(|ui| {
+                    let ready = !self.source.1.is_empty() && !self.target.1.is_empty();
+                    let comparing = self.request.is_some() && self.result.is_none();
+                    if ui
+                        .add_enabled(ready && !comparing, egui::Button::new("⚖ Compare"))
+                        .clicked()
+                    {
+                        let request = CompareRequest {
+                            source_connection: self.source.0.clone(),
+                            source_schema: self.source.1.clone(),
+                            target_connection: self.target.0.clone(),
+                            target_schema: self.target.1.clone(),
+                        };
+                        self.request = Some(request.clone());
+                        self.result = None;
+                        self.compare_request = Some(request);
+                    }
+                    if comparing {
+                        ui.spinner();
+                        ui.label("Comparing...");
+                    }
+                });
+                ui.separator();
+
+                match &self.result {
+                    None => {}
+                    Some(Err(error)) => {
+                        ui.colored_label(ui.visuals().error_fg_color, error);
+                    }
+                    Some(Ok((diff, script))) => {
+                        let mut editor_request = None;
+                        render_comparison(ui, diff, script, &mut editor_request);
+                        if editor_request.is_some() {
+                            self.editor_request = editor_request;
+                            self.open = false;
+                        }
+                    }
+                }
+            });
+        self.open &= open;
+    }
+}
+
+/// Connection and schema combo boxes for one side of the comparison
+fn schema_picker(
+    ui: &mut Ui,
+    id: &str,
+    (connection_id, schema): &mut (String, String),
+    connections: &[(String, String, Vec<String>)],
+) {
+    let selected = connections.iter().find(|(id, _, _)| id == connection_id);
+    ui.horizontal(|ui| {
+        ComboBox::from_id_salt(format!("{}_connection", id))
+            .width(200.0)
+            .selected_text(
+                selected
+                    .map(|(_, name, _)| name.as_str())
+                    .unwrap_or("Choose a connection"),
+            )
+            .show_ui(ui, |ui| {
+                for (id, name, _) in connections {
+                    if ui.selectable_label(id == connection_id, name).clicked()
+                        && id != connection_id
+                    {
+                        *connection_id = id.clone();
+                        schema.clear();
+                    }
+                }
+            });
+        ComboBox::from_id_salt(format!("{}_schema", id))
+            .width(200.0)
+            .selected_text(if schema.is_empty() {
+                "Choose a schema"
+            } else {
+                schema.as_str()
+            })
+            .show_ui(ui, |ui| {
+                for name in selected
+                    .map(|(_, _, schemas)| schemas.as_slice())
+                    .unwrap_or(&[])
+                {
+                    ui.selectable_value(schema, name.clone(), name);
+                }
+            });
+    });
+}
+
+fn render_comparison(
+    ui: &mut Ui,
+    diff: &SchemaDiff,
+    script: &str,
+    editor_request: &mut Option<String>,
+) {
+    if diff.is_empty() {
+        ui.label("✅ The schemas are identical");
+        return;
+    }
+
+    let warning_height = if diff.drop_dependents.is_empty() {
+        0.0
+    } else {
+        120.0
+    };
+    let height = (ui.available_height() - 36.0 - warning_height) / 2.0;
+    ScrollArea::vertical()
+        .id_salt("schema_compare_objects")
+        .max_height(height)
+        .show(ui, |ui| {
+            for (category, title) in [
+                (ObjectCategory::Tables, "Tables"),
+                (ObjectCategory::Views, "Views"),
+                (ObjectCategory::Functions, "Functions"),
+                (ObjectCategory::Triggers, "Triggers"),
+                (ObjectCategory::Sequences, "Sequences"),
+                (ObjectCategory::Indexes, "Indexes"),
+            ] {
+                let objects: Vec<_> = diff
+                    .objects
+                    .iter()
+                    .filter(|o| o.category == category)
+                    .collect();
+                if objects.is_empty() {
+                    continue;
+                }
+                egui::CollapsingHeader::new(format!("{} ({})", title, objects.len()))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        Grid::new(format!("schema_compare_{}", title))
+                            .num_columns(3)
+                            .striped(true)
+                            .min_col_width(220.0)
+                            .show(ui, |ui| {
+                                ui.strong(&diff.source.schema);
+                                ui.label("");
+                                ui.strong(&diff.target.schema);
+                                ui.end_row();
+                                for object in objects {
+                                    let (icon, color) = match object.change {
+                                        ChangeKind::Added => ("➕", egui::Color32::DARK_GREEN),
+                                        ChangeKind::Removed => ("➖", ui.visuals().error_fg_color),
+                                        ChangeKind::Changed => ("✏", ui.visuals().warn_fg_color),
+                                    };
+                                    let source = match object.change {
+                                        ChangeKind::Removed => "",
+                                        _ => object.name.as_str(),
+                                    };
+                                    let target = match object.change {
+                                        ChangeKind::Added => "",
+                                        _ => object.name.as_str(),
+                                    };
+                                    ui.label(source);
+                                    let status = ui.colored_label(color, icon);
+                                    if !object.details.is_empty() {
+                                        status.on_hover_text(object.details.join("\n"));
+                                    }
+                                    ui.label(target);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            }
+        });
+
+    ui.separator();
+    if !diff.drop_dependents.is_empty() {
+        render_drop_warning(ui, diff);
+        ui.separator();
+    }
+    ScrollArea::both()
+        .id_salt("schema_compare_script")
+        .max_height(height)
+        .show(ui, |ui| {
+            // Read-only, but selectable for partial copies
+            let mut text = script;
+            ui.add(
+                TextEdit::multiline(&mut text)
+                    .code_editor()
+                    .desired_width(f32::INFINITY),
+            );
+        });
+    ui.horizontal(|ui| {
+        if ui.button("📋 Copy Script").clicked() {
+            ui.ctx().copy_text(script.to_string());
+        }
+        if ui.button("📝 Open in Editor").clicked() {
+            *editor_request = Some(script.to_string());
+        }
+    });
+}
+
+/// Objects of the target that the script would trip over
+fn render_drop_warning(ui: &mut Ui, diff: &SchemaDiff) {
+    ui.colored_label(
+        ui.visuals().warn_fg_color,
+        "⚠ Other objects depend on what the script drops or alters; it fails until they are dealt with",
+    );
+    ScrollArea::vertical()
+        .id_salt("schema_compare_dependents")
+        .max_height(96.0)
+        .show(ui, |ui| {
+            for warning in &diff.drop_dependents {
+                ui.horizontal_wrapped(|ui| {
+                    ui.strong(format!("{}:", warning.object));
+                    let dependents: Vec<String> = warning
+                        .dependents
+                        .iter()
+                        .map(|d| format!("{} {}", d.kind.label(), d.qualified_name()))
+                        .collect();
+                    ui.label(dependents.join(", "));
+                });
+            }
+        });
+}
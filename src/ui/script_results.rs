@@ -0,0 +1,196 @@
+use crate::database::{QueryResult, ScriptStatement};
+use crate::ui::ResultTable;
+use egui::{Color32, ScrollArea, Ui};
+use std::time::Duration;
+
+/// What running one statement of a script produced
+pub enum StatementOutcome {
+    /// The statement returned rows
+    Rows(Box<ResultTable>),
+    /// The statement returned no rows
+    Affected {
+        rows_affected: u64,
+        execution_time: Option<Duration>,
+    },
+    Failed(String),
+}
+
+/// A statement of the script together with its outcome
+pub struct StatementTab {
+    pub sql: String,
+    pub line: usize,
+    pub outcome: StatementOutcome,
+}
+
+impl StatementTab {
+    pub fn is_failed(&self) -> bool {
+        matches!(self.outcome, StatementOutcome::Failed(_))
+    }
+}
+
+/// Result area of a script run, with one tab per executed statement
+#[derive(Default)]
+pub struct ScriptResults {
+    tabs: Vec<StatementTab>,
+    selected: usize,
+    statement_count: usize,
+    running: bool,
+}
+
+impl ScriptResults {
+    /// Forget the previous run and wait for the results of a new one
+    pub fn start(&mut self, statement_count: usize) {
+        self.tabs.clear();
+        self.selected = 0;
+        self.statement_count = statement_count;
+        self.running = true;
+    }
+
+    /// Add the result of a statement and show its tab
+    ///
+    /// `row_limit_reached` marks results cut off by the display limit.
+    pub fn add_result(
+        &mut self,
+        statement: &ScriptStatement,
+        result: QueryResult,
+        row_limit_reached: bool,
+    ) {
+        let outcome = if result.columns.is_empty() {
+            StatementOutcome::Affected {
+                rows_affected: result.rows_affected.unwrap_or(0),
+                execution_time: result.execution_time,
+            }
+        } else {
+            let mut table = ResultTable::default();
+            table.set_result(result);
+            table.set_fetch_state(false, row_limit_reached);
+            StatementOutcome::Rows(Box::new(table))
+        };
+        self.push(statement, outcome);
+    }
+
+    /// Add a statement that failed and show its tab
+    pub fn add_error(&mut self, statement: &ScriptStatement, message: String) {
+        self.push(statement, StatementOutcome::Failed(message));
+    }
+
+    /// Mark the run as over, whether or not every statement ran
+    pub fn finish(&mut self) {
+        self.running = false;
+    }
+
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Whether there is no script run to show
+    pub fn is_empty(&self) -> bool {
+        self.tabs.is_empty() && !self.running
+    }
+
+    pub fn tabs(&self) -> &[StatementTab] {
+        &self.tabs
+    }
+
+    fn push(&mut self, statement: &ScriptStatement, outcome: StatementOutcome) {
+        self.tabs.push(StatementTab {
+            sql: statement.sql.clone(),
+            line: statement.line,
+            outcome,
+        });
+        self.selected = self.tabs.len() - 1;
+    }
+
+    pub fn render(&mut self, ui: &mut Ui) {
+        ui.vertical(|ui| {
+            self.render_summary(ui);
+            ui.separator();
+
+            if self.tabs.is_empty() {
+                return;
+            }
+
+            ScrollArea::horizontal()
+                .id_salt("script_result_tabs")
+                .show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        for (index, tab) in self.tabs.iter().enumerate() {
+                            let icon = if tab.is_failed() { "✖" } else { "✔" };
+                            let response = ui
+                                .selectable_label(
+                                    self.selected == index,
+                                    format!("{} {}", icon, index + 1),
+                                )
+                                .on_hover_text(&tab.sql);
+                            if response.clicked() {
+                                self.selected = index;
+                            }
+                        }
+                    });
+                });
+            ui.separator();
+
+            let Some(tab) = self.tabs.get_mut(self.selected) else {
+                return;
+            };
+
+            ui.label(
+                egui::RichText::new(format!(
+                    "Line {}: {}",
+                    tab.line,
+                    tab.sql.lines().next().unwrap_or_default()
+                ))
+                .monospace()
+                .weak(),
+            );
+
+            match &mut tab.outcome {
+                StatementOutcome::Rows(table) => table.render(ui),
+                StatementOutcome::Affected {
+                    rows_affected,
+                    execution_time,
+                } => {
+                    let mut text = format!("Statement executed, {} row(s) affected", rows_affected);
+                    if let Some(execution_time) = execution_time {
+                        text.push_str(&format!(" in {:.2}ms", execution_time.as_millis()));
+                    }
+                    ui.label(text);
+                }
+                StatementOutcome::Failed(message) => {
+                    ui.colored_label(Color32::from_rgb(220, 53, 69), message.as_str());
+                }
+            }
+        });
+    }
+
+    fn render_summary(&self, ui: &mut Ui) {
+        let done = self.tabs.len();
+        let failed = self.tabs.iter().filter(|tab| tab.is_failed()).count();
+
+        ui.horizontal(|ui| {
+            if self.running {
+                ui.spinner();
+                ui.label(format!(
+                    "Running statement {} of {}",
+                    (done + 1).min(self.statement_count),
+                    self.statement_count
+                ));
+            } else {
+                ui.label(format!(
+                    "Statements: {} of {} executed",
+                    done, self.statement_count
+                ));
+            }
+
+            if failed > 0 {
+                ui.separator();
+                ui.colored_label(Color32::from_rgb(220, 53, 69), format!("{} failed", failed));
+            }
+
+            if !self.running && done < self.statement_count {
+                ui.separator();
+                ui.label(format!("{} not run", self.statement_count - done));
+            }
+        });
+    }
+}
@@ -0,0 +1,69 @@
+use crate::utils::TaskInfo;
+use egui::Ui;
+use std::time::Duration;
+
+/// Panel listing the background tasks that are still running
+#[derive(Debug, Clone, Default)]
+pub struct TaskPanel {
+    pub show: bool,
+}
+
+impl TaskPanel {
+    /// Render the running-task indicator for the status bar
+    pub fn render_status(&mut self, ui: &mut Ui, tasks: &[TaskInfo]) {
+        if tasks.is_empty() {
+            return;
+        }
+
+        ui.spinner();
+        let label = if tasks.len() == 1 {
+            tasks[0].description.clone()
+        } else {
+            format!("{} tasks running", tasks.len())
+        };
+        if ui.link(label).on_hover_text("Show running tasks").clicked() {
+            self.show = !self.show;
+        }
+        ui.separator();
+    }
+
+    /// Render the running tasks window
+    pub fn render(&mut self, ctx: &egui::Context, tasks: &[TaskInfo]) {
+        if !self.show {
+            return;
+        }
+
+        egui::Window::new("Running Tasks")
+            .open(&mut self.show)
+            .collapsible(false)
+            .resizable(true)
+            .default_width(360.0)
+            .show(ctx, |ui| {
+                if tasks.is_empty() {
+                    ui.label("No running tasks");
+                    return;
+                }
+
+                egui::Grid::new("running_tasks_grid")
+                    .num_columns(2)
+                    .striped(true)
+                    .spacing([16.0, 4.0])
+                    .show(ui, |ui| {
+                        for task in tasks {
+                            ui.horizontal(|ui| {
+                                ui.spinner();
+                                ui.label(&task.description);
+                            });
+                            ui.monospace(format_elapsed(task.elapsed()));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+/// Format a task's running time as `m:ss.t`
+pub fn format_elapsed(elapsed: Duration) -> String {
+    let tenths = elapsed.as_millis() / 100;
+    format!("{}:{:02}.{}", tenths / 600, (tenths / 10) % 60, tenths % 10)
+}
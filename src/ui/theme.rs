@@ -143,6 +143,14 @@ pub struct TableColors {
     pub selected_bg: Color32,
     pub selected_text: Color32,
     pub border: Color32,
+    /// Background of cells with unsaved changes
+    pub edited_bg: Color32,
+    /// Background of rows waiting to be inserted
+    pub inserted_bg: Color32,
+    /// Text of rows waiting to be deleted
+    pub deleted_text: Color32,
+    /// Background of cells matching the quick search
+    pub search_match_bg: Color32,
 }
 
 pub fn get_table_colors() -> TableColors {
@@ -155,5 +163,9 @@ pub fn get_table_colors() -> TableColors {
         selected_bg: Color32::from_rgb(0, 123, 255).linear_multiply(0.2),
         selected_text: Color32::from_rgb(33, 37, 41),
         border: Color32::from_rgb(206, 212, 218),
+        edited_bg: Color32::from_rgb(255, 243, 205),
+        inserted_bg: Color32::from_rgb(212, 237, 218),
+        deleted_text: Color32::from_rgb(220, 53, 69),
+        search_match_bg: Color32::from_rgb(255, 236, 153),
     }
 }
@@ -0,0 +1,390 @@
+use crate::database::{QueryColumn, QueryValue};
+use egui::{CollapsingHeader, RichText, ScrollArea, TextureHandle, Ui};
+use serde_json::Value;
+
+/// Bytes shown in the hex dump; the rest can be saved to a file
+const HEX_DUMP_LIMIT: usize = 64 * 1024;
+
+/// Side panel showing the selected cell's value in full
+///
+/// Arrays, ranges, composites and JSON expand into a tree of their elements,
+/// binary values show as a hex dump, or as a picture when they hold a PNG or
+/// JPEG image, and text can show its whitespace.
+#[derive(Default)]
+pub struct ValueInspector {
+    pub open: bool,
+    /// Show spaces, tabs and line breaks as visible marks
+    show_whitespace: bool,
+    /// Show JSON as indented text rather than a tree
+    json_as_text: bool,
+    /// Row and column of the cell the hex dump and picture were made for
+    cell: Option<(usize, usize)>,
+    /// Hex dump of the inspected bytes
+    hex_dump: Option<String>,
+    /// Picture decoded from the inspected bytes
+    image: Option<Result<TextureHandle, String>>,
+    /// Outcome of the last save or load
+    status: Option<String>,
+}
+
+impl ValueInspector {
+    /// Draw the panel for the value of `cell`; returns the contents of a file
+    /// the user loaded into the cell, which is only offered for an `editable`
+    /// binary cell
+    pub fn render(
+        &mut self,
+        ui: &mut Ui,
+        cell: Option<(usize, usize)>,
+        column: Option<&QueryColumn>,
+        value: Option<&QueryValue>,
+        editable: bool,
+    ) -> Option<Vec<u8>> {
+        if self.cell != cell {
+            self.cell = cell;
+            self.invalidate();
+        }
+
+        ui.horizontal(|ui| {
+            ui.strong("Value");
+            ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.small_button("✖").on_hover_text("Close").clicked() {
+                    self.open = false;
+                }
+            });
+        });
+        ui.separator();
+
+        let (Some(column), Some(value)) = (column, value) else {
+            ui.label("Select a cell to inspect its value");
+            return None;
+        };
+
+        ui.label(format!("{}: {}", column.name, column.display_type()));
+        let loaded = self.render_value_toolbar(ui, value, editable);
+        if let Some(status) = &self.status {
+            ui.label(RichText::new(status).weak());
+        }
+        ui.separator();
+
+        ScrollArea::both()
+            .id_salt("value_inspector_scroll")
+            .auto_shrink([false, false])
+            .show(ui, |ui| match value {
+                QueryValue::Json(json) if self.json_as_text => {
+                    let text = serde_json::to_string_pretty(json).unwrap_or_default();
+                    render_text(ui, &text, self.show_whitespace);
+                }
+                QueryValue::Json(json) => render_json(ui, &column.name, json, 0),
+                QueryValue::Bytes(bytes) => self.render_bytes(ui, bytes),
+                QueryValue::String(text) => render_text(ui, text, self.show_whitespace),
+                _ if value.is_nested() => render_nested(ui, &column.name, value, 0),
+                _ => render_leaf(ui, value),
+            });
+        loaded
+    }
+
+    /// Forget the hex dump and picture of the inspected cell, for a result
+    /// whose values changed
+    pub fn invalidate(&mut self) {
+        self.hex_dump = None;
+        self.image = None;
+    }
+
+    /// Options for the kind of value shown, and saving or loading binary values
+    fn render_value_toolbar(
+        &mut self,
+        ui: &mut Ui,
+        value: &QueryValue,
+        editable: bool,
+    ) -> Option<Vec<u8>> {
+        let mut loaded = None;
+        ui.horizontal(|ui| match value {
+            QueryValue::String(text) => {
+                ui.label(
+                    RichText::new(format!(
+                        "{} characters, {} lines",
+                        text.chars().count(),
+                        text.lines().count().max(1)
+                    ))
+                    .weak(),
+                );
+                ui.checkbox(&mut self.show_whitespace, "Whitespace");
+            }
+            QueryValue::Json(_) => {
+                ui.selectable_value(&mut self.json_as_text, false, "Tree");
+                ui.selectable_value(&mut self.json_as_text, true, "Text");
+                if self.json_as_text {
+                    ui.checkbox(&mut self.show_whitespace, "Whitespace");
+                }
+            }
+            QueryValue::Bytes(bytes) => {
+                ui.label(RichText::new(format!("{} bytes", bytes.len())).weak());
+                if ui.button("Save...").clicked() {
+                    self.save_bytes(bytes);
+                }
+                if editable && ui.button("Load...").clicked() {
+                    loaded = self.load_bytes();
+                }
+            }
+            _ => {}
+        });
+        loaded
+    }
+
+    fn render_bytes(&mut self, ui: &mut Ui, bytes: &[u8]) {
+        if let Some(kind) = image_kind(bytes) {
+            ui.label(format!("{} image", kind));
+            match self.image_texture(ui.ctx(), bytes) {
+                Ok(texture) => {
+                    let size = texture.size_vec2();
+                    let scale = (ui.available_width() / size.x).min(1.0);
+                    ui.image((texture.id(), size * scale));
+                }
+                Err(error) => {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            }
+            ui.separator();
+        }
+
+        let dump = self
+            .hex_dump
+            .get_or_insert_with(|| hex_dump(bytes, HEX_DUMP_LIMIT));
+        ui.add(egui::Label::new(RichText::new(dump.as_str()).monospace()).extend());
+        if bytes.len() > HEX_DUMP_LIMIT {
+            ui.label(
+                RichText::new(format!(
+                    "Showing the first {} of {} bytes; save the value to see the rest",
+                    HEX_DUMP_LIMIT,
+                    bytes.len()
+                ))
+                .weak(),
+            );
+        }
+    }
+
+    /// The bytes' picture, decoded once per cell
+    fn image_texture(
+        &mut self,
+        ctx: &egui::Context,
+        bytes: &[u8],
+    ) -> Result<TextureHandle, String> {
+        self.image
+            .get_or_insert_with(|| {
+                image::load_from_memory(bytes)
+                    .map(|image| {
+                        let image = image.to_rgba8();
+                        let size = [image.width() as usize, image.height() as usize];
+                        let pixels = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+                        ctx.load_texture("value_inspector_image", pixels, Default::default())
+                    })
+                    .map_err(|e| format!("Cannot decode the image: {}", e))
+            })
+            .clone()
+    }
+
+    fn save_bytes(&mut self, bytes: &[u8]) {
+        let extension = match image_kind(bytes) {
+            Some("PNG") => "png",
+            Some("JPEG") => "jpg",
+            _ => "bin",
+        };
+        let Some(path) = rfd::FileDialog::new()
+            .set_title("Save Value")
+            .set_file_name(format!("value.{}", extension))
+            .save_file()
+        else {
+            return;
+        };
+        self.status = Some(match std::fs::write(&path, bytes) {
+            Ok(()) => format!("Saved to {}", path.display()),
+            Err(e) => format!("Failed to write {}: {}", path.display(), e),
+        });
+    }
+
+    fn load_bytes(&mut self) -> Option<Vec<u8>> {
+        let path = rfd::FileDialog::new().set_title("Load Value").pick_file()?;
+        match std::fs::read(&path) {
+            Ok(bytes) => {
+                self.status = Some(format!(
+                    "Loaded {} bytes from {}; save the changes to keep them",
+                    bytes.len(),
+                    path.display()
+                ));
+                Some(bytes)
+            }
+            Err(e) => {
+                self.status = Some(format!("Failed to read {}: {}", path.display(), e));
+                None
+            }
+        }
+    }
+}
+
+/// One node of the value tree, with its children below it
+fn render_nested(ui: &mut Ui, label: &str, value: &QueryValue, depth: usize) {
+    let children: Vec<(String, &QueryValue)> = match value {
+        QueryValue::Array(elements) => elements
+            .iter()
+            .enumerate()
+            .map(|(index, element)| (format!("[{}]", index + 1), element))
+            .collect(),
+        QueryValue::Composite(fields) => fields
+            .iter()
+            .map(|(name, value)| (name.clone(), value))
+            .collect(),
+        QueryValue::Range(range) => [("lower", &range.lower), ("upper", &range.upper)]
+            .into_iter()
+            .filter_map(|(name, bound)| Some((name.to_string(), bound.as_deref()?)))
+            .collect(),
+        QueryValue::Json(json) => {
+            render_json(ui, label, json, depth);
+            return;
+        }
+        _ => {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", label));
+                render_leaf(ui, value);
+            });
+            return;
+        }
+    };
+
+    let summary = match value {
+        QueryValue::Array(elements) => format!("{} ({} elements)", label, elements.len()),
+        QueryValue::Range(range) => format!("{} {}", label, range.to_display_string()),
+        _ => label.to_string(),
+    };
+
+    ui.push_id(label, |ui| {
+        CollapsingHeader::new(summary)
+            .default_open(depth < 2)
+            .show(ui, |ui| {
+                for (label, child) in children {
+                    render_nested(ui, &label, child, depth + 1);
+                }
+            });
+    });
+}
+
+/// One node of a JSON document, with its members below it
+fn render_json(ui: &mut Ui, label: &str, json: &Value, depth: usize) {
+    let children: Vec<(String, &Value)> = match json {
+        Value::Array(elements) => elements
+            .iter()
+            .enumerate()
+            .map(|(index, element)| (format!("[{}]", index), element))
+            .collect(),
+        Value::Object(members) => members
+            .iter()
+            .map(|(name, member)| (name.clone(), member))
+            .collect(),
+        _ => {
+            ui.horizontal(|ui| {
+                ui.label(format!("{}:", label));
+                let text = RichText::new(json.to_string()).monospace();
+                match json {
+                    Value::Null => ui.label(text.italics().weak()),
+                    _ => ui.add(egui::Label::new(text).wrap()),
+                };
+            });
+            return;
+        }
+    };
+
+    let summary = match json {
+        Value::Array(_) => format!("{} [{}]", label, children.len()),
+        _ => format!("{} {{{}}}", label, children.len()),
+    };
+    ui.push_id(label, |ui| {
+        CollapsingHeader::new(summary)
+            .default_open(depth < 2)
+            .show(ui, |ui| {
+                for (label, child) in children {
+                    render_json(ui, &label, child, depth + 1);
+                }
+            });
+    });
+}
+
+/// Text in full, on as many lines as it has
+fn render_text(ui: &mut Ui, text: &str, show_whitespace: bool) {
+    let text = if show_whitespace {
+        visualize_whitespace(text)
+    } else {
+        text.to_string()
+    };
+    ui.add(egui::Label::new(RichText::new(text).monospace()).wrap());
+}
+
+fn render_leaf(ui: &mut Ui, value: &QueryValue) {
+    // The input format is never shortened, unlike the grid's display text
+    let text = value
+        .to_sql_text()
+        .unwrap_or_else(|| value.to_display_string());
+    if value.is_null() {
+        ui.label(RichText::new(text).italics().weak());
+    } else {
+        ui.add(egui::Label::new(RichText::new(text).monospace()).wrap());
+    }
+}
+
+/// Name of the image format the bytes start with, if any
+pub fn image_kind(bytes: &[u8]) -> Option<&'static str> {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        Some("PNG")
+    } else if bytes.starts_with(&[0xFF, 0xD8, 0xFF]) {
+        Some("JPEG")
+    } else {
+        None
+    }
+}
+
+/// Lines of 16 bytes as offset, hex and printable ASCII, up to `limit` bytes
+pub fn hex_dump(bytes: &[u8], limit: usize) -> String {
+    let mut dump = String::new();
+    for (line, chunk) in bytes[..bytes.len().min(limit)].chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|byte| format!("{:02x}", byte)).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|byte| {
+                if byte.is_ascii_graphic() || *byte == b' ' {
+                    *byte as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        if !dump.is_empty() {
+            dump.push('\n');
+        }
+        dump.push_str(&format!(
+            "{:08x}  {:<47}  {}",
+            line * 16,
+            hex.join(" "),
+            ascii
+        ));
+    }
+    dump
+}
+
+/// Text with spaces as `·`, tabs as `→` and line ends as `↵`, keeping the
+/// line breaks
+pub fn visualize_whitespace(text: &str) -> String {
+    let mut visible = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ' ' => visible.push('·'),
+            '\t' => visible.push('→'),
+            '\r' if chars.peek() == Some(&'\n') => {
+                chars.next();
+                visible.push_str("␍↵\n");
+            }
+            '\r' => visible.push_str("␍\n"),
+            '\n' => visible.push_str("↵\n"),
+            _ => visible.push(c),
+        }
+    }
+    visible
+}
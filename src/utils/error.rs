@@ -42,6 +42,10 @@ impl AppError {
                 crate::database::DatabaseError::UnsupportedOperation(op) => {
                     format!("Unsupported operation: {}", op)
                 }
+                crate::database::DatabaseError::Cancelled => "Query was cancelled".to_string(),
+                crate::database::DatabaseError::Timeout(msg) => {
+                    format!("Query timed out: {}", msg)
+                }
                 crate::database::DatabaseError::Internal(msg) => {
                     format!("Internal database error: {}", msg)
                 }
@@ -63,6 +67,8 @@ impl AppError {
                 crate::database::DatabaseError::InvalidParams(_) => true,
                 crate::database::DatabaseError::NotConnected => true,
                 crate::database::DatabaseError::UnsupportedOperation(_) => false,
+                crate::database::DatabaseError::Cancelled => true,
+                crate::database::DatabaseError::Timeout(_) => true,
                 crate::database::DatabaseError::Internal(_) => false,
             },
             AppError::Config(_) => true,
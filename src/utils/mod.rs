@@ -1,5 +1,7 @@
 pub mod error;
 pub mod logging;
+pub mod tasks;
 
 pub use error::*;
 pub use logging::*;
+pub use tasks::*;
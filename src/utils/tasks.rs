@@ -0,0 +1,136 @@
+use std::future::Future;
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::time::{Duration, Instant};
+use tokio::runtime::Runtime;
+
+/// Identifier of a background task
+pub type TaskId = u64;
+
+/// A background task that has not finished yet
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    pub id: TaskId,
+    pub description: String,
+    pub started_at: Instant,
+}
+
+impl TaskInfo {
+    pub fn elapsed(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+}
+
+/// Runs futures on the tokio runtime and hands their outputs back to the UI thread
+///
+/// Outputs are queued on a channel and collected with [`TaskManager::poll`], which
+/// the app calls once per frame. When a repaint context is set, every finished
+/// task wakes the UI so results show up without waiting for input events.
+pub struct TaskManager<T> {
+    runtime: Runtime,
+    sender: Sender<(TaskId, Option<T>)>,
+    receiver: Receiver<(TaskId, Option<T>)>,
+    running: Vec<TaskInfo>,
+    next_id: TaskId,
+    repaint_context: Option<egui::Context>,
+}
+
+impl<T: Send + 'static> TaskManager<T> {
+    pub fn new(runtime: Runtime) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        Self {
+            runtime,
+            sender,
+            receiver,
+            running: Vec::new(),
+            next_id: 1,
+            repaint_context: None,
+        }
+    }
+
+    /// Request a repaint of this context whenever a task finishes
+    pub fn set_repaint_context(&mut self, ctx: egui::Context) {
+        self.repaint_context = Some(ctx);
+    }
+
+    /// Start a future in the background
+    pub fn spawn<F>(&mut self, description: impl Into<String>, future: F) -> TaskId
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.start(description.into(), None, future)
+    }
+
+    /// Start a future in the background, delivering `on_panic` in place of
+    /// its output if it panics so whatever waits for it can be reset
+    pub fn spawn_or<F>(&mut self, description: impl Into<String>, on_panic: T, future: F) -> TaskId
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        self.start(description.into(), Some(on_panic), future)
+    }
+
+    fn start<F>(&mut self, description: String, on_panic: Option<T>, future: F) -> TaskId
+    where
+        F: Future<Output = T> + Send + 'static,
+    {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        log::debug!("Starting task {}: {}", id, description);
+        self.running.push(TaskInfo {
+            id,
+            description,
+            started_at: Instant::now(),
+        });
+
+        let sender = self.sender.clone();
+        let repaint_context = self.repaint_context.clone();
+        let handle = self.runtime.handle().clone();
+        self.runtime.spawn(async move {
+            // Run the work as its own task so a panic is reported instead of
+            // leaving the task listed as running forever
+            let output = match handle.spawn(future).await {
+                Ok(output) => Some(output),
+                Err(err) => {
+                    log::error!("Task {} failed: {}", id, err);
+                    on_panic
+                }
+            };
+
+            // The receiver only goes away when the app shuts down
+            let _ = sender.send((id, output));
+            if let Some(ctx) = repaint_context {
+                ctx.request_repaint();
+            }
+        });
+
+        id
+    }
+
+    /// Collect the outputs of all tasks that finished since the last call
+    pub fn poll(&mut self) -> Vec<T> {
+        let mut outputs = Vec::new();
+        while let Ok((id, output)) = self.receiver.try_recv() {
+            if let Some(task) = self.running.iter().position(|t| t.id == id) {
+                let task = self.running.remove(task);
+                log::debug!(
+                    "Task {} finished in {:?}: {}",
+                    id,
+                    task.elapsed(),
+                    task.description
+                );
+            }
+            outputs.extend(output);
+        }
+        outputs
+    }
+
+    /// Tasks that are still running, oldest first
+    pub fn running(&self) -> &[TaskInfo] {
+        &self.running
+    }
+
+    pub fn has_running_tasks(&self) -> bool {
+        !self.running.is_empty()
+    }
+}